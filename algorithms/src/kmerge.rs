@@ -0,0 +1,184 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Lazily merges sorted iterators into a single sorted iterator.
+///
+/// Equal values are yielded in the order of their sources, so the merge is
+/// stable. Sources that aren't sorted produce an unsorted result.
+///
+/// Time Complexity: O(log k) per value
+/// Space Complexity: O(k)
+///
+/// # Example
+///
+/// ```
+/// use algorithms::kmerge;
+///
+/// let merged = kmerge(vec![
+///     vec![1, 4, 7].into_iter(),
+///     vec![2, 5].into_iter(),
+///     vec![3, 6, 9].into_iter(),
+/// ]);
+///
+/// assert_eq!(merged.collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6, 7, 9]);
+/// ```
+pub fn kmerge<I>(mut sources: Vec<I>) -> KMerge<I>
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+    let heap = sources
+        .iter_mut()
+        .enumerate()
+        .filter_map(|(source, iter)| iter.next().map(|value| Head { value, source }))
+        .collect();
+
+    KMerge { sources, heap }
+}
+
+/// The Iterator returned by `kmerge()`.
+pub struct KMerge<I>
+where
+    I: Iterator,
+{
+    sources: Vec<I>,
+    // The next value of each source that isn't exhausted.
+    heap: BinaryHeap<Head<I::Item>>,
+}
+
+impl<I> Iterator for KMerge<I>
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let Head { value, source } = self.heap.pop()?;
+
+        if let Some(next) = self.sources[source].next() {
+            self.heap.push(Head {
+                value: next,
+                source,
+            });
+        }
+
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let mut lower = self.heap.len();
+        let mut upper = Some(self.heap.len());
+
+        for source in &self.sources {
+            let (l, u) = source.size_hint();
+            lower = lower.saturating_add(l);
+            upper = upper.and_then(|upper| u.and_then(|u| upper.checked_add(u)));
+        }
+
+        (lower, upper)
+    }
+}
+
+/// The next value of a source. Heads are ordered in reverse, so the
+/// BinaryHeap pops the smallest value, and the earliest source among equal
+/// values.
+struct Head<T> {
+    value: T,
+    source: usize,
+}
+
+impl<T: Ord> Ord for Head<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .value
+            .cmp(&self.value)
+            .then_with(|| other.source.cmp(&self.source))
+    }
+}
+
+impl<T: Ord> PartialOrd for Head<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord> PartialEq for Head<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Ord> Eq for Head<T> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use doubly_linked_list::LinkedList;
+
+    #[test]
+    fn merges_list_iterators() {
+        let lists: Vec<LinkedList<u32>> = (0..3)
+            .map(|i| {
+                let mut list = LinkedList::default();
+                for v in (i..30).step_by(3) {
+                    list.push(v);
+                }
+                list
+            })
+            .collect();
+
+        let merged = kmerge(lists.iter().map(|list| list.into_iter()).collect());
+        assert_eq!(merged.collect::<Vec<_>>(), (0..30).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn stable_and_lazy() {
+        #[derive(Debug)]
+        struct Keyed(u32, &'static str);
+
+        impl PartialEq for Keyed {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+        impl Eq for Keyed {}
+        impl PartialOrd for Keyed {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Keyed {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let merged = kmerge(vec![
+            vec![Keyed(1, "a"), Keyed(2, "a")].into_iter(),
+            vec![Keyed(1, "b"), Keyed(2, "b")].into_iter(),
+        ]);
+        assert_eq!(
+            merged.map(|k| k.1).collect::<Vec<_>>(),
+            vec!["a", "b", "a", "b"]
+        );
+
+        // Values are pulled as they're needed, so a source can be infinite.
+        let mut merged = kmerge(vec![
+            Box::new(0u32..) as Box<dyn Iterator<Item = u32>>,
+            Box::new(vec![5].into_iter()),
+        ]);
+        assert_eq!(merged.nth(6), Some(5));
+        assert_eq!(merged.size_hint(), (usize::MAX, None));
+    }
+
+    #[test]
+    fn empty_sources() {
+        let merged = kmerge(Vec::<std::vec::IntoIter<u32>>::new());
+        assert_eq!(merged.count(), 0);
+
+        let merged = kmerge(vec![vec![].into_iter(), vec![1].into_iter()]);
+        assert_eq!(merged.size_hint(), (1, Some(1)));
+        assert_eq!(merged.collect::<Vec<_>>(), vec![1]);
+    }
+}
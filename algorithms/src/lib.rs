@@ -0,0 +1,5 @@
+//! A crate that implements algorithms over the data structures, working on
+//! their iterators.
+pub use crate::kmerge::{kmerge, KMerge};
+
+mod kmerge;
@@ -0,0 +1,120 @@
+//! Compares an AmortizedQueue with the LinkedList of the linked_list crate,
+//! both used through the Queue trait: the throughput of a batch of pushes
+//! and pops, and the slowest single pop, which for the AmortizedQueue is the
+//! one that moves the inbox to the outbox.
+//!
+//! cargo run --release --example queues
+use amortized_queue::AmortizedQueue;
+use linked_list::LinkedList;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use traits::Queue;
+
+// Dropping a LinkedList recurses through its nodes, so much longer lists
+// overflow the stack.
+const VALUES: u64 = 100_000;
+const ROUNDS: u32 = 5;
+
+/// Runs `f` a few times and returns the fastest run, the others being slowed
+/// down by whatever else the machine was doing.
+fn fastest<F: FnMut() -> Duration>(mut f: F) -> Duration {
+    (0..ROUNDS).map(|_| f()).min().unwrap()
+}
+
+/// Returns the time to enqueue every value and then dequeue them all.
+fn batch<Q: Queue<u64> + Default>() -> Duration {
+    let start = Instant::now();
+    let mut queue = Q::default();
+    for i in 0..VALUES {
+        queue.enqueue(black_box(i));
+    }
+    while let Some(v) = queue.dequeue() {
+        black_box(v);
+    }
+
+    start.elapsed()
+}
+
+/// Returns the time to enqueue and dequeue one value at a time, the queue
+/// holding a backlog of a hundred values.
+fn steady<Q: Queue<u64> + Default>() -> Duration {
+    let mut queue = Q::default();
+    for i in 0..100 {
+        queue.enqueue(i);
+    }
+
+    let start = Instant::now();
+    for i in 0..VALUES {
+        queue.enqueue(black_box(i));
+        black_box(queue.dequeue());
+    }
+    let elapsed = start.elapsed();
+    while queue.dequeue().is_some() {}
+
+    elapsed
+}
+
+/// Returns the time of the slowest dequeue after enqueuing every value.
+fn slowest_pop<Q: Queue<u64> + Default>() -> Duration {
+    let mut queue = Q::default();
+    for i in 0..VALUES {
+        queue.enqueue(i);
+    }
+
+    let mut slowest = Duration::ZERO;
+    loop {
+        let start = Instant::now();
+        let v = queue.dequeue();
+        slowest = slowest.max(start.elapsed());
+        if black_box(v).is_none() {
+            return slowest;
+        }
+    }
+}
+
+fn report(name: &str, elapsed: Duration, ops: u64) {
+    println!(
+        "{:<32} {:>10.3} ms  {:>8.1} M ops/s",
+        name,
+        elapsed.as_secs_f64() * 1000.0,
+        ops as f64 / elapsed.as_secs_f64() / 1_000_000.0
+    );
+}
+
+fn report_latency(name: &str, elapsed: Duration) {
+    println!("{:<32} {:>10.3} ms", name, elapsed.as_secs_f64() * 1000.0);
+}
+
+fn main() {
+    report(
+        "AmortizedQueue batch",
+        fastest(batch::<AmortizedQueue<u64>>),
+        2 * VALUES,
+    );
+    report(
+        "LinkedList batch",
+        fastest(batch::<LinkedList<u64>>),
+        2 * VALUES,
+    );
+    report(
+        "AmortizedQueue steady",
+        fastest(steady::<AmortizedQueue<u64>>),
+        2 * VALUES,
+    );
+    report(
+        "LinkedList steady",
+        fastest(steady::<LinkedList<u64>>),
+        2 * VALUES,
+    );
+
+    // A single pop, so the ops per second would mean nothing.
+    println!();
+    report_latency(
+        "AmortizedQueue slowest pop",
+        fastest(slowest_pop::<AmortizedQueue<u64>>),
+    );
+    report_latency(
+        "LinkedList slowest pop",
+        fastest(slowest_pop::<LinkedList<u64>>),
+    );
+}
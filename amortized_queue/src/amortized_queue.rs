@@ -0,0 +1,297 @@
+use std::iter::{Chain, Rev};
+use std::slice::Iter;
+use traits::{Collection, Queue};
+
+/// AmortizedQueue is a FIFO queue built from two stacks.
+///
+/// Values are pushed onto the `inbox` stack. When popping, the values are
+/// taken from the `outbox` stack, and only when the `outbox` is empty is the
+/// whole `inbox` reversed into it. Each value is moved between the stacks at
+/// most once, so although a single pop can cost O(n), a sequence of n
+/// operations costs O(n) in total.
+pub struct AmortizedQueue<T> {
+    inbox: Vec<T>,
+    outbox: Vec<T>,
+}
+
+impl<T> Default for AmortizedQueue<T> {
+    fn default() -> Self {
+        AmortizedQueue {
+            inbox: Vec::new(),
+            outbox: Vec::new(),
+        }
+    }
+}
+
+/// The Iterator implementation for the AmortizedQueue, from the front of the
+/// queue to the back. This Iterator will borrow the AmortizedQueue.
+pub struct AmortizedQueueIterator<'a, T> {
+    // The oldest values are at the top of the outbox, followed by the inbox
+    // from bottom to top.
+    values: Chain<Rev<Iter<'a, T>>, Iter<'a, T>>,
+}
+
+impl<'a, T> Iterator for AmortizedQueueIterator<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.values.next()
+    }
+}
+
+impl<T> Collection for AmortizedQueue<T> {
+    type Iter<'a>
+        = AmortizedQueueIterator<'a, T>
+    where
+        T: 'a;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn clear(&mut self) {
+        self.clear()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.iter()
+    }
+}
+
+impl<T> Queue<T> for AmortizedQueue<T> {
+    fn enqueue(&mut self, v: T) {
+        self.push(v)
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.pop()
+    }
+}
+
+impl<T> AmortizedQueue<T> {
+    /// Returns the length of the AmortizedQueue.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.inbox.len() + self.outbox.len()
+    }
+
+    /// Returns a boolean indicating the AmortizedQueue is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use amortized_queue::AmortizedQueue;
+    ///
+    /// let queue = AmortizedQueue::<u32>::default();
+    /// assert_eq!(queue.is_empty(), true);
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.inbox.is_empty() && self.outbox.is_empty()
+    }
+
+    /// Adds a value to the back of the AmortizedQueue.
+    ///
+    /// Time Complexity: O(1) amortized
+    /// Space Complexity: O(1) amortized
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use amortized_queue::AmortizedQueue;
+    ///
+    /// let mut queue = AmortizedQueue::default();
+    /// queue.push("Hello".to_string());
+    ///
+    /// assert_eq!(queue.peek(), Some(&"Hello".to_string()));
+    /// ```
+    pub fn push(&mut self, v: T) {
+        self.inbox.push(v);
+    }
+
+    /// Returns the value at the front of the AmortizedQueue and removes it
+    /// from the AmortizedQueue.
+    ///
+    /// Time Complexity: O(1) amortized, O(n) when the outbox is refilled
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use amortized_queue::AmortizedQueue;
+    ///
+    /// let mut queue = AmortizedQueue::default();
+    /// queue.push("Hello".to_string());
+    /// queue.push("World".to_string());
+    ///
+    /// assert_eq!(queue.pop(), Some("Hello".to_string()));
+    /// assert_eq!(queue.len(), 1);
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        if self.outbox.is_empty() {
+            // Reversing the inbox puts the oldest value at the top of the
+            // outbox.
+            while let Some(v) = self.inbox.pop() {
+                self.outbox.push(v);
+            }
+        }
+
+        self.outbox.pop()
+    }
+
+    /// Removes every value from the AmortizedQueue.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    pub fn clear(&mut self) {
+        self.inbox.clear();
+        self.outbox.clear();
+    }
+
+    /// Returns a borrowing iterator from the front to the back of the
+    /// AmortizedQueue, the same order the values would be popped in.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use amortized_queue::AmortizedQueue;
+    ///
+    /// let mut queue = AmortizedQueue::default();
+    /// queue.push(1);
+    /// queue.push(2);
+    ///
+    /// assert_eq!(queue.iter().collect::<Vec<_>>(), vec![&1, &2]);
+    /// ```
+    pub fn iter(&self) -> AmortizedQueueIterator<'_, T> {
+        AmortizedQueueIterator {
+            values: self.outbox.iter().rev().chain(self.inbox.iter()),
+        }
+    }
+
+    /// Returns a reference to the value at the front of the AmortizedQueue.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn peek(&self) -> Option<&T> {
+        // If the outbox is empty, the oldest value is at the bottom of the
+        // inbox.
+        self.outbox.last().or_else(|| self.inbox.first())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn init_queue() {
+        let queue = AmortizedQueue::<String>::default();
+        assert_eq!(queue.len(), 0);
+        assert_eq!(queue.peek(), None);
+    }
+
+    #[test]
+    fn fifo_order() {
+        let mut queue = AmortizedQueue::default();
+
+        for i in 1..5 {
+            queue.push(i);
+        }
+
+        assert_eq!(queue.len(), 4);
+        assert_eq!(queue.pop(), Some(1));
+        assert_eq!(queue.pop(), Some(2));
+        assert_eq!(queue.pop(), Some(3));
+        assert_eq!(queue.pop(), Some(4));
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn interleaved_push_and_pop() {
+        let mut queue = AmortizedQueue::default();
+
+        queue.push(1);
+        queue.push(2);
+        assert_eq!(queue.pop(), Some(1));
+
+        // 3 lands in the inbox while 2 is still waiting in the outbox.
+        queue.push(3);
+        assert_eq!(queue.peek(), Some(&2));
+        assert_eq!(queue.len(), 2);
+
+        assert_eq!(queue.pop(), Some(2));
+        assert_eq!(queue.peek(), Some(&3));
+        assert_eq!(queue.pop(), Some(3));
+        assert_eq!(queue.peek(), None);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn generic_over_queue_trait() {
+        fn fill<Q: Queue<u32>>(queue: &mut Q) {
+            for i in 1..4 {
+                queue.enqueue(i);
+            }
+        }
+
+        let mut queue = AmortizedQueue::default();
+        fill(&mut queue);
+
+        assert_eq!(queue.dequeue(), Some(1));
+        assert_eq!(queue.dequeue(), Some(2));
+        assert_eq!(queue.dequeue(), Some(3));
+        assert_eq!(queue.dequeue(), None);
+    }
+
+    #[test]
+    fn generic_over_collection() {
+        fn sizes<C: Collection>(collection: &C) -> (usize, usize) {
+            (collection.len(), collection.iter().count())
+        }
+
+        let mut queue = AmortizedQueue::default();
+        for i in 1..5 {
+            queue.push(i);
+        }
+
+        // Split the values across both stacks.
+        queue.pop();
+        queue.push(5);
+
+        assert_eq!(sizes(&queue), (4, 4));
+        assert_eq!(queue.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4, 5]);
+
+        Collection::clear(&mut queue);
+        assert_eq!(sizes(&queue), (0, 0));
+    }
+}
+
+// The ordering guarantees of the public API. These tests must keep passing
+// across any rewrite of the AmortizedQueue internals.
+#[cfg(test)]
+mod contract {
+    use super::*;
+
+    #[test]
+    fn iterates_in_pop_order() {
+        let mut queue = AmortizedQueue::default();
+        queue.push(1);
+        queue.push(2);
+
+        // Move the values to the outbox, then add more to the inbox.
+        assert_eq!(queue.pop(), Some(1));
+        queue.push(3);
+        queue.push(4);
+
+        assert_eq!(queue.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(
+            Collection::iter(&queue).copied().collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+
+        assert_eq!(queue.pop(), Some(2));
+        assert_eq!(queue.pop(), Some(3));
+        assert_eq!(queue.pop(), Some(4));
+    }
+}
@@ -0,0 +1,6 @@
+//! A crate that implements an AmortizedQueue.
+pub use crate::amortized_queue::{AmortizedQueue, AmortizedQueueIterator};
+pub use crate::round_robin::RoundRobinQueues;
+
+mod amortized_queue;
+mod round_robin;
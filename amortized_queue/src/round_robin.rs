@@ -0,0 +1,220 @@
+use crate::amortized_queue::AmortizedQueue;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// RoundRobinQueues holds a FIFO queue per key and pops from them in turn, so
+/// a key with a long backlog can't starve the others, e.g. when scheduling
+/// work per tenant.
+///
+/// The keys with values waiting form a ring, itself an AmortizedQueue. Each
+/// `pop_next()` takes the key at the front of the ring, pops the oldest value
+/// of its queue and, if the queue still has values, moves the key to the back
+/// of the ring.
+///
+/// # Example
+///
+/// ```
+/// use amortized_queue::RoundRobinQueues;
+///
+/// let mut queues = RoundRobinQueues::default();
+/// queues.push("a", 1);
+/// queues.push("a", 2);
+/// queues.push("a", 3);
+/// queues.push("b", 4);
+///
+/// assert_eq!(queues.pop_next(), Some(("a", 1)));
+/// assert_eq!(queues.pop_next(), Some(("b", 4)));
+/// assert_eq!(queues.pop_next(), Some(("a", 2)));
+/// assert_eq!(queues.pop_next(), Some(("a", 3)));
+/// assert_eq!(queues.pop_next(), None);
+/// ```
+pub struct RoundRobinQueues<K, T> {
+    queues: HashMap<K, AmortizedQueue<T>>,
+    // The keys whose queues aren't empty, each exactly once, in the order
+    // they will be served.
+    ring: AmortizedQueue<K>,
+    len: usize,
+}
+
+impl<K, T> Default for RoundRobinQueues<K, T> {
+    fn default() -> Self {
+        RoundRobinQueues {
+            queues: HashMap::new(),
+            ring: AmortizedQueue::default(),
+            len: 0,
+        }
+    }
+}
+
+impl<K, T> RoundRobinQueues<K, T>
+where
+    K: Eq + Hash + Clone,
+{
+    /// Returns the number of values across every queue.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns a boolean indicating every queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of values in the queue of a key.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn queue_len(&self, key: &K) -> usize {
+        self.queues.get(key).map_or(0, |queue| queue.len())
+    }
+
+    /// Adds a value to the back of the queue of a key. A key that had no
+    /// values waiting joins the back of the ring.
+    ///
+    /// Time Complexity: O(1) amortized
+    /// Space Complexity: O(1) amortized
+    pub fn push(&mut self, key: K, v: T) {
+        let queue = self.queues.entry(key.clone()).or_default();
+        if queue.is_empty() {
+            self.ring.push(key);
+        }
+
+        queue.push(v);
+        self.len += 1;
+    }
+
+    /// Removes and returns the oldest value of the next key in the ring,
+    /// together with its key.
+    ///
+    /// Time Complexity: O(1) amortized
+    /// Space Complexity: O(1)
+    pub fn pop_next(&mut self) -> Option<(K, T)> {
+        let key = self.ring.pop()?;
+        let queue = self
+            .queues
+            .get_mut(&key)
+            .expect("keys in the ring have a queue");
+        let v = queue.pop().expect("keys in the ring have values waiting");
+
+        if queue.is_empty() {
+            // Forget the key, so keys that come and go don't pile up.
+            self.queues.remove(&key);
+        } else {
+            self.ring.push(key.clone());
+        }
+
+        self.len -= 1;
+        Some((key, v))
+    }
+
+    /// Removes the queue of a key and returns its values, oldest first.
+    ///
+    /// Time Complexity: O(k) where k is the number of keys with values waiting
+    /// Space Complexity: O(k)
+    pub fn remove(&mut self, key: &K) -> Option<AmortizedQueue<T>> {
+        let queue = self.queues.remove(key)?;
+
+        let mut ring = AmortizedQueue::default();
+        while let Some(k) = self.ring.pop() {
+            if k != *key {
+                ring.push(k);
+            }
+        }
+        self.ring = ring;
+        self.len -= queue.len();
+
+        Some(queue)
+    }
+
+    /// Removes every queue.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    pub fn clear(&mut self) {
+        self.queues.clear();
+        self.ring.clear();
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn serves_keys_in_turn() {
+        let mut queues = RoundRobinQueues::default();
+        for i in 0..6 {
+            queues.push("a", i);
+        }
+        queues.push("b", 10);
+        queues.push("c", 20);
+        queues.push("c", 21);
+        assert_eq!(queues.len(), 9);
+        assert_eq!(queues.queue_len(&"a"), 6);
+
+        let mut popped = Vec::new();
+        while let Some(v) = queues.pop_next() {
+            popped.push(v);
+        }
+
+        assert_eq!(
+            popped,
+            vec![
+                ("a", 0),
+                ("b", 10),
+                ("c", 20),
+                ("a", 1),
+                ("c", 21),
+                ("a", 2),
+                ("a", 3),
+                ("a", 4),
+                ("a", 5),
+            ]
+        );
+        assert!(queues.is_empty());
+        assert_eq!(queues.queue_len(&"a"), 0);
+    }
+
+    #[test]
+    fn key_rejoins_at_the_back() {
+        let mut queues = RoundRobinQueues::default();
+        queues.push("a", 1);
+        queues.push("b", 2);
+        queues.push("b", 3);
+
+        assert_eq!(queues.pop_next(), Some(("a", 1)));
+        queues.push("a", 4);
+
+        assert_eq!(queues.pop_next(), Some(("b", 2)));
+        assert_eq!(queues.pop_next(), Some(("a", 4)));
+        assert_eq!(queues.pop_next(), Some(("b", 3)));
+        assert_eq!(queues.pop_next(), None);
+    }
+
+    #[test]
+    fn remove_queue() {
+        let mut queues = RoundRobinQueues::default();
+        queues.push("a", 1);
+        queues.push("b", 2);
+        queues.push("a", 3);
+        queues.push("c", 4);
+
+        let removed = queues.remove(&"a").unwrap();
+        assert_eq!(removed.iter().collect::<Vec<_>>(), vec![&1, &3]);
+        assert!(queues.remove(&"a").is_none());
+        assert_eq!(queues.len(), 2);
+
+        assert_eq!(queues.pop_next(), Some(("b", 2)));
+        assert_eq!(queues.pop_next(), Some(("c", 4)));
+        assert_eq!(queues.pop_next(), None);
+
+        queues.push("a", 5);
+        queues.clear();
+        assert!(queues.is_empty());
+        assert_eq!(queues.pop_next(), None);
+    }
+}
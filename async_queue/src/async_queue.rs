@@ -0,0 +1,380 @@
+use amortized_queue::AmortizedQueue;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Waker};
+
+/// The state shared by the clones of an AsyncQueue.
+struct Inner<T> {
+    items: AmortizedQueue<T>,
+    // The waker of each parked Pop, keyed by the order it first parked in, so
+    // a Pop has a single slot however many times it is polled.
+    wakers: BTreeMap<u64, Waker>,
+    next_id: u64,
+    closed: bool,
+}
+
+/// AsyncQueue is an unbounded MPMC queue. `pop().await` resolves to the next
+/// item, parking the task until one is pushed. Clones share the same queue,
+/// and can be sent to other threads.
+///
+/// Once the AsyncQueue is closed, pushing is refused and `pop()` resolves to
+/// None after the remaining items have been popped.
+///
+/// # Example
+///
+/// ```
+/// use async_queue::AsyncQueue;
+///
+/// let queue = AsyncQueue::default();
+/// let producer = queue.clone();
+///
+/// producer.push(1).unwrap();
+/// producer.close();
+///
+/// assert_eq!(queue.try_pop(), Some(1));
+/// assert!(queue.is_closed());
+/// ```
+pub struct AsyncQueue<T> {
+    inner: Arc<Mutex<Inner<T>>>,
+}
+
+impl<T> Default for AsyncQueue<T> {
+    fn default() -> Self {
+        AsyncQueue {
+            inner: Arc::new(Mutex::new(Inner {
+                items: AmortizedQueue::default(),
+                wakers: BTreeMap::new(),
+                next_id: 0,
+                closed: false,
+            })),
+        }
+    }
+}
+
+impl<T> Clone for AsyncQueue<T> {
+    fn clone(&self) -> Self {
+        AsyncQueue {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T> AsyncQueue<T> {
+    /// Locks the shared state. Every change to it is complete before the
+    /// lock is released, so a poisoned lock is still consistent.
+    fn lock(&self) -> MutexGuard<'_, Inner<T>> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the number of items waiting in the AsyncQueue.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.lock().items.len()
+    }
+
+    /// Returns a boolean indicating the AsyncQueue has no items waiting.
+    pub fn is_empty(&self) -> bool {
+        self.lock().items.is_empty()
+    }
+
+    /// Returns a boolean indicating the AsyncQueue has been closed.
+    pub fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    /// Adds an item to the AsyncQueue and wakes one parked task. Returns the
+    /// item if the AsyncQueue is closed.
+    ///
+    /// Time Complexity: O(log w), where w is the number of parked tasks
+    /// Space Complexity: O(1) amortized
+    pub fn push(&self, v: T) -> Result<(), T> {
+        let waker = {
+            let mut inner = self.lock();
+            if inner.closed {
+                return Err(v);
+            }
+
+            inner.items.push(v);
+            inner.wakers.pop_first().map(|(_, waker)| waker)
+        };
+
+        // The wakers are called after the lock is released, in case the
+        // executor polls the woken task straight away.
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+
+        Ok(())
+    }
+
+    /// Returns the next item if one is waiting, without parking.
+    ///
+    /// Time Complexity: O(1) amortized
+    /// Space Complexity: O(1)
+    pub fn try_pop(&self) -> Option<T> {
+        self.lock().items.pop()
+    }
+
+    /// Returns a Future that resolves to the next item, or to None once the
+    /// AsyncQueue is closed and empty.
+    pub fn pop(&self) -> Pop<T> {
+        Pop {
+            queue: self.clone(),
+            id: None,
+            parked: false,
+        }
+    }
+
+    /// Closes the AsyncQueue and wakes every parked task, so they can see it
+    /// is closed.
+    ///
+    /// Time Complexity: O(w), where w is the number of parked tasks
+    /// Space Complexity: O(1)
+    pub fn close(&self) {
+        let wakers = {
+            let mut inner = self.lock();
+            inner.closed = true;
+            std::mem::take(&mut inner.wakers)
+        };
+
+        for waker in wakers.into_values() {
+            waker.wake();
+        }
+    }
+}
+
+/// The Future returned by `AsyncQueue::pop()`.
+pub struct Pop<T> {
+    queue: AsyncQueue<T>,
+    // The key of the waker slot, given the first time the Future parks and
+    // kept, so a task polled again keeps its place in line.
+    id: Option<u64>,
+    // Whether the task was parked by this Future, so a wakeup meant for it
+    // can be passed on if it is dropped.
+    parked: bool,
+}
+
+impl<T> Future for Pop<T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let this = self.get_mut();
+        let mut inner = this.queue.lock();
+
+        let ready = match inner.items.pop() {
+            Some(v) => Some(Some(v)),
+            None if inner.closed => Some(None),
+            None => None,
+        };
+        if let Some(item) = ready {
+            if let Some(id) = this.id {
+                inner.wakers.remove(&id);
+            }
+            this.parked = false;
+            return Poll::Ready(item);
+        }
+
+        let id = *this.id.get_or_insert_with(|| {
+            inner.next_id += 1;
+            inner.next_id
+        });
+        // Polled again, the task replaces its own waker rather than taking
+        // another slot.
+        inner.wakers.insert(id, cx.waker().clone());
+        this.parked = true;
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for Pop<T> {
+    fn drop(&mut self) {
+        if !self.parked {
+            return;
+        }
+
+        let waker = {
+            let mut inner = self.queue.lock();
+            // A slot still there means the task wasn't woken.
+            if let Some(id) = self.id {
+                if inner.wakers.remove(&id).is_some() {
+                    return;
+                }
+            }
+
+            // The task was woken for an item it will never pop, so the next
+            // parked task is woken in its place.
+            if inner.items.is_empty() {
+                return;
+            }
+
+            inner.wakers.pop_first().map(|(_, waker)| waker)
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::Wake;
+
+    /// Counts how many times it has been woken.
+    #[derive(Default)]
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn waker() -> (Arc<CountingWaker>, Waker) {
+        let count = Arc::new(CountingWaker::default());
+        (count.clone(), Waker::from(count))
+    }
+
+    fn poll<T>(future: &mut Pop<T>, waker: &Waker) -> Poll<Option<T>> {
+        Pin::new(future).poll(&mut Context::from_waker(waker))
+    }
+
+    #[test]
+    fn pop_ready_item() {
+        let queue = AsyncQueue::default();
+        queue.push(1).unwrap();
+        let (_, waker) = waker();
+
+        assert_eq!(poll(&mut queue.pop(), &waker), Poll::Ready(Some(1)));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn push_wakes_parked_task() {
+        let queue = AsyncQueue::default();
+        let (count, waker) = waker();
+
+        let mut pop = queue.pop();
+        assert_eq!(poll(&mut pop, &waker), Poll::Pending);
+        assert_eq!(count.0.load(Ordering::SeqCst), 0);
+
+        queue.clone().push(7).unwrap();
+        assert_eq!(count.0.load(Ordering::SeqCst), 1);
+        assert_eq!(poll(&mut pop, &waker), Poll::Ready(Some(7)));
+    }
+
+    #[test]
+    fn tasks_are_woken_in_order() {
+        let queue = AsyncQueue::<u32>::default();
+        let (first_count, first) = waker();
+        let (second_count, second) = waker();
+
+        let mut a = queue.pop();
+        let mut b = queue.pop();
+        assert_eq!(poll(&mut a, &first), Poll::Pending);
+        assert_eq!(poll(&mut b, &second), Poll::Pending);
+
+        queue.push(1).unwrap();
+        assert_eq!(first_count.0.load(Ordering::SeqCst), 1);
+        assert_eq!(second_count.0.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn dropped_task_passes_on_its_wakeup() {
+        let queue = AsyncQueue::<u32>::default();
+        let (_, first) = waker();
+        let (second_count, second) = waker();
+
+        let mut a = queue.pop();
+        let mut b = queue.pop();
+        assert_eq!(poll(&mut a, &first), Poll::Pending);
+        assert_eq!(poll(&mut b, &second), Poll::Pending);
+
+        queue.push(1).unwrap();
+        drop(a);
+        assert_eq!(second_count.0.load(Ordering::SeqCst), 1);
+        assert_eq!(poll(&mut b, &second), Poll::Ready(Some(1)));
+    }
+
+    #[test]
+    fn repolled_task_keeps_one_slot() {
+        let queue = AsyncQueue::<u32>::default();
+        let (first_count, first) = waker();
+        let (second_count, second) = waker();
+
+        // Polled twice, a must not leave a stale waker behind.
+        let mut a = queue.pop();
+        let mut b = queue.pop();
+        assert_eq!(poll(&mut a, &first), Poll::Pending);
+        assert_eq!(poll(&mut a, &first), Poll::Pending);
+        assert_eq!(poll(&mut b, &second), Poll::Pending);
+
+        queue.push(1).unwrap();
+        assert_eq!(first_count.0.load(Ordering::SeqCst), 1);
+        assert_eq!(poll(&mut a, &first), Poll::Ready(Some(1)));
+
+        queue.push(2).unwrap();
+        assert_eq!(first_count.0.load(Ordering::SeqCst), 1);
+        assert_eq!(second_count.0.load(Ordering::SeqCst), 1);
+        assert_eq!(poll(&mut b, &second), Poll::Ready(Some(2)));
+    }
+
+    #[test]
+    fn dropped_parked_task_gives_up_its_slot() {
+        let queue = AsyncQueue::<u32>::default();
+        let (first_count, first) = waker();
+        let (second_count, second) = waker();
+
+        let mut a = queue.pop();
+        let mut b = queue.pop();
+        assert_eq!(poll(&mut a, &first), Poll::Pending);
+        assert_eq!(poll(&mut b, &second), Poll::Pending);
+        drop(a);
+
+        queue.push(1).unwrap();
+        assert_eq!(first_count.0.load(Ordering::SeqCst), 0);
+        assert_eq!(second_count.0.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn pop_across_threads() {
+        let queue = AsyncQueue::default();
+        let (count, waker) = waker();
+
+        let mut pop = queue.pop();
+        assert_eq!(poll(&mut pop, &waker), Poll::Pending);
+
+        let producer = queue.clone();
+        std::thread::spawn(move || producer.push(5).unwrap())
+            .join()
+            .unwrap();
+        assert_eq!(count.0.load(Ordering::SeqCst), 1);
+
+        let consumer = std::thread::spawn(move || poll(&mut pop, &waker));
+        assert_eq!(consumer.join().unwrap(), Poll::Ready(Some(5)));
+    }
+
+    #[test]
+    fn close() {
+        let queue = AsyncQueue::default();
+        let (count, waker) = waker();
+
+        let mut pop = queue.pop();
+        assert_eq!(poll(&mut pop, &waker), Poll::Pending);
+
+        queue.push(1).unwrap();
+        queue.close();
+        assert_eq!(queue.push(2), Err(2));
+        assert_eq!(count.0.load(Ordering::SeqCst), 1);
+
+        assert_eq!(poll(&mut pop, &waker), Poll::Ready(Some(1)));
+        assert_eq!(poll(&mut queue.pop(), &waker), Poll::Ready(None));
+    }
+}
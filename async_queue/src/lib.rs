@@ -0,0 +1,4 @@
+//! A crate that implements an AsyncQueue.
+pub use crate::async_queue::{AsyncQueue, Pop};
+
+mod async_queue;
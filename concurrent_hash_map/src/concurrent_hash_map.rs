@@ -0,0 +1,525 @@
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::mem;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use traits::{Collection, Map};
+
+/// The number of shards used by `ConcurrentHashMap::new()`.
+const DEFAULT_SHARDS: usize = 16;
+
+/// The number of buckets a shard starts with.
+const INITIAL_BUCKETS: usize = 8;
+
+/// The average length of the chains above which a shard doubles its buckets.
+const LOAD_FACTOR: usize = 2;
+
+/// A chained hash table holding the entries of one shard.
+struct Shard<K, V> {
+    buckets: Vec<Vec<(K, V)>>,
+    len: usize,
+}
+
+impl<K, V> Shard<K, V> {
+    fn new() -> Shard<K, V> {
+        Shard {
+            buckets: (0..INITIAL_BUCKETS).map(|_| Vec::new()).collect(),
+            len: 0,
+        }
+    }
+
+    /// Returns the bucket of a hash, the buckets are a power of two.
+    fn bucket(&self, hash: u64) -> usize {
+        hash as usize & (self.buckets.len() - 1)
+    }
+}
+
+/// ConcurrentHashMap is a hash map that can be shared between threads. Its
+/// entries are split across shards, each a chained hash table behind its own
+/// RwLock, so threads only contend when they touch the same shard.
+///
+/// # Example
+///
+/// ```
+/// use concurrent_hash_map::ConcurrentHashMap;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let map = Arc::new(ConcurrentHashMap::new());
+///
+/// let handles: Vec<_> = (0..4)
+///     .map(|t| {
+///         let map = Arc::clone(&map);
+///         thread::spawn(move || {
+///             for i in 0..100 {
+///                 map.insert(t * 100 + i, i);
+///             }
+///         })
+///     })
+///     .collect();
+/// for handle in handles {
+///     handle.join().unwrap();
+/// }
+///
+/// assert_eq!(map.len(), 400);
+/// assert_eq!(map.get(&250), Some(50));
+/// ```
+pub struct ConcurrentHashMap<K, V, S = RandomState> {
+    shards: Box<[RwLock<Shard<K, V>>]>,
+    hasher: S,
+    // The shard of a hash is in its top bits.
+    shift: u32,
+    len: AtomicUsize,
+}
+
+impl<K, V> Default for ConcurrentHashMap<K, V> {
+    fn default() -> Self {
+        ConcurrentHashMap::with_shards(DEFAULT_SHARDS)
+    }
+}
+
+impl<K, V> ConcurrentHashMap<K, V> {
+    /// Returns an empty ConcurrentHashMap with 16 shards.
+    pub fn new() -> ConcurrentHashMap<K, V> {
+        ConcurrentHashMap::default()
+    }
+
+    /// Returns an empty ConcurrentHashMap split into `shards` shards, rounded
+    /// up to a power of two. More shards let more writers run at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is 0.
+    pub fn with_shards(shards: usize) -> ConcurrentHashMap<K, V> {
+        ConcurrentHashMap::with_shards_and_hasher(shards, RandomState::new())
+    }
+}
+
+impl<K, V, S> ConcurrentHashMap<K, V, S> {
+    /// Returns an empty ConcurrentHashMap split into `shards` shards, rounded
+    /// up to a power of two, hashing its keys with `hasher`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is 0.
+    pub fn with_shards_and_hasher(shards: usize, hasher: S) -> ConcurrentHashMap<K, V, S> {
+        assert!(shards > 0, "a ConcurrentHashMap needs at least one shard");
+        let shards = shards.next_power_of_two();
+
+        ConcurrentHashMap {
+            shards: (0..shards).map(|_| RwLock::new(Shard::new())).collect(),
+            hasher,
+            // With a single shard the shift would be 64, which overflows, so
+            // it is checked when picking the shard.
+            shift: u64::BITS - shards.trailing_zeros(),
+            len: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the number of entries in the ConcurrentHashMap. Other threads
+    /// may change it as soon as it is read.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Acquire)
+    }
+
+    /// Returns a boolean indicating the ConcurrentHashMap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes every entry, one shard at a time. Entries inserted into a
+    /// shard after it was cleared are kept.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    pub fn clear(&self) {
+        for shard in self.shards.iter() {
+            let mut shard = write(shard);
+            let removed = shard.len;
+            *shard = Shard::new();
+            self.len.fetch_sub(removed, Ordering::AcqRel);
+        }
+    }
+}
+
+impl<K, V, S> ConcurrentHashMap<K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+{
+    /// Inserts a value, returning the value it replaced.
+    ///
+    /// Time Complexity: O(1) on average
+    /// Space Complexity: O(1) amortized
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        let hash = self.hash(&key);
+        let mut shard = write(self.shard(hash));
+
+        let bucket = shard.bucket(hash);
+        if let Some((_, v)) = shard.buckets[bucket].iter_mut().find(|(k, _)| *k == key) {
+            return Some(mem::replace(v, value));
+        }
+
+        shard.buckets[bucket].push((key, value));
+        shard.len += 1;
+        self.len.fetch_add(1, Ordering::AcqRel);
+
+        if shard.len > shard.buckets.len() * LOAD_FACTOR {
+            self.grow(&mut shard);
+        }
+
+        None
+    }
+
+    /// Returns a clone of the value of a key.
+    ///
+    /// Time Complexity: O(1) on average
+    /// Space Complexity: O(1)
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        V: Clone,
+    {
+        self.get_with(key, V::clone)
+    }
+
+    /// Calls `f` with the value of a key while the shard is locked for
+    /// reading, e.g. to read part of a value without cloning all of it.
+    ///
+    /// Time Complexity: O(1) on average
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use concurrent_hash_map::ConcurrentHashMap;
+    ///
+    /// let map = ConcurrentHashMap::new();
+    /// map.insert("a", vec![1, 2, 3]);
+    ///
+    /// assert_eq!(map.get_with("a", |v| v.len()), Some(3));
+    /// ```
+    pub fn get_with<Q, R, F>(&self, key: &Q, f: F) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: FnOnce(&V) -> R,
+    {
+        let hash = self.hash(key);
+        let shard = read(self.shard(hash));
+
+        shard.buckets[shard.bucket(hash)]
+            .iter()
+            .find(|(k, _)| k.borrow() == key)
+            .map(|(_, v)| f(v))
+    }
+
+    /// Returns a boolean indicating the key is in the ConcurrentHashMap.
+    ///
+    /// Time Complexity: O(1) on average
+    /// Space Complexity: O(1)
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_with(key, |_| ()).is_some()
+    }
+
+    /// Removes a key, returning its value.
+    ///
+    /// Time Complexity: O(1) on average
+    /// Space Complexity: O(1)
+    pub fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hash = self.hash(key);
+        let mut shard = write(self.shard(hash));
+
+        let bucket = shard.bucket(hash);
+        let i = shard.buckets[bucket]
+            .iter()
+            .position(|(k, _)| k.borrow() == key)?;
+        let (_, value) = shard.buckets[bucket].swap_remove(i);
+        shard.len -= 1;
+        self.len.fetch_sub(1, Ordering::AcqRel);
+
+        Some(value)
+    }
+
+    /// Applies `f` to the value of a key, or inserts `default` first if the
+    /// key is missing, as a single step no other thread can interleave with.
+    ///
+    /// Time Complexity: O(1) on average
+    /// Space Complexity: O(1) amortized
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use concurrent_hash_map::ConcurrentHashMap;
+    ///
+    /// let counts = ConcurrentHashMap::new();
+    /// counts.update("a", 0, |c| *c += 1);
+    /// counts.update("a", 0, |c| *c += 1);
+    ///
+    /// assert_eq!(counts.get("a"), Some(2));
+    /// ```
+    pub fn update<F>(&self, key: K, default: V, f: F)
+    where
+        F: FnOnce(&mut V),
+    {
+        let hash = self.hash(&key);
+        let mut shard = write(self.shard(hash));
+
+        let bucket = shard.bucket(hash);
+        if let Some((_, v)) = shard.buckets[bucket].iter_mut().find(|(k, _)| *k == key) {
+            f(v);
+            return;
+        }
+
+        let mut value = default;
+        f(&mut value);
+        shard.buckets[bucket].push((key, value));
+        shard.len += 1;
+        self.len.fetch_add(1, Ordering::AcqRel);
+
+        if shard.len > shard.buckets.len() * LOAD_FACTOR {
+            self.grow(&mut shard);
+        }
+    }
+
+    /// Returns clones of the entries, in no particular order. Each shard is
+    /// copied under its read lock, but the shards are copied one after the
+    /// other, so writes made meanwhile to the shards not yet copied show up.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(n)
+    pub fn snapshot(&self) -> Vec<(K, V)>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        let mut entries = Vec::with_capacity(self.len());
+        for shard in self.shards.iter() {
+            let shard = read(shard);
+            entries.extend(shard.buckets.iter().flatten().cloned());
+        }
+
+        entries
+    }
+
+    fn hash<Q: Hash + ?Sized>(&self, key: &Q) -> u64 {
+        self.hasher.hash_one(key)
+    }
+
+    /// Returns the shard of a hash, picked by its top bits so the low bits
+    /// are left to pick the bucket.
+    fn shard(&self, hash: u64) -> &RwLock<Shard<K, V>> {
+        let i = if self.shards.len() == 1 {
+            0
+        } else {
+            (hash >> self.shift) as usize
+        };
+
+        &self.shards[i]
+    }
+
+    /// Doubles the buckets of a shard and moves each entry to its new bucket.
+    fn grow(&self, shard: &mut Shard<K, V>) {
+        let buckets = mem::take(&mut shard.buckets);
+        shard.buckets = (0..buckets.len() * 2).map(|_| Vec::new()).collect();
+
+        for (key, value) in buckets.into_iter().flatten() {
+            let bucket = shard.bucket(self.hash(&key));
+            shard.buckets[bucket].push((key, value));
+        }
+    }
+}
+
+// The Collection iterates over a snapshot, the entries can't be borrowed out
+// of the shards once their locks are released.
+impl<K, V, S> Collection for ConcurrentHashMap<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher,
+{
+    type Iter<'a>
+        = std::vec::IntoIter<(K, V)>
+    where
+        Self: 'a;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn clear(&mut self) {
+        ConcurrentHashMap::clear(self)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.snapshot().into_iter()
+    }
+}
+
+impl<K, V, S> Map<K, V> for ConcurrentHashMap<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher,
+{
+    fn insert(&mut self, key: K, value: V) {
+        ConcurrentHashMap::insert(self, key, value);
+    }
+
+    fn get(&mut self, key: &K) -> Option<V> {
+        ConcurrentHashMap::get(self, key)
+    }
+}
+
+/// Locks a shard for reading. A panic while a shard was locked can only have
+/// happened between complete updates of a chain, so a poisoned lock is still
+/// used.
+fn read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Locks a shard for writing, see `read()`.
+fn write<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn insert_get_remove() {
+        let map = ConcurrentHashMap::new();
+
+        assert_eq!(map.insert("a".to_string(), 1), None);
+        assert_eq!(map.insert("b".to_string(), 2), None);
+        assert_eq!(map.insert("a".to_string(), 3), Some(1));
+        assert_eq!(map.len(), 2);
+
+        // Lookups can borrow the key as a &str.
+        assert_eq!(map.get("a"), Some(3));
+        assert!(map.contains_key("b"));
+        assert_eq!(map.remove("b"), Some(2));
+        assert_eq!(map.remove("b"), None);
+        assert!(!map.contains_key("b"));
+        assert_eq!(map.len(), 1);
+
+        map.clear();
+        assert!(map.is_empty());
+        assert_eq!(map.get("a"), None);
+    }
+
+    #[test]
+    fn shards_grow() {
+        for shards in &[1, 3, 16] {
+            let map = ConcurrentHashMap::with_shards(*shards);
+            for i in 0..10_000 {
+                map.insert(i, i * 2);
+            }
+
+            assert_eq!(map.len(), 10_000);
+            for i in 0..10_000 {
+                assert_eq!(map.get(&i), Some(i * 2));
+            }
+            for shard in map.shards.iter() {
+                let shard = read(shard);
+                assert!(shard.len <= shard.buckets.len() * LOAD_FACTOR);
+            }
+        }
+    }
+
+    #[test]
+    fn concurrent_updates() {
+        let map = Arc::new(ConcurrentHashMap::with_shards(4));
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let map = Arc::clone(&map);
+                thread::spawn(move || {
+                    for i in 0..1_000 {
+                        map.update(i % 10, 0, |c| *c += 1);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(map.len(), 10);
+        for i in 0..10 {
+            assert_eq!(map.get(&i), Some(800));
+        }
+    }
+
+    #[test]
+    fn concurrent_readers_and_writers() {
+        let map = Arc::new(ConcurrentHashMap::new());
+        for i in 0..100 {
+            map.insert(i, i);
+        }
+
+        let writers: Vec<_> = (0..4)
+            .map(|t| {
+                let map = Arc::clone(&map);
+                thread::spawn(move || {
+                    for i in 0..1_000 {
+                        map.insert(100 + t * 1_000 + i, i);
+                        map.remove(&(100 + t * 1_000 + i));
+                    }
+                })
+            })
+            .collect();
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let map = Arc::clone(&map);
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        for i in 0..100 {
+                            assert_eq!(map.get(&i), Some(i));
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        for handle in writers.into_iter().chain(readers) {
+            handle.join().unwrap();
+        }
+        assert_eq!(map.len(), 100);
+    }
+
+    #[test]
+    fn generic_over_map() {
+        fn fill<M: Map<u32, u32>>(map: &mut M) {
+            map.insert(1, 1);
+            map.insert(2, 2);
+        }
+
+        let mut map = ConcurrentHashMap::new();
+        fill(&mut map);
+        assert_eq!(Map::get(&mut map, &2), Some(2));
+
+        let mut entries: Vec<_> = Collection::iter(&map).collect();
+        entries.sort();
+        assert_eq!(entries, vec![(1, 1), (2, 2)]);
+
+        Collection::clear(&mut map);
+        assert!(Collection::is_empty(&map));
+    }
+}
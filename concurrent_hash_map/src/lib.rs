@@ -0,0 +1,8 @@
+//! A crate that implements a ConcurrentHashMap, a hash map that can be shared
+//! between threads, and an RcuMap, a map of immutable snapshots for data that
+//! is read much more often than it is written.
+pub use crate::concurrent_hash_map::ConcurrentHashMap;
+pub use crate::rcu_map::RcuMap;
+
+mod concurrent_hash_map;
+mod rcu_map;
@@ -0,0 +1,237 @@
+use arc_swap::ArcSwap;
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// RcuMap is a read-copy-update map for data that is read by many threads
+/// and rarely written, e.g. config or routing tables.
+///
+/// Readers take a snapshot, an `Arc` of the whole map that no write will
+/// ever change. Writers clone the current map, change the clone and swap it
+/// in. The current `Arc` sits behind an atomic pointer, so readers never
+/// take a lock and never wait on a write in progress.
+///
+/// # Example
+///
+/// ```
+/// use concurrent_hash_map::RcuMap;
+///
+/// let routes = RcuMap::new();
+/// routes.insert("/", "index");
+///
+/// let before = routes.snapshot();
+/// routes.insert("/about", "about");
+///
+/// // A snapshot doesn't see the writes made after it was taken.
+/// assert_eq!(before.len(), 1);
+/// assert_eq!(routes.get("/about"), Some("about"));
+/// ```
+pub struct RcuMap<K, V> {
+    current: ArcSwap<BTreeMap<K, V>>,
+    // Writers run one at a time, otherwise two writers could clone the same
+    // map and the second swap would lose the first write.
+    writer: Mutex<()>,
+}
+
+impl<K: Ord, V> Default for RcuMap<K, V> {
+    fn default() -> Self {
+        RcuMap::from(BTreeMap::new())
+    }
+}
+
+impl<K: Ord, V> From<BTreeMap<K, V>> for RcuMap<K, V> {
+    fn from(map: BTreeMap<K, V>) -> Self {
+        RcuMap {
+            current: ArcSwap::from_pointee(map),
+            writer: Mutex::new(()),
+        }
+    }
+}
+
+impl<K: Ord, V> RcuMap<K, V> {
+    /// Returns an empty RcuMap.
+    pub fn new() -> RcuMap<K, V> {
+        RcuMap::default()
+    }
+
+    /// Returns the current version of the map. The snapshot stays the same
+    /// whatever is written afterwards, and is freed with its last reader.
+    /// No lock is taken, the Arc is loaded from an atomic pointer.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn snapshot(&self) -> Arc<BTreeMap<K, V>> {
+        self.current.load_full()
+    }
+
+    /// Returns a clone of the value of a key in the current version.
+    ///
+    /// Time Complexity: O(log n)
+    /// Space Complexity: O(1)
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        V: Clone,
+    {
+        self.snapshot().get(key).cloned()
+    }
+
+    /// Returns the number of entries in the current version.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.snapshot().len()
+    }
+
+    /// Returns a boolean indicating the current version is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> RcuMap<K, V> {
+    /// Applies `f` to a copy of the current map and publishes the copy as the
+    /// next version, so several changes show up to readers at once.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(n)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use concurrent_hash_map::RcuMap;
+    ///
+    /// let limits = RcuMap::new();
+    /// limits.update(|map| {
+    ///     map.insert("reads", 100);
+    ///     map.insert("writes", 10);
+    /// });
+    ///
+    /// assert_eq!(limits.snapshot().len(), 2);
+    /// ```
+    pub fn update<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut BTreeMap<K, V>) -> R,
+    {
+        // A writer that panicked never swapped its copy, so the current map
+        // is intact.
+        let _writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let mut next = BTreeMap::clone(&self.current.load());
+        let result = f(&mut next);
+        self.current.store(Arc::new(next));
+
+        result
+    }
+
+    /// Inserts a value into a new version, returning the value it replaced.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(n)
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        self.update(|map| map.insert(key, value))
+    }
+
+    /// Removes a key in a new version, returning its value. Nothing is
+    /// published if the key is missing.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(n)
+    pub fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if !self.snapshot().contains_key(key) {
+            return None;
+        }
+
+        self.update(|map| map.remove(key))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn snapshots_are_immutable() {
+        let map = RcuMap::new();
+        map.insert(1, "a");
+        let first = map.snapshot();
+
+        assert_eq!(map.insert(1, "b"), Some("a"));
+        map.insert(2, "c");
+        assert_eq!(map.remove(&3), None);
+        let second = map.snapshot();
+
+        assert_eq!(first.get(&1), Some(&"a"));
+        assert_eq!(first.len(), 1);
+        assert_eq!(second.get(&1), Some(&"b"));
+        assert_eq!(map.len(), 2);
+
+        assert_eq!(map.remove(&1), Some("b"));
+        assert_eq!(map.get(&1), None);
+        assert_eq!(second.len(), 2);
+    }
+
+    #[test]
+    fn concurrent_readers_see_whole_updates() {
+        let map = Arc::new(RcuMap::new());
+
+        // Every update writes both keys, so a reader must never see them differ.
+        let writer = {
+            let map = Arc::clone(&map);
+            thread::spawn(move || {
+                for i in 0..1_000 {
+                    map.update(|m| {
+                        m.insert("a", i);
+                        m.insert("b", i);
+                    });
+                }
+            })
+        };
+
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let map = Arc::clone(&map);
+                thread::spawn(move || {
+                    for _ in 0..1_000 {
+                        let snapshot = map.snapshot();
+                        assert_eq!(snapshot.get("a"), snapshot.get("b"));
+                    }
+                })
+            })
+            .collect();
+
+        writer.join().unwrap();
+        for reader in readers {
+            reader.join().unwrap();
+        }
+        assert_eq!(map.get("a"), Some(999));
+    }
+
+    #[test]
+    fn concurrent_writers_lose_nothing() {
+        let map = Arc::new(RcuMap::new());
+
+        let handles: Vec<_> = (0..4)
+            .map(|t| {
+                let map = Arc::clone(&map);
+                thread::spawn(move || {
+                    for i in 0..50 {
+                        map.insert(t * 50 + i, i);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(map.len(), 200);
+    }
+}
@@ -0,0 +1,60 @@
+//! Compares get(), which walks from whichever end of the LinkedList is closer
+//! to the index, with walking from the head alone, for every index.
+//!
+//! cargo run --release --example get
+use doubly_linked_list::LinkedList;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+// The walks add up to n^2 / 4 steps from the closer end, n^2 / 2 from the
+// head.
+const VALUES: u64 = 10_000;
+const ROUNDS: u32 = 5;
+
+/// Runs `f` a few times and returns the fastest run, the others being slowed
+/// down by whatever else the machine was doing.
+fn fastest<F: FnMut()>(mut f: F) -> Duration {
+    (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn report(name: &str, elapsed: Duration) {
+    let per_second = VALUES as f64 / elapsed.as_secs_f64();
+    println!(
+        "{:<24} {:>8.2} ms  {:>8.3} M gets/s",
+        name,
+        elapsed.as_secs_f64() * 1000.0,
+        per_second / 1_000_000.0
+    );
+}
+
+fn main() {
+    let mut list = LinkedList::default();
+    for i in 0..VALUES {
+        list.push(i);
+    }
+
+    report(
+        "get from the closer end",
+        fastest(|| {
+            for i in 0..VALUES as usize {
+                black_box(list.get(black_box(i)));
+            }
+        }),
+    );
+
+    report(
+        "get from the head",
+        fastest(|| {
+            for i in 0..VALUES as usize {
+                black_box(list.into_iter().nth(black_box(i)));
+            }
+        }),
+    );
+}
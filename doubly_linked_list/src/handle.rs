@@ -0,0 +1,118 @@
+use crate::node::NodeRef;
+use std::cell::{Ref, RefMut};
+use std::pin::Pin;
+use std::rc::Rc;
+
+/// Handle is an opaque reference to a node in a LinkedList.
+///
+/// A Handle stays valid while the node is moved around the list, so it can be
+/// held onto by algorithms that need to revisit a node later (e.g. an LRU
+/// requeueing an entry) without walking the list again. The value is accessed
+/// through `Ref`/`RefMut` guards, the node's links stay private to the list.
+///
+/// A value never moves while its node is alive, whatever is pushed, popped
+/// or moved around it, so `as_ptr()` stays valid until the node is dropped.
+/// Pinning a value with `value_pin()` or `value_pin_mut()` also keeps it in
+/// the node when the node is popped, the list returns a clone instead.
+///
+/// A Handle must only be passed back to the LinkedList it came from.
+#[derive(Clone)]
+pub struct Handle<T>(pub(crate) NodeRef<T>);
+
+impl<T> Handle<T>
+where
+    T: Clone,
+{
+    /// Borrows the value of the node.
+    ///
+    /// Panics if the value is currently borrowed mutably.
+    pub fn value(&self) -> Ref<'_, T> {
+        Ref::map(self.0 .0.borrow(), |n| &n.value)
+    }
+
+    /// Mutably borrows the value of the node.
+    ///
+    /// Panics if the value is currently borrowed, or if it is pinned, a
+    /// `&mut T` could move it. Use `value_pin_mut()` instead.
+    pub fn value_mut(&self) -> RefMut<'_, T> {
+        let node = self.0 .0.borrow_mut();
+        assert!(!node.pinned, "the value is pinned, use value_pin_mut()");
+
+        RefMut::map(node, |n| &mut n.value)
+    }
+
+    /// Pins the value in its node and borrows it.
+    ///
+    /// Panics if the value is currently borrowed mutably.
+    pub fn value_pin(&self) -> Pin<Ref<'_, T>> {
+        self.pin();
+        let value = self.value();
+
+        // SAFETY: see pin().
+        unsafe { Pin::new_unchecked(value) }
+    }
+
+    /// Pins the value in its node and mutably borrows it, e.g. to
+    /// pin-project into a value that points into itself.
+    ///
+    /// Panics if the value is currently borrowed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doubly_linked_list::LinkedList;
+    /// use std::future::Future;
+    /// use std::task::{Context, Poll, Waker};
+    ///
+    /// let mut futures = LinkedList::default();
+    /// futures.push(std::future::ready(1));
+    ///
+    /// let handle = futures.head_handle().unwrap();
+    /// let mut cx = Context::from_waker(Waker::noop());
+    /// let poll = handle.value_pin_mut().as_mut().poll(&mut cx);
+    ///
+    /// assert_eq!(poll, Poll::Ready(1));
+    /// ```
+    pub fn value_pin_mut(&self) -> Pin<RefMut<'_, T>> {
+        self.pin();
+        let value = RefMut::map(self.0 .0.borrow_mut(), |n| &mut n.value);
+
+        // SAFETY: see pin().
+        unsafe { Pin::new_unchecked(value) }
+    }
+
+    /// Returns a boolean indicating the value has been pinned.
+    pub fn is_pinned(&self) -> bool {
+        self.0 .0.borrow().pinned
+    }
+
+    /// Returns a pointer to the value, which stays valid while the node is
+    /// alive, see the stable addresses above.
+    pub fn as_ptr(&self) -> *const T {
+        &*self.value()
+    }
+
+    /// Marks the value as pinned. Once marked, the value is never moved out
+    /// of the node: the node lives in its own Rc allocation and is only
+    /// relinked, into_value() clones a pinned value instead of moving it, and
+    /// value_mut() refuses to hand out a `&mut T`. The value is dropped in
+    /// place with the node.
+    fn pin(&self) {
+        self.0 .0.borrow_mut().pinned = true;
+    }
+
+    /// Returns a Handle to the node after this one.
+    pub fn next(&self) -> Option<Handle<T>> {
+        self.0 .0.borrow().next.clone().map(Handle)
+    }
+
+    /// Returns a Handle to the node before this one.
+    pub fn previous(&self) -> Option<Handle<T>> {
+        self.0 .0.borrow().previous.clone().map(Handle)
+    }
+
+    /// Returns a boolean indicating both Handles refer to the same node.
+    pub fn ptr_eq(&self, other: &Handle<T>) -> bool {
+        Rc::ptr_eq(&self.0 .0, &other.0 .0)
+    }
+}
@@ -1,5 +1,8 @@
 //! A crate that implements a LinkedList.
-pub use crate::linked_list::LinkedList;
+pub use crate::handle::Handle;
+pub use crate::linked_list::{HandleIterator, LinkedList, LinkedListIterator};
 
+mod handle;
 mod linked_list;
 mod node;
+mod observer;
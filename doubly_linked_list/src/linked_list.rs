@@ -1,5 +1,8 @@
+use crate::handle::Handle;
 use crate::node::{Node, NodeRef};
-use std::iter::Iterator;
+use crate::observer::Observers;
+use std::iter::{FusedIterator, Iterator};
+use traits::{Collection, Deque, Queue, Stack};
 
 /// LinkedList is a data structure that references each item T in memory, forming
 /// a chain of referenced objects.
@@ -8,6 +11,7 @@ pub struct LinkedList<T> {
     head: Option<NodeRef<T>>,
     tail: Option<NodeRef<T>>,
     size: u32,
+    observers: Observers<T>,
 }
 
 impl<T> Default for LinkedList<T> {
@@ -16,6 +20,7 @@ impl<T> Default for LinkedList<T> {
             head: None,
             tail: None,
             size: 0,
+            observers: Observers::default(),
         }
     }
 }
@@ -32,17 +37,24 @@ where
 
     fn into_iter(self) -> Self::IntoIter {
         LinkedListIterator {
-            list: self,
-            current: None,
+            _list: self,
+            front: self.head.clone(),
+            back: self.tail.clone(),
+            remaining: self.size,
         }
     }
 }
 
 /// The Iterator implementation for the LinkedList. This Iterator will borrow
 /// the LinkedList.
+///
+/// It walks from both ends and counts the values left between them, so the
+/// two ends stop when they meet and its length is always known.
 pub struct LinkedListIterator<'a, T> {
-    list: &'a LinkedList<T>,
-    current: Option<NodeRef<T>>,
+    _list: &'a LinkedList<T>,
+    front: Option<NodeRef<T>>,
+    back: Option<NodeRef<T>>,
+    remaining: u32,
 }
 
 impl<'a, T> Iterator for LinkedListIterator<'a, T>
@@ -51,18 +63,20 @@ where
 {
     type Item = T;
     fn next(&mut self) -> Option<T> {
-        match self.current.clone() {
-            Some(_) => {
-                self.current
-                    .clone()
-                    .map(|v| self.current = v.0.borrow_mut().next.clone());
-            }
-            None => {
-                self.current = self.list.head.clone();
-            }
-        };
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node = self.front.take()?;
+        self.front = node.0.borrow().next.clone();
+        self.remaining -= 1;
 
-        self.current.clone().map(|v| v.0.borrow_mut().value.clone())
+        let value = node.0.borrow().value.clone();
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining as usize, Some(self.remaining as usize))
     }
 }
 
@@ -71,18 +85,110 @@ where
     T: Clone + std::fmt::Debug,
 {
     fn next_back(&mut self) -> Option<T> {
-        match self.current.clone() {
-            Some(_) => {
-                self.current
-                    .clone()
-                    .map(|v| self.current = v.0.borrow_mut().previous.clone());
-            }
-            None => {
-                self.current = self.list.tail.clone();
-            }
-        };
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node = self.back.take()?;
+        self.back = node.0.borrow().previous.clone();
+        self.remaining -= 1;
+
+        let value = node.0.borrow().value.clone();
+        Some(value)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for LinkedListIterator<'a, T> where T: Clone + std::fmt::Debug {}
+
+impl<'a, T> FusedIterator for LinkedListIterator<'a, T> where T: Clone + std::fmt::Debug {}
+
+/// Iterates over Handles to the nodes of a LinkedList, from head to tail.
+/// This Iterator will borrow the LinkedList.
+pub struct HandleIterator<'a, T> {
+    _list: &'a LinkedList<T>,
+    current: Option<Handle<T>>,
+}
+
+impl<'a, T> Iterator for HandleIterator<'a, T>
+where
+    T: Clone,
+{
+    type Item = Handle<T>;
+
+    fn next(&mut self) -> Option<Handle<T>> {
+        let handle = self.current.take()?;
+        self.current = handle.next();
+
+        Some(handle)
+    }
+}
+
+impl<T> Collection for LinkedList<T>
+where
+    T: Clone + std::fmt::Debug,
+{
+    type Iter<'a>
+        = LinkedListIterator<'a, T>
+    where
+        T: 'a;
 
-        self.current.clone().map(|v| v.0.borrow_mut().value.clone())
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn clear(&mut self) {
+        self.clear()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.into_iter()
+    }
+}
+
+impl<T> Stack<T> for LinkedList<T>
+where
+    T: Clone + std::fmt::Debug,
+{
+    fn push(&mut self, v: T) {
+        LinkedList::push(self, v)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop_back()
+    }
+}
+
+impl<T> Queue<T> for LinkedList<T>
+where
+    T: Clone + std::fmt::Debug,
+{
+    fn enqueue(&mut self, v: T) {
+        self.push(v)
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+}
+
+impl<T> Deque<T> for LinkedList<T>
+where
+    T: Clone + std::fmt::Debug,
+{
+    fn push_front(&mut self, v: T) {
+        LinkedList::push_front(self, v)
+    }
+
+    fn push_back(&mut self, v: T) {
+        self.push(v)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        LinkedList::pop_front(self)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        LinkedList::pop_back(self)
     }
 }
 
@@ -106,7 +212,7 @@ where
     /// # Example
     ///
     /// ```
-    /// use linked_list::LinkedList;
+    /// use doubly_linked_list::LinkedList;
     ///
     /// let mut linked_list = LinkedList::<String>::default();
     /// linked_list.push("Hello".to_string());
@@ -131,10 +237,43 @@ where
             };
         }
 
+        self.observers.inserted(&new.0.borrow().value);
         self.tail = Some(new);
         self.size += 1;
     }
 
+    /// Adds a value to the front of a LinkedList.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doubly_linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<String>::default();
+    /// linked_list.push("World".to_string());
+    /// linked_list.push_front("Hello".to_string());
+    ///
+    /// assert_eq!(linked_list.head(), Some("Hello".to_string()));
+    /// ```
+    pub fn push_front(&mut self, v: T) {
+        let new = NodeRef::new(Node::new(v));
+
+        match self.head.take() {
+            Some(old) => {
+                old.0.borrow_mut().previous = Some(new.clone());
+                new.0.borrow_mut().next = Some(old);
+            }
+            None => self.tail = Some(new.clone()),
+        };
+
+        self.observers.inserted(&new.0.borrow().value);
+        self.head = Some(new);
+        self.size += 1;
+    }
+
     /// Returns the value the head of a LinkedList and removes it from the
     /// LinkedList.
     ///
@@ -144,7 +283,7 @@ where
     /// # Example
     ///
     /// ```
-    /// use linked_list::LinkedList;
+    /// use doubly_linked_list::LinkedList;
     ///
     /// let mut linked_list = LinkedList::<String>::default();
     /// linked_list.push("Hello".to_string());
@@ -157,13 +296,15 @@ where
         // map() applies to the inner value of Option (Rc)
         // map() will return an Option, but we'll change the inner value of it
         // to T.
-        self.head.take().map(|mut h| {
+        self.head.take().map(|h| {
             // borrow_mut() - borrows inner value mutably (NodeRef<T>)
             // Takes ownership of next
             //
             // Assign head to next,
             // If there isn't something, head is None, so tail should be None.
             if let Some(next) = h.0.borrow_mut().next.take() {
+                // The new head must not point back at the popped node.
+                next.0.borrow_mut().previous = None;
                 self.head = Some(next);
             } else {
                 self.tail.take();
@@ -172,8 +313,11 @@ where
             // Decrement the size as we have popped from the list.
             self.size -= 1;
 
-            // Extracts the value from h and returns it.
-            h.get_value()
+            // Moves the value out of h and returns it.
+            let v = h.into_value();
+            self.observers.removed(&v);
+
+            v
         })
     }
 
@@ -186,7 +330,7 @@ where
     /// # Example
     ///
     /// ```
-    /// use linked_list::LinkedList;
+    /// use doubly_linked_list::LinkedList;
     ///
     /// let mut linked_list = LinkedList::<String>::default();
     /// linked_list.push("Hello".to_string());
@@ -196,24 +340,60 @@ where
     /// assert_eq!(linked_list.len(), 1);
     /// ```
     pub fn pop_back(&mut self) -> Option<T> {
-        self.tail.take().map(|mut v| {
+        self.tail.take().map(|v| {
             if let Some(previous) = v.0.borrow_mut().previous.take() {
+                // The new tail must not point forward to the popped node.
+                previous.0.borrow_mut().next = None;
                 self.tail = Some(previous);
             } else {
                 self.head.take();
             }
 
             self.size -= 1;
-            v.get_value()
+
+            let v = v.into_value();
+            self.observers.removed(&v);
+
+            v
         })
     }
 
+    /// Removes every item from the LinkedList.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doubly_linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<String>::default();
+    /// linked_list.push("Hello".to_string());
+    /// linked_list.clear();
+    ///
+    /// assert_eq!(linked_list.is_empty(), true);
+    /// ```
+    pub fn clear(&mut self) {
+        self.tail = None;
+        self.size = 0;
+
+        // Break the next and previous links between the nodes, otherwise each
+        // pair of neighbours keep each other alive.
+        let mut current = self.head.take();
+        while let Some(node) = current {
+            self.observers.removed(&node.0.borrow().value);
+            current = node.0.borrow_mut().next.take();
+            node.0.borrow_mut().previous = None;
+        }
+    }
+
     /// Returns a boolean indicating the LinkedList is empty.
     ///
     /// # Example
     ///
     /// ```
-    /// use linked_list::LinkedList;
+    /// use doubly_linked_list::LinkedList;
     ///
     /// let mut linked_list = LinkedList::<String>::default();
     /// assert_eq!(linked_list.is_empty(), true);
@@ -222,15 +402,55 @@ where
         self.size == 0
     }
 
-    /// Gets the value from a LinkedList according to an index.
+    /// Registers a callback that is called with every value added to the
+    /// LinkedList, e.g. to keep an external index in sync with it.
     ///
-    /// Time Complexity: O(n)
+    /// Clones of the LinkedList share their nodes, so they share their
+    /// callbacks too.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doubly_linked_list::LinkedList;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    ///
+    /// let seen = Rc::new(RefCell::new(Vec::new()));
+    /// let mut linked_list = LinkedList::<u32>::default();
+    ///
+    /// let sink = seen.clone();
+    /// linked_list.on_insert(move |v| sink.borrow_mut().push(*v));
+    /// linked_list.push(2);
+    /// linked_list.push_front(1);
+    ///
+    /// assert_eq!(*seen.borrow(), vec![2, 1]);
+    /// ```
+    pub fn on_insert<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) + 'static,
+    {
+        self.observers.on_insert(Box::new(f));
+    }
+
+    /// Registers a callback that is called with every value removed from the
+    /// LinkedList, by pop_front(), pop_back() or clear().
+    pub fn on_remove<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) + 'static,
+    {
+        self.observers.on_remove(Box::new(f));
+    }
+
+    /// Gets the value from a LinkedList according to an index. The list is
+    /// walked from whichever end is closer to the index.
+    ///
+    /// Time Complexity: O(n), at most n / 2 steps
     /// Space Complexity: O(1)
     ///
     /// # Example
     ///
     /// ```
-    /// use linked_list::LinkedList;
+    /// use doubly_linked_list::LinkedList;
     ///
     /// let mut linked_list = LinkedList::<String>::default();
     /// linked_list.push("Hello".to_string());
@@ -238,15 +458,34 @@ where
     /// assert_eq!(linked_list.get(0), Some("Hello".to_string()));
     /// ```
     pub fn get(&self, index: usize) -> Option<T> {
-        let mut current = self.head.clone();
+        self.node_at(index).map(|v| v.value())
+    }
 
-        for _i in 0..index {
-            current
-                .clone()
-                .map(|v| current = v.0.borrow_mut().next.clone());
+    /// Returns the node at an index, walking from the head for the first half
+    /// of the list and from the tail for the second half.
+    fn node_at(&self, index: usize) -> Option<NodeRef<T>> {
+        let len = self.len();
+        if index >= len {
+            return None;
         }
 
-        current.map(|mut v| v.get_value())
+        if index < len / 2 {
+            let mut current = self.head.clone()?;
+            for _i in 0..index {
+                let next = current.0.borrow().next.clone()?;
+                current = next;
+            }
+
+            Some(current)
+        } else {
+            let mut current = self.tail.clone()?;
+            for _i in index..len - 1 {
+                let previous = current.0.borrow().previous.clone()?;
+                current = previous;
+            }
+
+            Some(current)
+        }
     }
 
     /// Returns the head of the List as an Option<T>.
@@ -257,7 +496,7 @@ where
     /// # Example
     ///
     /// ```
-    /// use linked_list::LinkedList;
+    /// use doubly_linked_list::LinkedList;
     ///
     /// let mut linked_list = LinkedList::<String>::default();
     /// linked_list.push("Hello".to_string());
@@ -276,7 +515,7 @@ where
     /// # Example
     ///
     /// ```
-    /// use linked_list::LinkedList;
+    /// use doubly_linked_list::LinkedList;
     ///
     /// let mut linked_list = LinkedList::<String>::default();
     /// linked_list.push("Hello".to_string());
@@ -288,6 +527,196 @@ where
         self.tail.as_ref().map(|t| t.0.borrow().value.clone())
     }
 
+    /// Returns an iterator over Handles to each node, from head to tail.
+    ///
+    /// Handles give access to the values and to the neighbouring nodes, and
+    /// can be passed back to the list (e.g. `move_to_front()`) to operate on a
+    /// node without searching for it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doubly_linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    /// linked_list.push(2);
+    ///
+    /// for handle in linked_list.iter_handles() {
+    ///     *handle.value_mut() *= 10;
+    /// }
+    ///
+    /// assert_eq!(linked_list.get(1), Some(20));
+    /// ```
+    pub fn iter_handles(&self) -> HandleIterator<'_, T> {
+        HandleIterator {
+            _list: self,
+            current: self.head_handle(),
+        }
+    }
+
+    /// Returns a Handle to the head of the List.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn head_handle(&self) -> Option<Handle<T>> {
+        self.head.clone().map(Handle)
+    }
+
+    /// Returns a Handle to the tail of the List.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn tail_handle(&self) -> Option<Handle<T>> {
+        self.tail.clone().map(Handle)
+    }
+
+    /// Moves the node referred to by a Handle to the head of the List. Does
+    /// nothing if the node was removed from the List, e.g. by pop_back().
+    ///
+    /// The Handle must have come from this LinkedList.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doubly_linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    /// linked_list.push(2);
+    /// linked_list.push(3);
+    ///
+    /// let tail = linked_list.tail_handle().unwrap();
+    /// linked_list.move_to_front(&tail);
+    ///
+    /// assert_eq!(linked_list.head(), Some(3));
+    /// assert_eq!(linked_list.tail(), Some(2));
+    /// ```
+    pub fn move_to_front(&mut self, handle: &Handle<T>) {
+        let node = &handle.0;
+
+        // The head is the only linked node without a previous node.
+        if node.0.borrow().previous.is_none() {
+            return;
+        }
+
+        // Unlink the node, joining its neighbours together.
+        let previous = node.0.borrow_mut().previous.take();
+        let next = node.0.borrow_mut().next.take();
+
+        if let Some(p) = &previous {
+            p.0.borrow_mut().next = next.clone();
+        }
+
+        match &next {
+            Some(n) => n.0.borrow_mut().previous = previous,
+            None => self.tail = previous,
+        }
+
+        // Link the node in front of the old head.
+        if let Some(old) = self.head.take() {
+            old.0.borrow_mut().previous = Some(node.clone());
+            node.0.borrow_mut().next = Some(old);
+        }
+
+        self.head = Some(node.clone());
+    }
+
+    /// Exchanges the positions of the nodes referred to by two Handles. The
+    /// nodes are relinked and keep their values, so the Handles keep
+    /// referring to the same values at their new positions. Does nothing if
+    /// either node was removed from the List.
+    ///
+    /// Both Handles must have come from this LinkedList.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doubly_linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    /// linked_list.push(2);
+    /// linked_list.push(3);
+    ///
+    /// let head = linked_list.head_handle().unwrap();
+    /// let tail = linked_list.tail_handle().unwrap();
+    /// linked_list.swap_nodes(&head, &tail);
+    ///
+    /// assert_eq!(linked_list.head(), Some(3));
+    /// assert_eq!(linked_list.tail(), Some(1));
+    /// assert!(linked_list.head_handle().unwrap().ptr_eq(&tail));
+    /// ```
+    pub fn swap_nodes(&mut self, a: &Handle<T>, b: &Handle<T>) {
+        if a.ptr_eq(b) || !self.is_linked(a) || !self.is_linked(b) {
+            return;
+        }
+
+        // Neighbouring nodes are relinked as a pair, with `a` before `b`.
+        if b.next().is_some_and(|next| next.ptr_eq(a)) {
+            return self.swap_nodes(b, a);
+        }
+
+        let (a, b) = (&a.0, &b.0);
+        let a_previous = a.0.borrow_mut().previous.take();
+        let a_next = a.0.borrow_mut().next.take();
+        let b_previous = b.0.borrow_mut().previous.take();
+        let b_next = b.0.borrow_mut().next.take();
+
+        let neighbours = a_next
+            .as_ref()
+            .is_some_and(|next| std::rc::Rc::ptr_eq(&next.0, &b.0));
+        let (a_previous_new, b_next_new) = if neighbours {
+            (Some(b.clone()), Some(a.clone()))
+        } else {
+            (b_previous.clone(), a_next.clone())
+        };
+
+        self.relink(&a_previous, b);
+        self.relink_back(&b_next, a);
+        if !neighbours {
+            self.relink(&b_previous, a);
+            self.relink_back(&a_next, b);
+        }
+
+        let mut node = a.0.borrow_mut();
+        node.previous = a_previous_new;
+        node.next = b_next;
+        drop(node);
+
+        let mut node = b.0.borrow_mut();
+        node.previous = a_previous;
+        node.next = b_next_new;
+    }
+
+    /// Returns a boolean indicating the node of a Handle is in the List: it
+    /// has a previous node, or it is the head.
+    fn is_linked(&self, handle: &Handle<T>) -> bool {
+        handle.previous().is_some() || self.head_handle().is_some_and(|h| h.ptr_eq(handle))
+    }
+
+    /// Points the node before a position, or the head, at `node`.
+    fn relink(&mut self, previous: &Option<NodeRef<T>>, node: &NodeRef<T>) {
+        match previous {
+            Some(p) => p.0.borrow_mut().next = Some(node.clone()),
+            None => self.head = Some(node.clone()),
+        }
+    }
+
+    /// Points the node after a position, or the tail, at `node`.
+    fn relink_back(&mut self, next: &Option<NodeRef<T>>, node: &NodeRef<T>) {
+        match next {
+            Some(n) => n.0.borrow_mut().previous = Some(node.clone()),
+            None => self.tail = Some(node.clone()),
+        }
+    }
+
     // /// Deletes an item from the list according to an index.
     //
     // /// Time Complexity: O(n)
@@ -295,7 +724,7 @@ where
     //
     // /// # Example
     // /// ```
-    // /// use linked_list::LinkedList;
+    // /// use doubly_linked_list::LinkedList;
     // /// let mut linked_list = LinkedList::<String>::default();
     // /// linked_list.push("Hello".to_string());
     // /// linked_list.push("World".to_string());
@@ -559,6 +988,46 @@ mod doubly_linked_list {
         assert_eq!(None, iter.next_back());
     }
 
+    #[test]
+    fn push_front() {
+        let mut linked_list = LinkedList::<String>::default();
+
+        linked_list.push_front("2".to_string());
+        assert_eq!(linked_list.head(), Some("2".to_string()));
+        assert_eq!(linked_list.tail(), Some("2".to_string()));
+
+        linked_list.push_front("1".to_string());
+        linked_list.push("3".to_string());
+        assert_eq!(linked_list.len(), 3);
+
+        let mut iter = linked_list.into_iter();
+        assert_eq!(iter.next(), Some("1".to_string()));
+        assert_eq!(iter.next(), Some("2".to_string()));
+        assert_eq!(iter.next(), Some("3".to_string()));
+
+        let mut iter = linked_list.into_iter();
+        assert_eq!(iter.next_back(), Some("3".to_string()));
+        assert_eq!(iter.next_back(), Some("2".to_string()));
+        assert_eq!(iter.next_back(), Some("1".to_string()));
+    }
+
+    #[test]
+    fn generic_over_traits() {
+        fn fill<D: Deque<u32>>(deque: &mut D) {
+            deque.push_back(2);
+            deque.push_front(1);
+            deque.push_back(3);
+        }
+
+        let mut linked_list = LinkedList::default();
+        fill(&mut linked_list);
+
+        assert_eq!(linked_list.dequeue(), Some(1));
+        assert_eq!(Stack::pop(&mut linked_list), Some(3));
+        assert_eq!(linked_list.dequeue(), Some(2));
+        assert_eq!(linked_list.dequeue(), None);
+    }
+
     #[test]
     fn iterator_function_calls() {
         let mut linked_list = linked_list![1, 2, 3, 4, 5];
@@ -576,4 +1045,365 @@ mod doubly_linked_list {
         assert_eq!(result[1], 6);
         assert_eq!(result[2], 4);
     }
+
+    #[test]
+    fn generic_over_collection() {
+        fn sizes<C: Collection>(collection: &C) -> (usize, usize) {
+            (collection.len(), collection.iter().count())
+        }
+
+        let mut linked_list = linked_list![1, 2, 3];
+        assert_eq!(sizes(&linked_list), (3, 3));
+
+        Collection::clear(&mut linked_list);
+        assert_eq!(sizes(&linked_list), (0, 0));
+        assert_eq!(linked_list.head(), None);
+        assert_eq!(linked_list.tail(), None);
+
+        linked_list.push(4);
+        assert_eq!(linked_list.head(), Some(4));
+        assert_eq!(linked_list.tail(), Some(4));
+    }
+
+    #[test]
+    fn iter_handles() {
+        let linked_list = linked_list![1, 2, 3];
+
+        let handles: Vec<Handle<u32>> = linked_list.iter_handles().collect();
+        assert_eq!(handles.len(), 3);
+        assert_eq!(*handles[0].value(), 1);
+        assert_eq!(*handles[2].value(), 3);
+
+        assert!(handles[1].next().unwrap().ptr_eq(&handles[2]));
+        assert!(handles[1].previous().unwrap().ptr_eq(&handles[0]));
+        assert!(handles[0].previous().is_none());
+        assert!(handles[2].next().is_none());
+
+        *handles[1].value_mut() = 20;
+        assert_eq!(linked_list.get(1), Some(20));
+
+        assert_eq!(LinkedList::<u32>::default().iter_handles().count(), 0);
+    }
+
+    #[test]
+    fn swap_nodes() {
+        let mut linked_list = linked_list![1, 2, 3, 4, 5];
+        let handles: Vec<Handle<u32>> = linked_list.iter_handles().collect();
+        let values = |list: &LinkedList<u32>| {
+            let forward: Vec<u32> = list.into_iter().collect();
+            let mut backward: Vec<u32> = list.into_iter().rev().collect();
+            backward.reverse();
+            assert_eq!(forward, backward);
+            forward
+        };
+
+        // Apart, in the middle.
+        linked_list.swap_nodes(&handles[1], &handles[3]);
+        assert_eq!(values(&linked_list), vec![1, 4, 3, 2, 5]);
+
+        // Neighbours, passed in either order.
+        linked_list.swap_nodes(&handles[3], &handles[2]);
+        assert_eq!(values(&linked_list), vec![1, 3, 4, 2, 5]);
+        linked_list.swap_nodes(&handles[1], &handles[3]);
+        assert_eq!(values(&linked_list), vec![1, 3, 2, 4, 5]);
+
+        // The head and the tail.
+        linked_list.swap_nodes(&handles[4], &handles[0]);
+        assert_eq!(values(&linked_list), vec![5, 3, 2, 4, 1]);
+        assert!(linked_list.head_handle().unwrap().ptr_eq(&handles[4]));
+        assert!(linked_list.tail_handle().unwrap().ptr_eq(&handles[0]));
+
+        // The same node.
+        linked_list.swap_nodes(&handles[2], &handles[2]);
+        assert_eq!(values(&linked_list), vec![5, 3, 2, 4, 1]);
+
+        // Removed nodes stay where they are.
+        let removed = linked_list.tail_handle().unwrap();
+        linked_list.pop_back();
+        linked_list.swap_nodes(&handles[4], &removed);
+        assert_eq!(values(&linked_list), vec![5, 3, 2, 4]);
+        assert_eq!(linked_list.len(), 4);
+
+        let mut pair = linked_list![1, 2];
+        let (head, tail) = (pair.head_handle().unwrap(), pair.tail_handle().unwrap());
+        pair.swap_nodes(&head, &tail);
+        assert_eq!(values(&pair), vec![2, 1]);
+        assert_eq!(pair.len(), 2);
+    }
+
+    #[test]
+    fn move_to_front_ignores_removed_nodes() {
+        let mut linked_list = linked_list![1, 2, 3, 4];
+        let handles: Vec<Handle<u32>> = linked_list.iter_handles().collect();
+
+        linked_list.pop_front();
+        linked_list.pop_back();
+        linked_list.move_to_front(&handles[0]);
+        linked_list.move_to_front(&handles[3]);
+
+        assert_eq!(linked_list.len(), 2);
+        assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(
+            linked_list.into_iter().rev().collect::<Vec<_>>(),
+            vec![3, 2]
+        );
+
+        linked_list.clear();
+        linked_list.move_to_front(&handles[1]);
+        assert!(linked_list.is_empty());
+        assert_eq!(linked_list.head(), None);
+    }
+
+    #[test]
+    fn move_to_front() {
+        let mut linked_list = linked_list![1, 2, 3, 4];
+        let handles: Vec<Handle<u32>> = linked_list.iter_handles().collect();
+
+        // Middle node.
+        linked_list.move_to_front(&handles[2]);
+        assert_eq!(
+            linked_list.into_iter().collect::<Vec<_>>(),
+            vec![3, 1, 2, 4]
+        );
+
+        // Tail node.
+        linked_list.move_to_front(&handles[3]);
+        assert_eq!(
+            linked_list.into_iter().collect::<Vec<_>>(),
+            vec![4, 3, 1, 2]
+        );
+        assert_eq!(linked_list.tail(), Some(2));
+
+        // Head node.
+        linked_list.move_to_front(&handles[3]);
+        assert_eq!(
+            linked_list.into_iter().collect::<Vec<_>>(),
+            vec![4, 3, 1, 2]
+        );
+
+        assert_eq!(
+            linked_list.into_iter().rev().collect::<Vec<_>>(),
+            vec![2, 1, 3, 4]
+        );
+        assert_eq!(linked_list.len(), 4);
+    }
+
+    #[test]
+    fn get_walks_from_nearer_end() {
+        let mut linked_list = LinkedList::<u32>::default();
+        for i in 0..7 {
+            linked_list.push(i);
+        }
+
+        for i in 0..7 {
+            assert_eq!(linked_list.get(i), Some(i as u32));
+        }
+        assert_eq!(linked_list.get(7), None);
+
+        // The second half is reached through the previous links, so they must
+        // stay correct as both ends change.
+        linked_list.push_front(100);
+        linked_list.pop_back();
+        linked_list.pop_back();
+        assert_eq!(linked_list.len(), 6);
+        assert_eq!(linked_list.get(0), Some(100));
+        assert_eq!(linked_list.get(4), Some(3));
+        assert_eq!(linked_list.get(5), Some(4));
+        assert_eq!(linked_list.get(6), None);
+
+        let empty = LinkedList::<u32>::default();
+        assert_eq!(empty.get(0), None);
+    }
+
+    #[test]
+    fn observers() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let inserted = Rc::new(RefCell::new(Vec::new()));
+        let removed = Rc::new(RefCell::new(Vec::new()));
+
+        let mut linked_list = LinkedList::<u32>::default();
+        let sink = inserted.clone();
+        linked_list.on_insert(move |v| sink.borrow_mut().push(*v));
+        let sink = removed.clone();
+        linked_list.on_remove(move |v| sink.borrow_mut().push(*v));
+
+        linked_list.push(2);
+        linked_list.push(3);
+        linked_list.push_front(1);
+        linked_list.push(4);
+        assert_eq!(*inserted.borrow(), vec![2, 3, 1, 4]);
+
+        linked_list.pop_back();
+        linked_list.pop_front();
+        assert_eq!(*removed.borrow(), vec![4, 1]);
+
+        // Moving a node is neither an insert nor a remove.
+        let handle = linked_list.tail_handle().unwrap();
+        linked_list.move_to_front(&handle);
+
+        linked_list.clear();
+        assert_eq!(*removed.borrow(), vec![4, 1, 3, 2]);
+        assert_eq!(inserted.borrow().len(), 4);
+    }
+
+    #[test]
+    fn pop_moves_values() {
+        use std::rc::Rc;
+
+        let value = Rc::new(1);
+        let mut linked_list = LinkedList::default();
+        linked_list.push(value.clone());
+        linked_list.push(value.clone());
+        assert_eq!(Rc::strong_count(&value), 3);
+
+        // The popped nodes aren't shared, so their values are moved out.
+        let front = linked_list.pop_front().unwrap();
+        let back = linked_list.pop_back().unwrap();
+        assert_eq!(Rc::strong_count(&value), 3);
+
+        drop((front, back));
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn pop_shared_node() {
+        let mut linked_list = linked_list![1, 2];
+        let handle = linked_list.head_handle().unwrap();
+
+        // The handle still points at the node, so the value is cloned.
+        assert_eq!(linked_list.pop_front(), Some(1));
+        assert_eq!(*handle.value(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "the value is pinned, use value_pin_mut()")]
+    fn value_mut_of_pinned_value() {
+        let linked_list = linked_list![1];
+        let handle = linked_list.head_handle().unwrap();
+
+        handle.value_pin();
+        handle.value_mut();
+    }
+}
+
+// The ordering and stable address guarantees of the public API. These tests must keep passing
+// across any rewrite of the LinkedList internals.
+#[cfg(test)]
+mod contract {
+    use super::*;
+
+    #[test]
+    fn iterates_from_front_to_back() {
+        let mut linked_list = linked_list![2, 3];
+        linked_list.push_front(1);
+        linked_list.push(4);
+
+        assert_eq!(
+            linked_list.into_iter().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(
+            Collection::iter(&linked_list).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(
+            linked_list
+                .iter_handles()
+                .map(|h| *h.value())
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(
+            (0..4).map(|i| linked_list.get(i)).collect::<Vec<_>>(),
+            vec![Some(1), Some(2), Some(3), Some(4)]
+        );
+    }
+
+    #[test]
+    fn iterates_from_back_to_front() {
+        let linked_list = linked_list![1, 2, 3];
+
+        let mut iter = linked_list.into_iter();
+        assert_eq!(iter.next_back(), Some(3));
+        assert_eq!(iter.next_back(), Some(2));
+        assert_eq!(iter.next_back(), Some(1));
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn pops_from_both_ends() {
+        let mut linked_list = linked_list![1, 2, 3, 4];
+
+        assert_eq!(linked_list.pop_front(), Some(1));
+        assert_eq!(linked_list.pop_back(), Some(4));
+        assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![2, 3]);
+    }
+
+    #[test]
+    fn move_to_front_keeps_the_rest_in_order() {
+        let mut linked_list = linked_list![1, 2, 3, 4];
+        let handle = linked_list.iter_handles().nth(2).unwrap();
+
+        linked_list.move_to_front(&handle);
+        assert_eq!(
+            linked_list.into_iter().collect::<Vec<_>>(),
+            vec![3, 1, 2, 4]
+        );
+    }
+
+    #[test]
+    fn values_never_move() {
+        let mut linked_list = linked_list![1, 2, 3];
+        let handle = linked_list.iter_handles().nth(1).unwrap();
+        let address = handle.as_ptr();
+
+        linked_list.push(4);
+        linked_list.push_front(0);
+        linked_list.move_to_front(&handle);
+        linked_list.move_to_front(&linked_list.tail_handle().unwrap());
+        linked_list.pop_back();
+        linked_list.pop_front();
+        *handle.value_mut() += 10;
+
+        assert_eq!(handle.as_ptr(), address);
+        assert_eq!(*handle.value(), 12);
+    }
+
+    #[test]
+    fn pinned_values_stay_in_their_node() {
+        let mut linked_list = linked_list!["a".to_string(), "b".to_string()];
+        let handle = linked_list.head_handle().unwrap();
+        let address = handle.as_ptr();
+
+        handle.value_pin_mut().as_mut().get_mut().push('!');
+        assert!(handle.is_pinned());
+
+        // Popping a pinned value returns a clone, the value stays put.
+        assert_eq!(linked_list.pop_front(), Some("a!".to_string()));
+        assert_eq!(handle.as_ptr(), address);
+        assert_eq!(*handle.value_pin(), "a!".to_string());
+    }
+
+    #[test]
+    fn iterator_size() {
+        let linked_list = linked_list![1, 2, 3, 4];
+
+        let mut iter = linked_list.into_iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next_back(), Some(4));
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+
+        // Both ends stop where they meet.
+        assert_eq!(iter.next_back(), Some(3));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+
+        assert_eq!(LinkedList::<u32>::default().into_iter().len(), 0);
+    }
 }
@@ -0,0 +1,53 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+type Callback<T> = Box<dyn FnMut(&T)>;
+
+/// Observers holds the callbacks registered on a LinkedList. Clones of a
+/// LinkedList share their nodes, so they share their Observers too.
+pub(crate) struct Observers<T> {
+    on_insert: Rc<RefCell<Vec<Callback<T>>>>,
+    on_remove: Rc<RefCell<Vec<Callback<T>>>>,
+}
+
+impl<T> Clone for Observers<T> {
+    fn clone(&self) -> Self {
+        Observers {
+            on_insert: Rc::clone(&self.on_insert),
+            on_remove: Rc::clone(&self.on_remove),
+        }
+    }
+}
+
+impl<T> Default for Observers<T> {
+    fn default() -> Self {
+        Observers {
+            on_insert: Rc::new(RefCell::new(Vec::new())),
+            on_remove: Rc::new(RefCell::new(Vec::new())),
+        }
+    }
+}
+
+impl<T> Observers<T> {
+    pub fn on_insert(&self, f: Callback<T>) {
+        self.on_insert.borrow_mut().push(f);
+    }
+
+    pub fn on_remove(&self, f: Callback<T>) {
+        self.on_remove.borrow_mut().push(f);
+    }
+
+    /// Calls the insert callbacks with a value added to the LinkedList.
+    pub fn inserted(&self, v: &T) {
+        for f in self.on_insert.borrow_mut().iter_mut() {
+            f(v);
+        }
+    }
+
+    /// Calls the remove callbacks with a value removed from the LinkedList.
+    pub fn removed(&self, v: &T) {
+        for f in self.on_remove.borrow_mut().iter_mut() {
+            f(v);
+        }
+    }
+}
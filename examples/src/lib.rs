@@ -0,0 +1,14 @@
+//! End-to-end programs that drive the public APIs of several crates together.
+//!
+//! Each program is an integration test under `tests/`, so `cargo test` builds
+//! and runs them against the current APIs:
+//!
+//! - `web_cache` - a web cache in front of a slow origin, using an LRU with a
+//!   byte budget.
+//! - `scheduler` - a task scheduler, using a PriorityQueue for the ready tasks
+//!   and a timer wheel of IndexedDeques for the delayed ones.
+//! - `text_index` - an inverted index answering word and prefix queries, using
+//!   a Treap split and joined around the prefix range.
+//!
+//! `feature_powerset` isn't a program: it checks every crate of the repository
+//! with every combination of its features, see the README.
@@ -0,0 +1,159 @@
+//! Checks every crate of the repository with every combination of its
+//! features.
+//!
+//! The crates are independent, so a feature that only compiles together with
+//! another one, or a `no_std` build that still reaches for `std`, wouldn't be
+//! caught by building each crate with its default features. This program
+//! reads the `[features]` table of each crate and runs `cargo check` on its
+//! targets once per subset, without the default features. Any combination
+//! that fails to build fails the test, with cargo's errors above it.
+//!
+//! The checks share a target directory under this crate's, so only the first
+//! run builds the dependencies. `FEATURE_CRATES` restricts it to a comma
+//! separated list of crates:
+//!
+//! ```text
+//! FEATURE_CRATES=linked_list cargo test --test feature_powerset
+//! ```
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Returns the features declared by a manifest, except `default`, followed
+/// by the optional dependencies that no feature enables, which are features
+/// of their own.
+fn features(manifest: &str) -> Vec<String> {
+    let mut features = Vec::new();
+    let mut optional = Vec::new();
+    let mut table = "";
+
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            table = line;
+            continue;
+        }
+
+        let (name, value) = match line.split_once('=') {
+            Some((name, value)) if !line.starts_with('#') => (name.trim(), value),
+            _ => continue,
+        };
+
+        match table {
+            "[features]" if name != "default" => features.push(name.to_string()),
+            "[dependencies]" if value.contains("optional = true") => {
+                optional.push(name.to_string())
+            }
+            _ => {}
+        }
+    }
+
+    let enabled = |dependency: &String| {
+        let quoted = format!("\"{}\"", dependency);
+        manifest
+            .lines()
+            .skip_while(|line| line.trim() != "[features]")
+            .any(|line| line.contains(&quoted))
+    };
+    optional.retain(|dependency| !features.contains(dependency) && !enabled(dependency));
+    features.extend(optional);
+
+    features
+}
+
+/// Returns every subset of the features, the empty one first.
+fn powerset(features: &[String]) -> Vec<Vec<String>> {
+    (0..1usize << features.len())
+        .map(|mask| {
+            features
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| mask & (1 << i) != 0)
+                .map(|(_, f)| f.clone())
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the manifests of the crates next to this one.
+fn crates() -> Vec<PathBuf> {
+    let this = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let only = env::var("FEATURE_CRATES").ok();
+
+    let mut manifests: Vec<PathBuf> = fs::read_dir(this.parent().unwrap())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        // This crate has no features, and is the one being tested.
+        .filter(|dir| dir != this)
+        .map(|dir| dir.join("Cargo.toml"))
+        .filter(|manifest| manifest.is_file())
+        .filter(|manifest| match &only {
+            Some(only) => {
+                let name = manifest.parent().unwrap().file_name().unwrap();
+                only.split(',').any(|c| c == name)
+            }
+            None => true,
+        })
+        .collect();
+    manifests.sort();
+
+    manifests
+}
+
+#[test]
+fn parses_the_features_table() {
+    let manifest = r#"
+[dependencies]
+futures = { version = "0.3", optional = true }
+serde = { version = "1.0", optional = true }
+
+[features]
+default = ["std"]
+std = []
+# A comment.
+spill = ["std", "serde"]
+
+[dev-dependencies]
+fake = "1"
+"#;
+
+    // serde is only enabled through spill.
+    assert_eq!(features(manifest), vec!["std", "spill", "futures"]);
+    assert_eq!(powerset(&features(manifest)).len(), 8);
+    assert_eq!(powerset(&[]), vec![Vec::<String>::new()]);
+}
+
+#[test]
+fn every_feature_combination_builds() {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let target = Path::new(env!("CARGO_MANIFEST_DIR")).join("target/feature_powerset");
+    let mut failures = Vec::new();
+
+    let manifests = crates();
+    assert!(!manifests.is_empty(), "no crate matches FEATURE_CRATES");
+
+    for manifest in manifests {
+        let features = features(&fs::read_to_string(&manifest).unwrap());
+
+        for subset in powerset(&features) {
+            let status = Command::new(&cargo)
+                .arg("check")
+                .arg("--quiet")
+                .arg("--all-targets")
+                .arg("--manifest-path")
+                .arg(&manifest)
+                .arg("--no-default-features")
+                .arg("--features")
+                .arg(subset.join(","))
+                .env("CARGO_TARGET_DIR", &target)
+                .status()
+                .unwrap();
+
+            if !status.success() {
+                failures.push(format!("{} [{}]", manifest.display(), subset.join(",")));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "failed to build: {:#?}", failures);
+}
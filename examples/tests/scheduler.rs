@@ -0,0 +1,193 @@
+//! A task scheduler running on a logical clock.
+//!
+//! Delayed tasks wait in a hashed timer wheel: a ring of IndexedDeques, one
+//! per tick, with tasks further away than a turn of the wheel counting down
+//! the turns left. When their tick comes, tasks move to a PriorityQueue of
+//! ready tasks, and each tick runs the most urgent ones up to the number of
+//! workers.
+use indexed_deque::IndexedDeque;
+use priority_queue::PriorityQueue;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Task {
+    name: &'static str,
+    // Lower is more urgent, like the priorities of the PriorityQueue.
+    urgency: u32,
+    // Runs again this many ticks after it ran, if set.
+    every: Option<u64>,
+}
+
+struct Delayed {
+    task: Task,
+    turns: u64,
+}
+
+/// A hashed timer wheel, the slot of a deadline is its tick modulo the number
+/// of slots.
+struct TimerWheel {
+    slots: Vec<IndexedDeque<Delayed>>,
+    now: u64,
+}
+
+impl TimerWheel {
+    fn new(slots: usize) -> TimerWheel {
+        TimerWheel {
+            slots: (0..slots).map(|_| IndexedDeque::default()).collect(),
+            now: 0,
+        }
+    }
+
+    /// Schedules a task `delay` ticks from now, at least one.
+    fn schedule(&mut self, task: Task, delay: u64) {
+        let delay = delay.max(1);
+        let len = self.slots.len() as u64;
+        let slot = ((self.now + delay) % len) as usize;
+
+        self.slots[slot].push_back(Delayed {
+            task,
+            turns: (delay - 1) / len,
+        });
+    }
+
+    /// Advances the wheel by a tick and returns the tasks that are due.
+    fn tick(&mut self) -> Vec<Task> {
+        self.now += 1;
+        let index = (self.now % self.slots.len() as u64) as usize;
+        let slot = &mut self.slots[index];
+
+        let mut due = Vec::new();
+        for _ in 0..slot.len() {
+            let mut delayed = slot.pop_front().unwrap();
+            if delayed.turns == 0 {
+                due.push(delayed.task);
+            } else {
+                delayed.turns -= 1;
+                slot.push_back(delayed);
+            }
+        }
+
+        due
+    }
+}
+
+struct Scheduler {
+    wheel: TimerWheel,
+    ready: PriorityQueue<Task, (u32, u64)>,
+    // Breaks ties between equally urgent tasks in the order they got ready.
+    sequence: u64,
+    workers: usize,
+    log: Vec<(u64, &'static str)>,
+}
+
+impl Scheduler {
+    fn new(slots: usize, workers: usize) -> Scheduler {
+        Scheduler {
+            wheel: TimerWheel::new(slots),
+            ready: PriorityQueue::new(),
+            sequence: 0,
+            workers,
+            log: Vec::new(),
+        }
+    }
+
+    fn schedule(&mut self, task: Task, delay: u64) {
+        self.wheel.schedule(task, delay);
+    }
+
+    /// Runs a tick: the due tasks become ready and the most urgent ready
+    /// tasks run, the others wait for the next tick.
+    fn tick(&mut self) {
+        for task in self.wheel.tick() {
+            let priority = (task.urgency, self.sequence);
+            self.sequence += 1;
+            self.ready.push(task, priority);
+        }
+
+        for _ in 0..self.workers {
+            let (task, _) = match self.ready.pop() {
+                Some(entry) => entry,
+                None => break,
+            };
+
+            self.log.push((self.wheel.now, task.name));
+            if let Some(every) = task.every {
+                self.schedule(task, every);
+            }
+        }
+    }
+
+    fn ran(&self, name: &str) -> Vec<u64> {
+        self.log
+            .iter()
+            .filter(|(_, n)| *n == name)
+            .map(|(tick, _)| *tick)
+            .collect()
+    }
+}
+
+fn task(name: &'static str, urgency: u32) -> Task {
+    Task {
+        name,
+        urgency,
+        every: None,
+    }
+}
+
+#[test]
+fn runs_tasks_at_their_deadline() {
+    let mut scheduler = Scheduler::new(8, 4);
+
+    scheduler.schedule(task("soon", 0), 3);
+    // Further than a turn of the wheel.
+    scheduler.schedule(task("later", 0), 20);
+    scheduler.schedule(task("much later", 0), 65);
+
+    for _ in 0..70 {
+        scheduler.tick();
+    }
+
+    assert_eq!(scheduler.ran("soon"), vec![3]);
+    assert_eq!(scheduler.ran("later"), vec![20]);
+    assert_eq!(scheduler.ran("much later"), vec![65]);
+}
+
+#[test]
+fn periodic_tasks() {
+    let mut scheduler = Scheduler::new(4, 1);
+
+    scheduler.schedule(
+        Task {
+            every: Some(5),
+            ..task("heartbeat", 0)
+        },
+        5,
+    );
+
+    for _ in 0..30 {
+        scheduler.tick();
+    }
+
+    assert_eq!(scheduler.ran("heartbeat"), vec![5, 10, 15, 20, 25, 30]);
+}
+
+#[test]
+fn urgent_tasks_run_first_when_workers_are_busy() {
+    let mut scheduler = Scheduler::new(16, 2);
+
+    scheduler.schedule(task("backup", 9), 2);
+    scheduler.schedule(task("report", 5), 2);
+    scheduler.schedule(task("alert", 0), 2);
+    scheduler.schedule(task("email", 5), 2);
+
+    for _ in 0..4 {
+        scheduler.tick();
+    }
+
+    // Two workers: the alert and the first report run at the deadline, the
+    // rest wait a tick in order of urgency, then of readiness.
+    assert_eq!(
+        scheduler.log,
+        vec![(2, "alert"), (2, "report"), (3, "email"), (3, "backup")]
+    );
+    assert!(scheduler.ready.is_empty());
+}
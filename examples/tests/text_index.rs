@@ -0,0 +1,131 @@
+//! An inverted index over a few documents.
+//!
+//! The terms are kept in a Treap, each with the sorted ids of the documents
+//! it appears in. Words are looked up directly, and prefix queries split the
+//! Treap around the range of terms with the prefix, read the middle part and
+//! join the three parts back, without walking the terms outside the range.
+use treap::Treap;
+
+#[derive(Default)]
+struct TextIndex {
+    terms: Treap<String, Vec<usize>>,
+}
+
+impl TextIndex {
+    fn add(&mut self, id: usize, text: &str) {
+        for word in text.split_whitespace() {
+            let term = word
+                .trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase();
+            if term.is_empty() {
+                continue;
+            }
+
+            match self.terms.get_mut(&term) {
+                Some(ids) if ids.last() == Some(&id) => {}
+                Some(ids) => ids.push(id),
+                None => {
+                    self.terms.insert(term, vec![id]);
+                }
+            }
+        }
+    }
+
+    fn search(&self, word: &str) -> &[usize] {
+        self.terms
+            .get(&word.to_lowercase())
+            .map_or(&[], |ids| ids.as_slice())
+    }
+
+    /// Returns the terms starting with `prefix` and their documents.
+    fn prefix(&mut self, prefix: &str) -> Vec<(String, Vec<usize>)> {
+        let (start, end) = bounds(prefix);
+        let terms = std::mem::take(&mut self.terms);
+
+        let (low, rest) = terms.split(&start);
+        let (range, high) = rest.split(&end);
+        let found = range
+            .iter()
+            .map(|(term, ids)| (term.clone(), ids.clone()))
+            .collect();
+
+        self.terms = Treap::join(Treap::join(low, range), high);
+        found
+    }
+
+    /// Forgets every term starting with `prefix`, returning how many there
+    /// were.
+    fn forget_prefix(&mut self, prefix: &str) -> usize {
+        let (start, end) = bounds(prefix);
+        self.terms.remove_range(start..end).len()
+    }
+}
+
+/// Returns the range of the terms starting with `prefix`. The terms are
+/// ASCII words, so none of them goes past the prefix followed by the largest
+/// char.
+fn bounds(prefix: &str) -> (String, String) {
+    let start = prefix.to_lowercase();
+    let mut end = start.clone();
+    end.push(char::MAX);
+
+    (start, end)
+}
+
+fn index() -> TextIndex {
+    let mut index = TextIndex::default();
+    index.add(0, "The treap splits and joins by key.");
+    index.add(
+        1,
+        "A trie indexes the prefixes of words, a tree indexes keys.",
+    );
+    index.add(2, "Trees, treaps and tries: three trees for text.");
+    index.add(3, "Keys, keys and more keys.");
+
+    index
+}
+
+#[test]
+fn word_queries() {
+    let index = index();
+
+    assert_eq!(index.search("the"), &[0, 1]);
+    assert_eq!(index.search("KEYS"), &[1, 3]);
+    assert_eq!(index.search("trees"), &[2]);
+    assert_eq!(index.search("graph"), &[] as &[usize]);
+}
+
+#[test]
+fn prefix_queries() {
+    let mut index = index();
+    let terms = index.terms.len();
+
+    let found = index.prefix("tre");
+    assert_eq!(
+        found,
+        vec![
+            ("treap".to_string(), vec![0]),
+            ("treaps".to_string(), vec![2]),
+            ("tree".to_string(), vec![1]),
+            ("trees".to_string(), vec![2]),
+        ]
+    );
+
+    // The index is whole again after the query.
+    assert_eq!(index.terms.len(), terms);
+    assert_eq!(index.search("and"), &[0, 2, 3]);
+    assert!(index.prefix("zzz").is_empty());
+    assert_eq!(index.prefix("").len(), terms);
+}
+
+#[test]
+fn forget_a_prefix() {
+    let mut index = index();
+    let terms = index.terms.len();
+
+    assert_eq!(index.forget_prefix("tr"), 6);
+    assert_eq!(index.terms.len(), terms - 6);
+    assert!(index.prefix("tr").is_empty());
+    assert_eq!(index.search("text"), &[2]);
+    assert_eq!(index.search("the"), &[0, 1]);
+}
@@ -0,0 +1,112 @@
+//! A web cache in front of a slow origin server.
+//!
+//! Pages are cached in an LRU bounded both by the number of pages and by the
+//! bytes of their bodies. The remove callback counts the evictions, and the
+//! simulation checks that a skewed workload is mostly served from the cache
+//! without going over the byte budget.
+use lru::LRU;
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// The origin renders a page for a path, counting how often it is asked.
+struct Origin {
+    requests: u32,
+}
+
+impl Origin {
+    fn fetch(&mut self, path: &str) -> String {
+        self.requests += 1;
+
+        // Pages under /big are ten times the size of the others.
+        let repeat = if path.starts_with("/big") { 500 } else { 50 };
+        format!("<p>{}</p>", path).repeat(repeat)
+    }
+}
+
+struct WebCache {
+    pages: LRU<String, String>,
+    origin: Origin,
+    hits: u32,
+}
+
+impl WebCache {
+    fn new(pages: usize, bytes: usize) -> WebCache {
+        WebCache {
+            pages: LRU::builder().capacity(pages).byte_budget(bytes).build(),
+            origin: Origin { requests: 0 },
+            hits: 0,
+        }
+    }
+
+    fn get(&mut self, path: &str) -> String {
+        if let Some(body) = self.pages.get(path.to_string()) {
+            self.hits += 1;
+            return body;
+        }
+
+        let body = self.origin.fetch(path);
+        self.pages.add(path.to_string(), body.clone());
+
+        body
+    }
+}
+
+/// A small deterministic generator, so the workload is the same every run.
+fn next(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+#[test]
+fn serves_a_skewed_workload_from_the_cache() {
+    const BUDGET: usize = 64 * 1024;
+
+    let mut cache = WebCache::new(100, BUDGET);
+    let evictions = Rc::new(Cell::new(0));
+    let counter = evictions.clone();
+    cache
+        .pages
+        .on_remove(move |_, _| counter.set(counter.get() + 1));
+
+    let mut state = 0x2545_f491_4f6c_dd1d;
+    for _ in 0..5000 {
+        // Most requests go to a few hot pages, the rest to a long tail.
+        let r = next(&mut state);
+        let path = if r % 10 < 8 {
+            format!("/hot/{}", r % 20)
+        } else {
+            format!("/tail/{}", r % 1000)
+        };
+
+        let body = cache.get(&path);
+        assert!(body.contains(&path));
+
+        assert!(cache.pages.len() <= 100);
+        assert!(cache.pages.bytes_used().unwrap() <= BUDGET);
+    }
+
+    let requests = 5000;
+    assert_eq!(cache.hits + cache.origin.requests, requests);
+    assert!(cache.hits > requests * 7 / 10, "hits: {}", cache.hits);
+    assert!(evictions.get() > 0);
+}
+
+#[test]
+fn large_pages_are_evicted_by_bytes_before_count() {
+    let mut cache = WebCache::new(100, 16 * 1024);
+
+    for i in 0..10 {
+        cache.get(&format!("/big/{}", i));
+    }
+
+    // Each large page is around 6KB, so only two of them fit in the budget
+    // even though the LRU could hold a hundred pages.
+    assert_eq!(cache.pages.len(), 2);
+    assert_eq!(cache.get("/big/9").len(), cache.get("/big/8").len());
+    assert_eq!(cache.origin.requests, 10);
+
+    cache.get("/big/0");
+    assert_eq!(cache.origin.requests, 11);
+}
@@ -0,0 +1,225 @@
+use crate::graph::{EdgeId, Graph, NodeId};
+use std::collections::VecDeque;
+
+/// The result of `Graph::max_flow()`: the value of the flow and the flow
+/// through each edge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Flow {
+    value: u64,
+    edges: Vec<u64>,
+}
+
+impl Flow {
+    /// Returns the total flow from the source to the sink.
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// Returns the flow through an edge, at most its capacity.
+    pub fn on(&self, edge: EdgeId) -> u64 {
+        self.edges.get(edge.0).copied().unwrap_or(0)
+    }
+}
+
+/// An arc of the residual graph. Edge i has its forward arc at 2i and its
+/// backward arc at 2i + 1, so the arcs of a pair are found with `^ 1`.
+struct Arc {
+    to: usize,
+    capacity: u64,
+}
+
+/// The residual graph walked by Dinic's algorithm.
+struct Residual {
+    arcs: Vec<Arc>,
+    outgoing: Vec<Vec<usize>>,
+    // The BFS distance of each node from the source, None if unreachable.
+    level: Vec<Option<usize>>,
+    // The next arc to try from each node, arcs before it are saturated or
+    // lead nowhere in the current level graph.
+    next: Vec<usize>,
+}
+
+impl Residual {
+    /// Computes the levels from the source, returning a boolean indicating
+    /// the sink is reachable.
+    fn bfs(&mut self, source: usize, sink: usize) -> bool {
+        self.level.iter_mut().for_each(|l| *l = None);
+        self.level[source] = Some(0);
+
+        let mut queue = VecDeque::from(vec![source]);
+        while let Some(node) = queue.pop_front() {
+            for &a in &self.outgoing[node] {
+                let Arc { to, capacity } = self.arcs[a];
+                if capacity > 0 && self.level[to].is_none() {
+                    self.level[to] = self.level[node].map(|l| l + 1);
+                    queue.push_back(to);
+                }
+            }
+        }
+
+        self.level[sink].is_some()
+    }
+
+    /// Pushes at most `limit` along one path of the level graph, returning
+    /// the amount pushed.
+    fn augment(&mut self, node: usize, sink: usize, limit: u64) -> u64 {
+        if node == sink {
+            return limit;
+        }
+
+        while self.next[node] < self.outgoing[node].len() {
+            let a = self.outgoing[node][self.next[node]];
+            let Arc { to, capacity } = self.arcs[a];
+
+            if capacity > 0 && self.level[to] == self.level[node].map(|l| l + 1) {
+                let pushed = self.augment(to, sink, limit.min(capacity));
+                if pushed > 0 {
+                    self.arcs[a].capacity -= pushed;
+                    self.arcs[a ^ 1].capacity += pushed;
+                    return pushed;
+                }
+            }
+
+            self.next[node] += 1;
+        }
+
+        0
+    }
+}
+
+impl<N, E> Graph<N, E>
+where
+    E: Copy + Into<u64>,
+{
+    /// Returns the maximum flow from `source` to `sink`, using the payload of
+    /// each edge as its capacity. Uses Dinic's algorithm.
+    ///
+    /// Time Complexity: O(V^2 E)
+    /// Space Complexity: O(V + E)
+    ///
+    /// # Panics
+    ///
+    /// Panics if either node isn't in the Graph.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use graph::Graph;
+    ///
+    /// let mut pipes = Graph::new();
+    /// let source = pipes.add_node("source");
+    /// let a = pipes.add_node("a");
+    /// let b = pipes.add_node("b");
+    /// let sink = pipes.add_node("sink");
+    ///
+    /// pipes.add_edge(source, a, 3u32);
+    /// pipes.add_edge(source, b, 2);
+    /// let ab = pipes.add_edge(a, b, 1);
+    /// pipes.add_edge(a, sink, 2);
+    /// pipes.add_edge(b, sink, 3);
+    ///
+    /// let flow = pipes.max_flow(source, sink);
+    /// assert_eq!(flow.value(), 5);
+    /// assert_eq!(flow.on(ab), 1);
+    /// ```
+    pub fn max_flow(&self, source: NodeId, sink: NodeId) -> Flow {
+        let nodes = self.node_count();
+        assert!(
+            source.0 < nodes && sink.0 < nodes,
+            "the source and sink must be in the Graph"
+        );
+
+        let mut residual = Residual {
+            arcs: Vec::with_capacity(2 * self.edge_count()),
+            outgoing: vec![Vec::new(); nodes],
+            level: vec![None; nodes],
+            next: vec![0; nodes],
+        };
+        for (from, to, capacity) in self.edges() {
+            residual.outgoing[from.0].push(residual.arcs.len());
+            residual.arcs.push(Arc {
+                to: to.0,
+                capacity: (*capacity).into(),
+            });
+            residual.outgoing[to.0].push(residual.arcs.len());
+            residual.arcs.push(Arc {
+                to: from.0,
+                capacity: 0,
+            });
+        }
+
+        let mut value = 0;
+        while source != sink && residual.bfs(source.0, sink.0) {
+            residual.next.iter_mut().for_each(|n| *n = 0);
+            loop {
+                let pushed = residual.augment(source.0, sink.0, u64::MAX);
+                if pushed == 0 {
+                    break;
+                }
+                value += pushed;
+            }
+        }
+
+        // The flow through an edge is what its backward arc can send back.
+        let edges = residual
+            .arcs
+            .chunks(2)
+            .map(|pair| pair[1].capacity)
+            .collect();
+
+        Flow { value, edges }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn max_flow() {
+        // The network of CLRS figure 26.1.
+        let mut graph = Graph::new();
+        let n: Vec<NodeId> = (0..6).map(|i| graph.add_node(i)).collect();
+        let capacities = [
+            (0, 1, 16u32),
+            (0, 2, 13),
+            (1, 3, 12),
+            (2, 1, 4),
+            (2, 4, 14),
+            (3, 2, 9),
+            (3, 5, 20),
+            (4, 3, 7),
+            (4, 5, 4),
+        ];
+        let edges: Vec<EdgeId> = capacities
+            .iter()
+            .map(|&(a, b, c)| graph.add_edge(n[a], n[b], c))
+            .collect();
+
+        let flow = graph.max_flow(n[0], n[5]);
+        assert_eq!(flow.value(), 23);
+
+        // Every edge is within its capacity, and every inner node passes on
+        // what it receives.
+        let mut balance = [0i64; 6];
+        for (i, &(a, b, c)) in capacities.iter().enumerate() {
+            let f = flow.on(edges[i]);
+            assert!(f <= c as u64);
+            balance[a] -= f as i64;
+            balance[b] += f as i64;
+        }
+        assert_eq!(balance, [-23, 0, 0, 0, 0, 23]);
+    }
+
+    #[test]
+    fn no_path() {
+        let mut graph = Graph::new();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        let ab = graph.add_edge(b, a, 5u8);
+
+        assert_eq!(graph.max_flow(a, b).value(), 0);
+        assert_eq!(graph.max_flow(a, a).value(), 0);
+        assert_eq!(graph.max_flow(b, a).on(ab), 5);
+    }
+}
@@ -0,0 +1,146 @@
+use crate::graph::{Graph, NodeId};
+use std::fmt::{Display, Write};
+
+impl<N: Default, E> Graph<N, E> {
+    /// Returns a Graph built from a list of `(source, target, payload)`
+    /// edges, the nodes being referred to by their index. The Graph has one
+    /// node per index up to the largest one, each holding `N::default()`.
+    ///
+    /// Time Complexity: O(V + E)
+    /// Space Complexity: O(V + E)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use graph::Graph;
+    ///
+    /// let graph: Graph<(), u32> = Graph::from_edge_list(vec![(0, 1, 5), (1, 3, 7)]);
+    /// assert_eq!(graph.node_count(), 4);
+    /// assert_eq!(graph.to_edge_list(), vec![(0, 1, 5), (1, 3, 7)]);
+    /// ```
+    pub fn from_edge_list<I>(edges: I) -> Graph<N, E>
+    where
+        I: IntoIterator<Item = (usize, usize, E)>,
+    {
+        let mut graph = Graph::new();
+        for (source, target, weight) in edges {
+            while graph.node_count() <= source.max(target) {
+                graph.add_node(N::default());
+            }
+            graph.add_edge(NodeId(source), NodeId(target), weight);
+        }
+
+        graph
+    }
+}
+
+impl<N, E: Clone> Graph<N, E> {
+    /// Returns the edges as `(source, target, payload)`, the nodes being
+    /// referred to by their index, in the order the edges were added. Nodes
+    /// without edges and the node payloads aren't part of the list.
+    ///
+    /// Time Complexity: O(E)
+    /// Space Complexity: O(E)
+    pub fn to_edge_list(&self) -> Vec<(usize, usize, E)> {
+        self.edges()
+            .map(|(source, target, weight)| (source.0, target.0, weight.clone()))
+            .collect()
+    }
+}
+
+impl<N: Display, E: Display> Graph<N, E> {
+    /// Returns the Graph in the DOT format of Graphviz, the nodes and edges
+    /// labelled with their payloads, e.g. to render it with
+    /// `dot -Tsvg graph.dot`.
+    ///
+    /// Time Complexity: O(V + E)
+    /// Space Complexity: O(V + E)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// let a = graph.add_node("a");
+    /// let b = graph.add_node("b");
+    /// graph.add_edge(a, b, 3);
+    ///
+    /// assert_eq!(
+    ///     graph.to_dot(),
+    ///     "digraph {\n    0 [label=\"a\"];\n    1 [label=\"b\"];\n    0 -> 1 [label=\"3\"];\n}\n"
+    /// );
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n");
+        for (id, payload) in self.nodes() {
+            writeln!(dot, "    {} [label=\"{}\"];", id.0, escape(payload)).unwrap();
+        }
+        for (source, target, weight) in self.edges() {
+            writeln!(
+                dot,
+                "    {} -> {} [label=\"{}\"];",
+                source.0,
+                target.0,
+                escape(weight)
+            )
+            .unwrap();
+        }
+        dot.push_str("}\n");
+
+        dot
+    }
+}
+
+/// Returns a payload as the content of a quoted DOT string.
+fn escape<T: Display>(payload: &T) -> String {
+    payload
+        .to_string()
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn edge_list_round_trip() {
+        let mut graph = Graph::new();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        let c = graph.add_node(());
+        graph.add_edge(a, c, 'x');
+        graph.add_edge(c, b, 'y');
+        graph.add_edge(a, c, 'z');
+
+        let edges = graph.to_edge_list();
+        assert_eq!(edges, vec![(0, 2, 'x'), (2, 1, 'y'), (0, 2, 'z')]);
+
+        let copy: Graph<(), char> = Graph::from_edge_list(edges.clone());
+        assert_eq!(copy.node_count(), 3);
+        assert_eq!(copy.to_edge_list(), edges);
+        assert_eq!(copy.neighbors(a).collect::<Vec<_>>(), vec![c, c]);
+
+        let empty: Graph<(), char> = Graph::from_edge_list(Vec::new());
+        assert_eq!((empty.node_count(), empty.edge_count()), (0, 0));
+    }
+
+    #[test]
+    fn dot_escapes_labels() {
+        let mut graph = Graph::new();
+        let a = graph.add_node("say \"hi\"");
+        let b = graph.add_node("two\nlines");
+        graph.add_edge(b, a, "a\\b");
+
+        assert_eq!(
+            graph.to_dot(),
+            "digraph {\n    \
+             0 [label=\"say \\\"hi\\\"\"];\n    \
+             1 [label=\"two\\nlines\"];\n    \
+             1 -> 0 [label=\"a\\\\b\"];\n\
+             }\n"
+        );
+    }
+}
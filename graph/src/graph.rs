@@ -0,0 +1,294 @@
+use crate::topo::Order;
+
+/// NodeId refers to a node of a Graph, returned by `Graph::add_node()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(pub(crate) usize);
+
+impl NodeId {
+    /// Returns the index of the node, nodes are numbered from 0 in the order
+    /// they were added.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// EdgeId refers to an edge of a Graph, returned by `Graph::add_edge()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EdgeId(pub(crate) usize);
+
+impl EdgeId {
+    /// Returns the index of the edge, edges are numbered from 0 in the order
+    /// they were added.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+struct Edge<E> {
+    source: NodeId,
+    target: NodeId,
+    weight: E,
+}
+
+/// Graph is a directed graph whose nodes carry a payload `N` and whose edges
+/// carry a payload `E`. Several edges may join the same nodes.
+///
+/// # Example
+///
+/// ```
+/// use graph::Graph;
+///
+/// let mut roads = Graph::new();
+/// let paris = roads.add_node("Paris");
+/// let lyon = roads.add_node("Lyon");
+/// let nice = roads.add_node("Nice");
+///
+/// roads.add_edge(paris, lyon, 465);
+/// let a8 = roads.add_edge(lyon, nice, 470);
+/// *roads.edge_mut(a8).unwrap() -= 10;
+///
+/// let total: u32 = roads.edges().map(|(_, _, km)| km).sum();
+/// assert_eq!(total, 925);
+/// assert_eq!(roads.node(nice), Some(&"Nice"));
+/// ```
+pub struct Graph<N, E> {
+    nodes: Vec<N>,
+    edges: Vec<Edge<E>>,
+    // The outgoing edges of each node, in the order they were added.
+    outgoing: Vec<Vec<EdgeId>>,
+    // A topological order of the nodes, None once an edge added by
+    // `add_edge()` goes against it.
+    pub(crate) order: Option<Order>,
+}
+
+impl<N, E> Default for Graph<N, E> {
+    fn default() -> Self {
+        Graph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            outgoing: Vec::new(),
+            order: Some(Order::default()),
+        }
+    }
+}
+
+impl<N, E> Graph<N, E> {
+    /// Returns an empty Graph.
+    pub fn new() -> Graph<N, E> {
+        Graph::default()
+    }
+
+    /// Returns the number of nodes.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the number of edges.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Adds a node holding `payload`, returning its id.
+    ///
+    /// Time Complexity: O(1) amortized
+    /// Space Complexity: O(1)
+    pub fn add_node(&mut self, payload: N) -> NodeId {
+        self.nodes.push(payload);
+        self.outgoing.push(Vec::new());
+
+        let id = NodeId(self.nodes.len() - 1);
+        if let Some(order) = &mut self.order {
+            order.push(id);
+        }
+
+        id
+    }
+
+    /// Adds an edge from `source` to `target` holding `weight`, returning its
+    /// id.
+    ///
+    /// Time Complexity: O(1) amortized
+    /// Space Complexity: O(1)
+    ///
+    /// # Panics
+    ///
+    /// Panics if either node isn't in the Graph.
+    pub fn add_edge(&mut self, source: NodeId, target: NodeId, weight: E) -> EdgeId {
+        assert!(
+            source.0 < self.nodes.len() && target.0 < self.nodes.len(),
+            "the nodes of an edge must be in the Graph"
+        );
+
+        let id = EdgeId(self.edges.len());
+        self.edges.push(Edge {
+            source,
+            target,
+            weight,
+        });
+        self.outgoing[source.0].push(id);
+        if !self
+            .order
+            .as_ref()
+            .is_some_and(|o| o.before(source, target))
+        {
+            self.order = None;
+        }
+
+        id
+    }
+
+    /// Returns the payload of a node.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn node(&self, id: NodeId) -> Option<&N> {
+        self.nodes.get(id.0)
+    }
+
+    /// Returns the payload of a node to update it.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn node_mut(&mut self, id: NodeId) -> Option<&mut N> {
+        self.nodes.get_mut(id.0)
+    }
+
+    /// Returns the payload of an edge.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn edge(&self, id: EdgeId) -> Option<&E> {
+        self.edges.get(id.0).map(|e| &e.weight)
+    }
+
+    /// Returns the payload of an edge to update it.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn edge_mut(&mut self, id: EdgeId) -> Option<&mut E> {
+        self.edges.get_mut(id.0).map(|e| &mut e.weight)
+    }
+
+    /// Returns the source and target of an edge.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn endpoints(&self, id: EdgeId) -> Option<(NodeId, NodeId)> {
+        self.edges.get(id.0).map(|e| (e.source, e.target))
+    }
+
+    /// Returns the first edge added from `source` to `target`.
+    ///
+    /// Time Complexity: O(d), d the number of edges leaving `source`
+    /// Space Complexity: O(1)
+    pub fn find_edge(&self, source: NodeId, target: NodeId) -> Option<EdgeId> {
+        self.outgoing
+            .get(source.0)?
+            .iter()
+            .copied()
+            .find(|id| self.edges[id.0].target == target)
+    }
+
+    /// Returns an Iterator over the ids and payloads of the nodes, in the
+    /// order they were added.
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeId, &N)> + '_ {
+        self.nodes.iter().enumerate().map(|(i, n)| (NodeId(i), n))
+    }
+
+    /// Returns an Iterator over the edges as `(source, target, &payload)`, in
+    /// the order they were added.
+    pub fn edges(&self) -> impl Iterator<Item = (NodeId, NodeId, &E)> + '_ {
+        self.edges.iter().map(|e| (e.source, e.target, &e.weight))
+    }
+
+    /// Returns an Iterator over the edges leaving a node as
+    /// `(edge, target, &payload)`, empty if the node isn't in the Graph.
+    ///
+    /// Time Complexity: O(1) per edge
+    /// Space Complexity: O(1)
+    pub fn edges_from(&self, source: NodeId) -> impl Iterator<Item = (EdgeId, NodeId, &E)> + '_ {
+        self.outgoing
+            .get(source.0)
+            .into_iter()
+            .flatten()
+            .map(move |id| {
+                let edge = &self.edges[id.0];
+                (*id, edge.target, &edge.weight)
+            })
+    }
+
+    /// Returns an Iterator over the targets of the edges leaving a node.
+    pub fn neighbors(&self, source: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges_from(source).map(|(_, target, _)| target)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn payloads() {
+        let mut graph = Graph::new();
+        let a = graph.add_node(String::from("a"));
+        let b = graph.add_node(String::from("b"));
+        assert_eq!(graph.node_count(), 2);
+        assert_eq!((a.index(), b.index()), (0, 1));
+
+        graph.node_mut(b).unwrap().push('!');
+        assert_eq!(graph.node(b).map(String::as_str), Some("b!"));
+        assert_eq!(graph.node(NodeId(2)), None);
+
+        let ab = graph.add_edge(a, b, vec![1]);
+        graph.edge_mut(ab).unwrap().push(2);
+        assert_eq!(graph.edge(ab), Some(&vec![1, 2]));
+        assert_eq!(graph.endpoints(ab), Some((a, b)));
+        assert_eq!(graph.edge(EdgeId(1)), None);
+
+        let nodes: Vec<_> = graph.nodes().map(|(id, n)| (id, n.as_str())).collect();
+        assert_eq!(nodes, vec![(a, "a"), (b, "b!")]);
+    }
+
+    #[test]
+    fn edges() {
+        let mut graph = Graph::new();
+        let nodes: Vec<NodeId> = (0..3).map(|i| graph.add_node(i)).collect();
+        let (a, b, c) = (nodes[0], nodes[1], nodes[2]);
+
+        let ab = graph.add_edge(a, b, 'x');
+        let ac = graph.add_edge(a, c, 'y');
+        graph.add_edge(c, a, 'z');
+        // A second edge between the same nodes.
+        let ab2 = graph.add_edge(a, b, 'w');
+        assert_eq!(graph.edge_count(), 4);
+
+        assert_eq!(
+            graph.edges().collect::<Vec<_>>(),
+            vec![(a, b, &'x'), (a, c, &'y'), (c, a, &'z'), (a, b, &'w')]
+        );
+        assert_eq!(
+            graph.edges_from(a).collect::<Vec<_>>(),
+            vec![(ab, b, &'x'), (ac, c, &'y'), (ab2, b, &'w')]
+        );
+        assert_eq!(graph.neighbors(c).collect::<Vec<_>>(), vec![a]);
+        assert_eq!(graph.neighbors(b).count(), 0);
+        assert_eq!(graph.neighbors(NodeId(9)).count(), 0);
+
+        assert_eq!(graph.find_edge(a, b), Some(ab));
+        assert_eq!(graph.find_edge(b, a), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "the nodes of an edge must be in the Graph")]
+    fn edge_to_missing_node() {
+        let mut graph = Graph::new();
+        let a = graph.add_node(());
+        graph.add_edge(a, NodeId(1), ());
+    }
+}
@@ -0,0 +1,14 @@
+//! A crate that implements a directed Graph whose nodes and edges carry
+//! payloads, with maximum flow and bipartite matching algorithms, a
+//! topological order kept as edges are added, and conversions to and from edge lists and the DOT format.
+pub use crate::flow::Flow;
+pub use crate::graph::{EdgeId, Graph, NodeId};
+pub use crate::topo::Cycle;
+pub use crate::traverse::{Children, Traversal};
+
+mod flow;
+mod format;
+mod graph;
+mod matching;
+mod topo;
+mod traverse;
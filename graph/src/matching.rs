@@ -0,0 +1,184 @@
+use crate::graph::{Graph, NodeId};
+use std::collections::VecDeque;
+
+/// The state of the Hopcroft-Karp algorithm. The left nodes are numbered by
+/// their position in the slice given, the right nodes by their NodeId.
+struct Matching {
+    // The right nodes each left node has an edge to.
+    adjacent: Vec<Vec<usize>>,
+    left_match: Vec<Option<usize>>,
+    right_match: Vec<Option<usize>>,
+    // The BFS layer of each left node, None once it can't reach a free right
+    // node in the current phase.
+    layer: Vec<Option<usize>>,
+}
+
+impl Matching {
+    /// Layers the left nodes by the length of the alternating paths from the
+    /// free left nodes, returning a boolean indicating an augmenting path
+    /// exists.
+    fn bfs(&mut self) -> bool {
+        let mut queue = VecDeque::new();
+        for (u, matched) in self.left_match.iter().enumerate() {
+            self.layer[u] = match matched {
+                Some(_) => None,
+                None => {
+                    queue.push_back(u);
+                    Some(0)
+                }
+            };
+        }
+
+        let mut found = false;
+        while let Some(u) = queue.pop_front() {
+            for &v in &self.adjacent[u] {
+                match self.right_match[v] {
+                    None => found = true,
+                    Some(w) if self.layer[w].is_none() => {
+                        self.layer[w] = self.layer[u].map(|l| l + 1);
+                        queue.push_back(w);
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        found
+    }
+
+    /// Looks for an augmenting path from a left node along the layers,
+    /// flipping the matches along it if one is found.
+    fn dfs(&mut self, u: usize) -> bool {
+        for i in 0..self.adjacent[u].len() {
+            let v = self.adjacent[u][i];
+            let next = match self.right_match[v] {
+                None => true,
+                Some(w) => self.layer[w] == self.layer[u].map(|l| l + 1) && self.dfs(w),
+            };
+
+            if next {
+                self.left_match[u] = Some(v);
+                self.right_match[v] = Some(u);
+                return true;
+            }
+        }
+
+        // No path from here in this phase, don't look again.
+        self.layer[u] = None;
+        false
+    }
+}
+
+impl<N, E> Graph<N, E> {
+    /// Returns a maximum matching between the `left` nodes and the nodes
+    /// their edges lead to, as `(left, right)` pairs in the order of `left`.
+    /// Edges between two left nodes are ignored. Uses the Hopcroft-Karp
+    /// algorithm.
+    ///
+    /// Time Complexity: O(E sqrt(V))
+    /// Space Complexity: O(V + E)
+    ///
+    /// # Panics
+    ///
+    /// Panics if a left node isn't in the Graph.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use graph::Graph;
+    ///
+    /// // Workers are joined to the tasks they can do.
+    /// let mut graph = Graph::new();
+    /// let ann = graph.add_node("ann");
+    /// let bob = graph.add_node("bob");
+    /// let build = graph.add_node("build");
+    /// let test = graph.add_node("test");
+    ///
+    /// graph.add_edge(ann, build, ());
+    /// graph.add_edge(ann, test, ());
+    /// graph.add_edge(bob, build, ());
+    ///
+    /// let matching = graph.bipartite_matching(&[ann, bob]);
+    /// assert_eq!(matching, vec![(ann, test), (bob, build)]);
+    /// ```
+    pub fn bipartite_matching(&self, left: &[NodeId]) -> Vec<(NodeId, NodeId)> {
+        let mut is_left = vec![false; self.node_count()];
+        for node in left {
+            is_left[node.0] = true;
+        }
+
+        let mut matching = Matching {
+            adjacent: left
+                .iter()
+                .map(|&u| {
+                    self.neighbors(u)
+                        .filter(|v| !is_left[v.0])
+                        .map(|v| v.0)
+                        .collect()
+                })
+                .collect(),
+            left_match: vec![None; left.len()],
+            right_match: vec![None; self.node_count()],
+            layer: vec![None; left.len()],
+        };
+
+        while matching.bfs() {
+            for u in 0..left.len() {
+                if matching.left_match[u].is_none() {
+                    matching.dfs(u);
+                }
+            }
+        }
+
+        left.iter()
+            .zip(&matching.left_match)
+            .filter_map(|(&u, v)| v.map(|v| (u, NodeId(v))))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn augmenting_paths() {
+        let mut graph = Graph::new();
+        let left: Vec<NodeId> = (0..4).map(|i| graph.add_node(i)).collect();
+        let right: Vec<NodeId> = (4..8).map(|i| graph.add_node(i)).collect();
+
+        // A greedy matching in order would pair 0-0 and 1-1, leaving 2 and 3
+        // to reroute them.
+        for &(u, v) in &[(0, 0), (0, 1), (1, 1), (1, 2), (2, 0), (3, 2), (3, 3)] {
+            graph.add_edge(left[u], right[v], ());
+        }
+        // Ignored, both ends are on the left.
+        graph.add_edge(left[2], left[3], ());
+
+        let matching = graph.bipartite_matching(&left);
+        assert_eq!(matching.len(), 4);
+
+        let mut matched: Vec<NodeId> = matching.iter().map(|&(_, v)| v).collect();
+        matched.sort();
+        assert_eq!(matched, right);
+        for (u, v) in matching {
+            assert!(graph.find_edge(u, v).is_some());
+        }
+    }
+
+    #[test]
+    fn partial_matching() {
+        let mut graph = Graph::new();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        let c = graph.add_node(());
+        let x = graph.add_node(());
+
+        graph.add_edge(a, x, ());
+        graph.add_edge(b, x, ());
+
+        let matching = graph.bipartite_matching(&[a, b, c]);
+        assert_eq!(matching.len(), 1);
+        assert!(graph.bipartite_matching(&[]).is_empty());
+    }
+}
@@ -0,0 +1,267 @@
+use crate::graph::{EdgeId, Graph, NodeId};
+use std::error::Error;
+use std::fmt;
+
+/// The error returned by `Graph::add_edge_checked()` when the edge would
+/// create a cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle;
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the edge would create a cycle")
+    }
+}
+
+impl Error for Cycle {}
+
+/// A topological order of the nodes of a Graph: every edge goes from a node
+/// to a later one.
+#[derive(Default)]
+pub(crate) struct Order {
+    // The position of each node in the order, indexed by NodeId.
+    position: Vec<usize>,
+    // The nodes, indexed by position.
+    nodes: Vec<NodeId>,
+}
+
+impl Order {
+    /// Returns the order of a Graph found by Kahn's algorithm, None if the
+    /// Graph has a cycle.
+    fn sorted<N, E>(graph: &Graph<N, E>) -> Option<Order> {
+        let mut incoming = vec![0; graph.node_count()];
+        for (_, target, _) in graph.edges() {
+            incoming[target.0] += 1;
+        }
+
+        let mut nodes: Vec<NodeId> = graph
+            .nodes()
+            .map(|(id, _)| id)
+            .filter(|id| incoming[id.0] == 0)
+            .collect();
+        let mut next = 0;
+        while let Some(&node) = nodes.get(next) {
+            next += 1;
+            for target in graph.neighbors(node) {
+                incoming[target.0] -= 1;
+                if incoming[target.0] == 0 {
+                    nodes.push(target);
+                }
+            }
+        }
+
+        if nodes.len() < graph.node_count() {
+            return None;
+        }
+
+        let mut position = vec![0; nodes.len()];
+        for (i, node) in nodes.iter().enumerate() {
+            position[node.0] = i;
+        }
+
+        Some(Order { position, nodes })
+    }
+
+    /// Adds a node, without edges, at the end of the order.
+    pub fn push(&mut self, node: NodeId) {
+        self.position.push(self.nodes.len());
+        self.nodes.push(node);
+    }
+
+    /// Returns a boolean indicating `a` comes before `b` in the order.
+    pub fn before(&self, a: NodeId, b: NodeId) -> bool {
+        self.position[a.0] < self.position[b.0]
+    }
+
+    /// Reorders the nodes so an edge from `source` to `target` can be added,
+    /// with the algorithm of Marchetti-Spaccamela, Nanni and Rohnert. Only
+    /// the nodes between `target` and `source` in the order are looked at.
+    fn make_room<N, E>(
+        &mut self,
+        graph: &Graph<N, E>,
+        source: NodeId,
+        target: NodeId,
+    ) -> Result<(), Cycle> {
+        if source == target {
+            return Err(Cycle);
+        }
+        if self.before(source, target) {
+            return Ok(());
+        }
+
+        let (low, high) = (self.position[target.0], self.position[source.0]);
+
+        // The nodes in the range reachable from target must move after
+        // source, and if source is one of them the edge closes a cycle.
+        let mut reached = vec![false; high - low + 1];
+        let mut stack = vec![target];
+        reached[0] = true;
+        while let Some(node) = stack.pop() {
+            for next in graph.neighbors(node) {
+                let at = self.position[next.0];
+                if at <= high && !reached[at - low] {
+                    if next == source {
+                        return Err(Cycle);
+                    }
+                    reached[at - low] = true;
+                    stack.push(next);
+                }
+            }
+        }
+
+        // The other nodes keep their relative order and move first, then the
+        // reached ones keep theirs. No edge leaves a reached node for an
+        // unreached one in the range, or it would have been reached.
+        let range = &self.nodes[low..=high];
+        let kept = range.iter().enumerate().filter(|&(i, _)| !reached[i]);
+        let moved = range.iter().enumerate().filter(|&(i, _)| reached[i]);
+        let reordered: Vec<NodeId> = kept.chain(moved).map(|(_, &node)| node).collect();
+
+        for (i, node) in reordered.into_iter().enumerate() {
+            self.position[node.0] = low + i;
+            self.nodes[low + i] = node;
+        }
+
+        Ok(())
+    }
+}
+
+impl<N, E> Graph<N, E> {
+    /// Adds an edge from `source` to `target` holding `weight` unless it
+    /// would create a cycle, returning its id. The Graph keeps a topological
+    /// order of its nodes and updates it as edges are added, e.g. to add the
+    /// dependencies of a build one at a time.
+    ///
+    /// An edge added by `add_edge()` that goes against the order drops it,
+    /// and the next call sorts the Graph again. If that edge made a cycle,
+    /// every checked edge is rejected.
+    ///
+    /// Time Complexity: O(V + E) worst case, O(k) for the k nodes and edges
+    /// between `target` and `source` in the order
+    /// Space Complexity: O(k)
+    ///
+    /// # Panics
+    ///
+    /// Panics if either node isn't in the Graph.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use graph::{Cycle, Graph};
+    ///
+    /// let mut build = Graph::new();
+    /// let app = build.add_node("app");
+    /// let lib = build.add_node("lib");
+    /// let core = build.add_node("core");
+    ///
+    /// build.add_edge_checked(core, lib, ()).unwrap();
+    /// build.add_edge_checked(lib, app, ()).unwrap();
+    /// assert_eq!(build.add_edge_checked(app, core, ()), Err(Cycle));
+    ///
+    /// assert_eq!(build.topological_order(), Some(vec![core, lib, app]));
+    /// ```
+    pub fn add_edge_checked(
+        &mut self,
+        source: NodeId,
+        target: NodeId,
+        weight: E,
+    ) -> Result<EdgeId, Cycle> {
+        assert!(
+            source.0 < self.node_count() && target.0 < self.node_count(),
+            "the nodes of an edge must be in the Graph"
+        );
+
+        let mut order = match self.order.take() {
+            Some(order) => order,
+            None => Order::sorted(self).ok_or(Cycle)?,
+        };
+        let room = order.make_room(self, source, target);
+        self.order = Some(order);
+        room?;
+
+        Ok(self.add_edge(source, target, weight))
+    }
+
+    /// Returns the nodes in a topological order, every edge going from a node
+    /// to a later one, None if the Graph has a cycle.
+    ///
+    /// Time Complexity: O(V) if every edge was added by
+    /// `add_edge_checked()`, O(V + E) otherwise
+    /// Space Complexity: O(V)
+    pub fn topological_order(&self) -> Option<Vec<NodeId>> {
+        match &self.order {
+            Some(order) => Some(order.nodes.clone()),
+            None => Order::sorted(self).map(|order| order.nodes),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_sorted<N, E>(graph: &Graph<N, E>) {
+        let order = graph.topological_order().unwrap();
+        let mut position = vec![0; order.len()];
+        for (i, node) in order.iter().enumerate() {
+            position[node.0] = i;
+        }
+        for (source, target, _) in graph.edges() {
+            assert!(position[source.0] < position[target.0]);
+        }
+    }
+
+    #[test]
+    fn checked_edges_keep_an_order() {
+        let mut graph = Graph::new();
+        let nodes: Vec<NodeId> = (0..6).map(|i| graph.add_node(i)).collect();
+
+        // The edges chain 5 -> 4 -> 3 -> 0 -> 1 -> 2, most of them against
+        // the order the nodes were added in.
+        for &(u, v) in &[(0, 1), (5, 4), (4, 3), (3, 0), (1, 2)] {
+            let edge = graph.add_edge_checked(nodes[u], nodes[v], ()).unwrap();
+            assert_eq!(graph.endpoints(edge), Some((nodes[u], nodes[v])));
+            assert_sorted(&graph);
+        }
+        assert_eq!(
+            graph.topological_order().unwrap(),
+            [5, 4, 3, 0, 1, 2]
+                .iter()
+                .map(|&i| nodes[i])
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(graph.add_edge_checked(nodes[2], nodes[5], ()), Err(Cycle));
+        assert_eq!(graph.add_edge_checked(nodes[1], nodes[1], ()), Err(Cycle));
+        assert_eq!(graph.edge_count(), 5);
+
+        // A shortcut along the order and a node added later.
+        graph.add_edge_checked(nodes[4], nodes[2], ()).unwrap();
+        let last = graph.add_node(6);
+        graph.add_edge_checked(last, nodes[5], ()).unwrap();
+        assert_sorted(&graph);
+        assert_eq!(graph.topological_order().unwrap()[0], last);
+    }
+
+    #[test]
+    fn unchecked_edges_resort() {
+        let mut graph = Graph::new();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        let c = graph.add_node(());
+
+        // Against the order, so it has to be sorted again.
+        graph.add_edge(c, a, ());
+        assert!(graph.order.is_none());
+        assert_eq!(graph.topological_order(), Some(vec![b, c, a]));
+
+        graph.add_edge_checked(a, b, ()).unwrap();
+        assert_sorted(&graph);
+        assert_eq!(graph.add_edge_checked(b, c, ()), Err(Cycle));
+
+        // A cycle made by add_edge() rejects every checked edge.
+        graph.add_edge(b, c, ());
+        assert_eq!(graph.topological_order(), None);
+        assert_eq!(graph.add_edge_checked(a, c, ()), Err(Cycle));
+    }
+}
@@ -0,0 +1,218 @@
+use crate::graph::{Graph, NodeId};
+use std::collections::VecDeque;
+use std::ops::ControlFlow;
+
+/// The order `Graph::traverse()` visits the nodes in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Traversal {
+    /// Nearest nodes first, the neighbors of a node in the order their edges
+    /// were added.
+    BreadthFirst,
+    /// Each edge followed as deep as it goes before the next one, in the
+    /// order the edges were added.
+    DepthFirst,
+}
+
+/// Returned by the visitor of `Graph::traverse()` to continue into the
+/// neighbors of a node or not.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Children {
+    /// Goes on to the neighbors of the node.
+    Visit,
+    /// Skips the neighbors of the node.
+    Prune,
+}
+
+impl<N, E> Graph<N, E> {
+    /// Visits the nodes reachable from `start` once each, in the given order,
+    /// calling `visit` with the id and payload of each. The visitor returns
+    /// `ControlFlow::Continue(Children::Prune)` to skip the neighbors of a
+    /// node, which are still visited if reached another way, or
+    /// `ControlFlow::Break(value)` to stop, `value` being returned.
+    ///
+    /// Time Complexity: O(V + E)
+    /// Space Complexity: O(V) breadth first, O(V + E) depth first
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` isn't in the Graph.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use graph::{Children, Graph, Traversal};
+    /// use std::ops::ControlFlow;
+    ///
+    /// let mut web = Graph::new();
+    /// let home = web.add_node("/");
+    /// let blog = web.add_node("/blog");
+    /// let post = web.add_node("/blog/post");
+    /// let about = web.add_node("/about");
+    /// web.add_edge(home, blog, ());
+    /// web.add_edge(blog, post, ());
+    /// web.add_edge(home, about, ());
+    ///
+    /// // Crawl without following the links of the blog.
+    /// let mut crawled = Vec::new();
+    /// let found = web.traverse(home, Traversal::BreadthFirst, |_, page| {
+    ///     crawled.push(*page);
+    ///     match *page {
+    ///         "/about" => ControlFlow::Break(page.len()),
+    ///         "/blog" => ControlFlow::Continue(Children::Prune),
+    ///         _ => ControlFlow::Continue(Children::Visit),
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(found, ControlFlow::Break(6));
+    /// assert_eq!(crawled, vec!["/", "/blog", "/about"]);
+    /// ```
+    pub fn traverse<B, F>(
+        &self,
+        start: NodeId,
+        traversal: Traversal,
+        mut visit: F,
+    ) -> ControlFlow<B>
+    where
+        F: FnMut(NodeId, &N) -> ControlFlow<B, Children>,
+    {
+        assert!(
+            start.0 < self.node_count(),
+            "the start must be in the Graph"
+        );
+
+        let mut seen = vec![false; self.node_count()];
+        let mut pending = VecDeque::new();
+        pending.push_back(start);
+        if traversal == Traversal::BreadthFirst {
+            seen[start.0] = true;
+        }
+
+        loop {
+            let node = match traversal {
+                Traversal::BreadthFirst => pending.pop_front(),
+                Traversal::DepthFirst => pending.pop_back(),
+            };
+            let node = match node {
+                Some(node) => node,
+                None => return ControlFlow::Continue(()),
+            };
+
+            // Depth first, a node is pushed once per edge reaching it and
+            // visited the first time it's popped, so the deepest path wins.
+            if traversal == Traversal::DepthFirst {
+                if seen[node.0] {
+                    continue;
+                }
+                seen[node.0] = true;
+            }
+
+            let payload = self.node(node).expect("the node is in the Graph");
+            if visit(node, payload)? == Children::Prune {
+                continue;
+            }
+
+            match traversal {
+                Traversal::BreadthFirst => {
+                    for next in self.neighbors(node) {
+                        if !seen[next.0] {
+                            seen[next.0] = true;
+                            pending.push_back(next);
+                        }
+                    }
+                }
+                Traversal::DepthFirst => {
+                    // Pushed in reverse, so the first edge is followed first.
+                    let next: Vec<NodeId> = self.neighbors(node).filter(|n| !seen[n.0]).collect();
+                    pending.extend(next.into_iter().rev());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn diamond() -> (Graph<usize, ()>, Vec<NodeId>) {
+        // 0 -> 1 -> 3 -> 4
+        // 0 -> 2 -> 3, and 4 -> 0 back to the start.
+        let mut graph = Graph::new();
+        let nodes: Vec<NodeId> = (0..6).map(|i| graph.add_node(i)).collect();
+        for &(u, v) in &[(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (4, 0)] {
+            graph.add_edge(nodes[u], nodes[v], ());
+        }
+
+        (graph, nodes)
+    }
+
+    fn order(graph: &Graph<usize, ()>, start: NodeId, traversal: Traversal) -> Vec<usize> {
+        let mut visited = Vec::new();
+        let flow: ControlFlow<()> = graph.traverse(start, traversal, |_, &i| {
+            visited.push(i);
+            ControlFlow::Continue(Children::Visit)
+        });
+        assert_eq!(flow, ControlFlow::Continue(()));
+
+        visited
+    }
+
+    #[test]
+    fn visits_reachable_nodes_once() {
+        let (graph, nodes) = diamond();
+
+        assert_eq!(
+            order(&graph, nodes[0], Traversal::BreadthFirst),
+            vec![0, 1, 2, 3, 4]
+        );
+        assert_eq!(
+            order(&graph, nodes[0], Traversal::DepthFirst),
+            vec![0, 1, 3, 4, 2]
+        );
+        assert_eq!(
+            order(&graph, nodes[2], Traversal::DepthFirst),
+            vec![2, 3, 4, 0, 1]
+        );
+        // Node 5 has no edges.
+        assert_eq!(order(&graph, nodes[5], Traversal::BreadthFirst), vec![5]);
+    }
+
+    #[test]
+    fn prune_and_stop() {
+        let (graph, nodes) = diamond();
+
+        for &traversal in &[Traversal::BreadthFirst, Traversal::DepthFirst] {
+            // Pruning 1 still reaches 3 through 2.
+            let mut visited = Vec::new();
+            let flow: ControlFlow<()> = graph.traverse(nodes[0], traversal, |_, &i| {
+                visited.push(i);
+                match i {
+                    1 => ControlFlow::Continue(Children::Prune),
+                    _ => ControlFlow::Continue(Children::Visit),
+                }
+            });
+            assert_eq!(flow, ControlFlow::Continue(()));
+            assert_eq!(visited, vec![0, 1, 2, 3, 4]);
+
+            let mut count = 0;
+            let flow = graph.traverse(nodes[0], traversal, |id, &i| {
+                count += 1;
+                match i {
+                    3 => ControlFlow::Break(id),
+                    _ => ControlFlow::Continue(Children::Visit),
+                }
+            });
+            assert_eq!(flow, ControlFlow::Break(nodes[3]));
+            assert!(count < 5);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "the start must be in the Graph")]
+    fn start_not_in_graph() {
+        let graph: Graph<(), ()> = Graph::new();
+        let _: ControlFlow<()> = graph.traverse(NodeId(0), Traversal::DepthFirst, |_, _| {
+            ControlFlow::Continue(Children::Visit)
+        });
+    }
+}
@@ -0,0 +1,277 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+
+/// The number of virtual nodes per node used by `HashRing::default()`.
+const DEFAULT_REPLICAS: usize = 100;
+
+/// HashRing routes keys to nodes with consistent hashing. Every node is
+/// placed on the ring `replicas` times, and a key belongs to the first node
+/// found clockwise from the hash of the key.
+///
+/// The hashes come from std's DefaultHasher created with `new()`, so the
+/// routing is the same across runs of the same build.
+///
+/// Two virtual nodes may hash to the same position. The position then keeps
+/// every node placed on it, the first one to join owning it, so removing that
+/// node hands the position to the next one.
+///
+/// # Example
+///
+/// ```
+/// use hash_ring::HashRing;
+///
+/// let mut ring = HashRing::default();
+/// ring.add_node("cache-a");
+/// ring.add_node("cache-b");
+///
+/// let node = ring.route(&"user:42").unwrap();
+/// assert!(node == &"cache-a" || node == &"cache-b");
+/// assert_eq!(ring.route(&"user:42"), Some(node));
+/// ```
+pub struct HashRing<N> {
+    replicas: usize,
+    // The nodes placed on each position, in the order they joined. The first
+    // one owns the position.
+    ring: BTreeMap<u64, Vec<N>>,
+    nodes: Vec<N>,
+}
+
+impl<N> Default for HashRing<N> {
+    fn default() -> Self {
+        HashRing::new(DEFAULT_REPLICAS)
+    }
+}
+
+impl<N> HashRing<N> {
+    /// Returns an empty HashRing placing each node `replicas` times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `replicas` is 0.
+    pub fn new(replicas: usize) -> HashRing<N> {
+        assert!(
+            replicas > 0,
+            "a HashRing needs at least one replica per node"
+        );
+
+        HashRing {
+            replicas,
+            ring: BTreeMap::new(),
+            nodes: Vec::new(),
+        }
+    }
+
+    /// Returns the number of nodes in the HashRing.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns a boolean indicating the HashRing has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the nodes in the order they were added.
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+}
+
+impl<N> HashRing<N>
+where
+    N: Hash + Eq + Clone,
+{
+    /// Adds a node to the HashRing. Returns false if it is already present.
+    ///
+    /// Time Complexity: O(v log(n * v))
+    /// Space Complexity: O(v)
+    pub fn add_node(&mut self, node: N) -> bool {
+        if self.nodes.contains(&node) {
+            return false;
+        }
+
+        for replica in 0..self.replicas {
+            // A position taken by another node is kept by it, the two nodes
+            // would otherwise swap keys depending on the order they joined.
+            self.ring
+                .entry(hash(&(&node, replica)))
+                .or_default()
+                .push(node.clone());
+        }
+
+        self.nodes.push(node);
+        true
+    }
+
+    /// Removes a node from the HashRing. Returns false if it wasn't present.
+    ///
+    /// Time Complexity: O(n + v log(n * v))
+    /// Space Complexity: O(1)
+    pub fn remove_node(&mut self, node: &N) -> bool {
+        let index = match self.nodes.iter().position(|n| n == node) {
+            Some(i) => i,
+            None => return false,
+        };
+        self.nodes.remove(index);
+
+        for replica in 0..self.replicas {
+            let position = hash(&(node, replica));
+            let owners = match self.ring.get_mut(&position) {
+                Some(owners) => owners,
+                None => continue,
+            };
+
+            if let Some(i) = owners.iter().position(|n| n == node) {
+                owners.remove(i);
+            }
+            if owners.is_empty() {
+                self.ring.remove(&position);
+            }
+        }
+
+        true
+    }
+
+    /// Returns the node responsible for `key`, or None if the HashRing is
+    /// empty.
+    ///
+    /// Time Complexity: O(log(n * v))
+    /// Space Complexity: O(1)
+    pub fn route<K>(&self, key: &K) -> Option<&N>
+    where
+        K: Hash + ?Sized,
+    {
+        let position = hash(key);
+
+        self.ring
+            .range(position..)
+            .next()
+            .or_else(|| self.ring.iter().next())
+            .map(|(_, owners)| &owners[0])
+    }
+}
+
+fn hash<K>(key: &K) -> u64
+where
+    K: Hash + ?Sized,
+{
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn empty_ring() {
+        let ring = HashRing::<&str>::default();
+
+        assert!(ring.is_empty());
+        assert_eq!(ring.route(&1), None);
+    }
+
+    #[test]
+    fn single_node_takes_every_key() {
+        let mut ring = HashRing::new(3);
+        ring.add_node("a");
+
+        for key in 0..100 {
+            assert_eq!(ring.route(&key), Some(&"a"));
+        }
+    }
+
+    #[test]
+    fn add_and_remove() {
+        let mut ring = HashRing::new(10);
+
+        assert!(ring.add_node(1));
+        assert!(!ring.add_node(1));
+        assert!(ring.add_node(2));
+        assert_eq!(ring.nodes(), &[1, 2]);
+
+        assert!(ring.remove_node(&1));
+        assert!(!ring.remove_node(&1));
+        assert_eq!(ring.len(), 1);
+        assert_eq!(ring.route("anything"), Some(&2));
+    }
+
+    #[test]
+    fn removing_a_node_only_moves_its_keys() {
+        let mut ring = HashRing::default();
+        for node in 0..5 {
+            ring.add_node(node);
+        }
+
+        let before: HashMap<u32, u32> = (0..1000).map(|k| (k, *ring.route(&k).unwrap())).collect();
+        ring.remove_node(&3);
+
+        for (key, node) in before {
+            let after = *ring.route(&key).unwrap();
+            if node != 3 {
+                assert_eq!(after, node);
+            } else {
+                assert_ne!(after, 3);
+            }
+        }
+    }
+
+    /// A node whose hash ignores its name, so nodes of the same shard are
+    /// placed on the same positions.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Colliding {
+        name: &'static str,
+        shard: u32,
+    }
+
+    impl Hash for Colliding {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.shard.hash(state);
+        }
+    }
+
+    #[test]
+    fn collided_positions_survive_removal() {
+        let a = Colliding { name: "a", shard: 0 };
+        let b = Colliding { name: "b", shard: 0 };
+        let mut ring = HashRing::new(10);
+        ring.add_node(a.clone());
+        ring.add_node(b.clone());
+
+        // a joined first and owns every position.
+        assert_eq!(ring.route(&1), Some(&a));
+
+        ring.remove_node(&a);
+        for key in 0..100 {
+            assert_eq!(ring.route(&key), Some(&b));
+        }
+
+        ring.add_node(a.clone());
+        ring.remove_node(&b);
+        assert_eq!(ring.route(&1), Some(&a));
+        ring.remove_node(&a);
+        assert_eq!(ring.route(&1), None);
+    }
+
+    #[test]
+    fn keys_are_spread_across_nodes() {
+        let mut ring = HashRing::default();
+        for node in 0..4 {
+            ring.add_node(node);
+        }
+
+        let mut counts = HashMap::new();
+        for key in 0..4000 {
+            *counts.entry(*ring.route(&key).unwrap()).or_insert(0) += 1;
+        }
+
+        assert_eq!(counts.len(), 4);
+        assert!(counts.values().all(|c| *c > 500));
+    }
+}
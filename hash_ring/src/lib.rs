@@ -0,0 +1,4 @@
+//! A crate that implements a consistent HashRing.
+pub use crate::hash_ring::HashRing;
+
+mod hash_ring;
@@ -0,0 +1,635 @@
+use std::iter::Iterator;
+use std::ops::{Index, IndexMut};
+use traits::{Collection, Deque, Queue, Stack};
+
+/// The number of slots allocated the first time an empty IndexedDeque grows.
+const MIN_CAPACITY: usize = 4;
+
+/// IndexedDeque is a ring buffer that supports pushing and popping at both
+/// ends and accessing any item by index in constant time.
+///
+/// The items live in a contiguous buffer of slots. `head` is the slot holding
+/// the front of the deque and the remaining items follow it, wrapping around
+/// to the start of the buffer when the end is reached.
+pub struct IndexedDeque<T> {
+    buf: Vec<Option<T>>,
+    head: usize,
+    size: usize,
+}
+
+impl<T> Default for IndexedDeque<T> {
+    fn default() -> Self {
+        IndexedDeque {
+            buf: Vec::new(),
+            head: 0,
+            size: 0,
+        }
+    }
+}
+
+/// Implements IntoIter for an IndexedDeque with a lifetime of 'a - the same
+/// lifetime as the IndexedDeque that is being referenced.
+impl<'a, T> IntoIterator for &'a IndexedDeque<T> {
+    type Item = &'a T;
+    type IntoIter = IndexedDequeIterator<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IndexedDequeIterator {
+            deque: self,
+            front: 0,
+            back: self.size,
+        }
+    }
+}
+
+/// The Iterator implementation for the IndexedDeque. This Iterator will borrow
+/// the IndexedDeque.
+pub struct IndexedDequeIterator<'a, T> {
+    deque: &'a IndexedDeque<T>,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> Iterator for IndexedDequeIterator<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.front == self.back {
+            return None;
+        }
+
+        let result = self.deque.get(self.front);
+        self.front += 1;
+
+        result
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IndexedDequeIterator<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        self.deque.get(self.back)
+    }
+}
+
+impl<T> Index<usize> for IndexedDeque<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+impl<T> IndexMut<usize> for IndexedDeque<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.get_mut(index).expect("index out of bounds")
+    }
+}
+
+impl<T> Collection for IndexedDeque<T> {
+    type Iter<'a>
+        = IndexedDequeIterator<'a, T>
+    where
+        T: 'a;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn clear(&mut self) {
+        self.clear()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.iter()
+    }
+}
+
+impl<T> Stack<T> for IndexedDeque<T> {
+    fn push(&mut self, v: T) {
+        self.push_back(v)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop_back()
+    }
+}
+
+impl<T> Queue<T> for IndexedDeque<T> {
+    fn enqueue(&mut self, v: T) {
+        self.push_back(v)
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+}
+
+impl<T> Deque<T> for IndexedDeque<T> {
+    fn push_front(&mut self, v: T) {
+        IndexedDeque::push_front(self, v)
+    }
+
+    fn push_back(&mut self, v: T) {
+        IndexedDeque::push_back(self, v)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        IndexedDeque::pop_front(self)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        IndexedDeque::pop_back(self)
+    }
+}
+
+impl<T> IndexedDeque<T> {
+    /// Returns an empty IndexedDeque with room for `capacity` items before it
+    /// needs to grow.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indexed_deque::IndexedDeque;
+    ///
+    /// let deque = IndexedDeque::<u32>::with_capacity(10);
+    /// assert_eq!(deque.capacity(), 10);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> IndexedDeque<T> {
+        let mut buf = Vec::with_capacity(capacity);
+        buf.resize_with(capacity, || None);
+
+        IndexedDeque {
+            buf,
+            head: 0,
+            size: 0,
+        }
+    }
+
+    /// Returns the length of the IndexedDeque.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of items the IndexedDeque can hold before it needs
+    /// to grow.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns a boolean indicating the IndexedDeque is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indexed_deque::IndexedDeque;
+    ///
+    /// let deque = IndexedDeque::<u32>::default();
+    /// assert_eq!(deque.is_empty(), true);
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Adds a value to the back of the IndexedDeque.
+    ///
+    /// Time Complexity: O(1) amortized
+    /// Space Complexity: O(1) amortized
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indexed_deque::IndexedDeque;
+    ///
+    /// let mut deque = IndexedDeque::default();
+    /// deque.push_back(1);
+    /// deque.push_back(2);
+    ///
+    /// assert_eq!(deque.back(), Some(&2));
+    /// ```
+    pub fn push_back(&mut self, v: T) {
+        self.grow_if_full();
+
+        let slot = self.slot(self.size);
+        self.buf[slot] = Some(v);
+        self.size += 1;
+    }
+
+    /// Adds a value to the front of the IndexedDeque.
+    ///
+    /// Time Complexity: O(1) amortized
+    /// Space Complexity: O(1) amortized
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indexed_deque::IndexedDeque;
+    ///
+    /// let mut deque = IndexedDeque::default();
+    /// deque.push_back(1);
+    /// deque.push_front(0);
+    ///
+    /// assert_eq!(deque.front(), Some(&0));
+    /// assert_eq!(deque.get(1), Some(&1));
+    /// ```
+    pub fn push_front(&mut self, v: T) {
+        self.grow_if_full();
+
+        // Step the head back by one, wrapping around to the end of the buffer.
+        self.head = (self.head + self.capacity() - 1) % self.capacity();
+        self.buf[self.head] = Some(v);
+        self.size += 1;
+    }
+
+    /// Returns the value at the back of the IndexedDeque and removes it from
+    /// the IndexedDeque.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indexed_deque::IndexedDeque;
+    ///
+    /// let mut deque = IndexedDeque::default();
+    /// deque.push_back(1);
+    /// deque.push_back(2);
+    ///
+    /// assert_eq!(deque.pop_back(), Some(2));
+    /// assert_eq!(deque.len(), 1);
+    /// ```
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        self.size -= 1;
+        let slot = self.slot(self.size);
+        self.buf[slot].take()
+    }
+
+    /// Returns the value at the front of the IndexedDeque and removes it from
+    /// the IndexedDeque.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indexed_deque::IndexedDeque;
+    ///
+    /// let mut deque = IndexedDeque::default();
+    /// deque.push_back(1);
+    /// deque.push_back(2);
+    ///
+    /// assert_eq!(deque.pop_front(), Some(1));
+    /// assert_eq!(deque.len(), 1);
+    /// ```
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let value = self.buf[self.head].take();
+        self.head = (self.head + 1) % self.capacity();
+        self.size -= 1;
+
+        value
+    }
+
+    /// Gets a reference to the value at an index, counting from the front.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indexed_deque::IndexedDeque;
+    ///
+    /// let mut deque = IndexedDeque::default();
+    /// deque.push_back("Hello".to_string());
+    ///
+    /// assert_eq!(deque.get(0), Some(&"Hello".to_string()));
+    /// assert_eq!(deque.get(1), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.size {
+            return None;
+        }
+
+        self.buf[self.slot(index)].as_ref()
+    }
+
+    /// Gets a mutable reference to the value at an index, counting from the
+    /// front.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.size {
+            return None;
+        }
+
+        let slot = self.slot(index);
+        self.buf[slot].as_mut()
+    }
+
+    /// Returns a reference to the front of the IndexedDeque.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns a reference to the back of the IndexedDeque.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn back(&self) -> Option<&T> {
+        match self.size {
+            0 => None,
+            n => self.get(n - 1),
+        }
+    }
+
+    /// Removes every item from the IndexedDeque, keeping the allocated buffer.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indexed_deque::IndexedDeque;
+    ///
+    /// let mut deque = IndexedDeque::default();
+    /// deque.push_back(1);
+    /// deque.clear();
+    ///
+    /// assert_eq!(deque.is_empty(), true);
+    /// ```
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+        self.head = 0;
+    }
+
+    /// Returns a borrowing iterator from the front to the back of the
+    /// IndexedDeque.
+    pub fn iter(&self) -> IndexedDequeIterator<'_, T> {
+        self.into_iter()
+    }
+
+    /// Maps a logical index to the slot in the buffer that holds it.
+    fn slot(&self, index: usize) -> usize {
+        (self.head + index) % self.capacity()
+    }
+
+    /// Doubles the buffer when it is full, moving the items so that the front
+    /// of the deque sits at the start of the new buffer.
+    fn grow_if_full(&mut self) {
+        if self.size < self.capacity() {
+            return;
+        }
+
+        let new_capacity = std::cmp::max(MIN_CAPACITY, self.capacity() * 2);
+        let mut buf = Vec::with_capacity(new_capacity);
+
+        for i in 0..self.size {
+            let slot = self.slot(i);
+            buf.push(self.buf[slot].take());
+        }
+        buf.resize_with(new_capacity, || None);
+
+        self.buf = buf;
+        self.head = 0;
+    }
+}
+
+#[allow(unused_macros)]
+macro_rules! indexed_deque {
+    // Push each item to the back of the deque, according to the number of
+    // inputs.
+    ($($x: expr),*) => {{
+        let mut deque = IndexedDeque::default();
+        $(deque.push_back($x);)*
+        deque
+    }};
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn init_deque() {
+        let deque = IndexedDeque::<String>::default();
+        assert_eq!(deque.len(), 0);
+        assert_eq!(deque.capacity(), 0);
+        assert_eq!(deque.front(), None);
+        assert_eq!(deque.back(), None);
+    }
+
+    #[test]
+    fn push_back_and_index() {
+        let mut deque = IndexedDeque::default();
+
+        for i in 0..10 {
+            deque.push_back(i);
+        }
+
+        assert_eq!(deque.len(), 10);
+        for i in 0..10 {
+            assert_eq!(deque[i], i);
+        }
+        assert_eq!(deque.get(10), None);
+    }
+
+    #[test]
+    fn push_front_and_index() {
+        let mut deque = IndexedDeque::default();
+
+        for i in 0..10 {
+            deque.push_front(i);
+        }
+
+        assert_eq!(deque.len(), 10);
+        assert_eq!(deque.front(), Some(&9));
+        assert_eq!(deque.back(), Some(&0));
+        assert_eq!(deque.get(3), Some(&6));
+    }
+
+    #[test]
+    fn pop_both_ends() {
+        let mut deque = indexed_deque![1, 2, 3, 4];
+
+        assert_eq!(deque.pop_front(), Some(1));
+        assert_eq!(deque.pop_back(), Some(4));
+        assert_eq!(deque.len(), 2);
+        assert_eq!(deque[0], 2);
+        assert_eq!(deque[1], 3);
+
+        assert_eq!(deque.pop_back(), Some(3));
+        assert_eq!(deque.pop_back(), Some(2));
+        assert_eq!(deque.pop_back(), None);
+        assert_eq!(deque.pop_front(), None);
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    fn wraps_around_without_growing() {
+        let mut deque = IndexedDeque::with_capacity(4);
+
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+        assert_eq!(deque.pop_front(), Some(1));
+        assert_eq!(deque.pop_front(), Some(2));
+
+        // The back now wraps around to the start of the buffer.
+        deque.push_back(4);
+        deque.push_back(5);
+        deque.push_back(6);
+
+        assert_eq!(deque.capacity(), 4);
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn grows_while_wrapped() {
+        let mut deque = IndexedDeque::with_capacity(4);
+
+        deque.push_back(2);
+        deque.push_back(3);
+        deque.push_front(1);
+        deque.push_front(0);
+        deque.push_back(4);
+
+        assert_eq!(deque.capacity(), 8);
+        assert_eq!(
+            deque.iter().copied().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn mutate_by_index() {
+        let mut deque = indexed_deque![1, 2, 3];
+
+        deque[1] = 20;
+        if let Some(v) = deque.get_mut(2) {
+            *v = 30;
+        }
+
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), vec![1, 20, 30]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let deque = indexed_deque![1, 2, 3];
+        let _ = deque[3];
+    }
+
+    #[test]
+    fn reverse_iterator() {
+        let mut deque = indexed_deque![2, 3];
+        deque.push_front(1);
+
+        let mut iter = deque.iter();
+        assert_eq!(iter.next_back(), Some(&3));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&2));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn generic_over_traits() {
+        fn fill<S: Stack<u32>>(stack: &mut S) {
+            for i in 1..4 {
+                stack.push(i);
+            }
+        }
+
+        let mut deque = IndexedDeque::default();
+        fill(&mut deque);
+
+        assert_eq!(Stack::pop(&mut deque), Some(3));
+        assert_eq!(deque.dequeue(), Some(1));
+        assert_eq!(Deque::pop_back(&mut deque), Some(2));
+        assert_eq!(deque.dequeue(), None);
+    }
+
+    #[test]
+    fn generic_over_collection() {
+        fn sizes<C: Collection>(collection: &C) -> (usize, usize) {
+            (collection.len(), collection.iter().count())
+        }
+
+        let mut deque = indexed_deque![1, 2, 3];
+        assert_eq!(sizes(&deque), (3, 3));
+
+        Collection::clear(&mut deque);
+        assert_eq!(sizes(&deque), (0, 0));
+        assert_eq!(deque.capacity(), 4);
+
+        deque.push_back(4);
+        assert_eq!(deque.front(), Some(&4));
+    }
+}
+
+// The ordering guarantees of the public API. These tests must keep passing
+// across any rewrite of the IndexedDeque internals.
+#[cfg(test)]
+mod contract {
+    use super::*;
+
+    #[test]
+    fn iterates_from_front_to_back() {
+        let mut deque = indexed_deque![2, 3];
+        deque.push_front(1);
+        deque.push_back(4);
+
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(
+            deque.iter().rev().copied().collect::<Vec<_>>(),
+            vec![4, 3, 2, 1]
+        );
+        assert_eq!(
+            (0..4).map(|i| deque[i]).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn order_survives_wrapping_and_growth() {
+        let mut deque = IndexedDeque::with_capacity(4);
+        for i in 0..4 {
+            deque.push_back(i);
+        }
+        deque.pop_front();
+        deque.pop_front();
+        for i in 4..10 {
+            deque.push_back(i);
+        }
+
+        assert_eq!(
+            deque.iter().copied().collect::<Vec<_>>(),
+            (2..10).collect::<Vec<_>>()
+        );
+    }
+}
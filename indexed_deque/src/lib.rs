@@ -0,0 +1,6 @@
+//! A crate that implements an IndexedDeque, and a SlidingWindow built on it.
+pub use crate::indexed_deque::{IndexedDeque, IndexedDequeIterator};
+pub use crate::sliding_window::SlidingWindow;
+
+mod indexed_deque;
+mod sliding_window;
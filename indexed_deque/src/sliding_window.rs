@@ -0,0 +1,249 @@
+use crate::indexed_deque::IndexedDeque;
+use std::ops::{Add, Sub};
+
+/// SlidingWindow keeps the last `size` values pushed to it and reports their
+/// sum, mean, min and max in constant time.
+///
+/// The values are held in an IndexedDeque ring buffer. The min and max are
+/// tracked by two monotonic deques of `(sequence, value)` pairs: each push
+/// removes the values it dominates from the back, so the front of each deque
+/// is always the min or max of the window.
+///
+/// The sum is updated by adding the pushed value and subtracting the evicted
+/// one, so floating point sums can drift over a long stream.
+///
+/// # Example
+///
+/// ```
+/// use indexed_deque::SlidingWindow;
+///
+/// let mut window = SlidingWindow::new(3);
+/// for v in [4, 1, 7, 3] {
+///     window.push(v);
+/// }
+///
+/// assert_eq!(window.sum(), 11);
+/// assert_eq!(window.min(), Some(1));
+/// assert_eq!(window.max(), Some(7));
+/// ```
+pub struct SlidingWindow<T> {
+    size: usize,
+    values: IndexedDeque<T>,
+    mins: IndexedDeque<(u64, T)>,
+    maxs: IndexedDeque<(u64, T)>,
+    sum: T,
+    // The sequence number of the next value pushed.
+    seq: u64,
+}
+
+impl<T> SlidingWindow<T>
+where
+    T: Copy + PartialOrd + Default + Add<Output = T> + Sub<Output = T>,
+{
+    /// Returns an empty SlidingWindow holding the last `size` values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn new(size: usize) -> SlidingWindow<T> {
+        assert!(size > 0, "a SlidingWindow must hold at least one value");
+
+        SlidingWindow {
+            size,
+            values: IndexedDeque::with_capacity(size),
+            mins: IndexedDeque::default(),
+            maxs: IndexedDeque::default(),
+            sum: T::default(),
+            seq: 0,
+        }
+    }
+
+    /// Returns the number of values the SlidingWindow holds once it is full.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of values currently in the SlidingWindow.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns a boolean indicating the SlidingWindow is empty.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns a boolean indicating the SlidingWindow holds `size` values.
+    pub fn is_full(&self) -> bool {
+        self.values.len() == self.size
+    }
+
+    /// Pushes a value into the SlidingWindow, returning the oldest value if it
+    /// fell out of the window.
+    ///
+    /// Time Complexity: O(1) amortized
+    /// Space Complexity: O(1) amortized
+    pub fn push(&mut self, v: T) -> Option<T> {
+        let evicted = if self.is_full() {
+            self.values.pop_front()
+        } else {
+            None
+        };
+
+        if let Some(old) = evicted {
+            self.sum = self.sum - old;
+
+            // The evicted value has the oldest sequence number in the window.
+            let oldest = self.seq - self.size as u64;
+            if self.mins.front().is_some_and(|(s, _)| *s == oldest) {
+                self.mins.pop_front();
+            }
+            if self.maxs.front().is_some_and(|(s, _)| *s == oldest) {
+                self.maxs.pop_front();
+            }
+        }
+
+        while self.mins.back().is_some_and(|(_, m)| *m >= v) {
+            self.mins.pop_back();
+        }
+        while self.maxs.back().is_some_and(|(_, m)| *m <= v) {
+            self.maxs.pop_back();
+        }
+
+        self.mins.push_back((self.seq, v));
+        self.maxs.push_back((self.seq, v));
+        self.values.push_back(v);
+        self.sum = self.sum + v;
+        self.seq += 1;
+
+        evicted
+    }
+
+    /// Returns the sum of the values in the SlidingWindow.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn sum(&self) -> T {
+        self.sum
+    }
+
+    /// Returns the mean of the values in the SlidingWindow, or None if it is
+    /// empty.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn mean(&self) -> Option<f64>
+    where
+        T: Into<f64>,
+    {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(self.sum.into() / self.len() as f64)
+    }
+
+    /// Returns the smallest value in the SlidingWindow.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn min(&self) -> Option<T> {
+        self.mins.front().map(|(_, v)| *v)
+    }
+
+    /// Returns the largest value in the SlidingWindow.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn max(&self) -> Option<T> {
+        self.maxs.front().map(|(_, v)| *v)
+    }
+
+    /// Returns an Iterator over the values, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.values.iter()
+    }
+
+    /// Removes every value from the SlidingWindow.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.mins.clear();
+        self.maxs.clear();
+        self.sum = T::default();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fills_then_slides() {
+        let mut window = SlidingWindow::new(3);
+
+        assert_eq!(window.push(1), None);
+        assert_eq!(window.push(2), None);
+        assert_eq!(window.push(3), None);
+        assert!(window.is_full());
+        assert_eq!(window.push(4), Some(1));
+
+        assert_eq!(window.iter().collect::<Vec<_>>(), vec![&2, &3, &4]);
+        assert_eq!(window.sum(), 9);
+        assert_eq!(window.mean(), Some(3.0));
+    }
+
+    #[test]
+    fn min_and_max_follow_the_window() {
+        let mut window = SlidingWindow::new(3);
+        let values = [5, 1, 4, 2, 8, 3, 3, 0];
+        let mut seen = Vec::new();
+
+        for (i, v) in values.iter().enumerate() {
+            window.push(*v);
+            seen.push(*v);
+
+            let start = (i + 1).saturating_sub(3);
+            let expected = &seen[start..];
+            assert_eq!(window.min(), expected.iter().min().copied());
+            assert_eq!(window.max(), expected.iter().max().copied());
+        }
+    }
+
+    #[test]
+    fn floats() {
+        let mut window = SlidingWindow::new(2);
+        window.push(1.5);
+        window.push(2.5);
+        window.push(0.5);
+
+        assert_eq!(window.sum(), 3.0);
+        assert_eq!(window.min(), Some(0.5));
+        assert_eq!(window.max(), Some(2.5));
+    }
+
+    #[test]
+    fn empty_and_clear() {
+        let mut window = SlidingWindow::<i32>::new(2);
+        assert_eq!(window.mean(), None);
+        assert_eq!(window.min(), None);
+
+        window.push(3);
+        window.clear();
+
+        assert!(window.is_empty());
+        assert_eq!(window.sum(), 0);
+        assert_eq!(window.max(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one value")]
+    fn zero_size() {
+        SlidingWindow::<u32>::new(0);
+    }
+}
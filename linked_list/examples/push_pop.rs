@@ -0,0 +1,84 @@
+//! Compares the push/pop throughput of a LinkedList and a RawList.
+//!
+//! cargo run --release --example push_pop
+use linked_list::{LinkedList, RawList};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+// Dropping a LinkedList recurses through its nodes, so much longer lists
+// overflow the stack.
+const VALUES: u64 = 100_000;
+const ROUNDS: u32 = 5;
+
+/// Runs `f` a few times and returns the fastest run, the others being slowed
+/// down by whatever else the machine was doing.
+fn fastest<F: FnMut()>(mut f: F) -> Duration {
+    (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn report(name: &str, elapsed: Duration) {
+    let per_second = VALUES as f64 / elapsed.as_secs_f64();
+    println!(
+        "{:<24} {:>8.2} ms  {:>8.1} M ops/s",
+        name,
+        elapsed.as_secs_f64() * 1000.0,
+        per_second / 1_000_000.0
+    );
+}
+
+fn main() {
+    report(
+        "LinkedList push",
+        fastest(|| {
+            let mut list = LinkedList::default();
+            for i in 0..VALUES {
+                list.push(black_box(i));
+            }
+            black_box(&list);
+        }),
+    );
+
+    report(
+        "RawList push",
+        fastest(|| {
+            let mut list = RawList::new();
+            for i in 0..VALUES {
+                list.push(black_box(i));
+            }
+            black_box(&list);
+        }),
+    );
+
+    report(
+        "LinkedList push + pop",
+        fastest(|| {
+            let mut list = LinkedList::default();
+            for i in 0..VALUES {
+                list.push(black_box(i));
+            }
+            while let Some(v) = list.pop() {
+                black_box(v);
+            }
+        }),
+    );
+
+    report(
+        "RawList push + pop",
+        fastest(|| {
+            let mut list = RawList::new();
+            for i in 0..VALUES {
+                list.push(black_box(i));
+            }
+            while let Some(v) = list.pop() {
+                black_box(v);
+            }
+        }),
+    );
+}
@@ -0,0 +1,61 @@
+//! Compares reading every value of a LinkedList by index in ascending order,
+//! where each get() walks on from the node the last one found, with reading
+//! them in descending order, where the cursor can't help and each get()
+//! walks from the head.
+//!
+//! cargo run --release --example sequential_get
+use linked_list::LinkedList;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+// Without the cursor the walks add up to n^2 / 2 steps.
+const VALUES: u64 = 10_000;
+const ROUNDS: u32 = 5;
+
+/// Runs `f` a few times and returns the fastest run, the others being slowed
+/// down by whatever else the machine was doing.
+fn fastest<F: FnMut()>(mut f: F) -> Duration {
+    (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn report(name: &str, elapsed: Duration) {
+    let per_second = VALUES as f64 / elapsed.as_secs_f64();
+    println!(
+        "{:<24} {:>8.2} ms  {:>8.3} M gets/s",
+        name,
+        elapsed.as_secs_f64() * 1000.0,
+        per_second / 1_000_000.0
+    );
+}
+
+fn main() {
+    let mut list = LinkedList::default();
+    for i in 0..VALUES {
+        list.push(i);
+    }
+
+    report(
+        "get with the cursor",
+        fastest(|| {
+            for i in 0..VALUES as usize {
+                black_box(list.get(black_box(i)));
+            }
+        }),
+    );
+
+    report(
+        "get without the cursor",
+        fastest(|| {
+            for i in (0..VALUES as usize).rev() {
+                black_box(list.get(black_box(i)));
+            }
+        }),
+    );
+}
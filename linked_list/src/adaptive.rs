@@ -0,0 +1,272 @@
+use crate::error::{LinkedListError, Result};
+use crate::linked_list::{LinkedList, LinkedListIterator};
+use alloc::vec::Vec;
+
+/// The length past which an AdaptiveSeq switches to linked nodes by default.
+const DEFAULT_MAX_LEN: usize = 1024;
+
+/// The number of inserts in the middle after which an AdaptiveSeq switches to
+/// linked nodes by default.
+const DEFAULT_MAX_MIDDLE_INSERTS: usize = 64;
+
+/// The storage currently used by an AdaptiveSeq.
+enum Backend<T> {
+    Vec(Vec<T>),
+    Linked(LinkedList<T>),
+}
+
+/// AdaptiveSeq is a sequence that starts out as a Vec and switches to a
+/// LinkedList once it grows past a length, or once enough values have been
+/// inserted in the middle, where a Vec has to shift every value after them.
+///
+/// The switch happens once and is never undone. `is_linked()` reports which
+/// storage is in use, the behaviour of the sequence is the same either way.
+///
+/// # Example
+///
+/// ```
+/// use linked_list::AdaptiveSeq;
+///
+/// let mut seq = AdaptiveSeq::with_thresholds(100, 1);
+/// seq.push(1);
+/// seq.push(4);
+/// seq.insert(1, 2).unwrap();
+/// assert!(!seq.is_linked());
+///
+/// seq.insert(2, 3).unwrap();
+/// assert!(seq.is_linked());
+/// assert_eq!(seq.iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+/// ```
+pub struct AdaptiveSeq<T> {
+    backend: Backend<T>,
+    max_len: usize,
+    max_middle_inserts: usize,
+    middle_inserts: usize,
+}
+
+impl<T> Default for AdaptiveSeq<T> {
+    fn default() -> Self {
+        AdaptiveSeq::with_thresholds(DEFAULT_MAX_LEN, DEFAULT_MAX_MIDDLE_INSERTS)
+    }
+}
+
+/// The Iterator implementation for the AdaptiveSeq, from the first value to
+/// the last. This Iterator will borrow the AdaptiveSeq.
+pub enum AdaptiveSeqIterator<'a, T> {
+    Vec(core::slice::Iter<'a, T>),
+    Linked(LinkedListIterator<'a, T>),
+}
+
+impl<'a, T> Iterator for AdaptiveSeqIterator<'a, T>
+where
+    T: Clone,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        match self {
+            AdaptiveSeqIterator::Vec(iter) => iter.next().cloned(),
+            AdaptiveSeqIterator::Linked(iter) => iter.next(),
+        }
+    }
+}
+
+impl<T> AdaptiveSeq<T> {
+    /// Returns an empty AdaptiveSeq that switches to linked nodes once it
+    /// holds more than `max_len` values, or after more than
+    /// `max_middle_inserts` inserts that aren't at either end.
+    pub fn with_thresholds(max_len: usize, max_middle_inserts: usize) -> AdaptiveSeq<T> {
+        AdaptiveSeq {
+            backend: Backend::Vec(Vec::new()),
+            max_len,
+            max_middle_inserts,
+            middle_inserts: 0,
+        }
+    }
+
+    /// Returns a boolean indicating the values are stored in linked nodes.
+    pub fn is_linked(&self) -> bool {
+        matches!(self.backend, Backend::Linked(_))
+    }
+}
+
+impl<T> AdaptiveSeq<T>
+where
+    T: Clone,
+{
+    /// Returns the number of values in the AdaptiveSeq.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        match &self.backend {
+            Backend::Vec(v) => v.len(),
+            Backend::Linked(l) => l.len() as usize,
+        }
+    }
+
+    /// Returns a boolean indicating the AdaptiveSeq is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds a value to the end of the AdaptiveSeq.
+    ///
+    /// Time Complexity: O(1) amortized, O(n) when switching to linked nodes
+    /// Space Complexity: O(1) amortized
+    pub fn push(&mut self, v: T) {
+        match &mut self.backend {
+            Backend::Vec(vec) => vec.push(v),
+            Backend::Linked(list) => list.push(v),
+        }
+
+        self.adapt();
+    }
+
+    /// Inserts a value at an index, shifting the values after it back.
+    /// Returns an error if the index is past the end.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1) amortized
+    pub fn insert(&mut self, index: usize, v: T) -> Result<()> {
+        let len = self.len();
+        if index > len {
+            return Err(LinkedListError::IndexOutOfRangeError);
+        }
+
+        if index != 0 && index != len {
+            self.middle_inserts += 1;
+        }
+
+        match &mut self.backend {
+            Backend::Vec(vec) => vec.insert(index, v),
+            Backend::Linked(list) => list.insert(index, v)?,
+        }
+
+        self.adapt();
+        Ok(())
+    }
+
+    /// Removes and returns the value at an index.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    pub fn remove(&mut self, index: usize) -> Result<T> {
+        if self.is_empty() {
+            return Err(LinkedListError::EmptyList);
+        }
+
+        if index >= self.len() {
+            return Err(LinkedListError::IndexOutOfRangeError);
+        }
+
+        match &mut self.backend {
+            Backend::Vec(vec) => Ok(vec.remove(index)),
+            Backend::Linked(list) => {
+                let v = list
+                    .get(index)
+                    .ok_or(LinkedListError::IndexOutOfRangeError)?;
+                list.delete(index as u32)?;
+                Ok(v)
+            }
+        }
+    }
+
+    /// Returns the value at an index.
+    ///
+    /// Time Complexity: O(1) as a Vec, O(n) as linked nodes
+    /// Space Complexity: O(1)
+    pub fn get(&self, index: usize) -> Option<T> {
+        match &self.backend {
+            Backend::Vec(vec) => vec.get(index).cloned(),
+            Backend::Linked(list) => list.get(index),
+        }
+    }
+
+    /// Returns an Iterator over the values, from the first to the last.
+    pub fn iter(&self) -> AdaptiveSeqIterator<'_, T> {
+        match &self.backend {
+            Backend::Vec(vec) => AdaptiveSeqIterator::Vec(vec.iter()),
+            Backend::Linked(list) => AdaptiveSeqIterator::Linked(list.into_iter()),
+        }
+    }
+
+    /// Moves the values into linked nodes once a threshold is crossed.
+    fn adapt(&mut self) {
+        let vec = match &mut self.backend {
+            Backend::Vec(vec) => vec,
+            Backend::Linked(_) => return,
+        };
+
+        if vec.len() <= self.max_len && self.middle_inserts <= self.max_middle_inserts {
+            return;
+        }
+
+        let mut list = LinkedList::default();
+        for v in vec.drain(..) {
+            list.push(v);
+        }
+        self.backend = Backend::Linked(list);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn values(seq: &AdaptiveSeq<u32>) -> Vec<u32> {
+        seq.iter().collect()
+    }
+
+    #[test]
+    fn switches_on_length() {
+        let mut seq = AdaptiveSeq::with_thresholds(3, 100);
+        for i in 0..3 {
+            seq.push(i);
+        }
+        assert!(!seq.is_linked());
+
+        seq.push(3);
+        assert!(seq.is_linked());
+        assert_eq!(values(&seq), vec![0, 1, 2, 3]);
+        assert_eq!(seq.get(3), Some(3));
+    }
+
+    #[test]
+    fn switches_on_middle_inserts() {
+        let mut seq = AdaptiveSeq::with_thresholds(100, 1);
+        seq.push(1);
+        seq.insert(0, 0).unwrap();
+        seq.insert(2, 3).unwrap();
+        assert!(!seq.is_linked());
+
+        seq.insert(2, 2).unwrap();
+        assert!(!seq.is_linked());
+        seq.insert(1, 5).unwrap();
+        assert!(seq.is_linked());
+        assert_eq!(values(&seq), vec![0, 5, 1, 2, 3]);
+    }
+
+    #[test]
+    fn same_behaviour_in_both_backends() {
+        for linked in [false, true] {
+            let mut seq = if linked {
+                AdaptiveSeq::with_thresholds(0, 0)
+            } else {
+                AdaptiveSeq::default()
+            };
+
+            seq.push(1);
+            seq.push(3);
+            seq.insert(1, 2).unwrap();
+            assert!(seq.insert(5, 5).is_err());
+            assert_eq!(seq.is_linked(), linked);
+
+            assert_eq!(seq.remove(0).unwrap(), 1);
+            assert!(seq.remove(2).is_err());
+            assert_eq!(seq.len(), 2);
+            assert_eq!(seq.get(1), Some(3));
+            assert_eq!(values(&seq), vec![2, 3]);
+        }
+    }
+}
@@ -1,7 +1,21 @@
-#[derive(Fail, Debug)]
+use core::fmt;
+
+#[derive(Debug)]
 pub enum LinkedListError {
-    #[fail(display = "Index out of bounds")]
     IndexOutOfRangeError,
+    EmptyList,
+}
+
+impl fmt::Display for LinkedListError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LinkedListError::IndexOutOfRangeError => write!(f, "Index out of bounds"),
+            LinkedListError::EmptyList => write!(f, "The list is empty"),
+        }
+    }
 }
 
-pub type Result<T> = std::result::Result<T, LinkedListError>;
+#[cfg(feature = "std")]
+impl std::error::Error for LinkedListError {}
+
+pub type Result<T> = core::result::Result<T, LinkedListError>;
@@ -1,11 +1,65 @@
 //! A crate that implements a LinkedList.
-extern crate failure;
-#[macro_use]
-extern crate failure_derive;
+//!
+//! # Features
+//!
+//! - `checked-iter` - panics when the nodes are modified through a copy made
+//!   by `LinkedList::share()` while an iterator is alive, like the debug
+//!   iterators of C++.
+//! - `futures` - adds `LinkedList::into_stream()` and `ListSink`, so lists can
+//!   be consumed and built by async pipelines.
+//! - `serde` - implements `Serialize` and `Deserialize` for LinkedList, as a
+//!   sequence of its values.
+//! - `spill` - adds `SpillList`, a FIFO list that spills the chunks in its
+//!   middle to temporary files past a memory budget. Implies `std` and
+//!   `serde`.
+//! - `std` (default) - adds the conversions to and from the doubly linked
+//!   list, and `SyncLinkedList` with `unsafe-fast`. Without it the crate is
+//!   `no_std` and only needs `alloc`, e.g. for embedded firmware with a
+//!   global allocator.
+//! - `unsafe-fast` (default) - adds `RawList`, the same list owning its nodes
+//!   through raw pointers, and `SyncLinkedList` which wraps it. Without it the
+//!   only unsafe code left is the one line of `LinkedList::iter_mut()` that
+//!   hands out `&mut T` into the nodes.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![cfg_attr(not(feature = "unsafe-fast"), deny(unsafe_code))]
 
-pub use crate::error::Result;
-pub use crate::linked_list::LinkedList;
+extern crate alloc;
 
+pub use crate::adaptive::{AdaptiveSeq, AdaptiveSeqIterator};
+pub use crate::error::{LinkedListError, Result};
+pub use crate::linked_list::{
+    Cursor, CursorMut, Drain, IterMut, LinkedList, LinkedListIterator, RefIterator,
+};
+#[cfg(feature = "unsafe-fast")]
+pub use crate::raw_list::{RawIntoIter, RawIter, RawIterMut, RawList};
+pub use crate::slice::ListSlice;
+#[cfg(feature = "spill")]
+pub use crate::spill::SpillList;
+#[cfg(feature = "futures")]
+pub use crate::stream::{ListSink, ListStream};
+#[cfg(all(feature = "std", feature = "unsafe-fast"))]
+pub use crate::sync_list::SyncLinkedList;
+pub use crate::transaction::Transaction;
+pub use crate::undoable::UndoableList;
+pub use crate::value_ref::ValueRef;
+
+mod adaptive;
 mod error;
 mod linked_list;
 mod node;
+mod observer;
+mod operation;
+#[cfg(feature = "unsafe-fast")]
+mod raw_list;
+#[cfg(feature = "serde")]
+mod serialize;
+mod slice;
+#[cfg(feature = "spill")]
+mod spill;
+#[cfg(feature = "futures")]
+mod stream;
+#[cfg(all(feature = "std", feature = "unsafe-fast"))]
+mod sync_list;
+mod transaction;
+mod undoable;
+mod value_ref;
@@ -1,15 +1,47 @@
 use crate::error::{LinkedListError, Result};
 use crate::node::{Node, NodeRef};
-use std::iter::Iterator;
+use crate::observer::Observers;
+use crate::slice::ListSlice;
+use crate::transaction::Transaction;
+use crate::value_ref::ValueRef;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::{Cell, RefCell};
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::{FusedIterator, Iterator};
+use core::ops::Range;
+use traits::{Collection, Queue};
 
+/// Copies the nodes of a LinkedList that are shared with another list.
+type Unshare<T> = fn(&mut LinkedList<T>);
 
 /// LinkedList is a data structure that references each item T in memory, forming
 /// a chain of referenced objects.
-#[derive(Clone)]
+///
+/// `clone()` copies the values into new nodes, see `share()` for a copy that
+/// shares the nodes instead.
 pub struct LinkedList<T> {
     head: Option<NodeRef<T>>,
     tail: Option<NodeRef<T>>,
     size: u32,
+    // The index and node of the last get(), so walking the list with
+    // get(i), get(i + 1), ... doesn't start from the head each time.
+    // Anything that changes which node sits at an index must reset it.
+    cursor: RefCell<Option<(usize, NodeRef<T>)>>,
+    // Counts the modifications of the nodes. Shared copies share the nodes,
+    // so they share the counter too.
+    #[cfg(feature = "checked-iter")]
+    version: Rc<Cell<u64>>,
+    observers: Observers<T>,
+    // Set while a mutation runs a user closure, which may panic and leave
+    // the links half updated, see recover().
+    poisoned: bool,
+    // unshare(), set by share() where T is Clone, so push() can copy the
+    // nodes before linking after a tail another list holds too.
+    unshare_nodes: Cell<Option<Unshare<T>>>,
 }
 
 impl<T> Default for LinkedList<T> {
@@ -18,508 +50,4554 @@ impl<T> Default for LinkedList<T> {
             head: None,
             tail: None,
             size: 0,
+            cursor: RefCell::new(None),
+            #[cfg(feature = "checked-iter")]
+            version: Rc::new(Cell::new(0)),
+            observers: Observers::default(),
+            poisoned: false,
+            unshare_nodes: Cell::new(None),
         }
     }
 }
 
+impl<T: Clone> Clone for LinkedList<T> {
+    fn clone(&self) -> Self {
+        self.deep_clone()
+    }
+}
+
 // Implements IntoIter for a LinkedList with a lifetime of 'a - the same lifetime
 // as the LinkedList that is being referenced.
 impl<'a, T> IntoIterator for &'a LinkedList<T>
 where
-    T: Clone + std::fmt::Debug,
+    T: Clone,
 {
     type Item = T;
     // IntoIter type is a LinkedListIterator of the same lifetime as the LinkedList.
     type IntoIter = LinkedListIterator<'a, T>;
 
     fn into_iter(self) -> Self::IntoIter {
-        LinkedListIterator {
-            list: self,
-            index: 0,
-        }
+        self.iter_from(0)
     }
 }
 
 /// The Iterator implementation for the LinkedList. This Iterator will borrow
-/// the LinkedList.
+/// the LinkedList and clone each value, see `RefIterator` to iterate without
+/// cloning.
+///
+/// With the `checked-iter` feature, the Iterator panics if the LinkedList is
+/// modified through a copy sharing its nodes, see `share()`, while the
+/// Iterator is alive, instead of silently skipping or repeating values.
 pub struct LinkedListIterator<'a, T> {
-    list: &'a LinkedList<T>,
-    index: usize,
+    _list: &'a LinkedList<T>,
+    current: Option<NodeRef<T>>,
+    // A shared copy of the list may have linked more nodes after it, so the
+    // walk stops after the LinkedList's own nodes.
+    remaining: usize,
+    #[cfg(feature = "checked-iter")]
+    version: u64,
 }
 
 impl<'a, T> Iterator for LinkedListIterator<'a, T>
 where
-    T: Clone + std::fmt::Debug,
+    T: Clone,
 {
     type Item = T;
     fn next(&mut self) -> Option<T> {
-        let result = self.list.get(self.index);
-        self.index += 1;
+        #[cfg(feature = "checked-iter")]
+        assert!(
+            self.version == self._list.version.get(),
+            "LinkedList was modified while it was being iterated"
+        );
+
+        if self.remaining == 0 {
+            return None;
+        }
+
+        // Follows the next link rather than getting by index, so a full walk
+        // is O(n) and doesn't move the cursor of the LinkedList.
+        let node = self.current.take()?;
+        self.current = node.0.borrow().next.clone();
+        self.remaining -= 1;
+
+        let value = node.0.borrow().value.clone();
+        Some(value)
+    }
 
-        return result;
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
     }
 }
 
-impl<T> LinkedList<T>
-where
-    T: Clone + std::fmt::Debug,
-{
-    /// Returns the length of the LinkedList.
+impl<'a, T> ExactSizeIterator for LinkedListIterator<'a, T> where T: Clone {}
+
+impl<'a, T> FusedIterator for LinkedListIterator<'a, T> where T: Clone {}
+
+/// RefIterator yields a ValueRef to each value of a LinkedList, from head to
+/// tail, created by `LinkedList::iter_ref()`. Unlike LinkedListIterator it
+/// never clones the values, so it works for values that aren't Clone. This
+/// Iterator will borrow the LinkedList.
+///
+/// With the `checked-iter` feature, the Iterator panics if the LinkedList is
+/// modified through a copy sharing its nodes, see `share()`, while the
+/// Iterator is alive.
+pub struct RefIterator<'a, T> {
+    _list: &'a LinkedList<T>,
+    current: Option<NodeRef<T>>,
+    // A shared copy of the list may have linked more nodes after it, so the
+    // walk stops after the LinkedList's own nodes.
+    remaining: u32,
+    #[cfg(feature = "checked-iter")]
+    version: u64,
+}
+
+impl<'a, T> Iterator for RefIterator<'a, T> {
+    type Item = ValueRef<T>;
+
+    fn next(&mut self) -> Option<ValueRef<T>> {
+        #[cfg(feature = "checked-iter")]
+        assert!(
+            self.version == self._list.version.get(),
+            "LinkedList was modified while it was being iterated"
+        );
+
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node = self.current.take()?;
+        self.current = node.0.borrow().next.clone();
+        self.remaining -= 1;
+
+        Some(ValueRef(node))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining as usize, Some(self.remaining as usize))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for RefIterator<'a, T> {}
+
+impl<'a, T> FusedIterator for RefIterator<'a, T> {}
+
+/// IterMut yields a mutable reference to each value of a LinkedList, from
+/// head to tail, created by `LinkedList::iter_mut()`. This Iterator will
+/// mutably borrow the LinkedList.
+pub struct IterMut<'a, T> {
+    _list: &'a mut LinkedList<T>,
+    current: Option<NodeRef<T>>,
+    remaining: u32,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    #[allow(unsafe_code)]
+    fn next(&mut self) -> Option<&'a mut T> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node = self.current.take()?;
+        self.current = node.0.borrow().next.clone();
+        self.remaining -= 1;
+
+        // SAFETY: iter_mut() unshared the nodes, so they are only reachable
+        // through the LinkedList, which is borrowed mutably for 'a: no
+        // RefCell borrow of them can be taken and none of them is dropped
+        // until then. Each node is yielded once, so the references are
+        // disjoint.
+        Some(unsafe { &mut (*node.0.as_ptr()).value })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining as usize, Some(self.remaining as usize))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+impl<'a, T> FusedIterator for IterMut<'a, T> {}
+
+/// Cursor points at a value of a LinkedList, or at the "ghost" position past
+/// the tail, and walks forward one node at a time, created by
+/// `LinkedList::cursor_front()`. Moving past the ghost wraps to the head.
+pub struct Cursor<'a, T> {
+    list: &'a LinkedList<T>,
+    current: Option<NodeRef<T>>,
+    index: Option<usize>,
+}
+
+impl<'a, T: Clone> Cursor<'a, T> {
+    /// Moves to the next value, or to the ghost from the tail, or to the head
+    /// from the ghost.
     ///
     /// Time Complexity: O(1)
     /// Space Complexity: O(1)
-    pub fn len(&self) -> u32 {
-        self.size
+    pub fn move_next(&mut self) {
+        let (current, index) = match (self.current.take(), self.index) {
+            // A shared copy of the list may link more nodes after it, so the
+            // tail is found by its index.
+            (Some(node), Some(i)) if i + 1 < self.list.size as usize => {
+                (node.0.borrow().next.clone(), Some(i + 1))
+            }
+            (Some(_), _) => (None, None),
+            (None, _) => (self.list.head.clone(), self.list.head.as_ref().map(|_| 0)),
+        };
+
+        self.current = current;
+        self.index = index;
     }
 
-    /// Adds a a value to the end of a LinkedList.
+    /// Returns a clone of the value at the cursor, or None at the ghost.
+    pub fn current(&self) -> Option<T> {
+        self.current.as_ref().map(NodeRef::value)
+    }
+
+    /// Returns the index of the cursor, or None at the ghost.
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+}
+
+/// CursorMut points at a value of a LinkedList, or at the "ghost" position
+/// past the tail, and walks forward inserting and removing values in O(1),
+/// created by `LinkedList::cursor_front_mut()`. Moving past the ghost wraps
+/// to the head.
+pub struct CursorMut<'a, T> {
+    list: &'a mut LinkedList<T>,
+    // The node before the current one, unlinking the current node relinks it.
+    previous: Option<NodeRef<T>>,
+    current: Option<NodeRef<T>>,
+    index: Option<usize>,
+}
+
+impl<'a, T: Clone> CursorMut<'a, T> {
+    /// Moves to the next value, or to the ghost from the tail, or to the head
+    /// from the ghost.
     ///
     /// Time Complexity: O(1)
     /// Space Complexity: O(1)
+    pub fn move_next(&mut self) {
+        match self.current.take() {
+            Some(node) => {
+                // The nodes aren't shared, so the tail's next is always None.
+                self.current = node.0.borrow().next.clone();
+                self.index = self.index.map(|i| i + 1).filter(|_| self.current.is_some());
+                self.previous = self.current.as_ref().map(|_| node);
+            }
+            None => {
+                self.previous = None;
+                self.current = self.list.head.clone();
+                self.index = self.current.as_ref().map(|_| 0);
+            }
+        }
+    }
+
+    /// Returns a clone of the value at the cursor, or None at the ghost.
+    pub fn current(&self) -> Option<T> {
+        self.current.as_ref().map(NodeRef::value)
+    }
+
+    /// Returns the index of the cursor, or None at the ghost.
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// Inserts a value after the cursor, or at the head if the cursor is at
+    /// the ghost. The cursor doesn't move.
     ///
-    /// # Example
-    ///
-    /// ```
-    /// use linked_list::LinkedList;
-    ///
-    /// let mut linked_list = LinkedList::<String>::default();
-    /// linked_list.push("Hello".to_string());
-    ///
-    /// assert_eq!(linked_list.tail(), Some("Hello".to_string()));
-    /// ```
-    pub fn push(&mut self, v: T) {
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn insert_after(&mut self, v: T) {
+        self.list.modified();
+        self.list.reset_cursor();
+
         let new = NodeRef::new(Node::new(v));
+        let next = match &self.current {
+            Some(node) => node.0.borrow_mut().next.replace(new.clone()),
+            None => self.list.head.replace(new.clone()),
+        };
 
-        if self.size == 0 {
-            self.head = Some(new.clone());
-        } else {
-            // This works because we take ownership of tail and leave None there.
-            // The reason why "old" still exists is because theres another
-            // NodeRef pointing to it.
-            match self.tail.take() {
-                Some(old) => old.0.borrow_mut().next = Some(new.clone()),
-                None => self.head = Some(new.clone()),
-            };
+        if next.is_none() {
+            self.list.tail = Some(new.clone());
         }
+        new.0.borrow_mut().next = next;
 
-        self.tail = Some(new);
-        self.size += 1;
+        self.list.size += 1;
+        self.list.observers.inserted(&new.0.borrow().value);
     }
 
-    /// Returns the value from a LinkedList and removes it from the LinkedList.
+    /// Removes the value at the cursor and returns it, moving the cursor to
+    /// the next value. Returns None at the ghost.
     ///
     /// Time Complexity: O(1)
     /// Space Complexity: O(1)
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// use linked_list::LinkedList;
-    ///
-    /// let mut linked_list = LinkedList::<String>::default();
-    /// linked_list.push("Hello".to_string());
-    ///
-    /// assert_eq!(linked_list.pop(), Some("Hello".to_string()));
-    /// assert_eq!(linked_list.is_empty(), true);
-    /// ```
-    pub fn pop(&mut self) -> Option<T> {
-        // Takes ownership of head.
-        // map() applies to the inner value of Option (Rc)
-        // map() will return an Option, but we'll change the inner value of it
-        // to T.
-        self.head.take().map(|h| {
-            // borrow_mut() - borrows inner value mutably (NodeRef<T>)
-            // Takes ownership of next
-            //
-            // Assign head to next,
-            // If there isn't something, head is None, so tail should be None.
-            if let Some(next) = h.0.borrow_mut().next.take() {
-                self.head = Some(next);
-            } else {
-                self.tail.take();
-            }
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.current.take()?;
+        self.list.modified();
+        self.list.reset_cursor();
 
-            // Decrement the size as we have popped from the list.
-            self.size -= 1;
+        let next = node.0.borrow_mut().next.take();
+        match &self.previous {
+            Some(previous) => previous.0.borrow_mut().next = next.clone(),
+            None => self.list.head = next.clone(),
+        }
 
-            // Extracts the value from h and returns it.
-            h.extract_value()
-        })
+        if next.is_none() {
+            self.list.tail = self.previous.take();
+            self.index = None;
+        }
+        self.current = next;
+        self.list.size -= 1;
+
+        let v = node.into_value();
+        self.list.observers.removed(&v);
+
+        Some(v)
     }
+}
 
-    /// Returns a boolean indicating the LinkedList is empty.
+/// Drain is an Iterator that moves the values out of a LinkedList, created by
+/// `LinkedList::drain()`. Dropping it empties the LinkedList.
+pub struct Drain<'a, T> {
+    list: &'a mut LinkedList<T>,
+}
+
+impl<'a, T> Iterator for Drain<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.list.pop_unshared()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.list.size as usize))
+    }
+}
+
+impl<'a, T> Drop for Drain<'a, T> {
+    fn drop(&mut self) {
+        self.list.clear();
+    }
+}
+
+impl<T> Collection for LinkedList<T>
+where
+    T: Clone,
+{
+    type Iter<'a>
+        = LinkedListIterator<'a, T>
+    where
+        T: 'a;
+
+    fn len(&self) -> usize {
+        self.len() as usize
+    }
+
+    fn clear(&mut self) {
+        self.clear()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.into_iter()
+    }
+}
+
+impl<T> Queue<T> for LinkedList<T>
+where
+    T: Clone,
+{
+    fn enqueue(&mut self, v: T) {
+        self.push(v)
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.pop()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> From<doubly_linked_list::LinkedList<T>> for LinkedList<T>
+where
+    // The doubly linked list requires Debug.
+    T: Clone + fmt::Debug,
+{
+    /// Moves the values of a doubly linked list into a LinkedList, keeping
+    /// their order. Values are only cloned if a node is still shared, e.g. by
+    /// a Handle.
     ///
     /// # Example
     ///
     /// ```
     /// use linked_list::LinkedList;
     ///
-    /// let mut linked_list = LinkedList::<String>::default();
-    /// assert_eq!(linked_list.is_empty(), true);
+    /// let mut doubly = doubly_linked_list::LinkedList::default();
+    /// doubly.push(1);
+    /// doubly.push(2);
+    ///
+    /// let singly = LinkedList::from(doubly);
+    /// assert_eq!(singly.head(), Some(1));
+    /// assert_eq!(singly.tail(), Some(2));
     /// ```
-    pub fn is_empty(&self) -> bool {
-        self.size == 0
+    fn from(mut list: doubly_linked_list::LinkedList<T>) -> Self {
+        let mut result = LinkedList::default();
+        while let Some(v) = list.pop_front() {
+            result.push(v);
+        }
+
+        result
     }
+}
 
-    /// Gets the value from a LinkedList according to an index.
-    ///
-    /// Time Complexity: O(n)
-    /// Space Complexity: O(1)
+#[cfg(feature = "std")]
+impl<T> From<LinkedList<T>> for doubly_linked_list::LinkedList<T>
+where
+    // The doubly linked list requires Debug.
+    T: Clone + fmt::Debug,
+{
+    /// Moves the values of a LinkedList into a doubly linked list, keeping
+    /// their order. Values are only cloned if a node is still shared with a
+    /// shared copy of the LinkedList.
     ///
     /// # Example
     ///
     /// ```
     /// use linked_list::LinkedList;
     ///
-    /// let mut linked_list = LinkedList::<String>::default();
-    /// linked_list.push("Hello".to_string());
+    /// let mut singly = LinkedList::default();
+    /// singly.push(1);
+    /// singly.push(2);
     ///
-    /// assert_eq!(linked_list.get(0), Some("Hello".to_string()));
+    /// let mut doubly = doubly_linked_list::LinkedList::from(singly);
+    /// assert_eq!(doubly.pop_back(), Some(2));
     /// ```
-    pub fn get(&self, index: usize) -> Option<T> {
-        let mut current: Option<NodeRef<T>> = self.head.clone();
-
-        for _i in 0..index {
-            current
-                .clone()
-                .map(|v| match v.0.borrow_mut().next.clone() {
-                    Some(n) => current = Some(n),
-                    None => current = None,
-                });
+    fn from(mut list: LinkedList<T>) -> Self {
+        let mut result = doubly_linked_list::LinkedList::default();
+        while let Some(v) = list.pop() {
+            result.push(v);
         }
 
-        current.map(|v| v.0.borrow_mut().value.clone())
+        result
     }
+}
 
-    /// Returns the head of the List as an Option<T>.
-    ///
-    /// Time Complexity: O(1)
-    /// Space Complexity: O(1)
+impl<T> From<Vec<T>> for LinkedList<T> {
+    /// Moves the values of a Vec into a LinkedList, keeping their order.
     ///
     /// # Example
     ///
     /// ```
     /// use linked_list::LinkedList;
     ///
-    /// let mut linked_list = LinkedList::<String>::default();
-    /// linked_list.push("Hello".to_string());
-    ///
-    /// assert_eq!(linked_list.head(), Some("Hello".to_string()));
+    /// let linked_list = LinkedList::from(vec![1, 2, 3]);
+    /// assert_eq!(linked_list.head(), Some(1));
+    /// assert_eq!(linked_list.tail(), Some(3));
     /// ```
-    pub fn head(&self) -> Option<T> {
-        self.head.as_ref().map(|h| h.0.borrow().value.clone())
+    fn from(values: Vec<T>) -> Self {
+        let mut result = LinkedList::default();
+        for v in values {
+            result.push(v);
+        }
+
+        result
     }
+}
 
-    /// Returns the tail of the List.
-    ///
-    /// Time Complexity: O(1)
-    /// Space Complexity: O(1)
+impl<T> From<&[T]> for LinkedList<T>
+where
+    T: Clone,
+{
+    /// Clones the values of a slice into a LinkedList, keeping their order.
     ///
     /// # Example
     ///
     /// ```
     /// use linked_list::LinkedList;
     ///
-    /// let mut linked_list = LinkedList::<String>::default();
-    /// linked_list.push("Hello".to_string());
-    /// linked_list.push("World".to_string());
-    ///
-    /// assert_eq!(linked_list.tail(), Some("World".to_string()));
+    /// let linked_list = LinkedList::from(&[1, 2, 3][..]);
+    /// assert_eq!(linked_list.len(), 3);
     /// ```
-    pub fn tail(&self) -> Option<T> {
-        self.tail.as_ref().map(|t| t.0.borrow().value.clone())
+    fn from(values: &[T]) -> Self {
+        let mut result = LinkedList::default();
+        for v in values {
+            result.push(v.clone());
+        }
+
+        result
     }
+}
 
-    /// Deletes an item from the list according to an index.
-    ///
-    /// Time Complexity: O(n)
-    /// Space Complexity: O(1)
+impl<T> From<LinkedList<T>> for Vec<T>
+where
+    T: Clone,
+{
+    /// Moves the values of a LinkedList into a Vec, keeping their order.
+    /// Values are only cloned if a node is still shared with a shared copy
+    /// of the LinkedList.
     ///
     /// # Example
+    ///
     /// ```
     /// use linked_list::LinkedList;
-    /// let mut linked_list = LinkedList::<String>::default();
-    /// linked_list.push("Hello".to_string());
-    /// linked_list.push("World".to_string());
     ///
-    /// linked_list.delete(1);
-    /// assert_eq!(linked_list.len(), 1);
+    /// let mut linked_list = LinkedList::default();
+    /// linked_list.push(1);
+    /// linked_list.push(2);
+    ///
+    /// assert_eq!(Vec::from(linked_list), vec![1, 2]);
     /// ```
-    pub fn delete(&mut self, index: u32) -> Result<()> {
-        if index > self.size - 1 {
-            return Err(LinkedListError::IndexOutOfRangeError);
+    fn from(mut list: LinkedList<T>) -> Self {
+        let mut result = Vec::with_capacity(list.len() as usize);
+        while let Some(v) = list.pop() {
+            result.push(v);
         }
 
-        // Current is the node that will be deleted.
-        // Previous will drop the pointer to current, and then point to the new
-        // next node, that comes after current.
-        let mut previous = self.head.clone();
-        let mut current = previous.clone().unwrap().0.borrow_mut().next.clone();
+        result
+    }
+}
 
-        if index == 0 {
-            self.head = current.clone();
-        }
+// Two LinkedLists are equal if they hold equal values in the same order.
+impl<T> PartialEq for LinkedList<T>
+where
+    T: PartialEq + Clone,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.into_iter().eq(other)
+    }
+}
 
-        if index > 0 {
-            for _i in 0..index - 1 {
-                previous = current.clone();
-                current = current.clone().unwrap().0.borrow_mut().next.clone();
-            }
-        }
+impl<T> Eq for LinkedList<T> where T: Eq + Clone {}
 
-        let new_next = current.take().and_then(|v| v.0.borrow_mut().next.clone());
-        previous
-            .clone()
-            .map(|v| v.0.borrow_mut().next = new_next.clone());
+// LinkedLists are ordered lexicographically by their values, like slices.
+impl<T> PartialOrd for LinkedList<T>
+where
+    T: PartialOrd + Clone,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.into_iter().partial_cmp(other)
+    }
+}
 
-        self.size -= 1;
+impl<T> Ord for LinkedList<T>
+where
+    T: Ord + Clone,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.into_iter().cmp(other)
+    }
+}
+
+// Hashes the length before the values, so the hash agrees with PartialEq and
+// lists nested in a tuple don't hash like their values concatenated.
+impl<T> Hash for LinkedList<T>
+where
+    T: Hash + Clone,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        for v in self {
+            v.hash(state);
+        }
+    }
+}
+
+/// Renders the chain of values from the head, ending in `None` where the last
+/// node points to nothing.
+///
+/// # Example
+///
+/// ```
+/// use linked_list::LinkedList;
+///
+/// let mut list = LinkedList::default();
+/// list.push(1);
+/// list.push(2);
+/// list.push(3);
+///
+/// assert_eq!(list.to_string(), "1 -> 2 -> 3 -> None");
+/// assert_eq!(LinkedList::<u32>::default().to_string(), "None");
+/// ```
+impl<T> fmt::Display for LinkedList<T>
+where
+    T: fmt::Display + Clone,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for v in self {
+            write!(f, "{} -> ", v)?;
+        }
+
+        write!(f, "None")
+    }
+}
+
+/// Shows the size, the head and tail values and the chain of values in
+/// between, without the Rc and RefCell wrapping each node.
+///
+/// # Example
+///
+/// ```
+/// use linked_list::LinkedList;
+///
+/// let mut list = LinkedList::default();
+/// list.push(1);
+/// list.push(2);
+///
+/// assert_eq!(
+///     format!("{:?}", list),
+///     "LinkedList { size: 2, head: Some(1), tail: Some(2), nodes: [1, 2] }"
+/// );
+/// ```
+impl<T> fmt::Debug for LinkedList<T>
+where
+    T: Clone + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LinkedList")
+            .field("size", &self.size)
+            .field("head", &self.head())
+            .field("tail", &self.tail())
+            .field("nodes", &Chain(self))
+            .finish()
+    }
+}
+
+// Debugs the values of a LinkedList as a list, so the Debug of the
+// LinkedList can show them as a field.
+struct Chain<'a, T>(&'a LinkedList<T>);
+
+impl<'a, T> fmt::Debug for Chain<'a, T>
+where
+    T: Clone + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0).finish()
+    }
+}
+
+// The methods that don't need to clone the values.
+impl<T> LinkedList<T> {
+    /// Returns the length of the LinkedList.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> u32 {
+        self.size
+    }
+
+    /// Adds a a value to the end of a LinkedList.
+    ///
+    /// If the tail is shared with a copy made by share(), the values are
+    /// first copied into nodes of their own, so the new node isn't linked
+    /// after a node the other list may link its own pushes after.
+    ///
+    /// Time Complexity: O(1), O(n) if the tail is shared
+    /// Space Complexity: O(1), O(n) if the tail is shared
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<String>::default();
+    /// linked_list.push("Hello".to_string());
+    ///
+    /// assert_eq!(linked_list.tail(), Some("Hello".to_string()));
+    /// ```
+    pub fn push(&mut self, v: T) {
+        self.modified();
+        if let Some(unshare) = self.unshare_nodes.get() {
+            if self.tail_is_shared() {
+                unshare(self);
+            }
+        }
+        let new = NodeRef::new(Node::new(v));
 
         if self.size == 0 {
-            self.tail = None;
-            self.head = None;
+            self.head = Some(new.clone());
+        } else {
+            // This works because we take ownership of tail and leave None there.
+            // The reason why "old" still exists is because theres another
+            // NodeRef pointing to it.
+            match self.tail.take() {
+                Some(old) => old.0.borrow_mut().next = Some(new.clone()),
+                None => self.head = Some(new.clone()),
+            };
+        }
+
+        self.observers.inserted(&new.0.borrow().value);
+        self.tail = Some(new);
+        self.size += 1;
+    }
+
+    /// Returns a boolean indicating the LinkedList is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<String>::default();
+    /// assert_eq!(linked_list.is_empty(), true);
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Returns a boolean indicating a closure panicked in the middle of a
+    /// mutation, e.g. the predicate of retain() or the comparator of
+    /// sort_by(), and the panic was caught. The head, tail and length may
+    /// not agree until recover() is called.
+    pub fn is_poisoned(&self) -> bool {
+        self.poisoned
+    }
+
+    /// Repairs a LinkedList after a panic in the middle of a mutation, by
+    /// rebuilding its bookkeeping from the links:
+    ///
+    /// - the nodes reachable from the head are kept, up to the tail if the
+    ///   tail is still in the chain, or to the end of the chain otherwise
+    /// - the length is counted again from those nodes
+    /// - the tail is set to the last of them
+    /// - the cursor of get() is reset
+    /// - the poisoned flag is cleared
+    ///
+    /// Values that were unlinked when the panic happened are lost. Nothing
+    /// else is checked or restored, e.g. the values themselves or the order a
+    /// sort had reached. On a LinkedList that isn't poisoned the same steps
+    /// run and leave the values as they were.
+    ///
+    /// Returns the repaired length.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    /// use std::panic::{catch_unwind, AssertUnwindSafe};
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// for v in 1..=4 {
+    ///     linked_list.push(v);
+    /// }
+    ///
+    /// let result = catch_unwind(AssertUnwindSafe(|| {
+    ///     linked_list.retain(|v| if *v == 3 { panic!() } else { v % 2 == 1 });
+    /// }));
+    ///
+    /// assert!(result.is_err());
+    /// assert!(linked_list.is_poisoned());
+    ///
+    /// linked_list.recover();
+    /// assert!(!linked_list.is_poisoned());
+    /// assert_eq!(linked_list.len(), linked_list.iter_ref().count() as u32);
+    /// ```
+    pub fn recover(&mut self) -> u32 {
+        self.modified();
+        self.reset_cursor();
+
+        let mut size = 0;
+        let mut last: Option<NodeRef<T>> = None;
+        let mut current = self.head.clone();
+
+        while let Some(node) = current {
+            size += 1;
+
+            // A shared copy may have linked more nodes after the tail, so the
+            // walk stops at the tail if it is still in the chain.
+            if let Some(tail) = &self.tail {
+                if Rc::ptr_eq(&tail.0, &node.0) {
+                    last = Some(node);
+                    break;
+                }
+            }
+
+            current = node.0.borrow().next.clone();
+            last = Some(node);
+        }
+
+        self.tail = last;
+        self.size = size;
+        self.poisoned = false;
+
+        size
+    }
+
+    /// Returns an Iterator of ValueRefs to the values, from head to tail.
+    /// The values are borrowed rather than cloned, so they don't need to be
+    /// Clone.
+    ///
+    /// Time Complexity: O(1) per value
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// // A Mutex isn't Clone, so it can't be iterated with iter().
+    /// let mut linked_list = LinkedList::default();
+    /// linked_list.push(std::sync::Mutex::new(1));
+    /// linked_list.push(std::sync::Mutex::new(2));
+    ///
+    /// let sum: u32 = linked_list
+    ///     .iter_ref()
+    ///     .map(|v| *v.value().lock().unwrap())
+    ///     .sum();
+    /// assert_eq!(sum, 3);
+    /// ```
+    pub fn iter_ref(&self) -> RefIterator<'_, T> {
+        RefIterator {
+            _list: self,
+            current: self.head.clone(),
+            remaining: self.size,
+            #[cfg(feature = "checked-iter")]
+            version: self.version.get(),
+        }
+    }
+
+    /// Returns a copy of the LinkedList with its values cloned into new
+    /// nodes, nothing is shared with the original. The copy starts without
+    /// observers. This is what `clone()` does.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(n)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    ///
+    /// let mut copy = linked_list.deep_clone();
+    /// copy.iter_mut().for_each(|v| *v = 2);
+    /// copy.push(3);
+    ///
+    /// assert_eq!(linked_list.head(), Some(1));
+    /// assert_eq!(linked_list.len(), 1);
+    /// ```
+    pub fn deep_clone(&self) -> LinkedList<T>
+    where
+        T: Clone,
+    {
+        let mut list = LinkedList::default();
+        for v in self.iter_ref() {
+            list.push(v.value().clone());
+        }
+
+        list
+    }
+
+    /// Removes every item from the LinkedList.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<String>::default();
+    /// linked_list.push("Hello".to_string());
+    /// linked_list.clear();
+    ///
+    /// assert_eq!(linked_list.is_empty(), true);
+    /// ```
+    pub fn clear(&mut self) {
+        self.modified();
+        self.reset_cursor();
+
+        if self.observers.observes_removes() {
+            let mut current = self.head.clone();
+            for _i in 0..self.size {
+                if let Some(node) = current {
+                    self.observers.removed(&node.0.borrow().value);
+                    current = node.0.borrow().next.clone();
+                }
+            }
+        }
+
+        self.tail = None;
+        self.size = 0;
+
+        // Unlink the nodes one at a time, rather than letting the drop of the
+        // head recurse through the whole chain. Stop at the first node shared
+        // with a shared copy, the rest of the chain still belongs to it.
+        let mut current = self.head.take();
+        while let Some(node) = current {
+            if Rc::strong_count(&node.0) > 1 {
+                break;
+            }
+
+            current = node.0.borrow_mut().next.take();
+        }
+    }
+
+    /// Returns an Iterator that pops the values from the head, moving them
+    /// out of their nodes without cloning them. The LinkedList is empty once
+    /// the Drain is dropped, even if it wasn't iterated to the end.
+    ///
+    /// A node shared with a copy made by share() can't give up its value, so
+    /// the Drain ends at the first shared node and the rest is left to the
+    /// copy. Use pop() to get copies of shared values.
+    ///
+    /// Time Complexity: O(1) per value
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<String>::default();
+    /// linked_list.push("Hello".to_string());
+    /// linked_list.push("World".to_string());
+    ///
+    /// let values: Vec<String> = linked_list.drain().collect();
+    /// assert_eq!(values, vec!["Hello".to_string(), "World".to_string()]);
+    /// assert!(linked_list.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain { list: self }
+    }
+
+    /// Removes the head and moves its value out, unless the node is shared
+    /// with a shared copy.
+    fn pop_unshared(&mut self) -> Option<T> {
+        self.modified();
+        self.reset_cursor();
+
+        // The tail is a second reference to the last node, any other
+        // reference comes from a shared copy.
+        let owners = if self.size == 1 { 2 } else { 1 };
+        if Rc::strong_count(&self.head.as_ref()?.0) > owners {
+            return None;
         }
 
         if self.size == 1 {
-            self.tail = self.head.clone();
+            self.tail = None;
+        }
+        let head = self.head.take().expect("the head was checked above");
+        let node = Rc::into_inner(head.0)
+            .expect("the node isn't shared")
+            .into_inner();
+
+        self.head = node.next;
+        self.size -= 1;
+        self.observers.removed(&node.value);
+
+        Some(node.value)
+    }
+
+    /// Returns a boolean indicating another list, or a ValueRef, holds the
+    /// tail node.
+    fn tail_is_shared(&self) -> bool {
+        let tail = match &self.tail {
+            Some(tail) => tail,
+            None => return false,
+        };
+
+        // The tail is referenced by its predecessor (or the head), by
+        // self.tail and maybe by the node remembered by get().
+        let cursor = self.cursor.borrow();
+        let remembered = cursor
+            .as_ref()
+            .is_some_and(|(_, node)| Rc::ptr_eq(&node.0, &tail.0));
+        Rc::strong_count(&tail.0) > 2 + remembered as usize
+    }
+
+    /// Forgets the node remembered by get().
+    fn reset_cursor(&mut self) {
+        *self.cursor.get_mut() = None;
+    }
+
+    /// Marks the nodes as modified, invalidating the live iterators of this
+    /// LinkedList and its shared copies.
+    fn modified(&self) {
+        #[cfg(feature = "checked-iter")]
+        self.version.set(self.version.get() + 1);
+    }
+}
+
+impl<T> LinkedList<T>
+where
+    T: Clone,
+{
+    /// Returns a copy of the LinkedList that shares its nodes, its observers
+    /// and, with `checked-iter`, its modification counter.
+    ///
+    /// Each list keeps its own head, tail and size. Mutations that would
+    /// change the other list's values or links, e.g. pushes after the shared
+    /// tail, sorts, removals and splits, first copy the values into nodes of
+    /// their own, see iter_mut(). Until then, reading and popping cost
+    /// nothing extra.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    ///
+    /// let mut shared = linked_list.share();
+    /// linked_list.push(2);
+    /// shared.push(3);
+    /// assert_eq!(linked_list.pop(), Some(1));
+    ///
+    /// assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![2]);
+    /// assert_eq!(shared.into_iter().collect::<Vec<_>>(), vec![1, 3]);
+    /// ```
+    pub fn share(&self) -> LinkedList<T> {
+        self.unshare_nodes.set(Some(LinkedList::unshare));
+
+        LinkedList {
+            head: self.head.clone(),
+            tail: self.tail.clone(),
+            size: self.size,
+            cursor: RefCell::new(None),
+            #[cfg(feature = "checked-iter")]
+            version: Rc::clone(&self.version),
+            observers: self.observers.clone(),
+            poisoned: self.poisoned,
+            unshare_nodes: Cell::new(Some(LinkedList::unshare)),
         }
+    }
+
+    /// Returns the value from a LinkedList and removes it from the LinkedList.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<String>::default();
+    /// linked_list.push("Hello".to_string());
+    ///
+    /// assert_eq!(linked_list.pop(), Some("Hello".to_string()));
+    /// assert_eq!(linked_list.is_empty(), true);
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        self.modified();
+        self.reset_cursor();
+
+        // Takes ownership of head.
+        // map() applies to the inner value of Option (Rc)
+        // map() will return an Option, but we'll change the inner value of it
+        // to T.
+        self.head.take().map(|h| {
+            // borrow() - borrows inner value (NodeRef<T>)
+            // Clones next rather than taking it, a shared copy of the list may
+            // share the node and still needs its link.
+            //
+            // Assign head to next,
+            // If there isn't something, head is None, so tail should be None.
+            if let Some(next) = h.0.borrow().next.clone() {
+                self.head = Some(next);
+            } else {
+                self.tail.take();
+            }
+
+            // Decrement the size as we have popped from the list.
+            self.size -= 1;
+
+            // Extracts the value from h and returns it, cloning it if the node
+            // is shared.
+            let v = h.into_value();
+            self.observers.removed(&v);
+
+            v
+        })
+    }
+
+    /// Like pop(), but returns an error instead of None if the LinkedList is
+    /// empty, to chain with the other fallible operations.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::{LinkedList, LinkedListError};
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    ///
+    /// assert_eq!(linked_list.try_pop().unwrap(), 1);
+    /// assert!(matches!(linked_list.try_pop(), Err(LinkedListError::EmptyList)));
+    /// ```
+    pub fn try_pop(&mut self) -> Result<T> {
+        self.pop().ok_or(LinkedListError::EmptyList)
+    }
+
+    /// Removes the value at the back of the LinkedList and returns it.
+    ///
+    /// The nodes only link forward, so this walks from the head to find the
+    /// node before the tail, which becomes the new tail.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1), O(n) to copy shared nodes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    /// linked_list.push(2);
+    ///
+    /// assert_eq!(linked_list.pop_back(), Some(2));
+    /// assert_eq!(linked_list.tail(), Some(1));
+    /// assert_eq!(linked_list.pop_back(), Some(1));
+    /// assert_eq!(linked_list.pop_back(), None);
+    /// ```
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.size == 0 {
+            return None;
+        }
+
+        self.remove(self.size as usize - 1).ok()
+    }
+
+    /// Gets the value from a LinkedList according to an index.
+    ///
+    /// The LinkedList remembers the last node it got, so getting the indexes in
+    /// ascending order walks forward from there instead of from the head.
+    ///
+    /// Time Complexity: O(n), O(1) amortized for sequential indexes
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<String>::default();
+    /// linked_list.push("Hello".to_string());
+    ///
+    /// assert_eq!(linked_list.get(0), Some("Hello".to_string()));
+    /// ```
+    pub fn get(&self, index: usize) -> Option<T> {
+        if index >= self.size as usize {
+            return None;
+        }
+
+        let (start, mut current) = match self.cursor.borrow().as_ref() {
+            Some((i, node)) if *i <= index => (*i, node.clone()),
+            _ => (0, self.head.clone()?),
+        };
+
+        for _i in start..index {
+            let next = current.0.borrow().next.clone()?;
+            current = next;
+        }
+
+        let value = current.0.borrow().value.clone();
+        *self.cursor.borrow_mut() = Some((index, current));
+
+        Some(value)
+    }
+
+    /// Returns an Iterator of mutable references, to change the values in
+    /// place from head to tail.
+    ///
+    /// Copies made by share() share the nodes, so if any node is shared the
+    /// LinkedList first copies its values into nodes of its own, and the
+    /// changes don't show up in the shared copies. The same goes for nodes
+    /// kept alive by a ValueRef.
+    ///
+    /// Time Complexity: O(1) per value, O(n) to copy shared nodes
+    /// Space Complexity: O(1), O(n) to copy shared nodes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    /// linked_list.push(2);
+    ///
+    /// for v in linked_list.iter_mut() {
+    ///     *v += 1;
+    /// }
+    ///
+    /// assert_eq!(linked_list.head(), Some(2));
+    /// assert_eq!(linked_list.tail(), Some(3));
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.unshare();
+
+        IterMut {
+            current: self.head.clone(),
+            remaining: self.size,
+            _list: self,
+        }
+    }
+
+    /// Returns a Cursor at the head, or at the ghost if the LinkedList is
+    /// empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    /// linked_list.push(2);
+    ///
+    /// let mut cursor = linked_list.cursor_front();
+    /// cursor.move_next();
+    /// assert_eq!(cursor.current(), Some(2));
+    ///
+    /// cursor.move_next();
+    /// assert_eq!(cursor.current(), None);
+    /// ```
+    pub fn cursor_front(&self) -> Cursor<'_, T> {
+        Cursor {
+            list: self,
+            current: self.head.clone(),
+            index: self.head.as_ref().map(|_| 0),
+        }
+    }
+
+    /// Returns a CursorMut at the head, or at the ghost if the LinkedList is
+    /// empty. Like iter_mut(), the LinkedList first copies its values into
+    /// nodes of its own if any node is shared, so relinking its nodes can't
+    /// change a shared copy.
+    ///
+    /// Time Complexity: O(1), O(n) to copy shared nodes
+    /// Space Complexity: O(1), O(n) to copy shared nodes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    /// linked_list.push(2);
+    /// linked_list.push(3);
+    ///
+    /// // Replace every even value by two halves.
+    /// let mut cursor = linked_list.cursor_front_mut();
+    /// while let Some(v) = cursor.current() {
+    ///     if v % 2 == 0 {
+    ///         cursor.insert_after(v / 2);
+    ///         cursor.insert_after(v / 2);
+    ///         cursor.remove_current();
+    ///         cursor.move_next();
+    ///     }
+    ///     cursor.move_next();
+    /// }
+    ///
+    /// assert_eq!(linked_list.len(), 4);
+    /// assert_eq!(linked_list.get(1), Some(1));
+    /// ```
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        self.unshare();
+
+        CursorMut {
+            previous: None,
+            current: self.head.clone(),
+            index: self.head.as_ref().map(|_| 0),
+            list: self,
+        }
+    }
+
+    /// Replaces the nodes with nodes of its own holding copies of the values,
+    /// if any node is shared, e.g. with share() or a ValueRef.
+    fn unshare(&mut self) {
+        self.unshare_first(self.size as usize);
+    }
+
+    /// Like unshare(), but only looks for shared nodes among the first
+    /// `nodes`. That is enough before relinking the node at `nodes - 1`: any
+    /// other list going through it holds it or one of the nodes before it.
+    fn unshare_first(&mut self, nodes: usize) {
+        self.reset_cursor();
+
+        let mut shared = false;
+        let mut current = self.head.clone();
+        for i in 0..nodes.min(self.size as usize) as u32 {
+            let node = current.expect("the list has size nodes");
+            // The node is referenced by its predecessor (or the head), by the
+            // tail if it's the last node, and by `node` here.
+            let owners = if i + 1 == self.size { 3 } else { 2 };
+            if Rc::strong_count(&node.0) > owners {
+                shared = true;
+                break;
+            }
+            current = node.0.borrow().next.clone();
+        }
+
+        if !shared {
+            return;
+        }
+
+        let values: Vec<T> = self.iter_ref().map(|v| v.value().clone()).collect();
+        let mut head: Option<NodeRef<T>> = None;
+        let mut tail: Option<NodeRef<T>> = None;
+        for v in values {
+            let node = NodeRef::new(Node::new(v));
+            match &tail {
+                Some(t) => t.0.borrow_mut().next = Some(node.clone()),
+                None => head = Some(node.clone()),
+            }
+            tail = Some(node);
+        }
+
+        self.head = head;
+        self.tail = tail;
+    }
+
+    /// Returns a read-only view of the values in `range`, or None if the range
+    /// is out of bounds. The view borrows the LinkedList, nothing is copied.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    /// linked_list.push(2);
+    /// linked_list.push(3);
+    ///
+    /// let slice = linked_list.slice(1..3).unwrap();
+    /// assert_eq!(slice.get(0), Some(2));
+    /// assert!(linked_list.slice(2..4).is_none());
+    /// ```
+    pub fn slice(&self, range: Range<usize>) -> Option<ListSlice<'_, T>> {
+        if range.start > range.end || range.end > self.size as usize {
+            return None;
+        }
+
+        Some(ListSlice::new(self, range.start, range.end - range.start))
+    }
+
+    /// Returns the callbacks registered on the LinkedList.
+    pub(crate) fn observers(&self) -> &Observers<T> {
+        &self.observers
+    }
+
+    /// Returns an Iterator starting at `index`.
+    ///
+    /// Time Complexity: O(index)
+    /// Space Complexity: O(1)
+    pub(crate) fn iter_from(&self, index: usize) -> LinkedListIterator<'_, T> {
+        let mut current = self.head.clone();
+        for _ in 0..index {
+            current = current.and_then(|node| node.0.borrow().next.clone());
+        }
+
+        LinkedListIterator {
+            _list: self,
+            current,
+            remaining: (self.size as usize).saturating_sub(index),
+            #[cfg(feature = "checked-iter")]
+            version: self.version.get(),
+        }
+    }
+
+    /// Registers a callback that is called with every value added to the
+    /// LinkedList, e.g. to keep an external index in sync with it.
+    ///
+    /// Copies made by share() share the callbacks too, copies made by
+    /// clone() start without any.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    ///
+    /// let seen = Rc::new(RefCell::new(Vec::new()));
+    /// let mut linked_list = LinkedList::<u32>::default();
+    ///
+    /// let sink = seen.clone();
+    /// linked_list.on_insert(move |v| sink.borrow_mut().push(*v));
+    /// linked_list.push(1);
+    /// linked_list.push(2);
+    ///
+    /// assert_eq!(*seen.borrow(), vec![1, 2]);
+    /// ```
+    pub fn on_insert<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) + 'static,
+    {
+        self.observers.on_insert(Box::new(f));
+    }
+
+    /// Registers a callback that is called with every value removed from the
+    /// LinkedList, by pop(), delete() or clear().
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    /// use std::cell::Cell;
+    /// use std::rc::Rc;
+    ///
+    /// let removed = Rc::new(Cell::new(0));
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    /// linked_list.push(2);
+    ///
+    /// let counter = removed.clone();
+    /// linked_list.on_remove(move |_| counter.set(counter.get() + 1));
+    /// linked_list.pop();
+    /// linked_list.clear();
+    ///
+    /// assert_eq!(removed.get(), 2);
+    /// ```
+    pub fn on_remove<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) + 'static,
+    {
+        self.observers.on_remove(Box::new(f));
+    }
+
+    /// Returns the head of the List as an Option<T>.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<String>::default();
+    /// linked_list.push("Hello".to_string());
+    ///
+    /// assert_eq!(linked_list.head(), Some("Hello".to_string()));
+    /// ```
+    pub fn head(&self) -> Option<T> {
+        self.head.as_ref().map(|h| h.0.borrow().value.clone())
+    }
+
+    /// Returns the tail of the List.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<String>::default();
+    /// linked_list.push("Hello".to_string());
+    /// linked_list.push("World".to_string());
+    ///
+    /// assert_eq!(linked_list.tail(), Some("World".to_string()));
+    /// ```
+    pub fn tail(&self) -> Option<T> {
+        self.tail.as_ref().map(|t| t.0.borrow().value.clone())
+    }
+
+    /// Returns the first value in the List, an alias for `head()`.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn first(&self) -> Option<T> {
+        self.head()
+    }
+
+    /// Returns the last value in the List, an alias for `tail()`.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn last(&self) -> Option<T> {
+        self.tail()
+    }
+
+    /// Returns the value at an index, or the last value if the index is past
+    /// the end of the List. Returns None only if the List is empty.
+    ///
+    /// Time Complexity: O(n), O(1) if the index is past the end
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    /// linked_list.push(2);
+    ///
+    /// assert_eq!(linked_list.get_clamped(0), Some(1));
+    /// assert_eq!(linked_list.get_clamped(10), Some(2));
+    /// ```
+    pub fn get_clamped(&self, index: usize) -> Option<T> {
+        if index >= self.size as usize {
+            return self.tail();
+        }
+
+        self.get(index)
+    }
+
+    /// Deletes an item from the list according to an index.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1), O(n) to copy shared nodes
+    ///
+    /// # Example
+    /// ```
+    /// use linked_list::LinkedList;
+    /// let mut linked_list = LinkedList::<String>::default();
+    /// linked_list.push("Hello".to_string());
+    /// linked_list.push("World".to_string());
+    ///
+    /// linked_list.delete(1);
+    /// assert_eq!(linked_list.len(), 1);
+    /// ```
+    pub fn delete(&mut self, index: u32) -> Result<()> {
+        if self.size == 0 {
+            return Err(LinkedListError::EmptyList);
+        }
+
+        if index > self.size - 1 {
+            return Err(LinkedListError::IndexOutOfRangeError);
+        }
+
+        self.modified();
+        self.unshare();
+
+        // Current is the node that will be deleted.
+        // Previous will drop the pointer to current, and then point to the new
+        // next node, that comes after current.
+        let mut previous = self.head.clone();
+        let mut current = previous.clone().unwrap().0.borrow_mut().next.clone();
+
+        if index == 0 {
+            self.head = current.clone();
+        }
+
+        if index > 0 {
+            for _i in 0..index - 1 {
+                previous = current.clone();
+                current = current.clone().unwrap().0.borrow_mut().next.clone();
+            }
+        }
+
+        let removed = if index == 0 {
+            previous.clone()
+        } else {
+            current.clone()
+        };
+        if let Some(node) = removed {
+            self.observers.removed(&node.0.borrow().value);
+        }
+
+        let new_next = current.take().and_then(|v| v.0.borrow_mut().next.clone());
+        previous
+            .clone()
+            .map(|v| v.0.borrow_mut().next = new_next.clone());
+
+        self.size -= 1;
+
+        if self.size == 0 {
+            self.tail = None;
+            self.head = None;
+        }
+
+        if self.size == 1 {
+            self.tail = self.head.clone();
+        }
+
+        // Only deleting the last node moves the tail back to the previous one.
+        if self.size > 1 && index == self.size {
+            self.tail = previous;
+        }
+
+        Ok(())
+    }
+
+    /// Removes the value at an index and returns it, shifting the values after
+    /// it forward. Returns an error if the List is empty or the index is past
+    /// the end of the List.
+    ///
+    /// The value is moved out of its node, unless the node is shared with a
+    /// copy made by share(): then, like iter_mut(), the List first copies its
+    /// values into nodes of its own.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1), O(n) to copy shared nodes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<String>::default();
+    /// linked_list.push("Hello".to_string());
+    /// linked_list.push("World".to_string());
+    ///
+    /// assert_eq!(linked_list.remove(0).unwrap(), "Hello".to_string());
+    /// assert_eq!(linked_list.len(), 1);
+    /// assert!(linked_list.remove(1).is_err());
+    /// ```
+    pub fn remove(&mut self, index: usize) -> Result<T> {
+        if self.size == 0 {
+            return Err(LinkedListError::EmptyList);
+        }
+
+        if index >= self.size as usize {
+            return Err(LinkedListError::IndexOutOfRangeError);
+        }
+
+        let mut cursor = self.cursor_front_mut();
+        for _ in 0..index {
+            cursor.move_next();
+        }
+
+        Ok(cursor.remove_current().expect("index is within the List"))
+    }
+
+    /// Exchanges the values at two indexes, the nodes stay where they are.
+    /// Returns an error if the List is empty or either index is past the end
+    /// of the List.
+    ///
+    /// Like iter_mut(), the List first copies its values into nodes of its
+    /// own if a node is shared with a copy made by share().
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1), O(n) to copy shared nodes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    /// linked_list.push(2);
+    /// linked_list.push(3);
+    ///
+    /// linked_list.swap(0, 2).unwrap();
+    /// assert_eq!(linked_list.head(), Some(3));
+    /// assert_eq!(linked_list.tail(), Some(1));
+    /// assert!(linked_list.swap(0, 3).is_err());
+    /// ```
+    pub fn swap(&mut self, i: usize, j: usize) -> Result<()> {
+        if self.size == 0 {
+            return Err(LinkedListError::EmptyList);
+        }
+
+        if i.max(j) >= self.size as usize {
+            return Err(LinkedListError::IndexOutOfRangeError);
+        }
+
+        if i == j {
+            return Ok(());
+        }
+
+        self.unshare();
+        self.modified();
+
+        let (low, high) = (i.min(j), i.max(j));
+        let mut current = self.head.clone().expect("index is within the List");
+        let mut first = None;
+        for index in 0..high {
+            if index == low {
+                first = Some(current.clone());
+            }
+            let next = current
+                .0
+                .borrow()
+                .next
+                .clone()
+                .expect("index is within the List");
+            current = next;
+        }
+
+        let first = first.expect("low is less than high");
+        core::mem::swap(
+            &mut first.0.borrow_mut().value,
+            &mut current.0.borrow_mut().value,
+        );
+
+        Ok(())
+    }
+
+    /// Inserts a value at an index, shifting the values after it back. An index
+    /// equal to the length appends the value. Returns an error if the index is
+    /// past the end of the List.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1), O(n) to copy shared nodes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::{LinkedList, LinkedListError};
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    /// linked_list.push(3);
+    ///
+    /// linked_list.insert(1, 2).unwrap();
+    /// assert_eq!(linked_list.get(1), Some(2));
+    ///
+    /// match linked_list.insert(5, 4) {
+    ///     Err(LinkedListError::IndexOutOfRangeError) => {}
+    ///     _ => panic!("expected an out of range error"),
+    /// }
+    /// ```
+    pub fn insert(&mut self, index: usize, v: T) -> Result<()> {
+        if index > self.size as usize {
+            return Err(LinkedListError::IndexOutOfRangeError);
+        }
+
+        self.insert_at(index, v);
+        Ok(())
+    }
+
+    /// Links a new node holding `v` in at `index`, which must be at most the
+    /// length of the LinkedList.
+    pub(crate) fn insert_at(&mut self, index: usize, v: T) {
+        if index == self.size as usize {
+            return self.push(v);
+        }
+
+        self.modified();
+        self.unshare();
+
+        let new = NodeRef::new(Node::new(v));
+        self.observers.inserted(&new.0.borrow().value);
+
+        if index == 0 {
+            new.0.borrow_mut().next = self.head.take();
+            self.head = Some(new);
+        } else {
+            let mut previous = self.head.clone().expect("index is within the list");
+            for _i in 1..index {
+                let next = previous
+                    .0
+                    .borrow()
+                    .next
+                    .clone()
+                    .expect("index is within the list");
+                previous = next;
+            }
+
+            new.0.borrow_mut().next = previous.0.borrow_mut().next.take();
+            previous.0.borrow_mut().next = Some(new);
+        }
+
+        self.size += 1;
+    }
+
+    /// Returns a boolean indicating whether the List holds a value equal to
+    /// `value`. The values are compared in place, nothing is cloned.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<String>::default();
+    /// linked_list.push("Hello".to_string());
+    ///
+    /// assert!(linked_list.contains(&"Hello".to_string()));
+    /// assert!(!linked_list.contains(&"World".to_string()));
+    /// ```
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter_ref().any(|v| *v.value() == *value)
+    }
+
+    /// Returns the first value that matches `pred`, walking the nodes once.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    /// linked_list.push(4);
+    /// linked_list.push(6);
+    ///
+    /// assert_eq!(linked_list.find(|v| v % 2 == 0), Some(4));
+    /// assert_eq!(linked_list.find(|v| *v > 10), None);
+    /// ```
+    pub fn find<F>(&self, mut pred: F) -> Option<T>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.iter_ref()
+            .find(|v| pred(&v.value()))
+            .map(|v| v.value().clone())
+    }
+
+    /// Returns the index of the first value that matches `pred`, walking the
+    /// nodes once.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    /// linked_list.push(4);
+    /// linked_list.push(6);
+    ///
+    /// assert_eq!(linked_list.position(|v| v % 2 == 0), Some(1));
+    /// assert_eq!(linked_list.position(|v| *v > 10), None);
+    /// ```
+    pub fn position<F>(&self, mut pred: F) -> Option<usize>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.iter_ref().position(|v| pred(&v.value()))
+    }
+
+    /// Sorts the List in ascending order. The sort is stable, equal values
+    /// keep their order.
+    ///
+    /// Time Complexity: O(n log n)
+    /// Space Complexity: O(log n), O(n) to copy shared nodes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(3);
+    /// linked_list.push(1);
+    /// linked_list.push(2);
+    ///
+    /// linked_list.sort();
+    /// assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// ```
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(|a, b| a.cmp(b));
+    }
+
+    /// Sorts the List with a comparator. The sort is a stable merge sort that
+    /// relinks the existing nodes, no values are moved or cloned and nothing
+    /// is allocated besides the recursion, unless a node is shared with a
+    /// copy made by share() and the values are copied first.
+    ///
+    /// Time Complexity: O(n log n)
+    /// Space Complexity: O(log n), O(n) to copy shared nodes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    /// linked_list.push(3);
+    /// linked_list.push(2);
+    ///
+    /// linked_list.sort_by(|a, b| b.cmp(a));
+    /// assert_eq!(linked_list.head(), Some(3));
+    /// assert_eq!(linked_list.tail(), Some(1));
+    /// ```
+    pub fn sort_by<F>(&mut self, mut cmp: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if self.size < 2 {
+            return;
+        }
+
+        self.modified();
+        self.unshare();
+        self.poisoned = true;
+
+        self.head = LinkedList::merge_sort(self.head.take(), self.size as usize, &mut cmp);
+
+        // The last node of the sorted chain is the new tail.
+        let mut tail = self.head.clone();
+        while let Some(next) = tail.as_ref().and_then(|t| t.0.borrow().next.clone()) {
+            tail = Some(next);
+        }
+        self.tail = tail;
+        self.poisoned = false;
+    }
+
+    /// Merges another sorted LinkedList into this sorted List by relinking
+    /// their nodes, the values are neither moved nor cloned. On a tie the
+    /// values of this List go first.
+    ///
+    /// Like iter_mut(), both Lists first copy their values into nodes of their
+    /// own if a node is shared with a copy made by share().
+    ///
+    /// Time Complexity: O(n + m)
+    /// Space Complexity: O(1), O(n + m) to copy shared nodes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut odd = LinkedList::<u32>::default();
+    /// odd.push(1);
+    /// odd.push(3);
+    ///
+    /// let mut even = LinkedList::<u32>::default();
+    /// even.push(2);
+    /// even.push(4);
+    ///
+    /// odd.merge(even);
+    /// assert_eq!(odd.into_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    /// ```
+    pub fn merge(&mut self, mut other: LinkedList<T>)
+    where
+        T: Ord,
+    {
+        if other.size == 0 {
+            return;
+        }
+
+        self.unshare();
+        other.unshare();
+        self.adopted(&other.head);
+        self.modified();
+        other.modified();
+        self.poisoned = true;
+
+        // The chain with the larger last value ends the merge, on a tie the
+        // other chain's nodes are placed last.
+        self.tail = match (self.tail.take(), other.tail.take()) {
+            (Some(mine), Some(theirs)) => {
+                if theirs.0.borrow().value < mine.0.borrow().value {
+                    Some(mine)
+                } else {
+                    Some(theirs)
+                }
+            }
+            (mine, theirs) => mine.or(theirs),
+        };
+
+        self.head =
+            LinkedList::merge_chains(self.head.take(), other.head.take(), &mut |a: &T, b: &T| {
+                a.cmp(b)
+            });
+        self.size += other.size;
+        other.size = 0;
+        self.poisoned = false;
+    }
+
+    /// Moves every value of `other` to the end of the List by linking its head
+    /// to the tail of the List, leaving `other` empty.
+    ///
+    /// The moved values are reported to the insert callbacks of the List and
+    /// the remove callbacks of `other`, which walks the values only when such
+    /// callbacks are registered.
+    ///
+    /// Like iter_mut(), `other` first copies its values into nodes of its own
+    /// if a node is shared with a copy made by share(), and so does the List
+    /// if its tail is shared, so the copies keep their values.
+    ///
+    /// Time Complexity: O(m), O(n + m) to copy shared nodes
+    /// Space Complexity: O(1), O(n + m) to copy shared nodes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    ///
+    /// let mut other = LinkedList::<u32>::default();
+    /// other.push(2);
+    /// other.push(3);
+    ///
+    /// linked_list.append(&mut other);
+    /// assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// assert!(other.is_empty());
+    /// ```
+    pub fn append(&mut self, other: &mut LinkedList<T>) {
+        if other.size == 0 {
+            return;
+        }
+
+        self.adopted(&other.head);
+        if other.observers.observes_removes() {
+            for v in &*other {
+                other.observers.removed(&v);
+            }
+        }
+
+        if self.tail_is_shared() {
+            self.unshare();
+        }
+        other.unshare();
+
+        // The cursor of the List stays valid, the values before it don't move.
+        self.modified();
+        other.modified();
+        other.reset_cursor();
+
+        let head = other.head.take();
+        match &self.tail {
+            Some(tail) => tail.0.borrow_mut().next = head,
+            None => self.head = head,
+        }
+
+        self.tail = other.tail.take();
+        self.size += other.size;
+        other.size = 0;
+    }
+
+    /// Alternates the values of `other` with the values of the List by
+    /// relinking their nodes, starting with the head of the List. The values
+    /// left over from the longer List end it, in their order.
+    ///
+    /// Like iter_mut(), both Lists first copy their values into nodes of their
+    /// own if a node is shared with a copy made by share().
+    ///
+    /// Time Complexity: O(n + m)
+    /// Space Complexity: O(1), O(n + m) to copy shared nodes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut left = LinkedList::<u32>::default();
+    /// left.push(1);
+    /// left.push(3);
+    ///
+    /// let mut right = LinkedList::<u32>::default();
+    /// right.push(2);
+    /// right.push(4);
+    /// right.push(5);
+    ///
+    /// left.interleave(right);
+    /// assert_eq!(left.into_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+    /// ```
+    pub fn interleave(&mut self, mut other: LinkedList<T>) {
+        if self.size == 0 || other.size == 0 {
+            return self.append(&mut other);
+        }
+
+        self.unshare();
+        other.unshare();
+        self.adopted(&other.head);
+        self.modified();
+        self.reset_cursor();
+        other.modified();
+
+        // The longer List ends the interleaving, on a tie the other List's
+        // node is placed last.
+        if other.size >= self.size {
+            self.tail = other.tail.take();
+        }
+
+        let mut mine = self.head.clone();
+        let mut theirs = other.head.take();
+        while let (Some(a), Some(b)) = (mine.take(), theirs.take()) {
+            let a_next = a.0.borrow_mut().next.replace(b.clone());
+            let b_next = b.0.borrow_mut().next.take();
+
+            match a_next {
+                Some(next) => {
+                    b.0.borrow_mut().next = Some(next.clone());
+                    mine = Some(next);
+                    theirs = b_next;
+                }
+                None => b.0.borrow_mut().next = b_next,
+            }
+        }
+
+        self.size += other.size;
+        other.size = 0;
+        other.tail = None;
+    }
+
+    /// Splits the List into `n` Lists of roughly equal length by relinking its
+    /// nodes, keeping their order. The first `len % n` shards hold one more
+    /// value than the rest, and shards are empty when `n` is greater than the
+    /// length.
+    ///
+    /// The shards share no nodes and start without observers.
+    ///
+    /// Time Complexity: O(n + len)
+    /// Space Complexity: O(n), O(len) to copy shared nodes
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// for i in 0..5 {
+    ///     linked_list.push(i);
+    /// }
+    ///
+    /// let shards = linked_list.shards(2);
+    /// assert_eq!(shards[0].into_iter().collect::<Vec<_>>(), vec![0, 1, 2]);
+    /// assert_eq!(shards[1].into_iter().collect::<Vec<_>>(), vec![3, 4]);
+    /// ```
+    pub fn shards(mut self, n: usize) -> Vec<LinkedList<T>> {
+        assert!(n > 0, "a LinkedList can't be split into 0 shards");
+
+        let len = self.size as usize;
+        let mut shards = Vec::with_capacity(n);
+        let mut rest = self.split_off(0);
+
+        for i in 0..n {
+            let shard_len = len / n + (i < len % n) as usize;
+            let next = rest.split_off(shard_len);
+            shards.push(rest);
+            rest = next;
+        }
+
+        shards
+    }
+
+    /// Cuts the List in two at `index`, keeping the first `index` values and
+    /// returning the rest as a new List. Returns an error if the index is past
+    /// the end of the List.
+    ///
+    /// The values moved to the new List are reported to the remove callbacks,
+    /// and the new List starts without observers.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1), O(n) to copy shared nodes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    /// linked_list.push(2);
+    /// linked_list.push(3);
+    ///
+    /// let suffix = linked_list.split_at(1).unwrap();
+    /// assert_eq!(linked_list.tail(), Some(1));
+    /// assert_eq!(suffix.head(), Some(2));
+    /// assert_eq!(suffix.len(), 2);
+    /// ```
+    pub fn split_at(&mut self, index: usize) -> Result<LinkedList<T>> {
+        if index > self.size as usize {
+            return Err(LinkedListError::IndexOutOfRangeError);
+        }
+
+        let suffix = self.split_off(index);
+
+        if self.observers.observes_removes() {
+            for v in &suffix {
+                self.observers.removed(&v);
+            }
+        }
+
+        Ok(suffix)
+    }
+
+    /// Moves the first `n` values out into a new List, or every value if the
+    /// List is shorter. The nodes are relinked, no value is cloned.
+    ///
+    /// The values moved to the new List are reported to the remove callbacks,
+    /// and the new List starts without observers.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1), O(n) to copy shared nodes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    /// linked_list.push(2);
+    /// linked_list.push(3);
+    ///
+    /// let batch = linked_list.take_front(2);
+    /// assert_eq!(batch.tail(), Some(2));
+    /// assert_eq!(linked_list.head(), Some(3));
+    /// assert_eq!(linked_list.take_front(5).len(), 1);
+    /// ```
+    pub fn take_front(&mut self, n: usize) -> LinkedList<T> {
+        let mut front = self.split_off(n.min(self.size as usize));
+
+        // The List kept the front, swap the chains so it keeps the rest along
+        // with its observers.
+        core::mem::swap(&mut self.head, &mut front.head);
+        core::mem::swap(&mut self.tail, &mut front.tail);
+        core::mem::swap(&mut self.size, &mut front.size);
+
+        if self.observers.observes_removes() {
+            for v in front.iter_ref() {
+                self.observers.removed(&v.value());
+            }
+        }
+
+        front
+    }
+
+    /// Drops the first `n` values, or every value if the List is shorter.
+    /// The dropped values are reported to the remove callbacks.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1), O(n) to copy shared nodes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    /// linked_list.push(2);
+    /// linked_list.push(3);
+    ///
+    /// linked_list.skip_front(2);
+    /// assert_eq!(linked_list.head(), Some(3));
+    /// assert_eq!(linked_list.len(), 1);
+    /// ```
+    pub fn skip_front(&mut self, n: usize) {
+        // The taken List has no observers, so clearing it reports nothing
+        // twice.
+        self.take_front(n).clear();
+    }
+
+    /// Cuts the List after the first `index` nodes and returns the rest as a
+    /// new List without observers. `index` must be at most the length.
+    fn split_off(&mut self, index: usize) -> LinkedList<T> {
+        self.modified();
+        self.reset_cursor();
+
+        let mut suffix = LinkedList::default();
+        if index == 0 {
+            suffix.head = self.head.take();
+            suffix.tail = self.tail.take();
+            suffix.size = self.size;
+            self.size = 0;
+
+            return suffix;
+        }
+
+        if index == self.size as usize {
+            return suffix;
+        }
+
+        self.unshare_first(index);
+        let mut last = self.head.clone().expect("index is within the List");
+        for _ in 1..index {
+            let next = last
+                .0
+                .borrow()
+                .next
+                .clone()
+                .expect("index is within the List");
+            last = next;
+        }
+
+        suffix.head = last.0.borrow_mut().next.take();
+        suffix.tail = self.tail.replace(last);
+        suffix.size = self.size - index as u32;
+        self.size = index as u32;
+
+        suffix
+    }
+
+    /// Calls the insert callbacks with every value in a chain of nodes being
+    /// linked into the List.
+    fn adopted(&self, head: &Option<NodeRef<T>>) {
+        if !self.observers.observes_inserts() {
+            return;
+        }
+
+        let mut current = head.clone();
+        while let Some(node) = current {
+            self.observers.inserted(&node.0.borrow().value);
+            current = node.0.borrow().next.clone();
+        }
+    }
+
+    /// Sorts the chain of `len` nodes starting at `head` by splitting it in
+    /// half, sorting each half and merging them. Returns the new first node.
+    fn merge_sort<F>(head: Option<NodeRef<T>>, len: usize, cmp: &mut F) -> Option<NodeRef<T>>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if len < 2 {
+            return head;
+        }
+
+        let middle = len / 2;
+        let mut last_left = head.clone()?;
+        for _ in 1..middle {
+            let next = last_left.0.borrow().next.clone()?;
+            last_left = next;
+        }
+        let right = last_left.0.borrow_mut().next.take();
+
+        let left = LinkedList::merge_sort(head, middle, cmp);
+        let right = LinkedList::merge_sort(right, len - middle, cmp);
+
+        LinkedList::merge_chains(left, right, cmp)
+    }
+
+    /// Merges two sorted chains by relinking their nodes. On a tie the node
+    /// from `left` goes first, which keeps the sort stable.
+    fn merge_chains<F>(
+        mut left: Option<NodeRef<T>>,
+        mut right: Option<NodeRef<T>>,
+        cmp: &mut F,
+    ) -> Option<NodeRef<T>>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut head: Option<NodeRef<T>> = None;
+        let mut tail: Option<NodeRef<T>> = None;
+
+        while let (Some(l), Some(r)) = (left.clone(), right.clone()) {
+            let take_right = cmp(&r.0.borrow().value, &l.0.borrow().value) == Ordering::Less;
+
+            let node = if take_right {
+                right = r.0.borrow_mut().next.take();
+                r
+            } else {
+                left = l.0.borrow_mut().next.take();
+                l
+            };
+
+            match &tail {
+                Some(t) => t.0.borrow_mut().next = Some(node.clone()),
+                None => head = Some(node.clone()),
+            }
+            tail = Some(node);
+        }
+
+        // One of the chains is used up, the rest of the other is already
+        // sorted.
+        let rest = left.or(right);
+        match &tail {
+            Some(t) => t.0.borrow_mut().next = rest,
+            None => return rest,
+        }
+
+        head
+    }
+
+    /// Returns a new LinkedList holding the result of applying `f` to each
+    /// value, built during a single traversal of the list.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(n)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    /// linked_list.push(2);
+    ///
+    /// let doubled = linked_list.map(|v| v * 2);
+    /// assert_eq!(doubled.get(0), Some(2));
+    /// assert_eq!(doubled.get(1), Some(4));
+    /// ```
+    pub fn map<U, F>(&self, mut f: F) -> LinkedList<U>
+    where
+        U: Clone,
+        F: FnMut(&T) -> U,
+    {
+        let mut result = LinkedList::default();
+        for v in self.iter_ref() {
+            result.push(f(&v.value()));
+        }
+
+        result
+    }
+
+    /// Returns a new LinkedList holding the result of applying `f` to the
+    /// values at the same index of the List and `other`, as long as the
+    /// shorter of the two. Both are walked once, side by side.
+    ///
+    /// Time Complexity: O(min(n, m))
+    /// Space Complexity: O(min(n, m))
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut workers = LinkedList::<&str>::default();
+    /// workers.push("a");
+    /// workers.push("b");
+    ///
+    /// let mut jobs = LinkedList::<u32>::default();
+    /// jobs.push(1);
+    /// jobs.push(2);
+    /// jobs.push(3);
+    ///
+    /// let assigned = workers.zip_with(&jobs, |w, j| format!("{}{}", w, j));
+    /// assert_eq!(assigned.into_iter().collect::<Vec<_>>(), vec!["a1", "b2"]);
+    /// ```
+    pub fn zip_with<U, R, F>(&self, other: &LinkedList<U>, mut f: F) -> LinkedList<R>
+    where
+        R: Clone,
+        F: FnMut(&T, &U) -> R,
+    {
+        let mut result = LinkedList::default();
+        for (a, b) in self.iter_ref().zip(other.iter_ref()) {
+            result.push(f(&a.value(), &b.value()));
+        }
+
+        result
+    }
+
+    /// Returns a new LinkedList holding the values that match `pred`, built
+    /// during a single traversal of the list.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(n)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    /// linked_list.push(2);
+    /// linked_list.push(3);
+    ///
+    /// let odd = linked_list.filtered(|v| v % 2 == 1);
+    /// assert_eq!(odd.len(), 2);
+    /// assert_eq!(odd.tail(), Some(3));
+    /// ```
+    pub fn filtered<F>(&self, mut pred: F) -> LinkedList<T>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut result = LinkedList::default();
+        for v in self.iter_ref() {
+            let value = v.value();
+            if pred(&value) {
+                result.push(value.clone());
+            }
+        }
+
+        result
+    }
+
+    /// Removes the values that don't match `pred`, unlinking their nodes
+    /// during a single traversal of the list. The kept values stay in order
+    /// and nothing is cloned, unless a node is shared with a copy made by
+    /// share() and the values are copied first.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1), O(n) to copy shared nodes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    /// linked_list.push(2);
+    /// linked_list.push(3);
+    ///
+    /// linked_list.retain(|v| v % 2 == 1);
+    /// assert_eq!(linked_list.len(), 2);
+    /// assert_eq!(linked_list.head(), Some(1));
+    /// assert_eq!(linked_list.tail(), Some(3));
+    /// ```
+    pub fn retain<F>(&mut self, mut pred: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.modified();
+        self.unshare();
+        self.poisoned = true;
+
+        // Relink each kept node after the previous kept node, skipping over
+        // the removed ones.
+        let mut current = self.head.take();
+        let mut last: Option<NodeRef<T>> = None;
+        let mut size = 0;
+
+        while let Some(node) = current {
+            current = node.0.borrow().next.clone();
+
+            if !pred(&node.0.borrow().value) {
+                self.observers.removed(&node.0.borrow().value);
+                continue;
+            }
+
+            match &last {
+                Some(l) => l.0.borrow_mut().next = Some(node.clone()),
+                None => self.head = Some(node.clone()),
+            }
+            last = Some(node);
+            size += 1;
+        }
+
+        if let Some(l) = &last {
+            l.0.borrow_mut().next = None;
+        }
+        self.tail = last;
+        self.size = size;
+        self.poisoned = false;
+    }
+
+    /// Removes the consecutive repeated values, keeping the first of each run.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1), O(n) to copy shared nodes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// for v in &[1, 1, 2, 2, 2, 1] {
+    ///     linked_list.push(*v);
+    /// }
+    ///
+    /// linked_list.dedup();
+    /// assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![1, 2, 1]);
+    /// ```
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    /// Removes the consecutive values that map to the same key, keeping the
+    /// first of each run.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1), O(n) to copy shared nodes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// for v in &[10, 11, 20, 30, 31] {
+    ///     linked_list.push(*v);
+    /// }
+    ///
+    /// linked_list.dedup_by_key(|v| v / 10);
+    /// assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![10, 20, 30]);
+    /// ```
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: PartialEq,
+        F: FnMut(&T) -> K,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    /// Unlinks each node whose value is the same as the value of the node
+    /// before it, according to `same`.
+    fn dedup_by<F>(&mut self, mut same: F)
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        self.modified();
+        self.unshare();
+        self.poisoned = true;
+
+        let mut current = self.head.clone();
+        while let Some(node) = current {
+            let next = match node.0.borrow().next.clone() {
+                Some(next) => next,
+                None => break,
+            };
+
+            if !same(&node.0.borrow().value, &next.0.borrow().value) {
+                current = Some(next);
+                continue;
+            }
+
+            // Skip over the repeated node and compare the one after it with
+            // the same node again.
+            let after = next.0.borrow().next.clone();
+            if after.is_none() {
+                self.tail = Some(node.clone());
+            }
+            node.0.borrow_mut().next = after;
+            self.size -= 1;
+            self.observers.removed(&next.0.borrow().value);
+
+            current = Some(node);
+        }
+
+        self.poisoned = false;
+    }
+
+    /// Runs a batch of mutations as a single unit. If `f` returns an error,
+    /// every mutation it made is reverted, newest first, and the error is
+    /// returned. The LinkedList is left as it was before the transaction.
+    ///
+    /// The mutations are applied as they are made, but the observers only
+    /// see them once `f` returns Ok, so they never see a partly applied
+    /// transaction. A rollback isn't reported at all.
+    ///
+    /// Time Complexity: O(k * n), where k is the number of mutations
+    /// Space Complexity: O(k)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    ///
+    /// let result: linked_list::Result<()> = linked_list.transaction(|tx| {
+    ///     tx.push(2);
+    ///     tx.remove(0)?;
+    ///     tx.remove(5)?;
+    ///     Ok(())
+    /// });
+    ///
+    /// assert!(result.is_err());
+    /// assert_eq!(linked_list.len(), 1);
+    /// assert_eq!(linked_list.head(), Some(1));
+    /// ```
+    pub fn transaction<F, R, E>(&mut self, f: F) -> core::result::Result<R, E>
+    where
+        F: FnOnce(&mut Transaction<'_, T>) -> core::result::Result<R, E>,
+    {
+        let mut tx = Transaction::new(self);
+
+        match f(&mut tx) {
+            Ok(r) => {
+                tx.commit();
+                Ok(r)
+            }
+            Err(e) => {
+                tx.rollback();
+                Err(e)
+            }
+        }
+    }
+}
+
+#[allow(unused_macros)]
+macro_rules! linked_list {
+    // $ similar to bash script exec
+    // execution a variadic number of parameters
+    // each expr is seperated by "," for "*" amount of times
+    ($($x: expr),*) => {{
+        let mut linked_list = LinkedList::default();
+
+        // Push each item to the linked list, according to the number of inputs
+        $(linked_list.push($x);)*
+        linked_list
+    }};
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn init_list() {
+        let linked_list = LinkedList::<String>::default();
+        assert_eq!(linked_list.size, 0);
+    }
+
+    #[test]
+    fn push_one_node() {
+        let mut linked_list = LinkedList::<String>::default();
+
+        linked_list.push("1".to_string());
+        assert_eq!(linked_list.size, 1);
+    }
+
+    #[test]
+    fn push_two_nodes() {
+        let mut linked_list = LinkedList::<String>::default();
+
+        for i in 1..3 {
+            linked_list.push(i.to_string());
+        }
+
+        assert_eq!(*&linked_list.size, 2);
+        assert_eq!(linked_list.head(), Some("1".to_string()));
+        assert_eq!(linked_list.tail(), Some("2".to_string()));
+    }
+
+    #[test]
+    fn push_three_nodes() {
+        let mut linked_list = LinkedList::<String>::default();
+
+        for i in 1..4 {
+            linked_list.push(i.to_string());
+        }
+
+        assert_eq!(*&linked_list.size, 3);
+        assert_eq!(linked_list.tail(), Some("3".to_string()));
+    }
+
+    #[test]
+    fn push_keeps_shared_copy() {
+        let mut linked_list = linked_list![1];
+        let mut shared = linked_list.share();
+
+        // Both lists push after the node they share.
+        linked_list.push(2);
+        shared.push(3);
+        assert_eq!(linked_list.iter().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(linked_list.tail(), Some(2));
+        assert_eq!(shared.iter().collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(shared.tail(), Some(3));
+
+        // The other way round, after get() remembered the tail.
+        let mut linked_list = linked_list![1, 2];
+        assert_eq!(linked_list.get(1), Some(2));
+        let mut shared = linked_list.share();
+        shared.push(4);
+        linked_list.push(3);
+        assert_eq!(linked_list.iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(shared.iter().collect::<Vec<_>>(), vec![1, 2, 4]);
+    }
+
+    #[test]
+    fn access_none_head() {
+        let linked_list = LinkedList::<String>::default();
+        assert_eq!(linked_list.head(), None);
+    }
+
+    #[test]
+    fn access_none_tail() {
+        let linked_list = LinkedList::<String>::default();
+        assert_eq!(linked_list.tail(), None);
+    }
+
+    #[test]
+    fn head_and_tail() {
+        let mut linked_list = LinkedList::<String>::default();
+
+        linked_list.push(String::from("hello"));
+        assert_eq!(linked_list.head(), Some("hello".to_string()));
+        assert_eq!(linked_list.tail(), Some("hello".to_string()));
+        assert_eq!(linked_list.size, 1);
+
+        linked_list.push("world".to_string());
+        assert_eq!(linked_list.tail(), Some("world".to_string()));
+        assert_eq!(linked_list.size, 2);
+    }
+
+    #[test]
+    fn get_at_index() {
+        let mut linked_list = LinkedList::<String>::default();
+
+        for i in 1..5 {
+            linked_list.push(i.to_string());
+        }
+        assert_eq!(linked_list.head(), Some("1".to_string()));
+        assert_eq!(linked_list.tail(), Some("4".to_string()));
+
+        assert_eq!(linked_list.get(0), Some("1".to_string()));
+        assert_eq!(linked_list.get(1), Some("2".to_string()));
+        assert_eq!(linked_list.get(2), Some("3".to_string()));
+        assert_eq!(linked_list.get(3), Some("4".to_string()));
+        assert_eq!(linked_list.get(4), None);
+        assert_eq!(linked_list.get(100), None);
+    }
+
+    #[test]
+    fn pop() {
+        let mut linked_list = LinkedList::<String>::default();
+
+        for i in 1..5 {
+            linked_list.push(i.to_string());
+        }
+
+        assert_eq!(linked_list.head(), Some("1".to_string()));
+        assert_eq!(linked_list.pop(), Some("1".to_string()));
+        assert_eq!(linked_list.head(), Some("2".to_string()));
+    }
+
+    #[test]
+    fn pop_back() {
+        let mut linked_list = LinkedList::<u32>::default();
+        assert_eq!(linked_list.pop_back(), None);
+        assert_eq!(linked_list.len(), 0);
+
+        // One value: head and tail are the same node.
+        linked_list.push(1);
+        assert_eq!(linked_list.pop_back(), Some(1));
+        assert!(linked_list.is_empty());
+        assert_eq!(linked_list.head(), None);
+        assert_eq!(linked_list.tail(), None);
+
+        // Two values: the head becomes the tail.
+        linked_list.push(1);
+        linked_list.push(2);
+        assert_eq!(linked_list.pop_back(), Some(2));
+        assert_eq!(linked_list.len(), 1);
+        assert_eq!(linked_list.head(), Some(1));
+        assert_eq!(linked_list.tail(), Some(1));
+
+        // Pushing after a pop_back() links from the new tail.
+        linked_list.push(3);
+        linked_list.push(4);
+        assert_eq!(linked_list.pop_back(), Some(4));
+        assert_eq!(linked_list.tail(), Some(3));
+        assert_eq!(linked_list.iter().collect::<Vec<_>>(), vec![1, 3]);
+
+        // A shared list keeps its values.
+        let shared = linked_list.share();
+        assert_eq!(linked_list.pop_back(), Some(3));
+        assert_eq!(shared.iter().collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(shared.tail(), Some(3));
+    }
+
+    #[test]
+    fn iterator() {
+        let mut linked_list = LinkedList::<String>::default();
+
+        for i in 1..5 {
+            linked_list.push(i.to_string());
+        }
+
+        for i in linked_list.into_iter() {
+            assert_eq!(i, format!("{}", i));
+        }
+
+        // Assert the iterator did not consume the linked_list.
+        assert_eq!(linked_list.get(2), Some("3".to_string()));
+    }
+
+    #[test]
+    fn iterator_function_calls() {
+        let linked_list = linked_list![1, 2, 3, 4, 5];
+        let result: Vec<u32> = linked_list.into_iter().map(|v| (v * 2) as u32).collect();
+
+        assert_eq!(result[0], 2);
+        assert_eq!(result[1], 4);
+    }
+
+    #[test]
+    fn iterator_size() {
+        let mut linked_list = linked_list![1, 2, 3];
+
+        let mut iter = linked_list.iter_from(0);
+        assert_eq!(iter.len(), 3);
+        iter.next();
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        iter.by_ref().for_each(drop);
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+
+        assert_eq!(linked_list.iter_ref().len(), 3);
+        assert_eq!(linked_list.iter_mut().skip(1).len(), 2);
+        assert_eq!(LinkedList::<u32>::default().iter_from(0).len(), 0);
+    }
+
+    #[test]
+    fn iterator_follows_the_nodes() {
+        let linked_list = linked_list![1, 2, 3];
+
+        // Interleaved iterators each keep their own position.
+        let mut a = linked_list.into_iter();
+        let mut b = linked_list.into_iter();
+        assert_eq!(a.next(), Some(1));
+        assert_eq!(b.next(), Some(1));
+        assert_eq!(a.next(), Some(2));
+        assert_eq!(b.collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(a.next(), Some(3));
+
+        // Stops at its own tail when a shared copy linked more nodes.
+        let mut shared = linked_list.share();
+        shared.push(4);
+        assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(linked_list.iter_from(1).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(linked_list.iter_from(5).next(), None);
+    }
+
+    #[test]
+    fn iter_ref_without_clone() {
+        #[derive(Debug, PartialEq)]
+        struct Unique(u32);
+
+        let mut linked_list = LinkedList::default();
+        linked_list.push(Unique(1));
+        linked_list.push(Unique(2));
+        assert_eq!(linked_list.len(), 2);
+
+        let mut iter = linked_list.iter_ref();
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        let first = iter.next().unwrap();
+        assert!(first == Unique(1));
+        assert_eq!(iter.next().unwrap().value().0, 2);
+        assert!(iter.next().is_none());
+
+        // A ValueRef keeps its node alive after the LinkedList is gone.
+        drop(linked_list);
+        assert_eq!(format!("{:?}", first), "ValueRef(Unique(1))");
+    }
+
+    /// Returns the List [1, 2] with a node holding 3 linked after its tail,
+    /// as a copy sharing the nodes could leave it.
+    fn linked_past_tail() -> LinkedList<u32> {
+        let linked_list = linked_list![1, 2];
+        let past = linked_list![3];
+        linked_list.tail.as_ref().unwrap().0.borrow_mut().next = past.head.clone();
+
+        linked_list
+    }
+
+    #[test]
+    fn iter_ref_stops_at_own_tail() {
+        let mut linked_list = LinkedList::<u32>::default();
+        linked_list.push(1);
+
+        // The shared list has the tail and links a node after it.
+        let mut shared = linked_list.share();
+        shared.push(2);
+
+        let values: Vec<u32> = linked_list.iter_ref().map(|v| *v.value()).collect();
+        assert_eq!(values, vec![1]);
+
+        let linked_list = linked_past_tail();
+        let values: Vec<u32> = linked_list.iter_ref().map(|v| *v.value()).collect();
+        assert_eq!(values, vec![1, 2]);
+    }
+
+    #[test]
+    fn iter_mut() {
+        let mut linked_list = linked_list![1, 2, 3];
+        for v in linked_list.iter_mut() {
+            *v *= 10;
+        }
+        assert_eq!(
+            linked_list.into_iter().collect::<Vec<_>>(),
+            vec![10, 20, 30]
+        );
+
+        let mut iter = linked_list.iter_mut();
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        let first = iter.next().unwrap();
+        let second = iter.next().unwrap();
+        std::mem::swap(first, second);
+        drop(iter);
+
+        linked_list.push(40);
+        assert_eq!(
+            linked_list.into_iter().collect::<Vec<_>>(),
+            vec![20, 10, 30, 40]
+        );
+    }
+
+    #[test]
+    fn iter_mut_copies_shared_nodes() {
+        let mut linked_list = linked_list![1, 2, 3];
+        let shared = linked_list.share();
+        let first = linked_list.iter_ref().next().unwrap();
+
+        for v in linked_list.iter_mut() {
+            *v += 1;
+        }
+
+        assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(shared.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert!(first == 1);
+
+        *linked_list.iter_mut().last().unwrap() = 5;
+        assert_eq!(linked_list.tail(), Some(5));
+
+        // The walk stops at the tail.
+        let mut linked_list = linked_past_tail();
+        linked_list.iter_mut().for_each(|v| *v *= 10);
+        assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![10, 20]);
+    }
+
+    #[test]
+    fn macro_linked_list() {
+        let linked_list = linked_list!["1".to_string(), "2".to_string()];
+        assert_eq!(linked_list.tail(), Some("2".to_string()));
+    }
+
+    #[test]
+    fn delete_item() {
+        let mut linked_list = linked_list![
+            "1".to_string(),
+            "2".to_string(),
+            "3".to_string(),
+            "4".to_string(),
+            "5".to_string()
+        ];
+        assert_eq!(linked_list.len(), 5);
+        assert_eq!(linked_list.get(2), Some("3".to_string()));
+
+        linked_list.delete(2).unwrap();
+        assert_eq!(linked_list.len(), 4);
+        assert_eq!(linked_list.get(0), Some("1".to_string()));
+        assert_eq!(linked_list.get(1), Some("2".to_string()));
+        assert_eq!(linked_list.get(2), Some("4".to_string()));
+        assert_eq!(linked_list.get(3), Some("5".to_string()));
+    }
+
+    #[test]
+    fn deleting_head() {
+        let mut linked_list = linked_list!["1".to_string()];
+        assert_eq!(linked_list.len(), 1);
+
+        linked_list.delete(0).unwrap();
+        assert_eq!(linked_list.len(), 0);
+        assert_eq!(linked_list.head(), None);
+
+        linked_list.push("2".to_string());
+        linked_list.push("3".to_string());
+        assert_eq!(linked_list.len(), 2);
+        assert_eq!(linked_list.head(), Some("2".to_string()));
+        assert_eq!(linked_list.tail(), Some("3".to_string()));
+        assert_eq!(linked_list.get(0), Some("2".to_string()));
+        assert_eq!(linked_list.get(1), Some("3".to_string()));
+
+        linked_list.delete(0).unwrap();
+        assert_eq!(linked_list.len(), 1);
+        assert_eq!(linked_list.head(), Some("3".to_string()));
+        assert_eq!(linked_list.tail(), Some("3".to_string()));
+        assert_eq!(linked_list.get(0), Some("3".to_string()));
+        assert_eq!(linked_list.get(1), None);
+    }
+
+    #[test]
+    fn deleting_tail() {
+        let mut linked_list = linked_list!["1".to_string(), "2".to_string()];
+        assert_eq!(linked_list.len(), 2);
+        assert_eq!(linked_list.tail(), Some("2".to_string()));
+        assert_eq!(linked_list.get(1), Some("2".to_string()));
+
+        linked_list.delete(1).unwrap();
+        assert_eq!(linked_list.len(), 1);
+        assert_eq!(linked_list.get(0), Some("1".to_string()));
+        assert_eq!(linked_list.get(1), None);
+        assert_eq!(linked_list.tail(), Some("1".to_string()));
+
+        linked_list.delete(0).unwrap();
+        assert_eq!(linked_list.len(), 0);
+        assert_eq!(linked_list.get(0), None);
+        assert_eq!(linked_list.head(), None);
+        assert_eq!(linked_list.tail(), None);
+
+        for i in 0..10 {
+            linked_list.push(i.to_string());
+        }
+        assert_eq!(linked_list.len(), 10);
+        assert_eq!(linked_list.head(), Some("0".to_string()));
+        assert_eq!(linked_list.tail(), Some("9".to_string()));
+
+        linked_list.delete(9).unwrap();
+        assert_eq!(linked_list.len(), 9);
+        assert_eq!(linked_list.tail(), Some("8".to_string()));
+
+        linked_list.delete(8).unwrap();
+        assert_eq!(linked_list.len(), 8);
+        assert_eq!(linked_list.tail(), Some("7".to_string()));
+    }
+
+    #[test]
+    fn delete_keeps_shared_copy() {
+        let mut linked_list = linked_list![1, 2, 3, 4];
+        let shared = linked_list.share();
+
+        linked_list.delete(1).unwrap();
+        linked_list.delete(2).unwrap();
+        assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![1, 3]);
+
+        assert_eq!(shared.into_iter().len(), 4);
+        assert_eq!(shared.into_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn delete_index_greater_than_size() {
+        let mut linked_list = linked_list!["1".to_string(), "2".to_string()];
+        linked_list.delete(10).unwrap();
+    }
+
+    #[test]
+    fn remove() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let mut linked_list = linked_list![1, 2, 3, 4];
+        let shared = linked_list.share();
+
+        let sink = seen.clone();
+        linked_list.on_remove(move |v| sink.borrow_mut().push(*v));
+
+        assert_eq!(linked_list.remove(1).unwrap(), 2);
+        assert_eq!(linked_list.remove(2).unwrap(), 4);
+        assert_eq!(linked_list.tail(), Some(3));
+        assert_eq!(linked_list.remove(0).unwrap(), 1);
+        assert!(linked_list.remove(1).is_err());
+
+        assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![3]);
+        assert_eq!(*seen.borrow(), vec![2, 4, 1]);
+        assert_eq!(shared.into_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+
+        assert_eq!(linked_list.remove(0).unwrap(), 3);
+        assert!(linked_list.is_empty());
+        assert_eq!(linked_list.head(), None);
+        assert_eq!(linked_list.tail(), None);
+    }
+
+    #[test]
+    fn remove_moves_value() {
+        let value = Rc::new(1);
+        let mut linked_list = LinkedList::default();
+        linked_list.push(value.clone());
+
+        let removed = linked_list.remove(0).unwrap();
+        assert_eq!(Rc::strong_count(&value), 2);
+        drop(removed);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn mutations_on_empty_list() {
+        let mut linked_list = LinkedList::<u32>::default();
+
+        assert!(matches!(
+            linked_list.delete(0),
+            Err(LinkedListError::EmptyList)
+        ));
+        assert!(matches!(
+            linked_list.remove(0),
+            Err(LinkedListError::EmptyList)
+        ));
+        assert!(matches!(
+            linked_list.try_pop(),
+            Err(LinkedListError::EmptyList)
+        ));
+        assert_eq!(linked_list.pop(), None);
+
+        // A non-empty list still reports the index.
+        linked_list.push(1);
+        assert!(matches!(
+            linked_list.delete(1),
+            Err(LinkedListError::IndexOutOfRangeError)
+        ));
+        assert!(matches!(
+            linked_list.remove(1),
+            Err(LinkedListError::IndexOutOfRangeError)
+        ));
+    }
+
+    #[test]
+    fn generic_over_queue_trait() {
+        fn fill<Q: Queue<u32>>(queue: &mut Q) {
+            for i in 1..4 {
+                queue.enqueue(i);
+            }
+        }
+
+        let mut linked_list = LinkedList::default();
+        fill(&mut linked_list);
+
+        assert_eq!(linked_list.dequeue(), Some(1));
+        assert_eq!(linked_list.dequeue(), Some(2));
+        assert_eq!(linked_list.dequeue(), Some(3));
+        assert_eq!(linked_list.dequeue(), None);
+    }
+
+    #[test]
+    fn generic_over_collection() {
+        fn sizes<C: Collection>(collection: &C) -> (usize, usize) {
+            (collection.len(), collection.iter().count())
+        }
+
+        let mut linked_list = linked_list![1, 2, 3];
+        assert_eq!(sizes(&linked_list), (3, 3));
+
+        Collection::clear(&mut linked_list);
+        assert_eq!(sizes(&linked_list), (0, 0));
+        assert_eq!(linked_list.head(), None);
+        assert_eq!(linked_list.tail(), None);
+
+        linked_list.push(4);
+        assert_eq!(linked_list.head(), Some(4));
+        assert_eq!(linked_list.tail(), Some(4));
+    }
+
+    #[test]
+    fn map() {
+        let linked_list = linked_list![1, 2, 3];
+        let mapped = linked_list.map(|v| v.to_string());
+
+        assert_eq!(mapped.len(), 3);
+        assert_eq!(mapped.head(), Some("1".to_string()));
+        assert_eq!(mapped.get(1), Some("2".to_string()));
+        assert_eq!(mapped.tail(), Some("3".to_string()));
+
+        // The original list is left untouched.
+        assert_eq!(linked_list.get(1), Some(2));
+
+        let empty = LinkedList::<u32>::default().map(|v| v + 1);
+        assert!(empty.is_empty());
+        assert_eq!(empty.head(), None);
+
+        // The walk stops at the tail.
+        let mapped = linked_past_tail().map(|v| v * 10);
+        assert_eq!(mapped.into_iter().collect::<Vec<_>>(), vec![10, 20]);
+    }
+
+    #[test]
+    fn zip_with() {
+        let linked_list = linked_list![1, 2, 3];
+        let sums = linked_list.zip_with(&linked_list![10, 20], |a, b| a + b);
+
+        assert_eq!(sums.into_iter().collect::<Vec<_>>(), vec![11, 22]);
+        assert_eq!(sums.tail(), Some(22));
+        assert!(linked_list
+            .zip_with(&LinkedList::<u32>::default(), |a, b| a + b)
+            .is_empty());
+    }
+
+    #[test]
+    fn filtered() {
+        let linked_list = linked_list![1, 2, 3, 4, 5];
+
+        let even = linked_list.filtered(|v| v % 2 == 0);
+        assert_eq!(even.len(), 2);
+        assert_eq!(even.head(), Some(2));
+        assert_eq!(even.tail(), Some(4));
+
+        let none = linked_list.filtered(|_| false);
+        assert!(none.is_empty());
+        assert_eq!(none.tail(), None);
+
+        assert_eq!(linked_list.len(), 5);
+
+        // The walk stops at the tail.
+        let all = linked_past_tail().filtered(|_| true);
+        assert_eq!(all.into_iter().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn cursor() {
+        let linked_list = linked_list![1, 2];
+        let mut shared = linked_list.share();
+        shared.push(3);
+
+        // The walk ends at the list's own tail, not the shared list's.
+        let mut cursor = linked_list.cursor_front();
+        let mut values = Vec::new();
+        while let Some(v) = cursor.current() {
+            values.push((cursor.index(), v));
+            cursor.move_next();
+        }
+        assert_eq!(values, vec![(Some(0), 1), (Some(1), 2)]);
+        assert_eq!(cursor.index(), None);
+
+        // Moving past the ghost wraps to the head.
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(1));
+
+        let empty = LinkedList::<u32>::default();
+        let mut cursor = empty.cursor_front();
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+    }
+
+    #[test]
+    fn cursor_mut() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let mut linked_list = linked_list![1, 2, 3];
+        let shared = linked_list.share();
+
+        let sink = seen.clone();
+        linked_list.on_remove(move |v| sink.borrow_mut().push(*v));
+
+        let mut cursor = linked_list.cursor_front_mut();
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert_eq!(cursor.current(), Some(2));
+        assert_eq!(cursor.index(), Some(0));
+
+        cursor.move_next();
+        cursor.insert_after(4);
+        assert_eq!(cursor.remove_current(), Some(3));
+        assert_eq!(cursor.current(), Some(4));
+
+        // Removing the tail moves the cursor to the ghost.
+        assert_eq!(cursor.remove_current(), Some(4));
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.remove_current(), None);
+
+        // At the ghost, values are inserted at the head.
+        cursor.insert_after(0);
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(0));
+
+        assert_eq!(linked_list.len(), 2);
+        assert_eq!(linked_list.tail(), Some(2));
+        linked_list.push(5);
+        assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![0, 2, 5]);
+        assert_eq!(*seen.borrow(), vec![1, 3, 4]);
+
+        // The shared list's nodes were copied, not relinked.
+        assert_eq!(shared.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn cursor_mut_empties_list() {
+        let mut linked_list = linked_list![1];
+
+        let mut cursor = linked_list.cursor_front_mut();
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert!(linked_list.is_empty());
+        assert_eq!(linked_list.head(), None);
+        assert_eq!(linked_list.tail(), None);
+
+        let mut cursor = linked_list.cursor_front_mut();
+        cursor.insert_after(2);
+        assert_eq!(linked_list.head(), Some(2));
+        assert_eq!(linked_list.tail(), Some(2));
+    }
+
+    #[test]
+    fn get_sequential_uses_cursor() {
+        let mut linked_list = LinkedList::<u32>::default();
+        for i in 0..10 {
+            linked_list.push(i);
+        }
+
+        for i in 0..10 {
+            assert_eq!(linked_list.get(i), Some(i as u32));
+            assert_eq!(linked_list.cursor.borrow().as_ref().unwrap().0, i);
+        }
+
+        // Going backwards starts from the head again.
+        assert_eq!(linked_list.get(3), Some(3));
+        assert_eq!(linked_list.get(7), Some(7));
+        assert_eq!(linked_list.get(10), None);
+        assert_eq!(linked_list.cursor.borrow().as_ref().unwrap().0, 7);
+    }
+
+    #[test]
+    fn cursor_is_reset_by_mutations() {
+        let mut linked_list = linked_list![0, 1, 2, 3, 4];
+
+        assert_eq!(linked_list.get(2), Some(2));
+        assert_eq!(linked_list.pop(), Some(0));
+        assert!(linked_list.cursor.borrow().is_none());
+        assert_eq!(linked_list.get(2), Some(3));
+
+        // Pushing doesn't move any existing node, the cursor stays valid.
+        linked_list.push(5);
+        assert_eq!(linked_list.get(4), Some(5));
+
+        linked_list.delete(1).unwrap();
+        assert_eq!(linked_list.get(2), Some(4));
+        assert_eq!(linked_list.get(3), Some(5));
+        assert_eq!(linked_list.get(4), None);
+
+        linked_list.clear();
+        assert_eq!(linked_list.get(0), None);
+        linked_list.push(6);
+        assert_eq!(linked_list.get(0), Some(6));
+    }
+
+    #[cfg(feature = "checked-iter")]
+    #[test]
+    #[should_panic(expected = "LinkedList was modified while it was being iterated")]
+    fn modified_while_iterating() {
+        let linked_list = linked_list![1, 2, 3];
+        let mut shared = linked_list.share();
+
+        let mut iter = linked_list.into_iter();
+        assert_eq!(iter.next(), Some(1));
+
+        shared.push(4);
+        iter.next();
+    }
+
+    #[cfg(feature = "checked-iter")]
+    #[test]
+    fn iterating_after_modification() {
+        let mut linked_list = linked_list![1, 2, 3];
+        linked_list.push(4);
+
+        // A new iterator sees the latest version.
+        let values: Vec<u32> = linked_list.into_iter().collect();
+        assert_eq!(values, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn pop_from_cloned_list() {
+        let mut linked_list = linked_list!["1".to_string(), "2".to_string()];
+        let mut shared = linked_list.share();
+
+        // The nodes are shared, so the values are cloned out of them.
+        assert_eq!(linked_list.pop(), Some("1".to_string()));
+        assert_eq!(linked_list.head(), Some("2".to_string()));
+
+        // Popping didn't unlink the shared list's nodes.
+        assert_eq!(shared.len(), 2);
+        assert_eq!(shared.get(1), Some("2".to_string()));
+        assert_eq!(shared.pop(), Some("1".to_string()));
+        assert_eq!(shared.pop(), Some("2".to_string()));
+        assert_eq!(shared.pop(), None);
+
+        // The last node is no longer shared.
+        assert_eq!(linked_list.pop(), Some("2".to_string()));
+        assert_eq!(linked_list.pop(), None);
+        assert_eq!(linked_list.tail(), None);
+    }
+
+    #[test]
+    fn clear_cloned_list() {
+        let mut linked_list = linked_list![1, 2, 3];
+        let shared = linked_list.share();
+
+        linked_list.clear();
+        assert!(linked_list.is_empty());
+        assert_eq!(shared.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn deep_clone() {
+        let mut linked_list = LinkedList::<u32>::default();
+        for i in 1..4 {
+            linked_list.push(i);
+        }
+        let removed = Rc::new(RefCell::new(Vec::new()));
+        let sink = removed.clone();
+        linked_list.on_remove(move |v| sink.borrow_mut().push(*v));
+
+        let mut copy = linked_list.clone();
+        assert_eq!(copy, linked_list);
+
+        // Relinking, changing and emptying the copy leaves the original be.
+        copy.iter_mut().for_each(|v| *v *= 10);
+        copy.push(40);
+        copy.insert(0, 0).unwrap();
+        assert_eq!(copy.pop_back(), Some(40));
+        assert_eq!(copy.remove(1).unwrap(), 10);
+        assert_eq!(copy.iter().collect::<Vec<_>>(), vec![0, 20, 30]);
+        copy.clear();
+
+        assert_eq!(linked_list.iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(linked_list.tail(), Some(3));
+        assert_eq!(linked_list.len(), 3);
+
+        // The copy has no observers of its own, and the original's didn't see
+        // the copy's removals.
+        assert!(removed.borrow().is_empty());
+
+        // The same goes the other way round.
+        let copy = linked_list.deep_clone();
+        linked_list.iter_mut().for_each(|v| *v = 0);
+        linked_list.pop();
+        assert_eq!(copy.iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn delete_keeps_tail() {
+        let mut linked_list = linked_list![1, 2, 3, 4];
+
+        linked_list.delete(1).unwrap();
+        assert_eq!(linked_list.tail(), Some(4));
+
+        linked_list.delete(0).unwrap();
+        assert_eq!(linked_list.tail(), Some(4));
+
+        linked_list.push(5);
+        assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn insert_at() {
+        let mut linked_list = LinkedList::default();
+
+        linked_list.insert_at(0, 2);
+        linked_list.insert_at(0, 1);
+        linked_list.insert_at(2, 4);
+        linked_list.insert_at(2, 3);
+
+        assert_eq!(linked_list.len(), 4);
+        assert_eq!(linked_list.head(), Some(1));
+        assert_eq!(linked_list.tail(), Some(4));
+        assert_eq!(
+            linked_list.into_iter().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn observers() {
+        use std::cell::RefCell;
+
+        let inserted = Rc::new(RefCell::new(Vec::new()));
+        let removed = Rc::new(RefCell::new(Vec::new()));
+
+        let mut linked_list = LinkedList::<u32>::default();
+        let sink = inserted.clone();
+        linked_list.on_insert(move |v| sink.borrow_mut().push(*v));
+        let sink = removed.clone();
+        linked_list.on_remove(move |v| sink.borrow_mut().push(*v));
+
+        for i in 1..6 {
+            linked_list.push(i);
+        }
+        linked_list.insert_at(0, 0);
+        assert_eq!(*inserted.borrow(), vec![1, 2, 3, 4, 5, 0]);
+
+        linked_list.pop();
+        linked_list.delete(0).unwrap();
+        linked_list.delete(1).unwrap();
+        assert_eq!(*removed.borrow(), vec![0, 1, 3]);
+
+        linked_list.clear();
+        assert_eq!(*removed.borrow(), vec![0, 1, 3, 2, 4, 5]);
+
+        // Derived lists don't inherit the callbacks.
+        let mapped = linked_list![1, 2].map(|v| v * 2);
+        drop(mapped);
+        assert_eq!(inserted.borrow().len(), 6);
+    }
+
+    #[test]
+    fn transaction_commits() {
+        let mut linked_list = linked_list![1, 2, 3];
+
+        let removed = linked_list.transaction(|tx| {
+            tx.push(4);
+            tx.insert(0, 0)?;
+            assert_eq!(tx.pop(), Some(0));
+            tx.remove(1)
+        });
+
+        assert_eq!(removed.unwrap(), 2);
+        assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![1, 3, 4]);
+        assert_eq!(linked_list.tail(), Some(4));
+    }
+
+    #[test]
+    fn transaction_rolls_back() {
+        #[derive(Debug, PartialEq)]
+        struct Invalid;
+
+        let mut linked_list = linked_list![1, 2, 3];
+
+        let result: std::result::Result<(), Invalid> = linked_list.transaction(|tx| {
+            tx.push(4);
+            tx.remove(0).unwrap();
+            tx.insert(1, 5).unwrap();
+            tx.pop();
+            assert_eq!(tx.list().into_iter().collect::<Vec<_>>(), vec![5, 3, 4]);
+
+            Err(Invalid)
+        });
+
+        assert_eq!(result, Err(Invalid));
+        assert_eq!(linked_list.len(), 3);
+        assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(linked_list.tail(), Some(3));
+
+        // A failing operation inside the transaction rolls it back too.
+        let result = linked_list.transaction(|tx| {
+            tx.pop();
+            tx.remove(10)
+        });
+        assert!(result.is_err());
+        assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn transaction_notifies_on_commit() {
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let mut linked_list = linked_list![1, 2, 3];
+
+        let sink = events.clone();
+        linked_list.on_insert(move |v| sink.borrow_mut().push(('+', *v)));
+        let sink = events.clone();
+        linked_list.on_remove(move |v| sink.borrow_mut().push(('-', *v)));
+
+        let result: Result<()> = linked_list.transaction(|tx| {
+            tx.push(4);
+            tx.remove(0)?;
+            assert!(events.borrow().is_empty());
+            Ok(())
+        });
+        assert!(result.is_ok());
+        assert_eq!(*events.borrow(), vec![('+', 4), ('-', 1)]);
+
+        // A rollback is never seen, and the observers are released after it.
+        events.borrow_mut().clear();
+        let result = linked_list.transaction(|tx| {
+            tx.pop();
+            tx.remove(10)
+        });
+        assert!(result.is_err());
+        assert!(events.borrow().is_empty());
+
+        linked_list.push(5);
+        assert_eq!(*events.borrow(), vec![('+', 5)]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn convert_to_and_from_doubly() {
+        let singly = linked_list![1, 2, 3];
+
+        let mut doubly = doubly_linked_list::LinkedList::from(singly);
+        assert_eq!(doubly.len(), 3);
+        doubly.push_front(0);
+
+        let singly = LinkedList::from(doubly);
+        assert_eq!(singly.len(), 4);
+        assert_eq!(singly.tail(), Some(3));
+        assert_eq!(singly.into_iter().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn convert_moves_values() {
+        let value = Rc::new(1);
+        let mut singly = LinkedList::default();
+        singly.push(value.clone());
+        singly.push(value.clone());
+
+        let doubly = doubly_linked_list::LinkedList::from(singly);
+        assert_eq!(Rc::strong_count(&value), 3);
+
+        let singly = LinkedList::from(doubly);
+        assert_eq!(Rc::strong_count(&value), 3);
+
+        drop(singly);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn convert_to_and_from_vec() {
+        let linked_list = LinkedList::from(vec![1, 2, 3]);
+        assert_eq!(linked_list.len(), 3);
+        assert_eq!(linked_list.tail(), Some(3));
+
+        let shared = linked_list.share();
+        let values: Vec<u32> = linked_list.into();
+        assert_eq!(values, vec![1, 2, 3]);
+        assert_eq!(shared.len(), 3);
+
+        let linked_list = LinkedList::from(&values[1..]);
+        assert_eq!(Vec::from(linked_list), vec![2, 3]);
+        assert!(Vec::from(LinkedList::<u32>::from(Vec::new())).is_empty());
+
+        let value = Rc::new(1);
+        let linked_list = LinkedList::from(vec![value.clone(), value.clone()]);
+        let values = Vec::from(linked_list);
+        assert_eq!(Rc::strong_count(&value), 3);
+        drop(values);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn first_last_and_clamped() {
+        let mut list = LinkedList::<u32>::default();
+        assert_eq!(list.first(), None);
+        assert_eq!(list.last(), None);
+        assert_eq!(list.get_clamped(0), None);
+
+        list.push(1);
+        list.push(2);
+        list.push(3);
+
+        assert_eq!(list.first(), Some(1));
+        assert_eq!(list.last(), Some(3));
+        assert_eq!(list.get_clamped(1), Some(2));
+        assert_eq!(list.get_clamped(3), Some(3));
+        assert_eq!(list.get_clamped(usize::MAX), Some(3));
+    }
+
+    #[test]
+    fn insert_keeps_shared_copy() {
+        let mut linked_list = linked_list![1, 3];
+        let shared = linked_list.share();
+
+        linked_list.insert(1, 2).unwrap();
+        assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        assert_eq!(shared.into_iter().len(), 2);
+        assert_eq!(shared.into_iter().collect::<Vec<_>>(), vec![1, 3]);
+    }
+
+    #[test]
+    fn insert() {
+        let mut linked_list = linked_list![1, 3];
+
+        linked_list.insert(1, 2).unwrap();
+        linked_list.insert(3, 4).unwrap();
+        linked_list.insert(0, 0).unwrap();
+        assert!(linked_list.insert(6, 6).is_err());
+
+        assert_eq!(linked_list.len(), 5);
+        assert_eq!(linked_list.tail(), Some(4));
+        assert_eq!(
+            linked_list.into_iter().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn swap() {
+        let mut linked_list = linked_list![1, 2, 3, 4];
+        let shared = linked_list.share();
+
+        linked_list.swap(3, 0).unwrap();
+        linked_list.swap(1, 2).unwrap();
+        linked_list.swap(2, 2).unwrap();
+        assert_eq!(linked_list.head(), Some(4));
+        assert_eq!(linked_list.tail(), Some(1));
+        assert!(linked_list.swap(1, 4).is_err());
+
+        assert_eq!(
+            linked_list.into_iter().collect::<Vec<_>>(),
+            vec![4, 3, 2, 1]
+        );
+        assert_eq!(shared.into_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert!(LinkedList::<u32>::default().swap(0, 0).is_err());
+    }
+
+    #[test]
+    fn contains() {
+        let mut linked_list = linked_list![1, 2, 3];
+
+        assert!(linked_list.contains(&1));
+        assert!(linked_list.contains(&3));
+        assert!(!linked_list.contains(&4));
+
+        linked_list.clear();
+        assert!(!linked_list.contains(&1));
+
+        // The walk stops at the tail.
+        let linked_list = linked_past_tail();
+        assert!(linked_list.contains(&2));
+        assert!(!linked_list.contains(&3));
+    }
+
+    #[test]
+    fn find_and_position() {
+        let linked_list = linked_list![3, 8, 5, 10];
+
+        assert_eq!(linked_list.find(|v| *v > 4), Some(8));
+        assert_eq!(linked_list.position(|v| *v > 4), Some(1));
+        assert_eq!(linked_list.position(|v| *v == 10), Some(3));
+        assert_eq!(linked_list.find(|v| *v == 0), None);
+        assert_eq!(linked_list.position(|v| *v == 0), None);
+
+        let empty = LinkedList::<u32>::default();
+        assert_eq!(empty.find(|_| true), None);
+        assert_eq!(empty.position(|_| true), None);
+
+        // The walk stops at the tail.
+        let linked_list = linked_past_tail();
+        assert_eq!(linked_list.find(|v| *v > 2), None);
+        assert_eq!(linked_list.position(|v| *v > 2), None);
+    }
+
+    #[test]
+    fn sort() {
+        let mut linked_list = linked_list![5, 3, 9, 1, 3, 7, 2];
+        linked_list.sort();
+
+        assert_eq!(
+            linked_list.into_iter().collect::<Vec<_>>(),
+            vec![1, 2, 3, 3, 5, 7, 9]
+        );
+        assert_eq!(linked_list.head(), Some(1));
+        assert_eq!(linked_list.tail(), Some(9));
+
+        // The tail is relinked, so pushing still appends.
+        linked_list.push(10);
+        assert_eq!(linked_list.get(7), Some(10));
+        assert_eq!(linked_list.len(), 8);
+    }
+
+    #[test]
+    fn sort_keeps_shared_copy() {
+        let mut linked_list = linked_list![4, 1, 3, 2];
+        let shared = linked_list.share();
+
+        linked_list.sort();
+        assert_eq!(
+            linked_list.into_iter().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(linked_list.tail(), Some(4));
+
+        assert_eq!(shared.into_iter().len(), 4);
+        assert_eq!(shared.into_iter().collect::<Vec<_>>(), vec![4, 1, 3, 2]);
+        assert_eq!(shared.tail(), Some(2));
+    }
+
+    #[test]
+    fn sort_is_stable() {
+        let mut linked_list = linked_list![(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')];
+        linked_list.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(
+            linked_list.into_iter().collect::<Vec<_>>(),
+            vec![(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]
+        );
+    }
+
+    #[test]
+    fn sort_matches_std() {
+        for len in 0..40u32 {
+            let values: Vec<u32> = (0..len).map(|i| (i * 7919) % 13).collect();
+            let mut linked_list = LinkedList::default();
+            for v in &values {
+                linked_list.push(*v);
+            }
+
+            let mut expected = values.clone();
+            expected.sort();
+            linked_list.sort();
+
+            assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), expected);
+            assert_eq!(linked_list.tail(), expected.last().copied());
+        }
+    }
+
+    #[test]
+    fn interleave() {
+        let values = |list: &LinkedList<u32>| list.into_iter().collect::<Vec<_>>();
+
+        // The other List is longer, shorter or as long.
+        let mut linked_list = linked_list![1, 3];
+        linked_list.interleave(linked_list![2, 4, 5, 6]);
+        assert_eq!(values(&linked_list), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(linked_list.tail(), Some(6));
+
+        let mut linked_list = linked_list![1, 3, 5, 6];
+        linked_list.interleave(linked_list![2, 4]);
+        assert_eq!(values(&linked_list), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(linked_list.tail(), Some(6));
+
+        let mut linked_list = linked_list![1, 3];
+        let shared = linked_list.share();
+        linked_list.interleave(linked_list![2, 4]);
+        assert_eq!(values(&linked_list), vec![1, 2, 3, 4]);
+        assert_eq!(linked_list.len(), 4);
+        assert_eq!(values(&shared), vec![1, 3]);
+
+        linked_list.push(5);
+        assert_eq!(linked_list.get(4), Some(5));
+
+        let mut empty = LinkedList::default();
+        empty.interleave(linked_list![1, 2]);
+        empty.interleave(LinkedList::default());
+        assert_eq!(values(&empty), vec![1, 2]);
+        assert_eq!(empty.tail(), Some(2));
+    }
+
+    #[test]
+    fn merge_keeps_shared_copies() {
+        let mut linked_list = linked_list![1, 3];
+        let other = linked_list![2, 4];
+        let shared = linked_list.share();
+        let shared_other = other.share();
+
+        linked_list.merge(other);
+        assert_eq!(
+            linked_list.into_iter().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4]
+        );
+
+        assert_eq!(shared.into_iter().collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(shared_other.into_iter().collect::<Vec<_>>(), vec![2, 4]);
+        assert_eq!(shared_other.into_iter().len(), 2);
+    }
+
+    #[test]
+    fn merge() {
+        let mut linked_list = linked_list![1, 4, 4, 9];
+        linked_list.merge(linked_list![0, 4, 5, 10, 11]);
+
+        assert_eq!(
+            linked_list.into_iter().collect::<Vec<_>>(),
+            vec![0, 1, 4, 4, 4, 5, 9, 10, 11]
+        );
+        assert_eq!(linked_list.len(), 9);
+        assert_eq!(linked_list.tail(), Some(11));
+
+        linked_list.push(12);
+        assert_eq!(linked_list.get(9), Some(12));
+    }
+
+    #[test]
+    fn merge_keeps_own_values_first() {
+        // Ordered by the number only, the letter tells the lists apart.
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        struct Keyed(u32, char);
+
+        impl PartialOrd for Keyed {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for Keyed {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let mut linked_list = linked_list![Keyed(1, 'a'), Keyed(2, 'a')];
+        linked_list.merge(linked_list![Keyed(1, 'b'), Keyed(2, 'b')]);
+
+        assert_eq!(
+            linked_list.into_iter().collect::<Vec<_>>(),
+            vec![Keyed(1, 'a'), Keyed(1, 'b'), Keyed(2, 'a'), Keyed(2, 'b')]
+        );
+        assert_eq!(linked_list.tail(), Some(Keyed(2, 'b')));
+    }
+
+    #[test]
+    fn merge_into_empty() {
+        let mut linked_list = LinkedList::default();
+        linked_list.merge(linked_list![1, 2]);
+        assert_eq!(linked_list.head(), Some(1));
+        assert_eq!(linked_list.tail(), Some(2));
+
+        linked_list.merge(LinkedList::default());
+        assert_eq!(linked_list.len(), 2);
+    }
+
+    #[test]
+    fn merge_notifies_inserts() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let mut linked_list = linked_list![1, 3];
+
+        let sink = seen.clone();
+        linked_list.on_insert(move |v| sink.borrow_mut().push(*v));
+        linked_list.merge(linked_list![2, 4]);
+
+        assert_eq!(*seen.borrow(), vec![2, 4]);
+    }
+
+    #[test]
+    fn shards() {
+        let linked_list = linked_list![0, 1, 2, 3, 4, 5, 6];
+        let shards = linked_list.shards(3);
+
+        let values: Vec<Vec<u32>> = shards.iter().map(|s| s.into_iter().collect()).collect();
+        assert_eq!(values, vec![vec![0, 1, 2], vec![3, 4], vec![5, 6]]);
 
-        if self.size > 1 {
-            self.tail = previous;
+        for shard in &shards {
+            assert_eq!(shard.len() as usize, shard.into_iter().count());
         }
+        assert_eq!(shards[1].head(), Some(3));
+        assert_eq!(shards[1].tail(), Some(4));
 
-        Ok(())
+        // The shards are independent, pushing to one doesn't reach the next.
+        let mut first = shards.into_iter().next().unwrap();
+        first.push(9);
+        assert_eq!(first.into_iter().collect::<Vec<_>>(), vec![0, 1, 2, 9]);
     }
-}
-
-#[allow(unused_macros)]
-macro_rules! linked_list {
-    // $ similar to bash script exec
-    // execution a variadic number of parameters
-    // each expr is seperated by "," for "*" amount of times
-    ($($x: expr),*) => {{
-        let mut linked_list = LinkedList::default();
 
-        // Push each item to the linked list, according to the number of inputs
-        $(linked_list.push($x);)*
-        linked_list
-    }};
-}
+    #[test]
+    fn more_shards_than_values() {
+        let shards = linked_list![1, 2].shards(4);
 
-#[cfg(test)]
-mod test {
-    use super::*;
+        assert_eq!(shards.len(), 4);
+        assert_eq!(shards[0].head(), Some(1));
+        assert_eq!(shards[1].head(), Some(2));
+        assert!(shards[2].is_empty());
+        assert_eq!(shards[3].tail(), None);
+    }
 
     #[test]
-    fn init_list() {
-        let linked_list = LinkedList::<String>::default();
-        assert_eq!(linked_list.size, 0);
+    #[should_panic(expected = "0 shards")]
+    fn zero_shards() {
+        linked_list![1].shards(0);
     }
 
     #[test]
-    fn push_one_node() {
-        let mut linked_list = LinkedList::<String>::default();
+    fn split_at() {
+        let mut linked_list = linked_list![1, 2, 3, 4];
+        let suffix = linked_list.split_at(2).unwrap();
 
-        linked_list.push("1".to_string());
-        assert_eq!(linked_list.size, 1);
+        assert_eq!(linked_list.len(), 2);
+        assert_eq!(linked_list.head(), Some(1));
+        assert_eq!(linked_list.tail(), Some(2));
+        assert_eq!(suffix.len(), 2);
+        assert_eq!(suffix.head(), Some(3));
+        assert_eq!(suffix.tail(), Some(4));
+
+        linked_list.push(5);
+        assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![1, 2, 5]);
+        assert_eq!(suffix.into_iter().collect::<Vec<_>>(), vec![3, 4]);
     }
 
     #[test]
-    fn push_two_nodes() {
-        let mut linked_list = LinkedList::<String>::default();
+    fn split_at_keeps_shared_copy() {
+        let mut linked_list = linked_list![1, 2, 3, 4];
+        let shared = linked_list.share();
 
-        for i in 1..3 {
-            linked_list.push(i.to_string());
-        }
+        let suffix = linked_list.split_at(2).unwrap();
+        assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(suffix.into_iter().collect::<Vec<_>>(), vec![3, 4]);
 
-        assert_eq!(*&linked_list.size, 2);
-        assert_eq!(linked_list.head(), Some("1".to_string()));
-        assert_eq!(linked_list.tail(), Some("2".to_string()));
+        let front = linked_list.take_front(1);
+        assert_eq!(front.into_iter().collect::<Vec<_>>(), vec![1]);
+
+        assert_eq!(shared.into_iter().len(), 4);
+        assert_eq!(shared.into_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
     }
 
     #[test]
-    fn push_three_nodes() {
-        let mut linked_list = LinkedList::<String>::default();
+    fn split_at_ends() {
+        let mut linked_list = linked_list![1, 2];
 
-        for i in 1..4 {
-            linked_list.push(i.to_string());
-        }
+        let suffix = linked_list.split_at(2).unwrap();
+        assert!(suffix.is_empty());
+        assert_eq!(linked_list.len(), 2);
 
-        assert_eq!(*&linked_list.size, 3);
-        assert_eq!(linked_list.tail(), Some("3".to_string()));
+        let suffix = linked_list.split_at(0).unwrap();
+        assert!(linked_list.is_empty());
+        assert_eq!(linked_list.head(), None);
+        assert_eq!(suffix.tail(), Some(2));
+
+        assert!(linked_list.split_at(1).is_err());
     }
 
     #[test]
-    fn access_none_head() {
-        let linked_list = LinkedList::<String>::default();
-        assert_eq!(linked_list.head(), None);
+    fn split_at_notifies_removes() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let mut linked_list = linked_list![1, 2, 3];
+
+        let sink = seen.clone();
+        linked_list.on_remove(move |v| sink.borrow_mut().push(*v));
+        linked_list.split_at(1).unwrap();
+
+        assert_eq!(*seen.borrow(), vec![2, 3]);
     }
 
     #[test]
-    fn access_none_tail() {
-        let linked_list = LinkedList::<String>::default();
+    fn take_front() {
+        let mut linked_list = linked_list![1, 2, 3, 4];
+
+        let mut front = linked_list.take_front(3);
+        assert_eq!(front.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(linked_list.len(), 1);
+        assert_eq!(linked_list.head(), Some(4));
+
+        // Both lists keep working after the move.
+        linked_list.push(5);
+        front.push(6);
+        assert_eq!(front.tail(), Some(6));
+        assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![4, 5]);
+
+        assert!(linked_list.take_front(0).is_empty());
+        assert_eq!(linked_list.take_front(10).len(), 2);
+        assert!(linked_list.is_empty());
         assert_eq!(linked_list.tail(), None);
     }
 
     #[test]
-    fn head_and_tail() {
-        let mut linked_list = LinkedList::<String>::default();
+    fn skip_front_notifies_removes() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let mut linked_list = linked_list![1, 2, 3];
 
-        linked_list.push(String::from("hello"));
-        assert_eq!(linked_list.head(), Some("hello".to_string()));
-        assert_eq!(linked_list.tail(), Some("hello".to_string()));
-        assert_eq!(linked_list.size, 1);
+        let sink = seen.clone();
+        linked_list.on_remove(move |v| sink.borrow_mut().push(*v));
+        linked_list.skip_front(2);
+        linked_list.skip_front(5);
 
-        linked_list.push("world".to_string());
-        assert_eq!(linked_list.tail(), Some("world".to_string()));
-        assert_eq!(linked_list.size, 2);
+        assert_eq!(*seen.borrow(), vec![1, 2, 3]);
+        assert!(linked_list.is_empty());
     }
 
     #[test]
-    fn get_at_index() {
-        let mut linked_list = LinkedList::<String>::default();
+    fn append() {
+        let mut linked_list = linked_list![1, 2];
+        let mut other = linked_list![3, 4];
 
-        for i in 1..5 {
-            linked_list.push(i.to_string());
-        }
-        assert_eq!(linked_list.head(), Some("1".to_string()));
-        assert_eq!(linked_list.tail(), Some("4".to_string()));
+        assert_eq!(linked_list.get(1), Some(2));
+        linked_list.append(&mut other);
 
-        assert_eq!(linked_list.get(0), Some("1".to_string()));
-        assert_eq!(linked_list.get(1), Some("2".to_string()));
-        assert_eq!(linked_list.get(2), Some("3".to_string()));
-        assert_eq!(linked_list.get(3), Some("4".to_string()));
-        assert_eq!(linked_list.get(4), None);
-        assert_eq!(linked_list.get(100), None);
+        assert_eq!(linked_list.len(), 4);
+        assert_eq!(linked_list.tail(), Some(4));
+        assert_eq!(linked_list.get(3), Some(4));
+        assert!(other.is_empty());
+        assert_eq!(other.head(), None);
+        assert_eq!(other.tail(), None);
+
+        // Both lists keep working after the move.
+        linked_list.push(5);
+        other.push(6);
+        assert_eq!(
+            linked_list.into_iter().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+        assert_eq!(other.into_iter().collect::<Vec<_>>(), vec![6]);
     }
 
     #[test]
-    fn pop() {
-        let mut linked_list = LinkedList::<String>::default();
+    fn append_empty_lists() {
+        let mut linked_list = LinkedList::default();
+        let mut other = linked_list![1];
 
-        for i in 1..5 {
-            linked_list.push(i.to_string());
+        linked_list.append(&mut other);
+        assert_eq!(linked_list.head(), Some(1));
+        assert_eq!(linked_list.tail(), Some(1));
+
+        linked_list.append(&mut other);
+        assert_eq!(linked_list.len(), 1);
+    }
+
+    #[test]
+    fn append_rejoins_shards() {
+        let mut shards = linked_list![1, 2, 3, 4, 5].shards(3).into_iter();
+        let mut linked_list = shards.next().unwrap();
+        for mut shard in shards {
+            linked_list.append(&mut shard);
         }
 
-        assert_eq!(linked_list.head(), Some("1".to_string()));
-        assert_eq!(linked_list.pop(), Some("1".to_string()));
-        assert_eq!(linked_list.head(), Some("2".to_string()));
+        assert_eq!(
+            linked_list.into_iter().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
     }
 
     #[test]
-    fn iterator() {
-        let mut linked_list = LinkedList::<String>::default();
+    fn append_keeps_shared_copy() {
+        let mut linked_list = linked_list![1];
+        let mut other = linked_list![2];
+        let mut copy = other.share();
+        linked_list.append(&mut other);
+        linked_list.push(3);
+        copy.push(9);
 
-        for i in 1..5 {
-            linked_list.push(i.to_string());
-        }
+        assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(copy.into_iter().collect::<Vec<_>>(), vec![2, 9]);
 
-        for i in linked_list.into_iter() {
-            assert_eq!(i, format!("{}", i));
-        }
+        // The tail of the List shared with a copy.
+        let mut linked_list = linked_list![1];
+        let mut copy = linked_list.share();
+        let mut other = linked_list![2];
+        linked_list.append(&mut other);
+        copy.push(9);
 
-        // Assert the iterator did not consume the linked_list.
-        assert_eq!(linked_list.get(2), Some("3".to_string()));
+        assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(copy.into_iter().collect::<Vec<_>>(), vec![1, 9]);
     }
 
     #[test]
-    fn iterator_function_calls() {
-        let linked_list = linked_list![1, 2, 3, 4, 5];
-        let result: Vec<u32> = linked_list.into_iter().map(|v| (v * 2) as u32).collect();
+    fn append_notifies_observers() {
+        let inserted = Rc::new(RefCell::new(Vec::new()));
+        let removed = Rc::new(RefCell::new(Vec::new()));
+        let mut linked_list = linked_list![1];
+        let mut other = linked_list![2, 3];
 
-        assert_eq!(result[0], 2);
-        assert_eq!(result[1], 4);
+        let sink = inserted.clone();
+        linked_list.on_insert(move |v| sink.borrow_mut().push(*v));
+        let sink = removed.clone();
+        other.on_remove(move |v| sink.borrow_mut().push(*v));
+        linked_list.append(&mut other);
+
+        assert_eq!(*inserted.borrow(), vec![2, 3]);
+        assert_eq!(*removed.borrow(), vec![2, 3]);
     }
 
     #[test]
-    fn macro_linked_list() {
-        let linked_list = linked_list!["1".to_string(), "2".to_string()];
-        assert_eq!(linked_list.tail(), Some("2".to_string()));
+    // The cursor's RefCell makes clippy think the keys are mutable, but it
+    // isn't part of the hash.
+    #[allow(clippy::mutable_key_type)]
+    fn compare_and_hash() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashMap;
+
+        fn hash(list: &LinkedList<u32>) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            list.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let list = |values: &[u32]| {
+            let mut list = LinkedList::default();
+            for v in values {
+                list.push(*v);
+            }
+            list
+        };
+
+        assert!(list(&[1, 2, 3]) == list(&[1, 2, 3]));
+        assert!(list(&[1, 2, 3]) != list(&[1, 2]));
+        assert!(list(&[1, 2, 3]) != list(&[1, 2, 4]));
+        assert!(list(&[]) == LinkedList::default());
+        assert_eq!(hash(&list(&[1, 2, 3])), hash(&list(&[1, 2, 3])));
+
+        assert!(list(&[1, 2]) < list(&[1, 2, 3]));
+        assert!(list(&[1, 3]) > list(&[1, 2, 3]));
+        assert!(list(&[]) < list(&[0]));
+        assert_eq!(list(&[2, 1]).cmp(&list(&[2, 1])), Ordering::Equal);
+
+        let mut counts = HashMap::new();
+        *counts.entry(list(&[1, 2])).or_insert(0) += 1;
+        *counts.entry(list(&[1, 2])).or_insert(0) += 1;
+        *counts.entry(list(&[2, 1])).or_insert(0) += 1;
+        assert_eq!(counts[&list(&[1, 2])], 2);
+        assert_eq!(counts.len(), 2);
     }
 
     #[test]
-    fn delete_item() {
-        let mut linked_list = linked_list![
-            "1".to_string(),
-            "2".to_string(),
-            "3".to_string(),
-            "4".to_string(),
-            "5".to_string()
-        ];
-        assert_eq!(linked_list.len(), 5);
-        assert_eq!(linked_list.get(2), Some("3".to_string()));
+    fn display() {
+        let mut list = LinkedList::default();
+        assert_eq!(format!("{}", list), "None");
 
-        linked_list.delete(2).unwrap();
-        assert_eq!(linked_list.len(), 4);
-        assert_eq!(linked_list.get(0), Some("1".to_string()));
-        assert_eq!(linked_list.get(1), Some("2".to_string()));
-        assert_eq!(linked_list.get(2), Some("4".to_string()));
-        assert_eq!(linked_list.get(3), Some("5".to_string()));
+        list.push("a".to_string());
+        assert_eq!(format!("{}", list), "a -> None");
+
+        list.push("b".to_string());
+        assert_eq!(format!("{}", list), "a -> b -> None");
     }
 
     #[test]
-    fn deleting_head() {
-        let mut linked_list = linked_list!["1".to_string()];
-        assert_eq!(linked_list.len(), 1);
+    fn debug() {
+        let mut list = LinkedList::default();
+        assert_eq!(
+            format!("{:?}", list),
+            "LinkedList { size: 0, head: None, tail: None, nodes: [] }"
+        );
 
-        linked_list.delete(0).unwrap();
-        assert_eq!(linked_list.len(), 0);
-        assert_eq!(linked_list.head(), None);
+        list.push("a");
+        list.push("b");
+        list.push("c");
+        assert_eq!(
+            format!("{:?}", list),
+            r#"LinkedList { size: 3, head: Some("a"), tail: Some("c"), nodes: ["a", "b", "c"] }"#
+        );
+        assert!(format!("{:#?}", list).contains("nodes: [\n"));
+    }
 
-        linked_list.push("2".to_string());
-        linked_list.push("3".to_string());
-        assert_eq!(linked_list.len(), 2);
-        assert_eq!(linked_list.head(), Some("2".to_string()));
-        assert_eq!(linked_list.tail(), Some("3".to_string()));
-        assert_eq!(linked_list.get(0), Some("2".to_string()));
-        assert_eq!(linked_list.get(1), Some("3".to_string()));
+    #[test]
+    fn values_without_debug() {
+        #[derive(Clone, PartialEq)]
+        struct Opaque(u32);
 
-        linked_list.delete(0).unwrap();
-        assert_eq!(linked_list.len(), 1);
-        assert_eq!(linked_list.head(), Some("3".to_string()));
-        assert_eq!(linked_list.tail(), Some("3".to_string()));
-        assert_eq!(linked_list.get(0), Some("3".to_string()));
-        assert_eq!(linked_list.get(1), None);
+        let mut linked_list = LinkedList::default();
+        linked_list.push(Opaque(1));
+        linked_list.push(Opaque(2));
+        linked_list.insert(1, Opaque(3)).unwrap();
+
+        assert!(linked_list.contains(&Opaque(3)));
+        assert!(linked_list.pop() == Some(Opaque(1)));
+        assert!(linked_list.pop_back() == Some(Opaque(2)));
+        assert!(linked_list.iter().eq(vec![Opaque(3)]));
+        assert!(linked_list.clone() == linked_list);
     }
 
     #[test]
-    fn deleting_tail() {
-        let mut linked_list = linked_list!["1".to_string(), "2".to_string()];
-        assert_eq!(linked_list.len(), 2);
-        assert_eq!(linked_list.tail(), Some("2".to_string()));
-        assert_eq!(linked_list.get(1), Some("2".to_string()));
+    fn retain_keeps_shared_copy() {
+        let mut linked_list = linked_list![1, 2, 3, 4];
+        let shared = linked_list.share();
 
-        linked_list.delete(1).unwrap();
-        assert_eq!(linked_list.len(), 1);
-        assert_eq!(linked_list.get(0), Some("1".to_string()));
-        assert_eq!(linked_list.get(1), None);
-        assert_eq!(linked_list.tail(), Some("1".to_string()));
+        linked_list.retain(|v| v % 2 == 0);
+        assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![2, 4]);
 
-        linked_list.delete(0).unwrap();
-        assert_eq!(linked_list.len(), 0);
-        assert_eq!(linked_list.get(0), None);
-        assert_eq!(linked_list.head(), None);
-        assert_eq!(linked_list.tail(), None);
+        assert_eq!(shared.into_iter().len(), 4);
+        assert_eq!(shared.into_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    }
 
+    #[test]
+    fn retain() {
+        let removed = Rc::new(RefCell::new(Vec::new()));
+        let mut list = LinkedList::default();
+        let sink = removed.clone();
+        list.on_remove(move |v| sink.borrow_mut().push(*v));
         for i in 0..10 {
-            linked_list.push(i.to_string());
+            list.push(i);
         }
-        assert_eq!(linked_list.len(), 10);
-        assert_eq!(linked_list.head(), Some("0".to_string()));
-        assert_eq!(linked_list.tail(), Some("9".to_string()));
 
-        linked_list.delete(9).unwrap();
-        assert_eq!(linked_list.len(), 9);
-        assert_eq!(linked_list.tail(), Some("8".to_string()));
+        // Removes the head, the tail and a run in the middle.
+        list.retain(|v| *v != 0 && *v != 9 && !(4..7).contains(v));
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.head(), Some(1));
+        assert_eq!(list.tail(), Some(8));
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![1, 2, 3, 7, 8]);
+        assert_eq!(*removed.borrow(), vec![0, 4, 5, 6, 9]);
 
-        linked_list.delete(8).unwrap();
-        assert_eq!(linked_list.len(), 8);
-        assert_eq!(linked_list.tail(), Some("7".to_string()));
+        // The tail still links correctly.
+        list.push(10);
+        assert_eq!(list.get(5), Some(10));
+
+        list.retain(|v| *v == 3);
+        assert_eq!(list.len(), 1);
+        assert_eq!(list.head(), Some(3));
+        assert_eq!(list.tail(), Some(3));
+
+        list.retain(|_| false);
+        assert!(list.is_empty());
+        assert_eq!(list.head(), None);
+        assert_eq!(list.tail(), None);
+
+        list.push(1);
+        assert_eq!(list.head(), Some(1));
+        assert_eq!(list.tail(), Some(1));
     }
 
     #[test]
-    #[should_panic]
-    fn delete_index_greater_than_size() {
-        let mut linked_list = linked_list!["1".to_string(), "2".to_string()];
-        linked_list.delete(10).unwrap();
+    fn drain() {
+        use std::cell::Cell;
+
+        // Counts its clones, to check the values are moved out.
+        #[derive(Debug)]
+        struct Counted(u32, Rc<Cell<u32>>);
+
+        impl Clone for Counted {
+            fn clone(&self) -> Self {
+                self.1.set(self.1.get() + 1);
+                Counted(self.0, self.1.clone())
+            }
+        }
+
+        let clones = Rc::new(Cell::new(0));
+        let removed = Rc::new(Cell::new(0));
+        let mut list = LinkedList::default();
+        let counter = removed.clone();
+        list.on_remove(move |_| counter.set(counter.get() + 1));
+        for i in 0..4 {
+            list.push(Counted(i, clones.clone()));
+        }
+
+        let values: Vec<u32> = list.drain().map(|c| c.0).collect();
+        assert_eq!(values, vec![0, 1, 2, 3]);
+        assert_eq!(clones.get(), 0);
+        assert_eq!(removed.get(), 4);
+        assert!(list.is_empty());
+        assert!(list.tail().is_none());
+
+        // Dropping the Drain early still empties the List.
+        for i in 0..4 {
+            list.push(Counted(i, clones.clone()));
+        }
+        assert_eq!(list.drain().next().map(|c| c.0), Some(0));
+        assert!(list.is_empty());
+        assert_eq!(removed.get(), 8);
+
+        list.push(Counted(5, clones.clone()));
+        assert_eq!(list.head().map(|c| c.0), Some(5));
+        assert_eq!(list.tail().map(|c| c.0), Some(5));
+    }
+
+    #[test]
+    fn drain_stops_at_shared_nodes() {
+        let mut list = LinkedList::default();
+        list.push(1);
+        list.push(2);
+        list.pop();
+        list.push(3);
+
+        // The shared list has every node of the List.
+        let shared = list.share();
+        assert_eq!(list.drain().count(), 0);
+        assert!(list.is_empty());
+
+        assert_eq!(shared.head(), Some(2));
+        assert_eq!(shared.len(), 2);
+
+        // A push after share() copies the nodes, which can then be drained.
+        let mut list = shared.share();
+        list.push(4);
+        assert_eq!(list.drain().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(shared.iter().collect::<Vec<_>>(), vec![2, 3]);
+
+        let mut single = LinkedList::default();
+        single.push(1);
+        let shared = single.share();
+        assert_eq!(single.drain().next(), None);
+        assert!(single.is_empty());
+        assert_eq!(shared.tail(), Some(1));
+    }
+
+    #[test]
+    fn recover_after_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let mut list = LinkedList::default();
+        for i in 0..6 {
+            list.push(i);
+        }
+        assert!(!list.is_poisoned());
+        assert_eq!(list.recover(), 6);
+
+        // The panic leaves the kept nodes relinked and the rest of the
+        // chain after them, but the length and the tail stale.
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            list.retain(|v| {
+                assert!(*v != 4);
+                v % 2 == 0
+            })
+        }));
+        assert!(result.is_err());
+        assert!(list.is_poisoned());
+
+        assert_eq!(list.recover(), 5);
+        assert!(!list.is_poisoned());
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![0, 2, 3, 4, 5]);
+        assert_eq!(list.tail(), Some(5));
+        list.push(6);
+        assert_eq!(list.get(5), Some(6));
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            list.sort_by(|_, _| panic!("comparator"));
+        }));
+        assert!(result.is_err());
+        assert!(list.is_poisoned());
+
+        // Whatever is left is consistent again.
+        let len = list.recover();
+        assert_eq!(len, list.len());
+        assert_eq!(list.into_iter().count(), len as usize);
+        list.push(7);
+        assert_eq!(list.tail(), Some(7));
+    }
+
+    #[test]
+    fn dedup_keeps_shared_copy() {
+        let mut linked_list = linked_list![1, 1, 2, 2];
+        let shared = linked_list.share();
+
+        linked_list.dedup();
+        assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![1, 2]);
+
+        assert_eq!(shared.into_iter().len(), 4);
+        assert_eq!(shared.into_iter().collect::<Vec<_>>(), vec![1, 1, 2, 2]);
+    }
+
+    #[test]
+    fn dedup() {
+        let removed = Rc::new(RefCell::new(Vec::new()));
+        let mut list = LinkedList::default();
+        let sink = removed.clone();
+        list.on_remove(move |v| sink.borrow_mut().push(*v));
+        for v in &[1, 1, 1, 2, 3, 3, 1, 4, 4] {
+            list.push(*v);
+        }
+
+        list.dedup();
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![1, 2, 3, 1, 4]);
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.tail(), Some(4));
+        assert_eq!(*removed.borrow(), vec![1, 1, 3, 4]);
+
+        // The tail moved back and new values link after it.
+        list.push(5);
+        assert_eq!(list.get(5), Some(5));
+
+        list.dedup_by_key(|v| *v > 1);
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![1, 2, 1, 4]);
+
+        let mut same = LinkedList::default();
+        for _ in 0..3 {
+            same.push("a");
+        }
+        same.dedup();
+        assert_eq!(same.len(), 1);
+        assert_eq!(same.head(), Some("a"));
+        assert_eq!(same.tail(), Some("a"));
+
+        let mut empty = LinkedList::<u32>::default();
+        empty.dedup();
+        assert!(empty.is_empty());
+    }
+}
+
+// The ordering guarantees of the public API. These tests must keep passing
+// across any rewrite of the LinkedList internals.
+#[cfg(test)]
+mod contract {
+    use super::*;
+
+    #[test]
+    fn iterates_in_push_order() {
+        let linked_list = linked_list![3, 1, 2];
+
+        assert_eq!(linked_list.into_iter().collect::<Vec<_>>(), vec![3, 1, 2]);
+        assert_eq!(
+            Collection::iter(&linked_list).collect::<Vec<_>>(),
+            vec![3, 1, 2]
+        );
+        assert_eq!(
+            (0..3).map(|i| linked_list.get(i)).collect::<Vec<_>>(),
+            vec![Some(3), Some(1), Some(2)]
+        );
+    }
+
+    #[test]
+    fn pops_in_push_order() {
+        let mut linked_list = linked_list![3, 1, 2];
+
+        assert_eq!(linked_list.pop(), Some(3));
+        assert_eq!(linked_list.pop(), Some(1));
+        assert_eq!(linked_list.pop(), Some(2));
+        assert_eq!(linked_list.pop(), None);
+    }
+
+    #[test]
+    fn derived_lists_keep_order() {
+        let linked_list = linked_list![3, 1, 2, 4];
+
+        let mapped = linked_list.map(|v| v * 10);
+        assert_eq!(mapped.into_iter().collect::<Vec<_>>(), vec![30, 10, 20, 40]);
+
+        let filtered = linked_list.filtered(|v| v % 2 == 0);
+        assert_eq!(filtered.into_iter().collect::<Vec<_>>(), vec![2, 4]);
+
+        let shared = linked_list.share();
+        assert_eq!(shared.into_iter().collect::<Vec<_>>(), vec![3, 1, 2, 4]);
     }
 }
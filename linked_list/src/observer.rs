@@ -0,0 +1,92 @@
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::{Cell, RefCell};
+
+type Callback<T> = Box<dyn FnMut(&T)>;
+
+/// Observers holds the callbacks registered on a LinkedList. Copies of a
+/// LinkedList made by `share()` share their nodes, so they share their
+/// Observers too.
+pub(crate) struct Observers<T> {
+    on_insert: Rc<RefCell<Vec<Callback<T>>>>,
+    on_remove: Rc<RefCell<Vec<Callback<T>>>>,
+    // Set while a transaction runs, so the callbacks are only called once
+    // it has committed.
+    held: Rc<Cell<bool>>,
+}
+
+impl<T> Clone for Observers<T> {
+    fn clone(&self) -> Self {
+        Observers {
+            on_insert: Rc::clone(&self.on_insert),
+            on_remove: Rc::clone(&self.on_remove),
+            held: Rc::clone(&self.held),
+        }
+    }
+}
+
+impl<T> Default for Observers<T> {
+    fn default() -> Self {
+        Observers {
+            on_insert: Rc::new(RefCell::new(Vec::new())),
+            on_remove: Rc::new(RefCell::new(Vec::new())),
+            held: Rc::new(Cell::new(false)),
+        }
+    }
+}
+
+impl<T> Observers<T> {
+    /// Stops calling the callbacks until `release()`.
+    pub fn hold(&self) {
+        self.held.set(true);
+    }
+
+    /// Calls the callbacks again after `hold()`. The changes made in between
+    /// aren't reported.
+    pub fn release(&self) {
+        self.held.set(false);
+    }
+
+    pub fn on_insert(&self, f: Callback<T>) {
+        self.on_insert.borrow_mut().push(f);
+    }
+
+    pub fn on_remove(&self, f: Callback<T>) {
+        self.on_remove.borrow_mut().push(f);
+    }
+
+    /// Calls the insert callbacks with a value added to the LinkedList.
+    pub fn inserted(&self, v: &T) {
+        if self.held.get() {
+            return;
+        }
+
+        for f in self.on_insert.borrow_mut().iter_mut() {
+            f(v);
+        }
+    }
+
+    /// Calls the remove callbacks with a value removed from the LinkedList.
+    pub fn removed(&self, v: &T) {
+        if self.held.get() {
+            return;
+        }
+
+        for f in self.on_remove.borrow_mut().iter_mut() {
+            f(v);
+        }
+    }
+
+    /// Returns a boolean indicating there are insert callbacks, so callers
+    /// can skip walking the nodes when nobody is listening.
+    pub fn observes_inserts(&self) -> bool {
+        !self.held.get() && !self.on_insert.borrow().is_empty()
+    }
+
+    /// Returns a boolean indicating there are remove callbacks, so callers
+    /// can skip walking the nodes when nobody is listening.
+    pub fn observes_removes(&self) -> bool {
+        !self.held.get() && !self.on_remove.borrow().is_empty()
+    }
+}
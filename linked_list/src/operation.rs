@@ -0,0 +1,47 @@
+use crate::linked_list::LinkedList;
+
+/// Operation is a structural change made to a LinkedList, holding what is
+/// needed to both revert and reapply it.
+#[derive(Debug, Clone)]
+pub(crate) enum Operation<T> {
+    Push(T),
+    Pop(T),
+    Insert(usize, T),
+    Remove(usize, T),
+}
+
+impl<T> Operation<T>
+where
+    T: Clone,
+{
+    /// Makes the change again on a LinkedList in the state it was in after
+    /// the change was reverted.
+    pub fn apply(&self, list: &mut LinkedList<T>) {
+        match self {
+            Operation::Push(v) => list.push(v.clone()),
+            Operation::Pop(_) => remove_at(list, 0),
+            Operation::Insert(i, v) => list.insert_at(*i, v.clone()),
+            Operation::Remove(i, _) => remove_at(list, *i),
+        }
+    }
+
+    /// Reverts the change on a LinkedList in the state it was in right after
+    /// the change.
+    pub fn revert(&self, list: &mut LinkedList<T>) {
+        match self {
+            Operation::Push(_) => remove_at(list, list.len() as usize - 1),
+            Operation::Pop(v) => list.insert_at(0, v.clone()),
+            Operation::Insert(i, _) => remove_at(list, *i),
+            Operation::Remove(i, v) => list.insert_at(*i, v.clone()),
+        }
+    }
+}
+
+/// Removes the node at an index the Operation knows to be within the list.
+fn remove_at<T>(list: &mut LinkedList<T>, index: usize)
+where
+    T: Clone,
+{
+    list.delete(index as u32)
+        .expect("the operation is out of sync with the list");
+}
@@ -0,0 +1,89 @@
+use crate::linked_list::LinkedList;
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+
+/// A LinkedList is serialized as a sequence of its values, from head to tail,
+/// like a Vec. The values are serialized in place, nothing is cloned.
+impl<T> Serialize for LinkedList<T>
+where
+    T: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len() as usize))?;
+        for v in self.iter_ref() {
+            seq.serialize_element(&*v.value())?;
+        }
+
+        seq.end()
+    }
+}
+
+/// A LinkedList is deserialized from a sequence, each value pushed to the
+/// tail as it is read.
+impl<'de, T> Deserialize<'de> for LinkedList<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(ListVisitor(PhantomData))
+    }
+}
+
+struct ListVisitor<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for ListVisitor<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = LinkedList<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a sequence")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut list = LinkedList::default();
+        while let Some(value) = seq.next_element()? {
+            list.push(value);
+        }
+
+        Ok(list)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+    #[test]
+    fn round_trip() {
+        let mut linked_list = LinkedList::<u32>::default();
+        linked_list.push(1);
+        linked_list.push(2);
+
+        assert_tokens(
+            &linked_list,
+            &[
+                Token::Seq { len: Some(2) },
+                Token::U32(1),
+                Token::U32(2),
+                Token::SeqEnd,
+            ],
+        );
+        assert_tokens(
+            &LinkedList::<u32>::default(),
+            &[Token::Seq { len: Some(0) }, Token::SeqEnd],
+        );
+    }
+
+    #[test]
+    fn not_a_sequence() {
+        assert_de_tokens_error::<LinkedList<u32>>(
+            &[Token::U32(1)],
+            "invalid type: integer `1`, expected a sequence",
+        );
+    }
+}
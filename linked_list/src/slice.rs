@@ -0,0 +1,129 @@
+use crate::linked_list::{LinkedList, LinkedListIterator};
+use core::iter::Take;
+
+/// ListSlice is a read-only view of a contiguous range of a LinkedList,
+/// created by `LinkedList::slice()`. It borrows the LinkedList instead of
+/// copying or splitting its nodes.
+///
+/// # Example
+///
+/// ```
+/// use linked_list::LinkedList;
+///
+/// let mut linked_list = LinkedList::<u32>::default();
+/// for i in 0..5 {
+///     linked_list.push(i);
+/// }
+///
+/// let slice = linked_list.slice(1..4).unwrap();
+/// assert_eq!(slice.len(), 3);
+/// assert_eq!(slice.iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+/// ```
+pub struct ListSlice<'a, T> {
+    list: &'a LinkedList<T>,
+    start: usize,
+    len: usize,
+}
+
+impl<'a, T> ListSlice<'a, T>
+where
+    T: Clone,
+{
+    pub(crate) fn new(list: &'a LinkedList<T>, start: usize, len: usize) -> ListSlice<'a, T> {
+        ListSlice { list, start, len }
+    }
+
+    /// Returns the number of values in the ListSlice.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns a boolean indicating whether the ListSlice is empty.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the value at an index relative to the start of the ListSlice.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    pub fn get(&self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+
+        self.list.get(self.start + index)
+    }
+
+    /// Returns an Iterator over the values in the ListSlice.
+    ///
+    /// Time Complexity: O(n) to consume the Iterator
+    /// Space Complexity: O(1)
+    pub fn iter(&self) -> Take<LinkedListIterator<'a, T>> {
+        self.list.iter_from(self.start).take(self.len)
+    }
+}
+
+impl<'a, T> IntoIterator for &ListSlice<'a, T>
+where
+    T: Clone,
+{
+    type Item = T;
+    type IntoIter = Take<LinkedListIterator<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn list(n: u32) -> LinkedList<u32> {
+        let mut list = LinkedList::default();
+        for i in 0..n {
+            list.push(i);
+        }
+
+        list
+    }
+
+    #[test]
+    fn slice_values() {
+        let list = list(5);
+        let slice = list.slice(2..5).unwrap();
+
+        assert_eq!(slice.len(), 3);
+        assert_eq!(slice.get(0), Some(2));
+        assert_eq!(slice.get(2), Some(4));
+        assert_eq!(slice.get(3), None);
+        assert_eq!((&slice).into_iter().collect::<Vec<_>>(), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn empty_slice() {
+        let list = list(3);
+        let slice = list.slice(1..1).unwrap();
+
+        assert!(slice.is_empty());
+        assert_eq!(slice.iter().next(), None);
+    }
+
+    #[test]
+    fn out_of_range_slice() {
+        let list = list(3);
+
+        assert!(list.slice(0..4).is_none());
+        assert!(list.slice(4..4).is_none());
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = list.slice(2..1);
+        assert!(reversed.is_none());
+    }
+}
@@ -0,0 +1,380 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Numbers each SpillList, so lists in the same process don't share files.
+static NEXT_LIST: AtomicUsize = AtomicUsize::new(0);
+
+/// A run of values in the middle of a SpillList, either in memory or in a
+/// file.
+enum Chunk<T> {
+    Memory(Vec<T>),
+    Disk { path: PathBuf, len: usize },
+}
+
+impl<T> Chunk<T> {
+    fn len(&self) -> usize {
+        match self {
+            Chunk::Memory(values) => values.len(),
+            Chunk::Disk { len, .. } => *len,
+        }
+    }
+}
+
+/// SpillList is a FIFO list of chunks that moves the chunks in the middle to
+/// temporary files once they hold more than a budget of values, for queues
+/// that occasionally outgrow memory.
+///
+/// Values are pushed into the tail chunk and popped from the head chunk,
+/// which always stay in memory. When the tail chunk is full it joins the
+/// middle, and if the middle holds more than `budget` values in memory, the
+/// chunks nearest the tail, which are needed last, are written to disk. A
+/// chunk on disk is read back when it reaches the head, or when `get()`
+/// reaches into it.
+///
+/// The files are removed when they are read back and when the SpillList is
+/// dropped.
+///
+/// # Example
+///
+/// ```
+/// use linked_list::SpillList;
+///
+/// // Chunks of 4 values, at most 8 values in memory in the middle.
+/// let mut list = SpillList::new(4, 8);
+/// for i in 0..100 {
+///     list.push(i).unwrap();
+/// }
+///
+/// assert!(list.spilled() > 0);
+/// assert_eq!(list.get(50).unwrap(), Some(50));
+///
+/// for i in 0..100 {
+///     assert_eq!(list.pop().unwrap(), Some(i));
+/// }
+/// assert_eq!(list.spilled(), 0);
+/// ```
+pub struct SpillList<T> {
+    head: VecDeque<T>,
+    middle: VecDeque<Chunk<T>>,
+    tail: Vec<T>,
+    chunk_len: usize,
+    budget: usize,
+    // The number of values in the middle chunks that are in memory.
+    in_memory: usize,
+    len: usize,
+    dir: PathBuf,
+    id: usize,
+    next_file: usize,
+}
+
+impl<T> SpillList<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    /// Returns an empty SpillList that groups values in chunks of
+    /// `chunk_len` and keeps at most `budget` values in memory besides the
+    /// head and tail chunks, spilling to the system's temporary directory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_len` is 0.
+    pub fn new(chunk_len: usize, budget: usize) -> SpillList<T> {
+        SpillList::with_dir(std::env::temp_dir(), chunk_len, budget)
+    }
+
+    /// Returns an empty SpillList like `new()`, spilling to `dir`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_len` is 0.
+    pub fn with_dir<P: Into<PathBuf>>(dir: P, chunk_len: usize, budget: usize) -> SpillList<T> {
+        assert!(chunk_len > 0, "chunk_len must be greater than 0");
+
+        SpillList {
+            head: VecDeque::new(),
+            middle: VecDeque::new(),
+            tail: Vec::with_capacity(chunk_len),
+            chunk_len,
+            budget,
+            in_memory: 0,
+            len: 0,
+            dir: dir.into(),
+            id: NEXT_LIST.fetch_add(1, Ordering::Relaxed),
+            next_file: 0,
+        }
+    }
+
+    /// Returns the number of values in the SpillList.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns a boolean indicating the SpillList is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of chunks written to disk.
+    pub fn spilled(&self) -> usize {
+        self.middle
+            .iter()
+            .filter(|c| matches!(c, Chunk::Disk { .. }))
+            .count()
+    }
+
+    /// Adds a value to the end of the SpillList. Returns an error if a chunk
+    /// had to be spilled and couldn't be written, the value is kept either
+    /// way.
+    ///
+    /// Time Complexity: O(1) amortized, O(c) when a chunk is spilled
+    /// Space Complexity: O(1) amortized
+    pub fn push(&mut self, v: T) -> io::Result<()> {
+        self.tail.push(v);
+        self.len += 1;
+
+        if self.tail.len() < self.chunk_len {
+            return Ok(());
+        }
+
+        let chunk = std::mem::replace(&mut self.tail, Vec::with_capacity(self.chunk_len));
+        self.in_memory += chunk.len();
+        self.middle.push_back(Chunk::Memory(chunk));
+
+        self.enforce_budget()
+    }
+
+    /// Removes and returns the value at the head of the SpillList. Returns an
+    /// error if the next chunk is on disk and couldn't be read, the chunk is
+    /// kept so the pop can be retried.
+    ///
+    /// Time Complexity: O(1) amortized, O(c) when a chunk is read back
+    /// Space Complexity: O(c)
+    pub fn pop(&mut self) -> io::Result<Option<T>> {
+        if self.head.is_empty() {
+            // Read a spilled chunk before taking it out of the middle, so a
+            // failed read leaves it in place.
+            let loaded = match self.middle.front() {
+                Some(Chunk::Disk { path, .. }) => Some(read(path)?),
+                _ => None,
+            };
+
+            match self.middle.pop_front() {
+                Some(Chunk::Memory(values)) => {
+                    self.in_memory -= values.len();
+                    self.head = values.into();
+                }
+                Some(Chunk::Disk { path, .. }) => {
+                    let _ = fs::remove_file(path);
+                    self.head = loaded.unwrap_or_default().into();
+                }
+                None => self.head = std::mem::take(&mut self.tail).into(),
+            }
+        }
+
+        let v = self.head.pop_front();
+        if v.is_some() {
+            self.len -= 1;
+        }
+
+        Ok(v)
+    }
+
+    /// Returns the value at an index, reading it from disk if its chunk was
+    /// spilled. The chunk isn't kept in memory afterwards.
+    ///
+    /// Time Complexity: O(n / c + c)
+    /// Space Complexity: O(c)
+    pub fn get(&self, index: usize) -> io::Result<Option<T>>
+    where
+        T: Clone,
+    {
+        if index < self.head.len() {
+            return Ok(self.head.get(index).cloned());
+        }
+
+        let mut index = index - self.head.len();
+        for chunk in &self.middle {
+            if index < chunk.len() {
+                return Ok(match chunk {
+                    Chunk::Memory(values) => values.get(index).cloned(),
+                    Chunk::Disk { path, .. } => read(path)?.into_iter().nth(index),
+                });
+            }
+
+            index -= chunk.len();
+        }
+
+        Ok(self.tail.get(index).cloned())
+    }
+
+    /// Writes the in-memory chunks nearest the tail to disk until the middle
+    /// holds at most `budget` values in memory.
+    fn enforce_budget(&mut self) -> io::Result<()> {
+        while self.in_memory > self.budget {
+            let i = match self
+                .middle
+                .iter()
+                .rposition(|c| matches!(c, Chunk::Memory(_)))
+            {
+                Some(i) => i,
+                None => return Ok(()),
+            };
+
+            let path = self.dir.join(format!(
+                "spill-list-{}-{}-{}.bin",
+                process::id(),
+                self.id,
+                self.next_file
+            ));
+            self.next_file += 1;
+
+            if let Chunk::Memory(values) = &self.middle[i] {
+                if let Err(e) = write(&path, values) {
+                    let _ = fs::remove_file(&path);
+                    return Err(e);
+                }
+            }
+
+            let len = self.middle[i].len();
+            self.middle[i] = Chunk::Disk { path, len };
+            self.in_memory -= len;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Drop for SpillList<T> {
+    fn drop(&mut self) {
+        for chunk in &self.middle {
+            if let Chunk::Disk { path, .. } = chunk {
+                let _ = fs::remove_file(path);
+            }
+        }
+    }
+}
+
+/// Writes the values of a chunk being spilled.
+fn write<T: Serialize>(path: &Path, values: &[T]) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    bincode::serialize_into(&mut writer, values).map_err(io::Error::other)?;
+    writer.flush()
+}
+
+/// Reads the values of a spilled chunk.
+fn read<T: DeserializeOwned>(path: &Path) -> io::Result<Vec<T>> {
+    let reader = BufReader::new(File::open(path)?);
+    bincode::deserialize_from(reader).map_err(io::Error::other)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn files(dir: &Path) -> usize {
+        fs::read_dir(dir).unwrap().count()
+    }
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("spill-list-test-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn spills_past_the_budget() {
+        let dir = scratch("budget");
+        let mut list = SpillList::with_dir(&dir, 2, 4);
+
+        for i in 0..4 {
+            list.push(i).unwrap();
+        }
+        assert_eq!(list.spilled(), 0);
+
+        // The middle now holds 6 values, the newest chunk goes to disk.
+        list.push(4).unwrap();
+        list.push(5).unwrap();
+        assert_eq!(list.spilled(), 1);
+        assert_eq!(files(&dir), 1);
+
+        for i in 6..20 {
+            list.push(i).unwrap();
+        }
+        assert_eq!(list.len(), 20);
+        assert_eq!(list.spilled(), 8);
+
+        for i in 0..20 {
+            assert_eq!(list.get(i).unwrap(), Some(i));
+        }
+        assert_eq!(list.get(20).unwrap(), None);
+
+        // Popping reads the chunks back and removes their files.
+        for i in 0..20 {
+            assert_eq!(list.pop().unwrap(), Some(i));
+        }
+        assert_eq!(list.pop().unwrap(), None);
+        assert!(list.is_empty());
+        assert_eq!(files(&dir), 0);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn interleaved_push_and_pop() {
+        let dir = scratch("interleaved");
+        let mut list = SpillList::with_dir(&dir, 3, 0);
+        let mut expected = VecDeque::new();
+
+        for i in 0..200u32 {
+            list.push(i).unwrap();
+            expected.push_back(i);
+
+            if i % 3 == 0 {
+                assert_eq!(list.pop().unwrap(), expected.pop_front());
+            }
+        }
+        assert_eq!(list.len(), expected.len());
+
+        while let Some(v) = expected.pop_front() {
+            assert_eq!(list.pop().unwrap(), Some(v));
+        }
+        assert_eq!(files(&dir), 0);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn drop_removes_files() {
+        let dir = scratch("drop");
+        let mut list = SpillList::with_dir(&dir, 1, 0);
+        for i in 0..5u32 {
+            list.push(i).unwrap();
+        }
+        assert_eq!(files(&dir), 5);
+
+        drop(list);
+        assert_eq!(files(&dir), 0);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn unwritable_dir() {
+        let mut list = SpillList::with_dir("/nonexistent/spill-list", 1, 0);
+
+        assert!(list.push(1u32).is_err());
+        assert_eq!(list.len(), 1);
+        assert_eq!(list.spilled(), 0);
+        assert_eq!(list.pop().unwrap(), Some(1));
+    }
+}
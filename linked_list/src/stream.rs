@@ -0,0 +1,188 @@
+use crate::linked_list::LinkedList;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::channel::mpsc::{self, SendError, UnboundedReceiver, UnboundedSender};
+use futures::sink::Sink;
+use futures::stream::Stream;
+
+/// ListStream is a Stream that pops the values of a LinkedList, created by
+/// `LinkedList::into_stream()`. Every value is ready immediately.
+pub struct ListStream<T> {
+    list: LinkedList<T>,
+}
+
+impl<T> Stream for ListStream<T>
+where
+    T: Clone,
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<T>> {
+        Poll::Ready(self.get_mut().list.pop())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.list.len() as usize;
+        (len, Some(len))
+    }
+}
+
+impl<T> LinkedList<T>
+where
+    T: Clone,
+{
+    /// Converts the LinkedList into a Stream of its values, from head to tail.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures::executor::block_on;
+    /// use futures::stream::StreamExt;
+    /// use linked_list::LinkedList;
+    ///
+    /// let mut linked_list = LinkedList::<u32>::default();
+    /// linked_list.push(1);
+    /// linked_list.push(2);
+    ///
+    /// let values: Vec<u32> = block_on(linked_list.into_stream().collect());
+    /// assert_eq!(values, vec![1, 2]);
+    /// ```
+    pub fn into_stream(self) -> ListStream<T> {
+        ListStream { list: self }
+    }
+}
+
+/// ListSink is a Sink that collects values into a LinkedList.
+///
+/// The LinkedList can't be sent between threads, so the values go through an
+/// unbounded channel. `sender()` hands out Senders that can be moved to other
+/// tasks, and `into_list()` builds the LinkedList from every value received.
+///
+/// # Example
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::stream::{self, StreamExt};
+/// use linked_list::ListSink;
+///
+/// let mut sink = ListSink::default();
+/// block_on(stream::iter(vec![1, 2, 3]).map(Ok).forward(&mut sink)).unwrap();
+///
+/// let linked_list = sink.into_list();
+/// assert_eq!(linked_list.len(), 3);
+/// assert_eq!(linked_list.tail(), Some(3));
+/// ```
+pub struct ListSink<T> {
+    sender: UnboundedSender<T>,
+    receiver: UnboundedReceiver<T>,
+}
+
+impl<T> Default for ListSink<T> {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::unbounded();
+        ListSink { sender, receiver }
+    }
+}
+
+impl<T> ListSink<T>
+where
+    T: Clone,
+{
+    /// Returns a Sender feeding this ListSink.
+    pub fn sender(&self) -> UnboundedSender<T> {
+        self.sender.clone()
+    }
+
+    /// Closes the channel and returns a LinkedList of the values received, in
+    /// the order they were sent. Values sent after this call are refused.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(n)
+    pub fn into_list(self) -> LinkedList<T> {
+        let ListSink {
+            sender,
+            mut receiver,
+        } = self;
+        drop(sender);
+        receiver.close();
+
+        let mut list = LinkedList::default();
+        while let Ok(v) = receiver.try_recv() {
+            list.push(v);
+        }
+
+        list
+    }
+}
+
+impl<T> Sink<T> for ListSink<T> {
+    type Error = SendError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), SendError>> {
+        Pin::new(&mut self.get_mut().sender).poll_ready(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), SendError> {
+        Pin::new(&mut self.get_mut().sender).start_send(item)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), SendError>> {
+        Pin::new(&mut self.get_mut().sender).poll_flush(cx)
+    }
+
+    // Closing the ListSink leaves the channel open, the values are kept until
+    // `into_list()` is called.
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), SendError>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures::executor::block_on;
+    use futures::sink::SinkExt;
+    use futures::stream::StreamExt;
+
+    #[test]
+    fn stream_pops_in_order() {
+        let mut list = LinkedList::default();
+        for i in 0..5 {
+            list.push(i);
+        }
+
+        let stream = list.into_stream();
+        assert_eq!(stream.size_hint(), (5, Some(5)));
+        assert_eq!(block_on(stream.collect::<Vec<u32>>()), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn sink_collects_from_senders() {
+        let mut sink = ListSink::default();
+        let mut sender = sink.sender();
+
+        block_on(async {
+            sender.send(1).await.unwrap();
+            sink.send(2).await.unwrap();
+            sender.send(3).await.unwrap();
+        });
+
+        let list = sink.into_list();
+        assert_eq!(list.into_iter().collect::<Vec<u32>>(), vec![1, 2, 3]);
+        assert!(sender.unbounded_send(4).is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut list = LinkedList::default();
+        list.push("a".to_string());
+        list.push("b".to_string());
+
+        let mut sink = ListSink::default();
+        block_on(list.into_stream().map(Ok).forward(&mut sink)).unwrap();
+
+        let list = sink.into_list();
+        assert_eq!(list.head(), Some("a".to_string()));
+        assert_eq!(list.tail(), Some("b".to_string()));
+    }
+}
@@ -0,0 +1,242 @@
+use crate::raw_list::RawList;
+use std::sync::{Mutex, MutexGuard};
+
+/// SyncLinkedList is a linked list that can be shared between threads. It is
+/// a RawList behind a single Mutex, so every operation takes the lock and
+/// threads never see a half linked node.
+///
+/// A LinkedList can't cross threads at all, its nodes are `Rc<RefCell<_>>`.
+/// Like the ConcurrentHashMap, its methods take `&self`, so it is shared by
+/// wrapping it in an `Arc`.
+///
+/// # Example
+///
+/// ```
+/// use linked_list::SyncLinkedList;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let list = Arc::new(SyncLinkedList::new());
+///
+/// let handles: Vec<_> = (0..4)
+///     .map(|t| {
+///         let list = Arc::clone(&list);
+///         thread::spawn(move || {
+///             for i in 0..100 {
+///                 list.push(t * 100 + i);
+///             }
+///         })
+///     })
+///     .collect();
+/// for handle in handles {
+///     handle.join().unwrap();
+/// }
+///
+/// assert_eq!(list.len(), 400);
+/// ```
+pub struct SyncLinkedList<T> {
+    list: Mutex<RawList<T>>,
+}
+
+impl<T> Default for SyncLinkedList<T> {
+    fn default() -> Self {
+        SyncLinkedList {
+            list: Mutex::new(RawList::new()),
+        }
+    }
+}
+
+impl<T> From<RawList<T>> for SyncLinkedList<T> {
+    fn from(list: RawList<T>) -> Self {
+        SyncLinkedList {
+            list: Mutex::new(list),
+        }
+    }
+}
+
+impl<T> SyncLinkedList<T> {
+    /// Returns an empty SyncLinkedList.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the length of the SyncLinkedList. Other threads may change it
+    /// as soon as the lock is released.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> u32 {
+        self.lock().len()
+    }
+
+    /// Returns a boolean indicating the SyncLinkedList is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Adds a value to the end of the SyncLinkedList.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn push(&self, value: T) {
+        self.lock().push(value)
+    }
+
+    /// Adds a value to the front of the SyncLinkedList.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn push_front(&self, value: T) {
+        self.lock().push_front(value)
+    }
+
+    /// Removes the value at the front of the SyncLinkedList and returns it.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn pop(&self) -> Option<T> {
+        self.lock().pop()
+    }
+
+    /// Removes every value from the SyncLinkedList.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    pub fn clear(&self) {
+        // Swaps the nodes out, so they're dropped after the lock is released.
+        let list = std::mem::take(&mut *self.lock());
+        drop(list);
+    }
+
+    /// Locks the SyncLinkedList and returns the RawList inside, to run
+    /// several operations without another thread interleaving, e.g. to
+    /// iterate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linked_list::SyncLinkedList;
+    ///
+    /// let list = SyncLinkedList::new();
+    /// list.push(1);
+    /// list.push(2);
+    ///
+    /// let sum: u32 = list.lock().iter().sum();
+    /// assert_eq!(sum, 3);
+    /// ```
+    pub fn lock(&self) -> MutexGuard<'_, RawList<T>> {
+        // A panic while the lock was held can only have happened between
+        // complete operations of the RawList, so a poisoned lock is still
+        // consistent.
+        self.list.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Returns the RawList inside the SyncLinkedList.
+    pub fn into_inner(self) -> RawList<T> {
+        self.list.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<T: Clone> SyncLinkedList<T> {
+    /// Returns a copy of the value at the front of the SyncLinkedList. The
+    /// value is copied, a reference couldn't outlive the lock.
+    pub fn head(&self) -> Option<T> {
+        self.lock().head().cloned()
+    }
+
+    /// Returns a copy of the value at the end of the SyncLinkedList.
+    pub fn tail(&self) -> Option<T> {
+        self.lock().tail().cloned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn send_and_sync() {
+        assert_send_sync::<SyncLinkedList<u32>>();
+        assert_send_sync::<SyncLinkedList<Vec<u8>>>();
+    }
+
+    #[test]
+    fn push_pop() {
+        let list = SyncLinkedList::new();
+        list.push(2);
+        list.push_front(1);
+
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.head(), Some(1));
+        assert_eq!(list.tail(), Some(2));
+        assert_eq!(list.pop(), Some(1));
+
+        list.clear();
+        assert!(list.is_empty());
+        assert_eq!(list.pop(), None);
+    }
+
+    #[test]
+    fn producers_and_consumers() {
+        let list = Arc::new(SyncLinkedList::new());
+
+        let producers: Vec<_> = (0..4u64)
+            .map(|t| {
+                let list = Arc::clone(&list);
+                thread::spawn(move || {
+                    for i in 0..1000 {
+                        list.push(t * 1000 + i);
+                    }
+                })
+            })
+            .collect();
+        let consumers: Vec<_> = (0..2)
+            .map(|_| {
+                let list = Arc::clone(&list);
+                thread::spawn(move || {
+                    let mut popped = Vec::new();
+                    while popped.len() < 1000 {
+                        if let Some(v) = list.pop() {
+                            popped.push(v);
+                        } else {
+                            thread::yield_now();
+                        }
+                    }
+                    popped
+                })
+            })
+            .collect();
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        let mut values: Vec<u64> = consumers
+            .into_iter()
+            .flat_map(|c| c.join().unwrap())
+            .collect();
+        values.extend(Arc::try_unwrap(list).ok().unwrap().into_inner());
+
+        values.sort_unstable();
+        assert_eq!(values, (0..4000).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn poisoned_lock_is_still_usable() {
+        let list = Arc::new(SyncLinkedList::from((0..3).collect::<RawList<u32>>()));
+
+        let poisoner = Arc::clone(&list);
+        let result = thread::spawn(move || {
+            let _guard = poisoner.lock();
+            panic!("poison the lock");
+        })
+        .join();
+
+        assert!(result.is_err());
+        assert_eq!(list.pop(), Some(0));
+        assert_eq!(list.len(), 2);
+    }
+}
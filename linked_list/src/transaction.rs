@@ -0,0 +1,107 @@
+use crate::error::{LinkedListError, Result};
+use crate::linked_list::LinkedList;
+use crate::operation::Operation;
+use alloc::vec::Vec;
+
+/// Transaction is the handle passed to the closure of
+/// `LinkedList::transaction()`. Each operation is applied to the LinkedList
+/// straight away and recorded, so the whole batch can be reverted if the
+/// closure returns an error. The observers are held until the Transaction
+/// commits, and only then told about the recorded operations.
+pub struct Transaction<'a, T>
+where
+    T: Clone,
+{
+    list: &'a mut LinkedList<T>,
+    journal: Vec<Operation<T>>,
+}
+
+impl<'a, T> Transaction<'a, T>
+where
+    T: Clone,
+{
+    pub(crate) fn new(list: &'a mut LinkedList<T>) -> Transaction<'a, T> {
+        list.observers().hold();
+
+        Transaction {
+            list,
+            journal: Vec::new(),
+        }
+    }
+
+    /// Returns a reference to the LinkedList as modified so far.
+    pub fn list(&self) -> &LinkedList<T> {
+        self.list
+    }
+
+    /// Adds a value to the end of the list.
+    pub fn push(&mut self, v: T) {
+        self.list.push(v.clone());
+        self.journal.push(Operation::Push(v));
+    }
+
+    /// Removes and returns the value at the head of the list.
+    pub fn pop(&mut self) -> Option<T> {
+        let v = self.list.pop()?;
+        self.journal.push(Operation::Pop(v.clone()));
+
+        Some(v)
+    }
+
+    /// Inserts a value at an index, shifting the values after it back.
+    pub fn insert(&mut self, index: usize, v: T) -> Result<()> {
+        self.list.insert(index, v.clone())?;
+        self.journal.push(Operation::Insert(index, v));
+
+        Ok(())
+    }
+
+    /// Removes and returns the value at an index.
+    pub fn remove(&mut self, index: usize) -> Result<T> {
+        if self.list.is_empty() {
+            return Err(LinkedListError::EmptyList);
+        }
+
+        let v = self
+            .list
+            .get(index)
+            .ok_or(LinkedListError::IndexOutOfRangeError)?;
+        self.list.delete(index as u32)?;
+        self.journal.push(Operation::Remove(index, v.clone()));
+
+        Ok(v)
+    }
+
+    /// Releases the observers and calls them with every recorded operation,
+    /// oldest first.
+    pub(crate) fn commit(mut self) {
+        let journal = core::mem::take(&mut self.journal);
+        let observers = self.list.observers();
+        observers.release();
+
+        for op in &journal {
+            match op {
+                Operation::Push(v) | Operation::Insert(_, v) => observers.inserted(v),
+                Operation::Pop(v) | Operation::Remove(_, v) => observers.removed(v),
+            }
+        }
+    }
+
+    /// Reverts every recorded operation, newest first. The observers are
+    /// released without being called, as the LinkedList is left unchanged.
+    pub(crate) fn rollback(mut self) {
+        while let Some(op) = self.journal.pop() {
+            op.revert(self.list);
+        }
+    }
+}
+
+impl<'a, T> Drop for Transaction<'a, T>
+where
+    T: Clone,
+{
+    fn drop(&mut self) {
+        // Also releases the observers if the closure panics.
+        self.list.observers().release();
+    }
+}
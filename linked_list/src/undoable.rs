@@ -0,0 +1,252 @@
+use crate::error::{LinkedListError, Result};
+use crate::linked_list::LinkedList;
+use crate::operation::Operation;
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+/// UndoableList wraps a LinkedList and records each structural operation in
+/// a bounded journal, so the operations can be reverted with `undo()` and
+/// reapplied with `redo()`.
+///
+/// Once the journal holds `limit` operations, the oldest one is forgotten.
+/// Any new operation clears the operations that could be redone.
+///
+/// # Example
+///
+/// ```
+/// use linked_list::UndoableList;
+///
+/// let mut list = UndoableList::new(10);
+/// list.push(1);
+/// list.push(2);
+/// list.remove(0).unwrap();
+///
+/// assert!(list.undo());
+/// assert_eq!(list.list().head(), Some(1));
+///
+/// assert!(list.redo());
+/// assert_eq!(list.list().head(), Some(2));
+/// ```
+pub struct UndoableList<T> {
+    list: LinkedList<T>,
+    undo: VecDeque<Operation<T>>,
+    redo: Vec<Operation<T>>,
+    limit: usize,
+}
+
+impl<T> UndoableList<T>
+where
+    T: Clone,
+{
+    /// Returns an empty UndoableList that remembers up to `limit` operations.
+    pub fn new(limit: usize) -> UndoableList<T> {
+        UndoableList::from_list(LinkedList::default(), limit)
+    }
+
+    /// Wraps an existing LinkedList, its current state can't be undone.
+    pub fn from_list(list: LinkedList<T>, limit: usize) -> UndoableList<T> {
+        UndoableList {
+            list,
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            limit,
+        }
+    }
+
+    /// Returns a reference to the wrapped LinkedList.
+    pub fn list(&self) -> &LinkedList<T> {
+        &self.list
+    }
+
+    /// Returns the wrapped LinkedList, dropping the journal.
+    pub fn into_inner(self) -> LinkedList<T> {
+        self.list
+    }
+
+    /// Adds a value to the end of the list.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn push(&mut self, v: T) {
+        self.list.push(v.clone());
+        self.record(Operation::Push(v));
+    }
+
+    /// Removes and returns the value at the head of the list.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn pop(&mut self) -> Option<T> {
+        let v = self.list.pop()?;
+        self.record(Operation::Pop(v.clone()));
+
+        Some(v)
+    }
+
+    /// Inserts a value at an index, shifting the values after it back.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    pub fn insert(&mut self, index: usize, v: T) -> Result<()> {
+        self.list.insert(index, v.clone())?;
+        self.record(Operation::Insert(index, v));
+
+        Ok(())
+    }
+
+    /// Removes and returns the value at an index.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    pub fn remove(&mut self, index: usize) -> Result<T> {
+        if self.list.is_empty() {
+            return Err(LinkedListError::EmptyList);
+        }
+
+        let v = self
+            .list
+            .get(index)
+            .ok_or(LinkedListError::IndexOutOfRangeError)?;
+        self.list.delete(index as u32)?;
+        self.record(Operation::Remove(index, v.clone()));
+
+        Ok(v)
+    }
+
+    /// Reverts the most recent operation, returning false if there is nothing
+    /// to undo.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    pub fn undo(&mut self) -> bool {
+        let op = match self.undo.pop_back() {
+            Some(op) => op,
+            None => return false,
+        };
+
+        op.revert(&mut self.list);
+        self.redo.push(op);
+        true
+    }
+
+    /// Reapplies the most recently undone operation, returning false if there
+    /// is nothing to redo.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    pub fn redo(&mut self) -> bool {
+        let op = match self.redo.pop() {
+            Some(op) => op,
+            None => return false,
+        };
+
+        op.apply(&mut self.list);
+        self.push_undo(op);
+        true
+    }
+
+    /// Returns a boolean indicating there is an operation to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Returns a boolean indicating there is an operation to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Records a new operation, which can no longer be followed by a redo.
+    fn record(&mut self, op: Operation<T>) {
+        self.redo.clear();
+        self.push_undo(op);
+    }
+
+    fn push_undo(&mut self, op: Operation<T>) {
+        if self.limit == 0 {
+            return;
+        }
+
+        if self.undo.len() == self.limit {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(op);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn values(list: &UndoableList<u32>) -> Vec<u32> {
+        list.list().into_iter().collect()
+    }
+
+    #[test]
+    fn undo_and_redo_each_operation() {
+        let mut list = UndoableList::new(10);
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        list.insert(1, 4).unwrap();
+        assert_eq!(list.remove(2).unwrap(), 2);
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(values(&list), vec![4, 3]);
+
+        assert!(list.undo());
+        assert_eq!(values(&list), vec![1, 4, 3]);
+        assert!(list.undo());
+        assert_eq!(values(&list), vec![1, 4, 2, 3]);
+        assert!(list.undo());
+        assert_eq!(values(&list), vec![1, 2, 3]);
+        assert!(list.undo());
+        assert_eq!(values(&list), vec![1, 2]);
+        assert_eq!(list.list().tail(), Some(2));
+
+        assert!(list.redo());
+        assert!(list.redo());
+        assert!(list.redo());
+        assert!(list.redo());
+        assert!(!list.redo());
+        assert_eq!(values(&list), vec![4, 3]);
+        assert_eq!(list.list().tail(), Some(3));
+    }
+
+    #[test]
+    fn new_operation_clears_redo() {
+        let mut list = UndoableList::new(10);
+        list.push(1);
+        list.push(2);
+
+        assert!(list.undo());
+        assert!(list.can_redo());
+
+        list.push(3);
+        assert!(!list.can_redo());
+        assert!(!list.redo());
+        assert_eq!(values(&list), vec![1, 3]);
+    }
+
+    #[test]
+    fn journal_is_bounded() {
+        let mut list = UndoableList::new(2);
+        for i in 0..5 {
+            list.push(i);
+        }
+
+        assert!(list.undo());
+        assert!(list.undo());
+        assert!(!list.undo());
+        assert!(!list.can_undo());
+        assert_eq!(values(&list), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn failed_operations_are_not_recorded() {
+        let mut list = UndoableList::<u32>::new(10);
+
+        assert!(list.insert(1, 1).is_err());
+        assert!(list.remove(0).is_err());
+        assert_eq!(list.pop(), None);
+        assert!(!list.can_undo());
+    }
+}
@@ -0,0 +1,30 @@
+use crate::node::NodeRef;
+use core::cell::Ref;
+use core::fmt;
+
+/// ValueRef gives access to the value of a node without cloning it, yielded
+/// by `LinkedList::iter_ref()`. It keeps its node alive, so it can outlive
+/// the Iterator and even the LinkedList. The value is accessed through a
+/// `Ref` guard.
+pub struct ValueRef<T>(pub(crate) NodeRef<T>);
+
+impl<T> ValueRef<T> {
+    /// Borrows the value of the node.
+    ///
+    /// Panics if the value is currently borrowed mutably.
+    pub fn value(&self) -> Ref<'_, T> {
+        Ref::map(self.0 .0.borrow(), |n| &n.value)
+    }
+}
+
+impl<T: PartialEq> PartialEq<T> for ValueRef<T> {
+    fn eq(&self, other: &T) -> bool {
+        *self.value() == *other
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ValueRef<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ValueRef").field(&*self.value()).finish()
+    }
+}
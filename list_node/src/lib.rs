@@ -0,0 +1,11 @@
+//! A crate that implements the Node shared by the singly and doubly linked
+//! lists, parameterized by the number of links in each Node.
+//!
+//! The crate is `no_std`, it only needs `alloc` for the `Rc` of each Node.
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+pub use crate::node::{Arity, Doubly, Node, NodeRef, Singly};
+
+mod node;
@@ -0,0 +1,220 @@
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use core::fmt;
+
+/// Arity describes the links held by a Node. Every Node points to the next
+/// Node, `Previous` is the type of the link back to the previous Node.
+pub trait Arity: Sized {
+    type Previous<T>: Default + Clone;
+}
+
+/// Singly is the Arity of a Node that only links to the next Node.
+#[derive(Debug, Clone, Copy)]
+pub struct Singly;
+
+impl Arity for Singly {
+    type Previous<T> = ();
+}
+
+/// Doubly is the Arity of a Node that links to the next and previous Nodes.
+#[derive(Debug, Clone, Copy)]
+pub struct Doubly;
+
+impl Arity for Doubly {
+    type Previous<T> = Option<NodeRef<T, Doubly>>;
+}
+
+/// Alias for a referenced Node.
+pub struct NodeRef<T, A: Arity>(pub Rc<RefCell<Node<T, A>>>);
+
+impl<T, A: Arity> NodeRef<T, A> {
+    /// new returns a NodeRef that wraps a Node<T> in a Rc<RefCell<Node<T>>.
+    pub fn new(v: Node<T, A>) -> NodeRef<T, A> {
+        NodeRef(Rc::new(RefCell::new(v)))
+    }
+
+    /// Clones the value in the NodeRef.
+    pub fn value(&self) -> T
+    where
+        T: Clone,
+    {
+        self.0.borrow().value.clone()
+    }
+
+    /// Moves the value out of the NodeRef, or clones it if the node is still
+    /// shared, e.g. by a Handle or a clone of the LinkedList, or if its value
+    /// is pinned.
+    pub fn into_value(self) -> T
+    where
+        T: Clone,
+    {
+        if self.0.borrow().pinned {
+            return self.value();
+        }
+
+        // try_unwrap(h) will return the value in a Result if it has exactly
+        // ONLY one reference.
+        //
+        // into_inner() - returns the Node inside of the RefCell
+        match Rc::try_unwrap(self.0) {
+            Ok(node) => node.into_inner().value,
+            Err(shared) => shared.borrow().value.clone(),
+        }
+    }
+}
+
+impl<T, A: Arity> Clone for NodeRef<T, A> {
+    fn clone(&self) -> Self {
+        NodeRef(Rc::clone(&self.0))
+    }
+}
+
+impl<T: fmt::Debug, A: Arity> fmt::Debug for NodeRef<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("NodeRef").field(&self.0).finish()
+    }
+}
+
+/// Node is the structure in a LinkedList. It contains a pointer to the next
+/// Node in memory, the links described by its Arity and holds a value `T`.
+pub struct Node<T, A: Arity> {
+    pub value: T,
+    pub next: Option<NodeRef<T, A>>,
+    pub previous: A::Previous<T>,
+    /// Set once the value has been pinned, the value must then stay in the
+    /// Node until the Node is dropped.
+    pub pinned: bool,
+}
+
+impl<T, A: Arity> Node<T, A> {
+    pub fn new(value: T) -> Node<T, A> {
+        Node {
+            value,
+            next: None,
+            previous: A::Previous::<T>::default(),
+            pinned: false,
+        }
+    }
+
+    pub fn set_next(&mut self, next: Option<NodeRef<T, A>>) {
+        self.next = next;
+    }
+}
+
+impl<T: Clone, A: Arity> Clone for Node<T, A> {
+    fn clone(&self) -> Self {
+        Node {
+            value: self.value.clone(),
+            next: self.next.clone(),
+            previous: self.previous.clone(),
+            // The clone is a new value, it hasn't been pinned.
+            pinned: false,
+        }
+    }
+}
+
+// The previous link is left out, following it from a doubly linked Node
+// would print the same Nodes forever.
+impl<T: fmt::Debug, A: Arity> fmt::Debug for Node<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Node")
+            .field("value", &self.value)
+            .field("next", &self.next)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn init_node() {
+        let node = Node::<_, Singly>::new(5);
+        assert_eq!(node.value, 5);
+    }
+
+    #[test]
+    fn mutate_value() {
+        let mut head = Node::<_, Singly>::new("hello".to_string());
+
+        head.value = "!".to_string();
+        assert_eq!(head.value, "!".to_string());
+    }
+
+    #[test]
+    fn next_node() {
+        let mut head = Node::<_, Singly>::new("hello".to_string());
+        let tail = Node::new("world".to_string());
+
+        let next = Some(NodeRef::new(tail));
+        head.set_next(next);
+
+        assert_eq!(
+            head.next.unwrap().0.try_borrow().unwrap().value,
+            "world".to_string()
+        );
+    }
+
+    #[test]
+    fn previous_node() {
+        let mut head = Node::<_, Doubly>::new(1);
+        let mut mid = Node::new(2);
+        let mut tail = Node::new(3);
+
+        head.next = Some(NodeRef::new(mid.clone()));
+        mid.previous = Some(NodeRef::new(head.clone()));
+        mid.next = Some(NodeRef::new(tail.clone()));
+        tail.previous = Some(NodeRef::new(mid.clone()));
+
+        assert_eq!(mid.previous.unwrap().value(), 1);
+        assert_eq!(mid.next.unwrap().value(), 3);
+    }
+
+    #[test]
+    fn into_shared_value() {
+        let node = NodeRef::new(Node::<_, Singly>::new("hello".to_string()));
+        let shared = node.clone();
+
+        assert_eq!(node.into_value(), "hello".to_string());
+        assert_eq!(shared.into_value(), "hello".to_string());
+    }
+
+    #[test]
+    fn debug_skips_previous() {
+        let head = NodeRef::new(Node::<_, Doubly>::new(1));
+        let tail = NodeRef::new(Node::new(2));
+        head.0.borrow_mut().next = Some(tail.clone());
+        tail.0.borrow_mut().previous = Some(head.clone());
+
+        assert_eq!(
+            format!("{:?}", head),
+            "NodeRef(RefCell { value: Node { value: 1, next: Some(NodeRef(RefCell { value: Node { value: 2, next: None } })) } })"
+        );
+    }
+
+    #[test]
+    fn into_pinned_value() {
+        use std::cell::Cell;
+
+        // Counts its clones, to tell a moved value from a cloned one.
+        struct Counted(Rc<Cell<u32>>);
+
+        impl Clone for Counted {
+            fn clone(&self) -> Self {
+                self.0.set(self.0.get() + 1);
+                Counted(Rc::clone(&self.0))
+            }
+        }
+
+        let clones = Rc::new(Cell::new(0));
+        let node = NodeRef::new(Node::<_, Doubly>::new(Counted(clones.clone())));
+        node.clone().into_value();
+        assert_eq!(clones.get(), 1);
+
+        // Even the only reference to a pinned value clones it.
+        node.0.borrow_mut().pinned = true;
+        node.into_value();
+        assert_eq!(clones.get(), 2);
+    }
+}
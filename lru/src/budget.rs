@@ -0,0 +1,184 @@
+use std::mem::size_of;
+
+/// MemSized reports the number of bytes a value takes, so an LRU can hold its
+/// entries to a byte budget, see `LRU::set_byte_budget()`.
+///
+/// The count covers the value itself and the heap memory it owns. It only
+/// has to be consistent, not exact: allocator overhead can be left out.
+///
+/// # Example
+///
+/// ```
+/// use lru::MemSized;
+///
+/// struct Image {
+///     pixels: Vec<u8>,
+/// }
+///
+/// impl MemSized for Image {
+///     fn bytes(&self) -> usize {
+///         self.pixels.bytes()
+///     }
+/// }
+/// ```
+pub trait MemSized {
+    fn bytes(&self) -> usize;
+}
+
+macro_rules! mem_sized_by_size_of {
+    ($($t:ty),*) => {
+        $(
+            impl MemSized for $t {
+                fn bytes(&self) -> usize {
+                    size_of::<$t>()
+                }
+            }
+        )*
+    };
+}
+
+mem_sized_by_size_of!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64
+);
+
+/// A &str borrows its bytes, only the reference is counted.
+impl MemSized for &str {
+    fn bytes(&self) -> usize {
+        size_of::<&str>()
+    }
+}
+
+impl MemSized for String {
+    fn bytes(&self) -> usize {
+        size_of::<String>().saturating_add(self.capacity())
+    }
+}
+
+/// The elements are counted with their own `bytes()`, and the spare capacity
+/// by the size of the element type.
+impl<T: MemSized> MemSized for Vec<T> {
+    fn bytes(&self) -> usize {
+        let spare = (self.capacity() - self.len()).saturating_mul(size_of::<T>());
+
+        self.iter()
+            .fold(size_of::<Vec<T>>().saturating_add(spare), |total, v| {
+                total.saturating_add(v.bytes())
+            })
+    }
+}
+
+impl<T: MemSized> MemSized for Box<T> {
+    fn bytes(&self) -> usize {
+        size_of::<Box<T>>().saturating_add((**self).bytes())
+    }
+}
+
+impl<T: MemSized> MemSized for Option<T> {
+    fn bytes(&self) -> usize {
+        match self {
+            // The payload is stored inline, so only the rest of the Option
+            // is added to it.
+            Some(v) => (size_of::<Option<T>>() - size_of::<T>()).saturating_add(v.bytes()),
+            None => size_of::<Option<T>>(),
+        }
+    }
+}
+
+/// The byte budget of an LRU and the bytes its entries take.
+///
+/// The counts saturate rather than overflow, so an entry claiming usize::MAX
+/// bytes just evicts everything instead of wrapping the total around.
+pub(crate) struct Budget<K, V> {
+    pub limit: usize,
+    pub used: usize,
+    pub bytes: fn(&K, &V) -> usize,
+}
+
+impl<K: MemSized, V: MemSized> Budget<K, V> {
+    pub fn new(limit: usize) -> Budget<K, V> {
+        Budget {
+            limit,
+            used: 0,
+            bytes: |k, v| k.bytes().saturating_add(v.bytes()),
+        }
+    }
+}
+
+impl<K, V> Budget<K, V> {
+    /// Counts an entry added to the LRU.
+    pub fn add(&mut self, key: &K, value: &V) {
+        self.used = self.used.saturating_add((self.bytes)(key, value));
+    }
+
+    /// Uncounts an entry removed from the LRU.
+    pub fn remove(&mut self, key: &K, value: &V) {
+        self.used = self.used.saturating_sub((self.bytes)(key, value));
+    }
+
+    /// Returns a boolean indicating the entries take more than the budget.
+    pub fn exceeded(&self) -> bool {
+        self.used > self.limit
+    }
+
+    /// Returns a boolean indicating an entry fits in the budget on its own.
+    pub fn fits(&self, key: &K, value: &V) -> bool {
+        (self.bytes)(key, value) <= self.limit
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bytes() {
+        assert_eq!(7u32.bytes(), 4);
+        assert_eq!(String::with_capacity(10).bytes(), size_of::<String>() + 10);
+        assert_eq!(Some(1u64).bytes(), size_of::<Option<u64>>());
+
+        let mut strings = Vec::with_capacity(3);
+        strings.push(String::with_capacity(5));
+        assert_eq!(
+            strings.bytes(),
+            size_of::<Vec<String>>() + 3 * size_of::<String>() + 5
+        );
+    }
+
+    #[test]
+    fn saturating_accounting() {
+        struct Huge;
+
+        impl MemSized for Huge {
+            fn bytes(&self) -> usize {
+                usize::MAX
+            }
+        }
+
+        let mut budget = Budget::new(100);
+        budget.add(&1u8, &Huge);
+        budget.add(&2u8, &Huge);
+        assert_eq!(budget.used, usize::MAX);
+        assert!(budget.exceeded());
+
+        budget.remove(&1u8, &Huge);
+        budget.remove(&2u8, &Huge);
+        budget.remove(&3u8, &Huge);
+        assert_eq!(budget.used, 0);
+    }
+}
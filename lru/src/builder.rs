@@ -0,0 +1,251 @@
+use crate::budget::{Budget, MemSized};
+use crate::eviction::{EntryStats, Scorer};
+use crate::lru::LRU;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+
+#[cfg(feature = "metrics")]
+use crate::metrics::MetricsSink;
+
+/// The state of an LRUBuilder that hasn't been given a capacity yet.
+pub struct NoCapacity;
+
+/// The state of an LRUBuilder that has a capacity and can build an LRU.
+pub struct Capacity(usize);
+
+/// LRUBuilder configures an LRU. The capacity is tracked in the type of the
+/// builder, so calling `build()` before `capacity()` doesn't compile.
+///
+/// # Example
+///
+/// ```
+/// use lru::LRU;
+///
+/// let mut lru = LRU::builder().capacity(2).build();
+/// lru.add("a", 1);
+///
+/// assert_eq!(lru.get("a"), Some(1));
+/// ```
+///
+/// ```compile_fail
+/// use lru::LRU;
+///
+/// // There is no capacity, so there is no build().
+/// let lru: LRU<&str, u32> = LRU::builder().build();
+/// ```
+pub struct LRUBuilder<K, V, C, S = RandomState> {
+    capacity: C,
+    hasher: S,
+    scorer: Option<Scorer<K, V>>,
+    budget: Option<Budget<K, V>>,
+    sample: Option<usize>,
+    #[cfg(feature = "metrics")]
+    metrics: Option<Box<dyn MetricsSink>>,
+    _entries: PhantomData<(K, V)>,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> LRUBuilder<K, V, NoCapacity> {
+    pub(crate) fn new() -> LRUBuilder<K, V, NoCapacity> {
+        LRUBuilder {
+            capacity: NoCapacity,
+            hasher: RandomState::new(),
+            scorer: None,
+            budget: None,
+            sample: None,
+            #[cfg(feature = "metrics")]
+            metrics: None,
+            _entries: PhantomData,
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone, S> LRUBuilder<K, V, NoCapacity, S> {
+    /// Sets the maximum number of entries of the LRU.
+    pub fn capacity(self, capacity: usize) -> LRUBuilder<K, V, Capacity, S> {
+        LRUBuilder {
+            capacity: Capacity(capacity),
+            hasher: self.hasher,
+            scorer: self.scorer,
+            budget: self.budget,
+            sample: self.sample,
+            #[cfg(feature = "metrics")]
+            metrics: self.metrics,
+            _entries: PhantomData,
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone, C, S> LRUBuilder<K, V, C, S> {
+    /// Sets the BuildHasher used to hash the keys, e.g. a SeededState for
+    /// reproducible runs.
+    pub fn hasher<H: BuildHasher>(self, hasher: H) -> LRUBuilder<K, V, C, H> {
+        LRUBuilder {
+            capacity: self.capacity,
+            hasher,
+            scorer: self.scorer,
+            budget: self.budget,
+            sample: self.sample,
+            #[cfg(feature = "metrics")]
+            metrics: self.metrics,
+            _entries: PhantomData,
+        }
+    }
+
+    /// Sets the closure that picks the entry to evict out of the `sample`
+    /// least recently used entries, see `LRU::set_eviction_scorer()`.
+    pub fn eviction_scorer<F>(mut self, sample: usize, f: F) -> LRUBuilder<K, V, C, S>
+    where
+        F: FnMut(&K, &V, &EntryStats) -> u64 + 'static,
+    {
+        self.scorer = Some(Scorer {
+            sample,
+            score: Box::new(f),
+        });
+        self
+    }
+
+    /// Sets the byte budget of the entries, see `LRU::set_byte_budget()`.
+    pub fn byte_budget(mut self, bytes: usize) -> LRUBuilder<K, V, C, S>
+    where
+        K: MemSized,
+        V: MemSized,
+    {
+        self.budget = Some(Budget::new(bytes));
+        self
+    }
+
+    /// Evicts the least recently used of `sample` random entries instead of
+    /// keeping the entries in order, see `LRU::sample_evictions()`.
+    pub fn sampled_eviction(mut self, sample: usize) -> LRUBuilder<K, V, C, S> {
+        self.sample = Some(sample);
+        self
+    }
+
+    /// Sets the MetricsSink that receives the LRU's hits, misses, evictions
+    /// and size.
+    #[cfg(feature = "metrics")]
+    pub fn metrics_sink<M: MetricsSink + 'static>(mut self, sink: M) -> LRUBuilder<K, V, C, S> {
+        self.metrics = Some(Box::new(sink));
+        self
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone, S: BuildHasher> LRUBuilder<K, V, Capacity, S> {
+    /// Builds the LRU.
+    pub fn build(self) -> LRU<K, V, S> {
+        let mut lru = LRU::with_hasher(self.capacity.0, self.hasher);
+        lru.scorer = self.scorer;
+        lru.budget = self.budget;
+        if let Some(sample) = self.sample {
+            lru.sample_evictions(sample);
+        }
+
+        #[cfg(feature = "metrics")]
+        {
+            lru.metrics = self.metrics;
+        }
+
+        lru
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn build() {
+        let mut lru = LRU::builder().capacity(2).build();
+        lru.add("GOOGLE".to_string(), 50);
+        lru.add("FACEBOOK".to_string(), 100);
+        lru.add("APPLE".to_string(), 20);
+
+        assert_eq!(lru.len(), 2);
+        assert_eq!(lru.get("GOOGLE".to_string()), None);
+        assert_eq!(lru.get("APPLE".to_string()), Some(20));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn build_with_metrics_sink() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        struct Misses(Rc<Cell<u32>>);
+
+        impl MetricsSink for Misses {
+            fn miss(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        // The sink can be set before or after the capacity.
+        let misses = Rc::new(Cell::new(0));
+        let mut lru = LRU::<String, u32>::builder()
+            .metrics_sink(Misses(misses.clone()))
+            .capacity(2)
+            .build();
+
+        lru.get("GOOGLE".to_string());
+        assert_eq!(misses.get(), 1);
+    }
+
+    #[test]
+    fn build_with_hasher() {
+        use crate::hasher::SeededState;
+
+        let mut lru = LRU::builder()
+            .hasher(SeededState::new(1))
+            .capacity(1)
+            .build();
+        lru.add("a", 1);
+        lru.add("b", 2);
+
+        assert_eq!(lru.get("a"), None);
+        assert_eq!(lru.get("b"), Some(2));
+    }
+
+    #[test]
+    fn build_with_eviction_scorer() {
+        // Evict the largest value first, whatever its recency.
+        let mut lru = LRU::builder()
+            .eviction_scorer(3, |_: &&str, v: &u32, _| u64::MAX - *v as u64)
+            .capacity(3)
+            .build();
+        lru.add("a", 1);
+        lru.add("b", 30);
+        lru.add("c", 2);
+        lru.add("d", 4);
+
+        assert_eq!(lru.get("b"), None);
+        assert_eq!(lru.len(), 3);
+    }
+
+    #[test]
+    fn build_with_byte_budget() {
+        let mut lru = LRU::builder().byte_budget(40).capacity(10).build();
+        lru.add(1u64, String::from("a"));
+        lru.add(2u64, String::from("b"));
+
+        assert_eq!(lru.byte_budget(), Some(40));
+        assert_eq!(lru.len(), 1);
+        assert_eq!(lru.get(2), Some(String::from("b")));
+    }
+
+    #[test]
+    fn build_with_sampled_eviction() {
+        let mut lru = LRU::builder().sampled_eviction(4).capacity(4).build();
+        for (i, key) in ["a", "b", "c", "d"].iter().enumerate() {
+            lru.add(*key, i);
+        }
+        lru.get("a");
+
+        // The sample is drawn from a seeded generator, and the entry just
+        // read loses to any other entry it is sampled with.
+        lru.add("e", 4);
+        assert_eq!(lru.len(), 4);
+        assert_eq!(lru.get("a"), Some(0));
+        assert_eq!(lru.get("e"), Some(4));
+    }
+}
@@ -0,0 +1,22 @@
+use std::fmt;
+
+/// StructureError is returned when an operation would leave the structure in
+/// an inconsistent state, instead of panicking part way through it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructureError {
+    /// A node was already borrowed, or linked to itself, so it couldn't be
+    /// relinked.
+    AliasingConflict,
+}
+
+impl fmt::Display for StructureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StructureError::AliasingConflict => {
+                write!(f, "a node is already borrowed or linked to itself")
+            }
+        }
+    }
+}
+
+impl std::error::Error for StructureError {}
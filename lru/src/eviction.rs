@@ -0,0 +1,69 @@
+/// EntryStats describes how an entry of an LRU has been used, so an eviction
+/// scorer can weigh recency and frequency against the entry itself.
+///
+/// Times are ticks of the LRU's logical clock, which advances on every
+/// `add()` and every hit, so they can be compared but aren't durations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntryStats {
+    /// The tick the entry was added at.
+    pub inserted: u64,
+    /// The tick of the most recent hit, or of the insert if there was none.
+    pub last_access: u64,
+    /// The number of times the entry has been read.
+    pub hits: u64,
+}
+
+impl EntryStats {
+    pub(crate) fn new(now: u64) -> EntryStats {
+        EntryStats {
+            inserted: now,
+            last_access: now,
+            hits: 0,
+        }
+    }
+
+    /// Records a hit at tick `now`.
+    pub(crate) fn hit(&mut self, now: u64) {
+        self.last_access = now;
+        self.hits += 1;
+    }
+}
+
+/// A closure scoring an entry, the lowest scored entry is evicted.
+type ScoreFn<K, V> = Box<dyn FnMut(&K, &V, &EntryStats) -> u64>;
+
+/// A scoring closure and the number of least recently used entries it picks
+/// the victim from.
+pub(crate) struct Scorer<K, V> {
+    pub sample: usize,
+    pub score: ScoreFn<K, V>,
+}
+
+/// The entries of an LRU in sampled mode, in a Vec so that random entries can
+/// be picked in O(1). Each entry remembers its slot, so it can be swapped out
+/// of the Vec when it is removed.
+pub(crate) struct Sampler<E> {
+    pub sample: usize,
+    pub entries: Vec<E>,
+    // The state of a xorshift generator, never 0.
+    state: u64,
+}
+
+impl<E> Sampler<E> {
+    pub(crate) fn new(sample: usize) -> Sampler<E> {
+        Sampler {
+            sample: sample.max(1),
+            entries: Vec::new(),
+            state: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+
+    /// Returns the slot of a random entry, the Sampler must not be empty.
+    pub(crate) fn pick(&mut self) -> usize {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+
+        (self.state % self.entries.len() as u64) as usize
+    }
+}
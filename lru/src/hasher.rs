@@ -0,0 +1,64 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{BuildHasher, Hasher};
+
+/// SeededState is a BuildHasher whose hashes only depend on its seed, unlike
+/// std's RandomState which picks random keys for every map. Use it for tests
+/// and simulations that must behave the same on every run.
+///
+/// The hashers are std's DefaultHasher, primed with the seed. Its algorithm
+/// isn't guaranteed to stay the same across Rust releases, so the hashes are
+/// only reproducible with the same toolchain.
+///
+/// SeededState doesn't protect against HashDoS, keep RandomState for keys
+/// that come from untrusted input.
+///
+/// # Example
+///
+/// ```
+/// use lru::{SeededState, LRU};
+///
+/// let mut lru = LRU::with_hasher(2, SeededState::new(42));
+/// lru.add("a", 1);
+///
+/// assert_eq!(lru.get("a"), Some(1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SeededState {
+    seed: u64,
+}
+
+impl SeededState {
+    /// Returns a SeededState producing the hashes for `seed`.
+    pub fn new(seed: u64) -> SeededState {
+        SeededState { seed }
+    }
+
+    /// Returns the seed of the SeededState.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl BuildHasher for SeededState {
+    type Hasher = DefaultHasher;
+
+    fn build_hasher(&self) -> DefaultHasher {
+        let mut hasher = DefaultHasher::new();
+        hasher.write_u64(self.seed);
+        hasher
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_hash() {
+        let a = SeededState::new(7);
+        let b = SeededState::new(7);
+
+        assert_eq!(a.hash_one("key"), b.hash_one("key"));
+        assert_ne!(a.hash_one("key"), SeededState::new(8).hash_one("key"));
+    }
+}
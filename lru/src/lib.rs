@@ -0,0 +1,30 @@
+//! A crate that implements an LRU cache, a PriorityCache that expires
+//! entries by deadline and evicts them by priority, and a WeakValueCache that
+//! doesn't keep its values alive.
+//!
+//! # Features
+//!
+//! - `metrics` - reports hits, misses, evictions and size to a `MetricsSink`.
+pub use crate::budget::MemSized;
+pub use crate::builder::{Capacity, LRUBuilder, NoCapacity};
+pub use crate::error::StructureError;
+pub use crate::eviction::EntryStats;
+pub use crate::hasher::SeededState;
+pub use crate::lru::{LRUIterator, LRU};
+pub use crate::priority_cache::PriorityCache;
+pub use crate::weak_cache::WeakValueCache;
+pub use traits::{Clock, ManualClock, SystemClock};
+
+#[cfg(feature = "metrics")]
+pub use crate::metrics::MetricsSink;
+
+mod budget;
+mod builder;
+mod error;
+mod eviction;
+mod hasher;
+mod lru;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod priority_cache;
+mod weak_cache;
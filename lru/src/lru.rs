@@ -0,0 +1,1710 @@
+/// LRU - Least Recently Used Cache
+///
+/// Guarantees:
+/// - Read: O(1)
+/// - Write: O(1)
+/// - Eviction: O(1)
+///
+/// Abstract Datastructure:
+/// - read(T)
+///     - Look up MAP, follow ptr to get value in LIST
+/// - write(T)
+///     - When we add to the LRU we:
+///         - Check if it's in the HashMap, if cache miss:
+///             - Add item to HEAD of list
+///             - Add item in MAP with ptr to list
+///         - If adding new item of LRU will be greater than size limit then evict()
+///             - Then add new item
+///
+/// - (private) evict()
+///     - Look up TAIL in list:
+///         - remove previous pointer
+///         - remove tail pointer and give to previous
+///         - drop from memory
+///         - remove item in HashMap
+///
+/// Datastructure:
+/// - LinkedList (Doubly):
+///     - Contains: T: the key, V: some interesting value
+///
+/// - HashMap:
+///     - Contains: T (key), V (ptr)
+///
+/// Invariants:
+/// - size of LRU
+///
+/// LinkedList ADT:
+/// - insert_head()
+/// - remove() // assumes only removing from tail
+///
+use crate::budget::{Budget, MemSized};
+use crate::error::StructureError;
+use crate::eviction::{EntryStats, Sampler, Scorer};
+use std::{
+    cell::{RefCell, RefMut},
+    cmp::PartialEq,
+    collections::{
+        hash_map::{self, RandomState},
+        HashMap, HashSet,
+    },
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+    rc::Rc,
+};
+use traits::{Collection, Map};
+
+use crate::builder::{LRUBuilder, NoCapacity};
+#[cfg(feature = "metrics")]
+use crate::metrics::MetricsSink;
+
+/// The number of deferred entries dropped by each call to `add()`.
+const DEFERRED_DROP_STEP: usize = 8;
+
+/// A callback registered with `on_insert()` or `on_remove()`.
+type Observer<K, V> = Box<dyn FnMut(&K, &V)>;
+
+#[derive(Clone)]
+struct Node<K: Clone + PartialEq, V: Clone> {
+    pub value: (K, V),
+    pub next: Option<NodeRef<K, V>>,
+    pub prev: Option<NodeRef<K, V>>,
+    pub stats: EntryStats,
+    // The slot of the entry in the Sampler, in sampled mode.
+    pub slot: usize,
+}
+
+#[derive(Clone)]
+struct NodeRef<K: Clone + PartialEq, V: Clone>(pub Rc<RefCell<Node<K, V>>>);
+
+impl<K: Clone + PartialEq, V: Clone> NodeRef<K, V> {
+    pub fn init(key: K, value: V) -> NodeRef<K, V> {
+        NodeRef::init_at(key, value, 0)
+    }
+
+    /// Returns a node whose entry was added at tick `now` of the LRU's clock.
+    pub fn init_at(key: K, value: V, now: u64) -> NodeRef<K, V> {
+        let node = Node {
+            value: (key, value),
+            next: None,
+            prev: None,
+            stats: EntryStats::new(now),
+            slot: 0,
+        };
+
+        NodeRef(Rc::new(RefCell::new(node)))
+    }
+
+    pub fn get_value(&self) -> (K, V) {
+        self.0.borrow().value.clone()
+    }
+
+    pub fn get_next(&self) -> Option<NodeRef<K, V>> {
+        self.0.borrow().next.clone()
+    }
+
+    /// Mutably borrows the node, returning an AliasingConflict if it is
+    /// already borrowed.
+    pub fn try_borrow_mut(&self) -> Result<RefMut<'_, Node<K, V>>, StructureError> {
+        self.0
+            .try_borrow_mut()
+            .map_err(|_| StructureError::AliasingConflict)
+    }
+
+    /// Returns a boolean indicating both NodeRefs point to the same node.
+    pub fn ptr_eq(&self, other: &NodeRef<K, V>) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+struct DoublyLinkedList<K: Clone + PartialEq, V: Clone> {
+    pub head: Option<NodeRef<K, V>>,
+    pub tail: Option<NodeRef<K, V>>,
+    pub size: usize,
+}
+
+impl<K: Clone + PartialEq, V: Clone> DoublyLinkedList<K, V> {
+    pub fn init() -> DoublyLinkedList<K, V> {
+        DoublyLinkedList {
+            head: None,
+            tail: None,
+            size: 0,
+        }
+    }
+
+    pub fn get_head(&self) -> Option<NodeRef<K, V>> {
+        self.head.clone()
+    }
+
+    pub fn get_tail(&self) -> Option<NodeRef<K, V>> {
+        self.tail.clone()
+    }
+
+    #[allow(dead_code)]
+    pub fn insert(&mut self, value: (K, V)) {
+        self.insert_node(NodeRef::init(value.0, value.1), true);
+    }
+
+    pub fn insert_node(&mut self, new_head: NodeRef<K, V>, new_node: bool) {
+        match self.head.take() {
+            Some(prev) => {
+                prev.0.borrow_mut().prev = Some(new_head.clone());
+                new_head.0.borrow_mut().next = Some(prev.clone());
+
+                if self.size == 1 {
+                    self.tail = Some(prev.clone());
+                }
+            }
+            None => self.tail = Some(new_head.clone()),
+        }
+
+        self.head = Some(new_head.clone());
+
+        if new_node {
+            self.size += 1;
+        }
+    }
+
+    /// Moves a node to the head of the list.
+    ///
+    /// Every node involved is borrowed before any link is changed, so an
+    /// AliasingConflict leaves the list untouched.
+    pub fn requeue_node(&mut self, node: NodeRef<K, V>) -> Result<(), StructureError> {
+        // The node is already the most recently used, relinking it in front of
+        // itself would make it point at itself.
+        if let Some(head) = &self.head {
+            if head.ptr_eq(&node) {
+                return Ok(());
+            }
+        }
+
+        let (prev_node, next_node) = {
+            let n = node.try_borrow_mut()?;
+            (n.prev.clone(), n.next.clone())
+        };
+
+        // A node linked to itself, or a neighbour on both sides, would be
+        // borrowed twice while relinking.
+        let linked_to_itself = [&prev_node, &next_node]
+            .iter()
+            .any(|n| n.as_ref().is_some_and(|n| n.ptr_eq(&node)));
+        let same_neighbours = match (&prev_node, &next_node) {
+            (Some(p), Some(n)) => p.ptr_eq(n),
+            _ => false,
+        };
+        if linked_to_itself || same_neighbours {
+            return Err(StructureError::AliasingConflict);
+        }
+
+        let is_tail = self.tail.as_ref().is_some_and(|t| t.ptr_eq(&node));
+
+        {
+            let mut n = node.try_borrow_mut()?;
+            let mut p = prev_node.as_ref().map(|p| p.try_borrow_mut()).transpose()?;
+            let mut nx = next_node.as_ref().map(|n| n.try_borrow_mut()).transpose()?;
+
+            if let Some(p) = p.as_mut() {
+                p.next = next_node.clone();
+            }
+            if let Some(nx) = nx.as_mut() {
+                nx.prev = prev_node.clone();
+            }
+
+            n.prev = None;
+            n.next = None;
+        }
+
+        if is_tail {
+            self.tail = prev_node;
+        }
+
+        self.insert_node(node, false);
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub fn remove(&mut self) {
+        match self.tail.take() {
+            Some(old_tail) => {
+                let new_tail = old_tail.0.borrow_mut().prev.clone();
+
+                if let Some(t) = new_tail.clone() {
+                    t.clone().0.borrow_mut().next = None;
+                }
+                old_tail.0.borrow_mut().prev = None;
+
+                self.tail = new_tail.clone();
+                self.size -= 1;
+
+                if self.size == 0 {
+                    self.head = None;
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Removes a node from anywhere in the list.
+    pub fn unlink(&mut self, node: &NodeRef<K, V>) {
+        let (prev, next) = {
+            let mut n = node.0.borrow_mut();
+            (n.prev.take(), n.next.take())
+        };
+
+        match &prev {
+            Some(p) => p.0.borrow_mut().next = next.clone(),
+            None => self.head = next.clone(),
+        }
+        match &next {
+            Some(n) => n.0.borrow_mut().prev = prev.clone(),
+            None => self.tail = prev,
+        }
+
+        self.size -= 1;
+    }
+
+    pub fn clear(&mut self) {
+        self.tail = None;
+        self.size = 0;
+
+        // Break the next and prev links between the nodes, otherwise each pair
+        // of neighbours keep each other alive.
+        let mut current = self.head.take();
+        while let Some(node) = current {
+            current = node.0.borrow_mut().next.take();
+            node.0.borrow_mut().prev = None;
+        }
+    }
+}
+
+/// Iterates over the entries of an LRU, from the most recently used to the
+/// least recently used. This Iterator will borrow the LRU.
+pub struct LRUIterator<'a, K: Clone + PartialEq, V: Clone> {
+    _lru: PhantomData<&'a ()>,
+    current: Option<NodeRef<K, V>>,
+}
+
+impl<'a, K: Clone + PartialEq, V: Clone> Iterator for LRUIterator<'a, K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        self.current.take().map(|node| {
+            self.current = node.get_next();
+            node.get_value()
+        })
+    }
+}
+
+/// The keys are hashed with `S`, std's RandomState unless the LRU is created
+/// with `with_hasher()`.
+pub struct LRU<K: Clone + PartialEq, V: Clone, S = RandomState> {
+    list: DoublyLinkedList<K, V>,
+    map: HashMap<K, NodeRef<K, V>, S>,
+    limit: usize,
+    size: usize,
+    deferred: Vec<hash_map::IntoIter<K, NodeRef<K, V>>>,
+    on_insert: Vec<Observer<K, V>>,
+    on_remove: Vec<Observer<K, V>>,
+    // Picks the entry to evict, the least recently used when there is none.
+    pub(crate) scorer: Option<Scorer<K, V>>,
+    // The logical clock of the EntryStats, advanced by adds and hits.
+    clock: u64,
+    // The byte budget of the entries, on top of the limit on their number.
+    pub(crate) budget: Option<Budget<K, V>>,
+    // The entries to sample the victims from, in sampled mode.
+    sampler: Option<Sampler<NodeRef<K, V>>>,
+    #[cfg(feature = "metrics")]
+    pub(crate) metrics: Option<Box<dyn MetricsSink>>,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> LRU<K, V> {
+    pub fn init(limit: usize) -> LRU<K, V> {
+        LRU::with_hasher(limit, RandomState::new())
+    }
+
+    /// Returns an LRUBuilder, the capacity must be set before the LRU can be
+    /// built.
+    pub fn builder() -> LRUBuilder<K, V, NoCapacity> {
+        LRUBuilder::new()
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone, S: BuildHasher> LRU<K, V, S> {
+    /// Returns an LRU holding at most `limit` entries, hashing its keys with
+    /// `hasher`, e.g. a SeededState for reproducible runs.
+    pub fn with_hasher(limit: usize, hasher: S) -> LRU<K, V, S> {
+        LRU {
+            list: DoublyLinkedList::init(),
+            map: HashMap::with_hasher(hasher),
+            limit,
+            size: 0,
+            deferred: Vec::new(),
+            on_insert: Vec::new(),
+            on_remove: Vec::new(),
+            scorer: None,
+            clock: 0,
+            budget: None,
+            sampler: None,
+            #[cfg(feature = "metrics")]
+            metrics: None,
+        }
+    }
+
+    /// Switches the LRU to sampled eviction, like the approximated LRU of
+    /// Redis, for caches with millions of entries. Hits no longer move the
+    /// entry to the front of the list, they only record the time of the
+    /// access. When an entry has to be evicted, `sample` random entries are
+    /// picked and the least recently used of them is evicted, or the lowest
+    /// scored if there is an eviction scorer.
+    ///
+    /// Reads no longer write to the list, at the cost of sometimes evicting
+    /// an entry that isn't the least recently used. `iter()` then returns the
+    /// entries in the order they were added rather than used.
+    ///
+    /// Time Complexity: O(sample) per eviction, O(n) to switch a non-empty
+    /// LRU
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lru::LRU;
+    ///
+    /// let mut lru = LRU::init(3);
+    /// lru.sample_evictions(5);
+    ///
+    /// lru.add("a", 1);
+    /// lru.add("b", 2);
+    /// lru.add("c", 3);
+    /// lru.get("a");
+    ///
+    /// // One of the sampled entries is evicted, most likely "b".
+    /// lru.add("d", 4);
+    /// assert_eq!(lru.len(), 3);
+    /// assert_eq!(lru.get("d"), Some(4));
+    /// ```
+    pub fn sample_evictions(&mut self, sample: usize) {
+        let mut sampler = Sampler::new(sample);
+
+        let mut current = self.list.get_head();
+        while let Some(node) = current {
+            node.0.borrow_mut().slot = sampler.entries.len();
+            current = node.get_next();
+            sampler.entries.push(node);
+        }
+
+        self.sampler = Some(sampler);
+    }
+
+    /// Adds a new entry to the Sampler, in sampled mode.
+    fn track(&mut self, node: &NodeRef<K, V>) {
+        if let Some(sampler) = self.sampler.as_mut() {
+            node.0.borrow_mut().slot = sampler.entries.len();
+            sampler.entries.push(node.clone());
+        }
+    }
+
+    /// Removes an entry from the Sampler, in sampled mode. The last entry
+    /// takes its slot.
+    fn untrack(&mut self, node: &NodeRef<K, V>) {
+        if let Some(sampler) = self.sampler.as_mut() {
+            let slot = node.0.borrow().slot;
+            sampler.entries.swap_remove(slot);
+
+            if let Some(moved) = sampler.entries.get(slot) {
+                moved.0.borrow_mut().slot = slot;
+            }
+        }
+    }
+
+    /// Sets a closure that picks the entry to evict, like the sampled LRU of
+    /// Redis. When the LRU is full, the `sample` least recently used entries
+    /// are scored and the one with the lowest score is evicted, the least
+    /// recently used of them on a tie.
+    ///
+    /// The closure is given the key, the value and the EntryStats of each
+    /// entry, so it can weigh recency and frequency against e.g. the size of
+    /// the value or the cost of recomputing it.
+    ///
+    /// Time Complexity: O(sample) per eviction
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lru::LRU;
+    ///
+    /// // Keep the entries that are read the most.
+    /// let mut lru = LRU::init(2);
+    /// lru.set_eviction_scorer(2, |_: &&str, _: &u32, stats| stats.hits);
+    ///
+    /// lru.add("a", 1);
+    /// lru.add("b", 2);
+    /// lru.get("a");
+    /// lru.get("a");
+    /// lru.get("b");
+    ///
+    /// // "a" is the least recent, but it has been read twice.
+    /// lru.add("c", 3);
+    /// assert_eq!(lru.get("a"), Some(1));
+    /// assert_eq!(lru.get("b"), None);
+    /// ```
+    pub fn set_eviction_scorer<F>(&mut self, sample: usize, f: F)
+    where
+        F: FnMut(&K, &V, &EntryStats) -> u64 + 'static,
+    {
+        self.scorer = Some(Scorer {
+            sample,
+            score: Box::new(f),
+        });
+    }
+
+    /// Holds the entries to a total of `bytes`, as reported by MemSized, on
+    /// top of the limit on their number. After every `add()` entries are
+    /// evicted, as picked by the eviction scorer, until the total is within
+    /// the budget again. An entry larger than the whole budget isn't added at
+    /// all and the other entries are kept, but an older value of its key is
+    /// removed, as it would be stale, and passed to the remove callbacks.
+    ///
+    /// Setting a budget on a non-empty LRU counts the entries it already
+    /// holds and evicts down to the new budget straight away.
+    ///
+    /// Time Complexity: O(1) per evicted entry, O(n) to count the entries
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lru::LRU;
+    ///
+    /// let mut lru = LRU::init(100);
+    /// lru.set_byte_budget(2 * 4 + 2 * 8);
+    ///
+    /// lru.add(1u32, 10u64);
+    /// lru.add(2u32, 20u64);
+    /// lru.add(3u32, 30u64);
+    ///
+    /// // Each entry takes 12 bytes, only two fit.
+    /// assert_eq!(lru.len(), 2);
+    /// assert_eq!(lru.bytes_used(), Some(24));
+    /// assert_eq!(lru.get(1), None);
+    /// ```
+    pub fn set_byte_budget(&mut self, bytes: usize)
+    where
+        K: MemSized,
+        V: MemSized,
+    {
+        let mut budget = Budget::new(bytes);
+        for (key, value) in self.iter() {
+            budget.add(&key, &value);
+        }
+        self.budget = Some(budget);
+
+        self.enforce_budget();
+    }
+
+    /// Returns the byte budget set by `set_byte_budget()`.
+    pub fn byte_budget(&self) -> Option<usize> {
+        self.budget.as_ref().map(|b| b.limit)
+    }
+
+    /// Returns the bytes taken by the entries, or None without a byte budget.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn bytes_used(&self) -> Option<usize> {
+        self.budget.as_ref().map(|b| b.used)
+    }
+
+    /// Evicts entries until they are within the byte budget, if there is one.
+    fn enforce_budget(&mut self) {
+        while self.budget.as_ref().is_some_and(|b| b.exceeded()) {
+            if !self.evict() {
+                break;
+            }
+        }
+    }
+
+    /// Evicts the entry picked by victim(), returning false if the LRU is
+    /// empty.
+    fn evict(&mut self) -> bool {
+        let victim = match self.victim() {
+            Some(victim) => victim,
+            None => return false,
+        };
+
+        let (key, value) = victim.get_value();
+        self.map.remove(&key);
+        self.list.unlink(&victim);
+        self.untrack(&victim);
+        self.size -= 1;
+        self.forget(&key, &value);
+        self.removed(&key, &value);
+
+        #[cfg(feature = "metrics")]
+        self.record(|m| m.eviction());
+
+        true
+    }
+
+    /// Uncounts an entry from the byte budget, if there is one.
+    /// Unlinks the entry of `key`, if any, returning it.
+    fn detach(&mut self, key: &K) -> Option<(K, V)> {
+        let old = self.map.remove(key)?;
+        let (old_key, old_value) = old.get_value();
+        self.list.unlink(&old);
+        self.untrack(&old);
+        self.size -= 1;
+        self.forget(&old_key, &old_value);
+
+        Some((old_key, old_value))
+    }
+
+    fn forget(&mut self, key: &K, value: &V) {
+        if let Some(budget) = self.budget.as_mut() {
+            budget.remove(key, value);
+        }
+    }
+
+    /// Returns the entry to evict: the lowest scored of the sampled entries,
+    /// or the least recently used without a scorer.
+    fn victim(&mut self) -> Option<NodeRef<K, V>> {
+        if self.sampler.is_some() {
+            return self.sampled_victim();
+        }
+
+        let tail = self.list.get_tail();
+        let scorer = match self.scorer.as_mut() {
+            Some(scorer) => scorer,
+            None => return tail,
+        };
+
+        let mut victim: Option<(u64, NodeRef<K, V>)> = None;
+        let mut current = tail;
+        for _ in 0..scorer.sample.max(1) {
+            let node = match current {
+                Some(node) => node,
+                None => break,
+            };
+
+            let score = {
+                let n = node.0.borrow();
+                (scorer.score)(&n.value.0, &n.value.1, &n.stats)
+            };
+            if victim.as_ref().is_none_or(|(lowest, _)| score < *lowest) {
+                victim = Some((score, node.clone()));
+            }
+
+            current = node.0.borrow().prev.clone();
+        }
+
+        victim.map(|(_, node)| node)
+    }
+
+    /// Returns the entry to evict in sampled mode: the lowest scored of the
+    /// random entries, or the least recently used of them without a scorer.
+    fn sampled_victim(&mut self) -> Option<NodeRef<K, V>> {
+        let sampler = self.sampler.as_mut()?;
+        if sampler.entries.is_empty() {
+            return None;
+        }
+
+        let mut victim: Option<(u64, NodeRef<K, V>)> = None;
+        for _ in 0..sampler.sample {
+            let slot = sampler.pick();
+            let node = sampler.entries[slot].clone();
+
+            let score = {
+                let n = node.0.borrow();
+                match self.scorer.as_mut() {
+                    Some(scorer) => (scorer.score)(&n.value.0, &n.value.1, &n.stats),
+                    None => n.stats.last_access,
+                }
+            };
+            if victim.as_ref().is_none_or(|(lowest, _)| score < *lowest) {
+                victim = Some((score, node));
+            }
+        }
+
+        victim.map(|(_, node)| node)
+    }
+
+    /// Advances the logical clock, returning the new tick.
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    /// Sets the MetricsSink that receives the LRU's hits, misses, evictions
+    /// and size, replacing any previous sink.
+    #[cfg(feature = "metrics")]
+    pub fn set_metrics_sink<M: MetricsSink + 'static>(&mut self, sink: M) {
+        self.metrics = Some(Box::new(sink));
+    }
+
+    /// Passes the MetricsSink, if there is one, to `f`.
+    #[cfg(feature = "metrics")]
+    fn record<F: FnOnce(&mut dyn MetricsSink)>(&mut self, f: F) {
+        if let Some(sink) = self.metrics.as_mut() {
+            f(sink.as_mut());
+        }
+    }
+
+    pub fn add(&mut self, key: K, value: V) {
+        self.drain_deferred(DEFERRED_DROP_STEP);
+
+        // Evicting everything else wouldn't make room for it, but the older
+        // value of the key mustn't be served in its place.
+        if self.budget.as_ref().is_some_and(|b| !b.fits(&key, &value)) {
+            if let Some((old_key, old_value)) = self.detach(&key) {
+                self.removed(&old_key, &old_value);
+            }
+            return;
+        }
+
+        let now = self.tick();
+        let node = NodeRef::init_at(key.clone(), value.clone(), now);
+
+        // The new value replaces the entry of the key, rather than taking a
+        // second slot.
+        self.detach(&key);
+
+        if self.size == self.limit {
+            self.evict();
+        }
+
+        for f in self.on_insert.iter_mut() {
+            f(&key, &value);
+        }
+
+        if let Some(budget) = self.budget.as_mut() {
+            budget.add(&key, &value);
+        }
+        self.track(&node);
+        self.map.insert(key, node.clone());
+        self.list.insert_node(node, true);
+        self.size += 1;
+        self.enforce_budget();
+
+        #[cfg(feature = "metrics")]
+        {
+            let size = self.size;
+            self.record(|m| m.size(size));
+        }
+    }
+
+    pub fn get(&mut self, key: K) -> Option<V> {
+        self.try_get(key).expect("failed to requeue the LRU entry")
+    }
+
+    /// Looks up a key like `get()`, returning an AliasingConflict instead of
+    /// panicking if the entry can't be moved to the front of the LRU, e.g.
+    /// because its node is already borrowed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lru::LRU;
+    ///
+    /// let mut lru = LRU::init(2);
+    /// lru.add("a", 1);
+    ///
+    /// assert_eq!(lru.try_get("a"), Ok(Some(1)));
+    /// assert_eq!(lru.try_get("b"), Ok(None));
+    /// ```
+    pub fn try_get(&mut self, key: K) -> Result<Option<V>, StructureError> {
+        let value = match self.map.get(&key) {
+            Some(node) => {
+                let item = node.clone();
+                if self.sampler.is_none() {
+                    self.list.requeue_node(item.clone())?;
+                }
+
+                let now = self.tick();
+                let mut n = item.try_borrow_mut()?;
+                n.stats.hit(now);
+
+                Some(n.value.1.clone())
+            }
+            _ => None,
+        };
+
+        #[cfg(feature = "metrics")]
+        self.record(|m| match value {
+            Some(_) => m.hit(),
+            None => m.miss(),
+        });
+
+        Ok(value)
+    }
+
+    /// Looks up a batch of keys, returning the value for each key in the same
+    /// order as the keys.
+    ///
+    /// Every hit is promoted once, in the order the keys first appear, so the
+    /// LRU ends up as if each distinct key had been fetched by `get()` a single
+    /// time. Repeated keys in the batch don't shuffle the entries again.
+    ///
+    /// Time Complexity: O(k), where k is the number of keys
+    /// Space Complexity: O(k)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lru::LRU;
+    ///
+    /// let mut lru = LRU::init(3);
+    /// lru.add("a", 1);
+    /// lru.add("b", 2);
+    /// lru.add("c", 3);
+    ///
+    /// assert_eq!(lru.get_many(&["a", "x", "a"]), vec![Some(1), None, Some(1)]);
+    /// assert_eq!(lru.iter().next(), Some(("a", 1)));
+    /// ```
+    pub fn get_many(&mut self, keys: &[K]) -> Vec<Option<V>> {
+        let mut seen = HashSet::new();
+        let mut hits = Vec::new();
+
+        let values: Vec<Option<V>> = keys
+            .iter()
+            .map(|key| {
+                self.map.get(key).map(|node| {
+                    if seen.insert(key) {
+                        hits.push(node.clone());
+                    }
+
+                    node.0.borrow().value.1.clone()
+                })
+            })
+            .collect();
+
+        for node in hits {
+            let now = self.tick();
+            node.0.borrow_mut().stats.hit(now);
+            if self.sampler.is_none() {
+                self.list
+                    .requeue_node(node)
+                    .expect("failed to requeue the LRU entry");
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        for value in &values {
+            self.record(|m| match value {
+                Some(_) => m.hit(),
+                None => m.miss(),
+            });
+        }
+
+        values
+    }
+
+    /// Returns the number of entries in the LRU.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Returns a boolean indicating the LRU is empty.
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Removes every entry from the LRU.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    pub fn clear(&mut self) {
+        self.removed_all();
+        self.map.clear();
+        self.list.clear();
+        self.size = 0;
+        if let Some(sampler) = self.sampler.as_mut() {
+            sampler.entries.clear();
+        }
+        if let Some(budget) = self.budget.as_mut() {
+            budget.used = 0;
+        }
+
+        #[cfg(feature = "metrics")]
+        self.record(|m| m.size(0));
+    }
+
+    /// Registers a callback that is called with every entry added to the LRU,
+    /// e.g. to keep an external index in sync with the cache.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lru::LRU;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    ///
+    /// let cached = Rc::new(RefCell::new(Vec::new()));
+    /// let mut lru = LRU::init(1);
+    ///
+    /// let index = cached.clone();
+    /// lru.on_insert(move |k: &&str, _: &u32| index.borrow_mut().push(*k));
+    /// let index = cached.clone();
+    /// lru.on_remove(move |k, _| index.borrow_mut().retain(|c| c != k));
+    ///
+    /// lru.add("a", 1);
+    /// lru.add("b", 2);
+    ///
+    /// assert_eq!(*cached.borrow(), vec!["b"]);
+    /// ```
+    pub fn on_insert<F>(&mut self, f: F)
+    where
+        F: FnMut(&K, &V) + 'static,
+    {
+        self.on_insert.push(Box::new(f));
+    }
+
+    /// Registers a callback that is called with every entry removed from the
+    /// LRU, when it is evicted or the LRU is cleared.
+    ///
+    /// With a remove callback registered, `clear_lazy()` has to visit every
+    /// entry and is O(n).
+    pub fn on_remove<F>(&mut self, f: F)
+    where
+        F: FnMut(&K, &V) + 'static,
+    {
+        self.on_remove.push(Box::new(f));
+    }
+
+    /// Calls the remove callbacks with an entry removed from the LRU.
+    fn removed(&mut self, key: &K, value: &V) {
+        for f in self.on_remove.iter_mut() {
+            f(key, value);
+        }
+    }
+
+    /// Calls the remove callbacks with every entry, before they are cleared.
+    fn removed_all(&mut self) {
+        if self.on_remove.is_empty() {
+            return;
+        }
+
+        let entries: Vec<(K, V)> = self.iter().collect();
+        for (key, value) in entries {
+            self.removed(&key, &value);
+        }
+    }
+
+    /// Removes every entry from the LRU without dropping them. The entries are
+    /// moved to a deferred list and dropped a few at a time on each `add()`, or
+    /// explicitly through `drain_deferred()`, so clearing a huge cache doesn't
+    /// stall the caller while millions of values are dropped.
+    ///
+    /// In sampled mode, the Sampler lets go of its references to the entries
+    /// straight away, which is O(n) but doesn't drop any of them.
+    ///
+    /// Time Complexity: O(1), O(n) in sampled mode
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lru::LRU;
+    ///
+    /// let mut lru = LRU::init(3);
+    /// lru.add("a", 1);
+    /// lru.add("b", 2);
+    ///
+    /// lru.clear_lazy();
+    /// assert!(lru.is_empty());
+    /// assert_eq!(lru.deferred_len(), 2);
+    ///
+    /// assert_eq!(lru.drain_deferred(1), 1);
+    /// assert_eq!(lru.drain_deferred(10), 1);
+    /// assert_eq!(lru.deferred_len(), 0);
+    /// ```
+    pub fn clear_lazy(&mut self)
+    where
+        S: Clone,
+    {
+        self.removed_all();
+        let hasher = self.map.hasher().clone();
+        let map = std::mem::replace(&mut self.map, HashMap::with_hasher(hasher));
+        self.deferred.push(map.into_iter());
+
+        // The nodes are still linked to each other, the deferred entries keep
+        // hold of every node until drain_deferred() unlinks them.
+        self.list.head = None;
+        self.list.tail = None;
+        self.list.size = 0;
+        self.size = 0;
+        if let Some(sampler) = self.sampler.as_mut() {
+            sampler.entries.clear();
+        }
+        if let Some(budget) = self.budget.as_mut() {
+            budget.used = 0;
+        }
+
+        #[cfg(feature = "metrics")]
+        self.record(|m| m.size(0));
+    }
+
+    /// Drops up to `max` of the entries left behind by `clear_lazy()`,
+    /// returning the number of entries dropped.
+    ///
+    /// Time Complexity: O(max)
+    /// Space Complexity: O(1)
+    pub fn drain_deferred(&mut self, max: usize) -> usize {
+        let mut dropped = 0;
+
+        while dropped < max {
+            let entries = match self.deferred.last_mut() {
+                Some(entries) => entries,
+                None => break,
+            };
+
+            match entries.next() {
+                Some((_, node)) => {
+                    // Unlinking the node releases it once both of its
+                    // neighbours have been unlinked too, so each call only
+                    // frees a bounded number of nodes.
+                    let mut node = node.0.borrow_mut();
+                    node.next = None;
+                    node.prev = None;
+                    dropped += 1;
+                }
+                None => {
+                    self.deferred.pop();
+                }
+            }
+        }
+
+        dropped
+    }
+
+    /// Returns the number of entries waiting to be dropped after a call to
+    /// `clear_lazy()`.
+    ///
+    /// Time Complexity: O(c), where c is the number of calls to `clear_lazy()`
+    /// since the deferred entries were last drained
+    /// Space Complexity: O(1)
+    pub fn deferred_len(&self) -> usize {
+        self.deferred.iter().map(|entries| entries.len()).sum()
+    }
+
+    /// Returns an iterator over the entries from the most recently used to the
+    /// least recently used. Iterating does not change the order of the entries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lru::LRU;
+    ///
+    /// let mut lru = LRU::init(2);
+    /// lru.add("a", 1);
+    /// lru.add("b", 2);
+    ///
+    /// assert_eq!(lru.iter().collect::<Vec<_>>(), vec![("b", 2), ("a", 1)]);
+    /// ```
+    pub fn iter(&self) -> LRUIterator<'_, K, V> {
+        LRUIterator {
+            _lru: PhantomData,
+            current: self.list.get_head(),
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone, S: BuildHasher> Collection for LRU<K, V, S> {
+    type Iter<'a>
+        = LRUIterator<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+        S: 'a;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn clear(&mut self) {
+        self.clear()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.iter()
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone, S: BuildHasher> Map<K, V> for LRU<K, V, S> {
+    fn insert(&mut self, key: K, value: V) {
+        self.add(key, value)
+    }
+
+    fn get(&mut self, key: &K) -> Option<V> {
+        LRU::get(self, key.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn init_node() {
+        let node = NodeRef::init("hello".to_string(), 0);
+        assert_eq!(node.get_value(), ("hello".to_owned(), 0));
+    }
+
+    #[test]
+    fn init_list() {
+        let mut list = DoublyLinkedList::<String, u8>::init();
+
+        list.insert(("APPLE".to_owned(), 30));
+        list.insert(("GOOGLE".to_owned(), 50));
+
+        assert_eq!(list.get_head().unwrap().get_value().0, "GOOGLE".to_owned());
+        assert_eq!(
+            list.get_head().unwrap().get_next().unwrap().get_value().0,
+            "APPLE".to_owned()
+        );
+        assert_eq!(list.size, 2);
+
+        list.insert(("FACEBOOK".to_owned(), 100));
+        assert_eq!(list.size, 3);
+        assert_eq!(
+            list.get_head().unwrap().get_value().0,
+            "FACEBOOK".to_owned()
+        );
+
+        assert_eq!(list.get_tail().unwrap().get_value().0, "APPLE".to_owned());
+        assert_eq!(
+            list.get_head().unwrap().get_value().0,
+            "FACEBOOK".to_owned()
+        );
+        let next = list.get_head().unwrap().get_next();
+        assert_eq!(next.as_ref().unwrap().get_value().0, "GOOGLE".to_owned());
+        assert_eq!(
+            next.as_ref().unwrap().get_next().unwrap().get_value().0,
+            "APPLE".to_owned()
+        );
+
+        list.remove();
+        assert_eq!(list.size, 2);
+        assert_eq!(
+            list.get_head().unwrap().get_value().0,
+            "FACEBOOK".to_owned()
+        );
+        assert_eq!(list.get_tail().unwrap().get_value().0, "GOOGLE".to_owned());
+        assert!(list.get_tail().unwrap().get_next().is_none());
+
+        list.remove();
+        assert_eq!(list.size, 1);
+        assert_eq!(
+            list.get_head().unwrap().get_value().0,
+            "FACEBOOK".to_owned()
+        );
+        assert_eq!(
+            list.get_tail().unwrap().get_value().0,
+            "FACEBOOK".to_owned()
+        );
+        assert!(list.get_tail().unwrap().get_next().is_none());
+
+        list.remove();
+        assert_eq!(list.size, 0);
+        assert!(list.get_head().is_none());
+        assert!(list.get_tail().is_none());
+    }
+
+    #[test]
+    fn init_lru() {
+        let mut lru = LRU::<String, u32>::init(4);
+        lru.add("GOOGLE".to_string(), 50);
+        lru.add("FACEBOOK".to_string(), 100);
+        lru.add("APPLE".to_string(), 20);
+        lru.add("AMAZON".to_string(), 20);
+        lru.add("QUALCOMM".to_string(), 20);
+
+        assert_eq!(lru.size, 4);
+
+        // GOOGLE should have been evicted
+        assert!(lru.get("GOOGLE".to_string()).is_none());
+
+        assert_eq!(lru.get("FACEBOOK".to_string()).unwrap(), 100);
+        assert_eq!(lru.get("APPLE".to_string()).unwrap(), 20);
+        assert_eq!(lru.get("AMAZON".to_string()).unwrap(), 20);
+        assert_eq!(lru.get("QUALCOMM".to_string()).unwrap(), 20);
+        assert_eq!(lru.get("FACEBOOK".to_string()).unwrap(), 100);
+
+        lru.add("NVIDIA".to_string(), 20);
+        assert!(lru.get("APPLE".to_string()).is_none());
+    }
+
+    #[test]
+    fn generic_over_collection() {
+        fn fill<M: Map<String, u32>>(map: &mut M) {
+            map.insert("GOOGLE".to_string(), 50);
+            map.insert("FACEBOOK".to_string(), 100);
+            map.insert("APPLE".to_string(), 20);
+        }
+
+        let mut lru = LRU::<String, u32>::init(2);
+        fill(&mut lru);
+
+        assert_eq!(Collection::len(&lru), 2);
+        assert_eq!(Map::get(&mut lru, &"GOOGLE".to_string()), None);
+        assert_eq!(Map::get(&mut lru, &"FACEBOOK".to_string()), Some(100));
+
+        let keys: Vec<String> = Collection::iter(&lru).map(|(k, _)| k).collect();
+        assert_eq!(keys, vec!["FACEBOOK".to_string(), "APPLE".to_string()]);
+
+        Collection::clear(&mut lru);
+        assert!(Collection::is_empty(&lru));
+        assert_eq!(lru.iter().count(), 0);
+        assert_eq!(lru.get("FACEBOOK".to_string()), None);
+    }
+
+    #[test]
+    fn get_head_twice() {
+        let mut lru = LRU::<String, u32>::init(2);
+        lru.add("GOOGLE".to_string(), 50);
+        lru.add("FACEBOOK".to_string(), 100);
+
+        assert_eq!(lru.get("FACEBOOK".to_string()), Some(100));
+        assert_eq!(lru.get("FACEBOOK".to_string()), Some(100));
+
+        let keys: Vec<String> = lru.iter().map(|(k, _)| k).collect();
+        assert_eq!(keys, vec!["FACEBOOK".to_string(), "GOOGLE".to_string()]);
+    }
+
+    #[test]
+    fn get_many() {
+        let mut lru = LRU::<String, u32>::init(4);
+        lru.add("GOOGLE".to_string(), 50);
+        lru.add("FACEBOOK".to_string(), 100);
+        lru.add("APPLE".to_string(), 20);
+        lru.add("AMAZON".to_string(), 30);
+
+        let values = lru.get_many(&[
+            "GOOGLE".to_string(),
+            "NVIDIA".to_string(),
+            "APPLE".to_string(),
+            "GOOGLE".to_string(),
+        ]);
+        assert_eq!(values, vec![Some(50), None, Some(20), Some(50)]);
+
+        // Each hit is promoted once, in the order it first appeared.
+        let keys: Vec<String> = lru.iter().map(|(k, _)| k).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "APPLE".to_string(),
+                "GOOGLE".to_string(),
+                "AMAZON".to_string(),
+                "FACEBOOK".to_string()
+            ]
+        );
+
+        // FACEBOOK was not part of the batch, so it is evicted next.
+        lru.add("NVIDIA".to_string(), 40);
+        assert!(lru.get("FACEBOOK".to_string()).is_none());
+        assert_eq!(lru.get("AMAZON".to_string()), Some(30));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn metrics_sink() {
+        use std::cell::RefCell;
+
+        #[derive(Default, Debug, PartialEq)]
+        struct Counts {
+            hits: u32,
+            misses: u32,
+            evictions: u32,
+            size: usize,
+        }
+
+        struct Sink(Rc<RefCell<Counts>>);
+
+        impl MetricsSink for Sink {
+            fn hit(&mut self) {
+                self.0.borrow_mut().hits += 1;
+            }
+
+            fn miss(&mut self) {
+                self.0.borrow_mut().misses += 1;
+            }
+
+            fn eviction(&mut self) {
+                self.0.borrow_mut().evictions += 1;
+            }
+
+            fn size(&mut self, size: usize) {
+                self.0.borrow_mut().size = size;
+            }
+        }
+
+        let counts = Rc::new(RefCell::new(Counts::default()));
+        let mut lru = LRU::<String, u32>::init(2);
+        lru.set_metrics_sink(Sink(counts.clone()));
+
+        lru.add("GOOGLE".to_string(), 50);
+        lru.add("FACEBOOK".to_string(), 100);
+        lru.add("APPLE".to_string(), 20);
+
+        lru.get("GOOGLE".to_string());
+        lru.get("APPLE".to_string());
+        lru.get_many(&["FACEBOOK".to_string(), "NVIDIA".to_string()]);
+
+        assert_eq!(
+            *counts.borrow(),
+            Counts {
+                hits: 2,
+                misses: 2,
+                evictions: 1,
+                size: 2,
+            }
+        );
+
+        lru.clear();
+        assert_eq!(counts.borrow().size, 0);
+    }
+
+    #[test]
+    fn clear_lazy() {
+        let value = Rc::new(0);
+        let mut lru = LRU::<u32, Rc<u32>>::init(32);
+        for i in 0..20 {
+            lru.add(i, value.clone());
+        }
+        assert_eq!(Rc::strong_count(&value), 21);
+
+        lru.clear_lazy();
+        assert!(lru.is_empty());
+        assert_eq!(lru.iter().count(), 0);
+        assert_eq!(lru.get(0), None);
+        assert_eq!(lru.deferred_len(), 20);
+
+        // Nothing has been dropped until the deferred entries are drained.
+        assert_eq!(Rc::strong_count(&value), 21);
+
+        assert_eq!(lru.drain_deferred(5), 5);
+        assert_eq!(lru.deferred_len(), 15);
+        assert!(Rc::strong_count(&value) > 1);
+
+        // Each add drains a few more of the deferred entries.
+        lru.add(100, Rc::new(100));
+        assert_eq!(lru.deferred_len(), 15 - DEFERRED_DROP_STEP);
+
+        lru.clear_lazy();
+        assert_eq!(lru.deferred_len(), 15 - DEFERRED_DROP_STEP + 1);
+
+        assert_eq!(lru.drain_deferred(usize::MAX), 15 - DEFERRED_DROP_STEP + 1);
+        assert_eq!(lru.deferred_len(), 0);
+        assert_eq!(lru.drain_deferred(1), 0);
+        assert_eq!(Rc::strong_count(&value), 1);
+
+        lru.add(1, value.clone());
+        assert_eq!(lru.get(1), Some(value.clone()));
+        assert_eq!(lru.len(), 1);
+    }
+
+    #[test]
+    fn single_entry() {
+        let mut lru = LRU::<String, u32>::init(1);
+        lru.add("GOOGLE".to_string(), 50);
+        assert_eq!(lru.get("GOOGLE".to_string()), Some(50));
+        assert_eq!(lru.get("GOOGLE".to_string()), Some(50));
+
+        // The only entry is both the head and the tail, so it's evicted.
+        lru.add("FACEBOOK".to_string(), 100);
+        assert_eq!(lru.len(), 1);
+        assert_eq!(lru.get("GOOGLE".to_string()), None);
+        assert_eq!(lru.get("FACEBOOK".to_string()), Some(100));
+    }
+
+    #[test]
+    fn requeue_aliasing_conflict() {
+        let mut list = DoublyLinkedList::<String, u8>::init();
+        list.insert(("APPLE".to_owned(), 30));
+        list.insert(("GOOGLE".to_owned(), 50));
+        list.insert(("FACEBOOK".to_owned(), 100));
+
+        // The node is borrowed elsewhere.
+        let tail = list.get_tail().unwrap();
+        {
+            let _borrow = tail.0.borrow();
+            assert_eq!(
+                list.requeue_node(tail.clone()),
+                Err(StructureError::AliasingConflict)
+            );
+        }
+
+        // A node whose neighbours are the same node.
+        let middle = list.get_head().unwrap().get_next().unwrap();
+        middle.0.borrow_mut().next = Some(tail.clone());
+        middle.0.borrow_mut().prev = Some(tail.clone());
+        assert_eq!(
+            list.requeue_node(middle.clone()),
+            Err(StructureError::AliasingConflict)
+        );
+
+        // A node linked to itself.
+        tail.0.borrow_mut().prev = Some(tail.clone());
+        assert_eq!(
+            list.requeue_node(tail.clone()),
+            Err(StructureError::AliasingConflict)
+        );
+        tail.0.borrow_mut().prev = None;
+    }
+
+    #[test]
+    fn try_get_borrowed_entry() {
+        let mut lru = LRU::<String, u32>::init(2);
+        lru.add("GOOGLE".to_string(), 50);
+        lru.add("FACEBOOK".to_string(), 100);
+
+        let node = lru.map.get("GOOGLE").unwrap().clone();
+        let borrow = node.0.borrow_mut();
+        assert_eq!(
+            lru.try_get("GOOGLE".to_string()),
+            Err(StructureError::AliasingConflict)
+        );
+        drop(borrow);
+
+        // The failed lookup left the order untouched.
+        let keys: Vec<String> = lru.iter().map(|(k, _)| k).collect();
+        assert_eq!(keys, vec!["FACEBOOK".to_string(), "GOOGLE".to_string()]);
+        assert_eq!(lru.try_get("GOOGLE".to_string()), Ok(Some(50)));
+    }
+
+    #[test]
+    fn observers() {
+        use std::cell::RefCell;
+
+        let inserted = Rc::new(RefCell::new(Vec::new()));
+        let removed = Rc::new(RefCell::new(Vec::new()));
+
+        let mut lru = LRU::<u32, u32>::init(2);
+        let sink = inserted.clone();
+        lru.on_insert(move |k, v| sink.borrow_mut().push((*k, *v)));
+        let sink = removed.clone();
+        lru.on_remove(move |k, v| sink.borrow_mut().push((*k, *v)));
+
+        lru.add(1, 10);
+        lru.add(2, 20);
+        lru.get(1);
+        lru.add(3, 30);
+        assert_eq!(*inserted.borrow(), vec![(1, 10), (2, 20), (3, 30)]);
+        assert_eq!(*removed.borrow(), vec![(2, 20)]);
+
+        lru.clear();
+        assert_eq!(*removed.borrow(), vec![(2, 20), (3, 30), (1, 10)]);
+
+        lru.add(4, 40);
+        lru.clear_lazy();
+        assert_eq!(removed.borrow().last(), Some(&(4, 40)));
+        assert_eq!(removed.borrow().len(), 4);
+    }
+
+    #[test]
+    fn with_hasher() {
+        use crate::hasher::SeededState;
+        use std::hash::BuildHasherDefault;
+
+        let mut seeded = LRU::with_hasher(2, SeededState::new(3));
+        seeded.add(1, "a");
+        seeded.add(2, "b");
+        seeded.add(3, "c");
+        assert_eq!(keys_of(&seeded), vec![3, 2]);
+
+        seeded.clear_lazy();
+        seeded.add(4, "d");
+        assert_eq!(seeded.get(4), Some("d"));
+
+        let mut default: LRU<u32, &str, BuildHasherDefault<hash_map::DefaultHasher>> =
+            LRU::with_hasher(1, BuildHasherDefault::default());
+        default.add(1, "a");
+        assert_eq!(default.get(1), Some("a"));
+    }
+
+    fn keys_of<S: BuildHasher>(lru: &LRU<u32, &str, S>) -> Vec<u32> {
+        lru.iter().map(|(k, _)| k).collect()
+    }
+
+    #[test]
+    fn accessors_share_the_clock() {
+        // The entry read last is kept by a scorer preferring recent entries,
+        // whichever accessor read it.
+        for read in [LRU::get, |lru: &mut LRU<u32, u32>, k| {
+            lru.try_get(k).unwrap()
+        }] {
+            let mut lru = LRU::init(2);
+            lru.set_eviction_scorer(2, |_: &u32, _: &u32, stats| stats.last_access);
+            lru.add(1, 1);
+            lru.add(2, 2);
+            lru.get(2);
+            read(&mut lru, 1);
+
+            lru.add(3, 3);
+            assert_eq!(lru.get(1), Some(1));
+            assert_eq!(lru.get(2), None);
+        }
+    }
+
+    #[test]
+    fn eviction_scorer() {
+        let keys = |lru: &LRU<u32, u32>| lru.iter().map(|(k, _)| k).collect::<Vec<_>>();
+
+        let mut lru = LRU::init(3);
+        // Score by hits, only the two least recent entries are sampled.
+        lru.set_eviction_scorer(2, |_: &u32, _: &u32, stats| stats.hits);
+        lru.add(1, 1);
+        lru.add(2, 2);
+        lru.add(3, 3);
+        lru.get(1);
+        lru.get(2);
+        lru.get(2);
+        lru.get(3);
+
+        // 1 and 2 are sampled, 1 has fewer hits. 3 has the fewest hits but
+        // isn't sampled.
+        lru.add(4, 4);
+        assert_eq!(keys(&lru), vec![4, 3, 2]);
+
+        // On a tie, the least recent entry is evicted.
+        lru.set_eviction_scorer(3, |_, _, _| 0);
+        lru.add(5, 5);
+        assert_eq!(keys(&lru), vec![5, 4, 3]);
+
+        // Evicting from the middle keeps the links consistent.
+        lru.set_eviction_scorer(3, |k, _, _| if *k == 4 { 0 } else { 1 });
+        lru.add(6, 6);
+        assert_eq!(keys(&lru), vec![6, 5, 3]);
+        assert_eq!(lru.get(3), Some(3));
+        assert_eq!(keys(&lru), vec![3, 6, 5]);
+        lru.set_eviction_scorer(3, |k, _, _| if *k == 3 { 0 } else { 1 });
+        lru.add(7, 7);
+        assert_eq!(keys(&lru), vec![7, 6, 5]);
+        assert_eq!(lru.len(), 3);
+    }
+
+    #[test]
+    fn entry_stats() {
+        let mut lru = LRU::init(2);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let sink = seen.clone();
+        lru.set_eviction_scorer(2, move |k: &&str, _: &u32, stats| {
+            sink.borrow_mut().push((*k, *stats));
+            0
+        });
+
+        lru.add("a", 1);
+        lru.add("b", 2);
+        lru.get("a");
+        lru.get_many(&["b", "b"]);
+        lru.add("c", 3);
+
+        let mut seen = seen.borrow().clone();
+        seen.sort_by_key(|(k, _)| *k);
+        assert_eq!(
+            seen,
+            vec![
+                (
+                    "a",
+                    EntryStats {
+                        inserted: 1,
+                        last_access: 3,
+                        hits: 1
+                    }
+                ),
+                (
+                    "b",
+                    EntryStats {
+                        inserted: 2,
+                        last_access: 4,
+                        hits: 1
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn sampled_eviction() {
+        let mut lru = LRU::init(1000);
+        lru.sample_evictions(5);
+
+        for i in 0..1000 {
+            lru.add(i, i);
+        }
+        // Reads don't reorder the list.
+        for i in 0..500 {
+            lru.get(i);
+        }
+        assert_eq!(lru.iter().last(), Some((0, 0)));
+
+        // New entries mostly evict the entries that weren't read.
+        for i in 1000..1250 {
+            lru.add(i, i);
+        }
+        assert_eq!(lru.len(), 1000);
+        let kept = (0..500).filter(|i| lru.map.contains_key(i)).count();
+        assert!(kept > 450, "kept {} of the entries read", kept);
+
+        // Each entry knows its slot in the Sampler, through replacements and
+        // evictions.
+        lru.add(1100, 0);
+        let sampler = lru.sampler.as_ref().unwrap();
+        assert_eq!(sampler.entries.len(), lru.len());
+        for (slot, node) in sampler.entries.iter().enumerate() {
+            assert_eq!(node.0.borrow().slot, slot);
+        }
+
+        lru.clear();
+        assert!(lru.sampler.as_ref().unwrap().entries.is_empty());
+        lru.add(1, 1);
+        assert_eq!(lru.get(1), Some(1));
+    }
+
+    #[test]
+    fn switching_to_sampled_eviction() {
+        let mut lru = LRU::init(3);
+        lru.add("a", 1);
+        lru.add("b", 2);
+        lru.add("c", 3);
+
+        // The entries already in the LRU are sampled too.
+        lru.sample_evictions(1);
+        assert_eq!(lru.sampler.as_ref().unwrap().entries.len(), 3);
+
+        lru.add("d", 4);
+        assert_eq!(lru.len(), 3);
+        assert_eq!(lru.get("d"), Some(4));
+    }
+}
+
+// The ordering guarantees of the public API. These tests must keep passing
+// across any rewrite of the LRU internals.
+#[cfg(test)]
+mod contract {
+    use super::*;
+
+    fn keys(lru: &LRU<u32, u32>) -> Vec<u32> {
+        lru.iter().map(|(k, _)| k).collect()
+    }
+
+    #[test]
+    fn iterates_from_most_to_least_recent() {
+        let mut lru = LRU::init(3);
+        lru.add(1, 10);
+        lru.add(2, 20);
+        lru.add(3, 30);
+
+        assert_eq!(keys(&lru), vec![3, 2, 1]);
+        assert_eq!(
+            Collection::iter(&lru).collect::<Vec<_>>(),
+            vec![(3, 30), (2, 20), (1, 10)]
+        );
+    }
+
+    #[test]
+    fn lookups_promote_entries() {
+        let mut lru = LRU::init(3);
+        lru.add(1, 10);
+        lru.add(2, 20);
+        lru.add(3, 30);
+
+        lru.get(1);
+        assert_eq!(keys(&lru), vec![1, 3, 2]);
+
+        lru.get_many(&[2, 3]);
+        assert_eq!(keys(&lru), vec![3, 2, 1]);
+
+        // A miss doesn't change the order.
+        lru.get(4);
+        assert_eq!(keys(&lru), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn evicts_the_least_recent() {
+        let mut lru = LRU::init(3);
+        lru.add(1, 10);
+        lru.add(2, 20);
+        lru.add(3, 30);
+        lru.get(1);
+
+        lru.add(4, 40);
+        assert_eq!(keys(&lru), vec![4, 1, 3]);
+    }
+
+    #[test]
+    fn byte_budget() {
+        let mut lru = LRU::<u32, Vec<u8>>::init(10);
+        let entry = |n: usize| 4 + vec![0u8; n].bytes();
+
+        lru.add(1, vec![0; 10]);
+        lru.add(2, vec![0; 20]);
+        lru.add(3, vec![0; 30]);
+
+        // Setting the budget evicts down to it straight away.
+        lru.set_byte_budget(entry(20) + entry(30));
+        assert_eq!(lru.bytes_used(), Some(entry(20) + entry(30)));
+        assert_eq!(lru.get(1), None);
+
+        // Each add evicts the least recently used entries until it fits.
+        lru.get(2);
+        lru.add(4, vec![0; 25]);
+        assert_eq!(lru.bytes_used(), Some(entry(20) + entry(25)));
+        assert_eq!(lru.get(3), None);
+
+        // Replacing an entry counts the new value instead of the old one.
+        lru.add(2, vec![0; 5]);
+        assert_eq!(lru.len(), 2);
+        assert_eq!(lru.bytes_used(), Some(entry(5) + entry(25)));
+
+        // An entry larger than the budget isn't added.
+        lru.add(5, vec![0; 1000]);
+        assert_eq!(lru.len(), 2);
+        assert_eq!(lru.bytes_used(), Some(entry(5) + entry(25)));
+        assert_eq!(lru.get(5), None);
+
+        lru.add(6, vec![0; 1]);
+        lru.clear();
+        assert_eq!(lru.bytes_used(), Some(0));
+        assert_eq!(LRU::<u32, u32>::init(1).bytes_used(), None);
+    }
+
+    #[test]
+    fn oversized_entry_keeps_the_others() {
+        let removed = Rc::new(RefCell::new(Vec::new()));
+        let mut lru = LRU::<u32, String>::init(10);
+        lru.set_byte_budget(1024);
+        for i in 0..4 {
+            lru.add(i, i.to_string());
+        }
+        let sink = removed.clone();
+        lru.on_remove(move |k, v| sink.borrow_mut().push((*k, v.clone())));
+        let used = lru.bytes_used().unwrap();
+
+        lru.add(4, "x".repeat(2048));
+        assert_eq!(lru.len(), 4);
+        assert_eq!(lru.bytes_used(), Some(used));
+        assert_eq!(lru.get(4), None);
+
+        // The older value of the key is removed rather than kept.
+        lru.add(0, "x".repeat(2048));
+        assert_eq!(lru.len(), 3);
+        assert!(lru.bytes_used().unwrap() < used);
+        assert_eq!(lru.get(0), None);
+        assert_eq!(*removed.borrow(), vec![(0, "0".to_string())]);
+        for i in 1..4 {
+            assert_eq!(lru.get(i), Some(i.to_string()));
+        }
+    }
+
+    #[test]
+    fn replacing_an_entry_keeps_one_node() {
+        let mut lru = LRU::init(2);
+        lru.add("a", 1);
+        lru.add("b", 2);
+        lru.add("a", 3);
+
+        assert_eq!(lru.len(), 2);
+        assert_eq!(lru.iter().collect::<Vec<_>>(), vec![("a", 3), ("b", 2)]);
+        assert_eq!(lru.get("b"), Some(2));
+    }
+}
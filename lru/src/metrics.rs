@@ -0,0 +1,44 @@
+/// MetricsSink receives the counters and gauges of an LRU as they change, so
+/// they can be forwarded to a metrics system such as Prometheus.
+///
+/// Every method has an empty default, a sink only needs to implement the
+/// metrics it is interested in.
+///
+/// # Example
+///
+/// ```
+/// use lru::{MetricsSink, LRU};
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// struct HitCounter(Rc<Cell<u64>>);
+///
+/// impl MetricsSink for HitCounter {
+///     fn hit(&mut self) {
+///         self.0.set(self.0.get() + 1);
+///     }
+/// }
+///
+/// let hits = Rc::new(Cell::new(0));
+/// let mut lru = LRU::init(2);
+/// lru.set_metrics_sink(HitCounter(hits.clone()));
+///
+/// lru.add("a", 1);
+/// lru.get("a");
+/// lru.get("b");
+///
+/// assert_eq!(hits.get(), 1);
+/// ```
+pub trait MetricsSink {
+    /// Called when a lookup finds the key.
+    fn hit(&mut self) {}
+
+    /// Called when a lookup doesn't find the key.
+    fn miss(&mut self) {}
+
+    /// Called when the least recently used entry is evicted to make room.
+    fn eviction(&mut self) {}
+
+    /// Called with the number of entries whenever it changes.
+    fn size(&mut self, _size: usize) {}
+}
@@ -0,0 +1,448 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+use traits::{Clock, SystemClock};
+
+/// An entry in the PriorityCache. `seq` identifies this insert of the key, so
+/// stale deadlines left in the heap by a replaced entry can be recognized.
+struct Entry<V, P> {
+    value: V,
+    priority: P,
+    deadline: Instant,
+    seq: u64,
+}
+
+/// A deadline in the expiry heap, ordered by time and then by insert order.
+struct Deadline<K> {
+    at: Instant,
+    seq: u64,
+    key: K,
+}
+
+impl<K> PartialEq for Deadline<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.at == other.at && self.seq == other.seq
+    }
+}
+
+impl<K> Eq for Deadline<K> {}
+
+impl<K> PartialOrd for Deadline<K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K> Ord for Deadline<K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.at, self.seq).cmp(&(other.at, other.seq))
+    }
+}
+
+/// PriorityCache is a bounded cache where every entry has a deadline and a
+/// priority. Entries past their deadline expire, and when the cache is full
+/// the entry with the lowest priority is evicted, the oldest one on a tie.
+///
+/// Datastructure:
+/// - HashMap: key -> value, priority, deadline
+/// - BinaryHeap: min-heap of deadlines, a removed entry's deadline is left in
+///   the heap and skipped once it reaches the top
+/// - BTreeMap: (priority, insert order) -> key, the first entry is evicted
+///
+/// The time is passed in by the caller, or read from the Clock of the cache
+/// by the methods ending in `_now`. The Clock is the system clock unless
+/// another one is given to `with_clock()`, e.g. a ManualClock in tests.
+///
+/// # Example
+///
+/// ```
+/// use lru::PriorityCache;
+/// use std::time::{Duration, Instant};
+///
+/// let now = Instant::now();
+/// let mut cache = PriorityCache::new(2);
+///
+/// cache.insert("low", 1, 1, now + Duration::from_secs(60));
+/// cache.insert("high", 2, 9, now + Duration::from_secs(1));
+///
+/// // The cache is full, so the lowest priority entry makes room.
+/// assert_eq!(cache.insert("mid", 3, 5, now + Duration::from_secs(60)), Some(("low", 1)));
+///
+/// // Expiry follows the deadlines, whatever the priority.
+/// let expired = cache.expire(now + Duration::from_secs(2));
+/// assert_eq!(expired, vec![("high", 2)]);
+/// ```
+pub struct PriorityCache<K, V, P = u32, C = SystemClock> {
+    capacity: usize,
+    entries: HashMap<K, Entry<V, P>>,
+    deadlines: BinaryHeap<Reverse<Deadline<K>>>,
+    priorities: BTreeMap<(P, u64), K>,
+    seq: u64,
+    clock: C,
+}
+
+impl<K, V, P> PriorityCache<K, V, P>
+where
+    K: Hash + Eq + Clone,
+    P: Ord + Clone,
+{
+    /// Returns an empty PriorityCache holding at most `capacity` entries,
+    /// reading the system clock.
+    pub fn new(capacity: usize) -> PriorityCache<K, V, P> {
+        PriorityCache::with_clock(capacity, SystemClock)
+    }
+}
+
+impl<K, V, P, C> PriorityCache<K, V, P, C>
+where
+    K: Hash + Eq + Clone,
+    P: Ord + Clone,
+    C: Clock,
+{
+    /// Returns an empty PriorityCache holding at most `capacity` entries,
+    /// reading the time from `clock`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lru::{ManualClock, PriorityCache};
+    /// use std::time::Duration;
+    ///
+    /// let clock = ManualClock::default();
+    /// let mut sessions = PriorityCache::with_clock(10, clock.clone());
+    /// sessions.insert_ttl("alice", 1, 1, Duration::from_secs(30));
+    ///
+    /// clock.advance(Duration::from_secs(29));
+    /// assert_eq!(sessions.get_now(&"alice"), Some(&1));
+    ///
+    /// clock.advance(Duration::from_secs(1));
+    /// assert_eq!(sessions.get_now(&"alice"), None);
+    /// ```
+    pub fn with_clock(capacity: usize, clock: C) -> PriorityCache<K, V, P, C> {
+        PriorityCache {
+            capacity,
+            entries: HashMap::new(),
+            deadlines: BinaryHeap::new(),
+            priorities: BTreeMap::new(),
+            seq: 0,
+            clock,
+        }
+    }
+
+    /// Returns the Clock of the PriorityCache.
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Returns the maximum number of entries in the PriorityCache.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of entries in the PriorityCache, including expired
+    /// entries that haven't been removed by `expire()` or `get()` yet.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns a boolean indicating the PriorityCache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Inserts an entry, replacing any entry with the same key. If the
+    /// PriorityCache is full, the entry with the lowest priority is evicted
+    /// and returned. That may be the new entry itself.
+    ///
+    /// Time Complexity: O(log n)
+    /// Space Complexity: O(1)
+    pub fn insert(&mut self, key: K, value: V, priority: P, deadline: Instant) -> Option<(K, V)> {
+        self.remove(&key);
+
+        if self.capacity == 0 {
+            return Some((key, value));
+        }
+
+        let mut evicted = None;
+        if self.entries.len() == self.capacity {
+            let lowest = self.priorities.keys().next().cloned();
+            if let Some((p, _)) = lowest {
+                if priority < p {
+                    return Some((key, value));
+                }
+
+                evicted = self.evict();
+            }
+        }
+
+        let seq = self.seq;
+        self.seq += 1;
+
+        self.deadlines.push(Reverse(Deadline {
+            at: deadline,
+            seq,
+            key: key.clone(),
+        }));
+        self.priorities.insert((priority.clone(), seq), key.clone());
+        self.entries.insert(
+            key,
+            Entry {
+                value,
+                priority,
+                deadline,
+                seq,
+            },
+        );
+
+        evicted
+    }
+
+    /// Inserts an entry expiring `ttl` from now by the Clock, see `insert()`.
+    ///
+    /// Time Complexity: O(log n)
+    /// Space Complexity: O(1)
+    pub fn insert_ttl(&mut self, key: K, value: V, priority: P, ttl: Duration) -> Option<(K, V)> {
+        let deadline = self.clock.now() + ttl;
+        self.insert(key, value, priority, deadline)
+    }
+
+    /// Returns the value of an entry unless it has expired by the Clock, see
+    /// `get()`.
+    ///
+    /// Time Complexity: O(1), O(log n) if the entry expired
+    /// Space Complexity: O(1)
+    pub fn get_now(&mut self, key: &K) -> Option<&V> {
+        let now = self.clock.now();
+        self.get(key, now)
+    }
+
+    /// Removes and returns the entries that have expired by the Clock, see
+    /// `expire()`.
+    ///
+    /// Time Complexity: O(k log n), where k is the number of expired entries
+    /// Space Complexity: O(k)
+    pub fn expire_now(&mut self) -> Vec<(K, V)> {
+        let now = self.clock.now();
+        self.expire(now)
+    }
+
+    /// Returns the value of an entry, or None if it is missing or its deadline
+    /// has passed, in which case it is removed.
+    ///
+    /// Time Complexity: O(1), O(log n) if the entry expired
+    /// Space Complexity: O(1)
+    pub fn get(&mut self, key: &K, now: Instant) -> Option<&V> {
+        let expired = self.entries.get(key)?.deadline <= now;
+        if expired {
+            self.remove(key);
+            return None;
+        }
+
+        self.entries.get(key).map(|e| &e.value)
+    }
+
+    /// Removes an entry and returns its value.
+    ///
+    /// Time Complexity: O(log n)
+    /// Space Complexity: O(1)
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let entry = self.entries.remove(key)?;
+        self.priorities.remove(&(entry.priority, entry.seq));
+        self.prune_deadlines();
+
+        Some(entry.value)
+    }
+
+    /// Removes and returns every entry whose deadline is at or before `now`,
+    /// earliest deadline first.
+    ///
+    /// Time Complexity: O(k log n), where k is the number of expired entries
+    /// Space Complexity: O(k)
+    pub fn expire(&mut self, now: Instant) -> Vec<(K, V)> {
+        let mut expired = Vec::new();
+
+        while let Some(Reverse(top)) = self.deadlines.peek() {
+            if top.at > now {
+                break;
+            }
+
+            let key = top.key.clone();
+            if let Some(value) = self.remove(&key) {
+                expired.push((key, value));
+            }
+        }
+
+        expired
+    }
+
+    /// Returns the earliest deadline in the PriorityCache.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.deadlines.peek().map(|Reverse(d)| d.at)
+    }
+
+    /// Removes the entry with the lowest priority.
+    fn evict(&mut self) -> Option<(K, V)> {
+        let (_, key) = self.priorities.iter().next()?;
+        let key = key.clone();
+        let value = self.remove(&key)?;
+
+        Some((key, value))
+    }
+
+    /// Pops the deadlines of removed or replaced entries off the top of the
+    /// heap, so the top is always a live entry. Once most of the heap is
+    /// stale, it is rebuilt from the live entries.
+    fn prune_deadlines(&mut self) {
+        while let Some(Reverse(top)) = self.deadlines.peek() {
+            let live = self.entries.get(&top.key).is_some_and(|e| e.seq == top.seq);
+            if live {
+                break;
+            }
+
+            self.deadlines.pop();
+        }
+
+        if self.deadlines.len() > 2 * self.entries.len() + 1 {
+            self.deadlines = self
+                .entries
+                .iter()
+                .map(|(key, e)| {
+                    Reverse(Deadline {
+                        at: e.deadline,
+                        seq: e.seq,
+                        key: key.clone(),
+                    })
+                })
+                .collect();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use traits::ManualClock;
+
+    fn at(now: Instant, secs: u64) -> Instant {
+        now + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn evicts_lowest_priority() {
+        let now = Instant::now();
+        let mut cache = PriorityCache::new(3);
+
+        assert_eq!(cache.insert(1, "a", 5, at(now, 10)), None);
+        assert_eq!(cache.insert(2, "b", 1, at(now, 10)), None);
+        assert_eq!(cache.insert(3, "c", 1, at(now, 10)), None);
+
+        // Ties on priority evict the oldest entry.
+        assert_eq!(cache.insert(4, "d", 3, at(now, 10)), Some((2, "b")));
+        assert_eq!(cache.insert(5, "e", 3, at(now, 10)), Some((3, "c")));
+        assert_eq!(cache.len(), 3);
+    }
+
+    #[test]
+    fn lower_priority_than_every_entry_is_refused() {
+        let now = Instant::now();
+        let mut cache = PriorityCache::new(1);
+
+        cache.insert(1, "a", 5, at(now, 10));
+        assert_eq!(cache.insert(2, "b", 4, at(now, 10)), Some((2, "b")));
+        assert_eq!(cache.get(&1, now), Some(&"a"));
+    }
+
+    #[test]
+    fn expires_by_deadline() {
+        let now = Instant::now();
+        let mut cache = PriorityCache::new(10);
+
+        cache.insert(1, "a", 9, at(now, 3));
+        cache.insert(2, "b", 1, at(now, 1));
+        cache.insert(3, "c", 5, at(now, 2));
+        cache.insert(4, "d", 5, at(now, 5));
+
+        assert_eq!(cache.next_deadline(), Some(at(now, 1)));
+        assert_eq!(cache.expire(at(now, 3)), vec![(2, "b"), (3, "c"), (1, "a")]);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.next_deadline(), Some(at(now, 5)));
+    }
+
+    #[test]
+    fn get_expired_entry() {
+        let now = Instant::now();
+        let mut cache = PriorityCache::new(10);
+
+        cache.insert(1, "a", 1, at(now, 1));
+        assert_eq!(cache.get(&1, now), Some(&"a"));
+        assert_eq!(cache.get(&1, at(now, 1)), None);
+        assert!(cache.is_empty());
+        assert_eq!(cache.next_deadline(), None);
+    }
+
+    #[test]
+    fn replacing_an_entry_drops_its_old_deadline() {
+        let now = Instant::now();
+        let mut cache = PriorityCache::new(10);
+
+        cache.insert(1, "a", 1, at(now, 1));
+        cache.insert(1, "b", 1, at(now, 5));
+
+        assert_eq!(cache.next_deadline(), Some(at(now, 5)));
+        assert!(cache.expire(at(now, 2)).is_empty());
+        assert_eq!(cache.remove(&1), Some("b"));
+    }
+
+    #[test]
+    fn stale_deadlines_are_compacted() {
+        let now = Instant::now();
+        let mut cache = PriorityCache::new(10);
+
+        for i in 0..100 {
+            cache.insert(1, i, 1, at(now, 100 - i));
+        }
+
+        assert_eq!(cache.len(), 1);
+        assert!(cache.deadlines.len() <= 3);
+    }
+
+    #[test]
+    fn expires_by_clock() {
+        let clock = ManualClock::default();
+        let mut cache = PriorityCache::with_clock(10, clock.clone());
+        let start = cache.clock().now();
+
+        cache.insert_ttl(1, "a", 1, Duration::from_secs(5));
+        cache.insert_ttl(2, "b", 1, Duration::from_secs(1));
+        clock.advance(Duration::from_secs(2));
+        cache.insert_ttl(3, "c", 1, Duration::from_secs(2));
+        assert_eq!(cache.next_deadline(), Some(at(start, 1)));
+
+        assert_eq!(cache.expire_now(), vec![(2, "b")]);
+        assert_eq!(cache.get_now(&1), Some(&"a"));
+
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(cache.get_now(&3), None);
+        assert_eq!(cache.expire_now(), vec![]);
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(cache.expire_now(), vec![(1, "a")]);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let now = Instant::now();
+        let mut cache = PriorityCache::new(0);
+
+        assert_eq!(cache.insert(1, "a", 1, at(now, 1)), Some((1, "a")));
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Weak};
+
+// The fewest entries the map grows by before dead entries are purged.
+const MIN_PURGE: usize = 16;
+
+/// WeakValueCache is a cache that only holds weak references to its values.
+/// An entry stays usable while some `Arc` to its value is alive elsewhere,
+/// once the last one drops the entry reads as absent, so the cache never
+/// keeps a large value alive by itself.
+///
+/// Datastructure:
+/// - HashMap: key -> Weak<V>, dead entries are removed when they are read,
+///   and all at once each time the map has doubled in size
+///
+/// # Example
+///
+/// ```
+/// use lru::WeakValueCache;
+/// use std::sync::Arc;
+///
+/// let mut cache = WeakValueCache::new();
+/// let image = Arc::new(vec![0u8; 1024]);
+/// cache.insert("image", &image);
+///
+/// assert!(cache.get(&"image").is_some());
+///
+/// // The cache doesn't keep the value alive.
+/// drop(image);
+/// assert_eq!(cache.get(&"image"), None);
+/// ```
+pub struct WeakValueCache<K, V> {
+    entries: HashMap<K, Weak<V>>,
+    // The number of entries that triggers the next purge.
+    purge_at: usize,
+}
+
+impl<K: Hash + Eq, V> Default for WeakValueCache<K, V> {
+    fn default() -> Self {
+        WeakValueCache {
+            entries: HashMap::new(),
+            purge_at: MIN_PURGE,
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> WeakValueCache<K, V> {
+    /// Returns an empty WeakValueCache.
+    pub fn new() -> WeakValueCache<K, V> {
+        WeakValueCache::default()
+    }
+
+    /// Returns the number of entries in the WeakValueCache, including dead
+    /// entries that haven't been removed by `get()` or `purge()` yet.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns a boolean indicating the WeakValueCache has no entries, dead
+    /// or alive.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Inserts a weak reference to a value, returning the value it replaced
+    /// if that is still alive.
+    ///
+    /// Time Complexity: O(1) amortized
+    /// Space Complexity: O(1)
+    pub fn insert(&mut self, key: K, value: &Arc<V>) -> Option<Arc<V>> {
+        let replaced = self
+            .entries
+            .insert(key, Arc::downgrade(value))
+            .and_then(|weak| weak.upgrade());
+
+        if self.entries.len() >= self.purge_at {
+            self.purge();
+        }
+
+        replaced
+    }
+
+    /// Returns the value of an entry, or None if it is missing or its value
+    /// has been dropped, in which case the entry is removed.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn get(&mut self, key: &K) -> Option<Arc<V>> {
+        let value = self.entries.get(key)?.upgrade();
+        if value.is_none() {
+            self.entries.remove(key);
+        }
+
+        value
+    }
+
+    /// Returns the value of an entry if it is alive, otherwise creates it
+    /// with `f` and inserts it. The caller must keep the returned `Arc`
+    /// alive for the entry to stay.
+    ///
+    /// Time Complexity: O(1) amortized
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lru::WeakValueCache;
+    ///
+    /// let mut cache = WeakValueCache::new();
+    /// let a = cache.get_or_insert_with(1, || String::from("one"));
+    /// let b = cache.get_or_insert_with(1, || String::from("uno"));
+    ///
+    /// assert_eq!(*b, "one");
+    /// ```
+    pub fn get_or_insert_with<F>(&mut self, key: K, f: F) -> Arc<V>
+    where
+        F: FnOnce() -> V,
+    {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+
+        let value = Arc::new(f());
+        self.insert(key, &value);
+
+        value
+    }
+
+    /// Removes an entry, returning its value if that is still alive.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn remove(&mut self, key: &K) -> Option<Arc<V>> {
+        self.entries.remove(key)?.upgrade()
+    }
+
+    /// Removes every entry whose value has been dropped, returning how many
+    /// were removed.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    pub fn purge(&mut self) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|_, weak| weak.strong_count() > 0);
+        self.purge_at = (2 * self.entries.len()).max(MIN_PURGE);
+
+        before - self.entries.len()
+    }
+
+    /// Removes every entry.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.purge_at = MIN_PURGE;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn entries_follow_their_values() {
+        let mut cache = WeakValueCache::new();
+        let a = Arc::new("a");
+        let b = Arc::new("b");
+
+        assert_eq!(cache.insert(1, &a), None);
+        assert_eq!(cache.insert(2, &b), None);
+        assert_eq!(cache.get(&1), Some(a.clone()));
+        assert_eq!(Arc::strong_count(&a), 1);
+
+        drop(b);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&2), None);
+        assert_eq!(cache.len(), 1);
+
+        // Replacing returns the old value only while it's alive.
+        let c = Arc::new("c");
+        assert_eq!(cache.insert(1, &c), Some(a.clone()));
+        drop(a);
+        assert_eq!(cache.insert(1, &Arc::new("d")), Some(c.clone()));
+        assert_eq!(cache.get(&1), None);
+
+        assert_eq!(cache.remove(&1), None);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn get_or_insert_with() {
+        let mut cache = WeakValueCache::new();
+
+        let first = cache.get_or_insert_with("k", || vec![1]);
+        let same = cache.get_or_insert_with("k", || vec![2]);
+        assert!(Arc::ptr_eq(&first, &same));
+
+        drop(first);
+        drop(same);
+        assert_eq!(*cache.get_or_insert_with("k", || vec![3]), vec![3]);
+    }
+
+    #[test]
+    fn dead_entries_are_purged() {
+        let mut cache = WeakValueCache::new();
+        let kept: Vec<Arc<usize>> = (0..4).map(Arc::new).collect();
+        for (i, v) in kept.iter().enumerate() {
+            cache.insert(i, v);
+        }
+
+        // The values of these entries drop straight away.
+        for i in 4..1000 {
+            cache.insert(i, &Arc::new(i));
+            assert!(cache.len() < 2 * MIN_PURGE);
+        }
+
+        cache.purge();
+        assert_eq!(cache.len(), 4);
+        assert!((0..4).all(|i| cache.get(&i).as_deref() == Some(&i)));
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
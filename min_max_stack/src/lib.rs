@@ -0,0 +1,4 @@
+//! A crate that implements a MinMaxStack.
+pub use crate::min_max_stack::{MinMaxStack, MinMaxStackIterator};
+
+mod min_max_stack;
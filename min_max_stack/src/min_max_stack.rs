@@ -0,0 +1,352 @@
+use traits::{Collection, Stack};
+
+/// An entry in the MinMaxStack. Alongside the value, each entry records the
+/// position of the minimum and maximum values in the stack at the time it was
+/// pushed, so popping an entry restores the previous min and max for free.
+struct Entry<T> {
+    value: T,
+    min: usize,
+    max: usize,
+}
+
+/// MinMaxStack is a LIFO stack that reports its smallest and largest values in
+/// constant time.
+pub struct MinMaxStack<T> {
+    entries: Vec<Entry<T>>,
+}
+
+impl<T> Default for MinMaxStack<T> {
+    fn default() -> Self {
+        MinMaxStack {
+            entries: Vec::new(),
+        }
+    }
+}
+
+/// The Iterator implementation for the MinMaxStack, from the top of the stack
+/// to the bottom. This Iterator will borrow the MinMaxStack.
+pub struct MinMaxStackIterator<'a, T> {
+    entries: std::iter::Rev<std::slice::Iter<'a, Entry<T>>>,
+}
+
+impl<'a, T> Iterator for MinMaxStackIterator<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.entries.next().map(|e| &e.value)
+    }
+}
+
+impl<T> Collection for MinMaxStack<T>
+where
+    T: Ord,
+{
+    type Iter<'a>
+        = MinMaxStackIterator<'a, T>
+    where
+        T: 'a;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn clear(&mut self) {
+        self.clear()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.iter()
+    }
+}
+
+impl<T> Stack<T> for MinMaxStack<T>
+where
+    T: Ord,
+{
+    fn push(&mut self, v: T) {
+        MinMaxStack::push(self, v)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        MinMaxStack::pop(self)
+    }
+}
+
+impl<T> MinMaxStack<T>
+where
+    T: Ord,
+{
+    /// Returns the length of the MinMaxStack.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns a boolean indicating the MinMaxStack is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use min_max_stack::MinMaxStack;
+    ///
+    /// let stack = MinMaxStack::<u32>::default();
+    /// assert_eq!(stack.is_empty(), true);
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Adds a value to the top of the MinMaxStack.
+    ///
+    /// Time Complexity: O(1) amortized
+    /// Space Complexity: O(1) amortized
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use min_max_stack::MinMaxStack;
+    ///
+    /// let mut stack = MinMaxStack::default();
+    /// stack.push(3);
+    /// stack.push(1);
+    /// stack.push(2);
+    ///
+    /// assert_eq!(stack.peek(), Some(&2));
+    /// assert_eq!(stack.min(), Some(&1));
+    /// assert_eq!(stack.max(), Some(&3));
+    /// ```
+    pub fn push(&mut self, v: T) {
+        let index = self.entries.len();
+
+        let (min, max) = match self.entries.last() {
+            Some(top) => {
+                let min = if v < self.entries[top.min].value {
+                    index
+                } else {
+                    top.min
+                };
+
+                let max = if v > self.entries[top.max].value {
+                    index
+                } else {
+                    top.max
+                };
+
+                (min, max)
+            }
+            None => (index, index),
+        };
+
+        self.entries.push(Entry { value: v, min, max });
+    }
+
+    /// Returns the value at the top of the MinMaxStack and removes it from the
+    /// MinMaxStack.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use min_max_stack::MinMaxStack;
+    ///
+    /// let mut stack = MinMaxStack::default();
+    /// stack.push(1);
+    /// stack.push(5);
+    ///
+    /// assert_eq!(stack.pop(), Some(5));
+    /// assert_eq!(stack.max(), Some(&1));
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        self.entries.pop().map(|e| e.value)
+    }
+
+    /// Removes every value from the MinMaxStack.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Returns a borrowing iterator from the top to the bottom of the
+    /// MinMaxStack, the same order the values would be popped in.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use min_max_stack::MinMaxStack;
+    ///
+    /// let mut stack = MinMaxStack::default();
+    /// stack.push(1);
+    /// stack.push(2);
+    ///
+    /// assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&2, &1]);
+    /// ```
+    pub fn iter(&self) -> MinMaxStackIterator<'_, T> {
+        MinMaxStackIterator {
+            entries: self.entries.iter().rev(),
+        }
+    }
+
+    /// Returns a reference to the value at the top of the MinMaxStack.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn peek(&self) -> Option<&T> {
+        self.entries.last().map(|e| &e.value)
+    }
+
+    /// Returns a reference to the smallest value in the MinMaxStack.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn min(&self) -> Option<&T> {
+        self.entries.last().map(|top| &self.entries[top.min].value)
+    }
+
+    /// Returns a reference to the largest value in the MinMaxStack.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn max(&self) -> Option<&T> {
+        self.entries.last().map(|top| &self.entries[top.max].value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn init_stack() {
+        let stack = MinMaxStack::<u32>::default();
+        assert_eq!(stack.len(), 0);
+        assert_eq!(stack.peek(), None);
+        assert_eq!(stack.min(), None);
+        assert_eq!(stack.max(), None);
+    }
+
+    #[test]
+    fn push_and_pop() {
+        let mut stack = MinMaxStack::default();
+
+        for i in 1..5 {
+            stack.push(i);
+        }
+
+        assert_eq!(stack.len(), 4);
+        assert_eq!(stack.pop(), Some(4));
+        assert_eq!(stack.pop(), Some(3));
+        assert_eq!(stack.pop(), Some(2));
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.pop(), None);
+    }
+
+    #[test]
+    fn min_and_max_follow_pops() {
+        let mut stack = MinMaxStack::default();
+
+        stack.push(5);
+        stack.push(2);
+        stack.push(8);
+        stack.push(1);
+        stack.push(9);
+
+        assert_eq!(stack.min(), Some(&1));
+        assert_eq!(stack.max(), Some(&9));
+
+        stack.pop();
+        assert_eq!(stack.min(), Some(&1));
+        assert_eq!(stack.max(), Some(&8));
+
+        stack.pop();
+        assert_eq!(stack.min(), Some(&2));
+        assert_eq!(stack.max(), Some(&8));
+
+        stack.pop();
+        assert_eq!(stack.min(), Some(&2));
+        assert_eq!(stack.max(), Some(&5));
+
+        stack.pop();
+        assert_eq!(stack.min(), Some(&5));
+        assert_eq!(stack.max(), Some(&5));
+
+        stack.pop();
+        assert_eq!(stack.min(), None);
+        assert_eq!(stack.max(), None);
+    }
+
+    #[test]
+    fn duplicate_values() {
+        let mut stack = MinMaxStack::default();
+
+        stack.push("b".to_string());
+        stack.push("a".to_string());
+        stack.push("a".to_string());
+
+        stack.pop();
+        assert_eq!(stack.min(), Some(&"a".to_string()));
+        assert_eq!(stack.max(), Some(&"b".to_string()));
+    }
+
+    #[test]
+    fn generic_over_stack_trait() {
+        fn fill<S: Stack<u32>>(stack: &mut S) {
+            for i in &[4, 1, 7] {
+                stack.push(*i);
+            }
+        }
+
+        let mut stack = MinMaxStack::default();
+        fill(&mut stack);
+
+        assert_eq!(stack.min(), Some(&1));
+        assert_eq!(stack.max(), Some(&7));
+        assert_eq!(Stack::pop(&mut stack), Some(7));
+    }
+
+    #[test]
+    fn generic_over_collection() {
+        fn sizes<C: Collection>(collection: &C) -> (usize, usize) {
+            (collection.len(), collection.iter().count())
+        }
+
+        let mut stack = MinMaxStack::default();
+        stack.push(3);
+        stack.push(1);
+        assert_eq!(sizes(&stack), (2, 2));
+
+        Collection::clear(&mut stack);
+        assert_eq!(sizes(&stack), (0, 0));
+        assert_eq!(stack.min(), None);
+    }
+}
+
+// The ordering guarantees of the public API. These tests must keep passing
+// across any rewrite of the MinMaxStack internals.
+#[cfg(test)]
+mod contract {
+    use super::*;
+
+    #[test]
+    fn iterates_from_top_to_bottom() {
+        let mut stack = MinMaxStack::default();
+        stack.push(2);
+        stack.push(3);
+        stack.push(1);
+
+        assert_eq!(stack.iter().copied().collect::<Vec<_>>(), vec![1, 3, 2]);
+        assert_eq!(
+            Collection::iter(&stack).copied().collect::<Vec<_>>(),
+            vec![1, 3, 2]
+        );
+
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.pop(), Some(3));
+        assert_eq!(stack.pop(), Some(2));
+    }
+}
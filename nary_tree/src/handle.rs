@@ -0,0 +1,68 @@
+use crate::node::NodeRef;
+use std::cell::{Ref, RefMut};
+use std::rc::Rc;
+
+/// NodeHandle is an opaque reference to a node in a NaryTree, used to read
+/// the node's value and navigate to its parent and children.
+///
+/// A NodeHandle must only be passed back to the NaryTree it came from.
+pub struct NodeHandle<T>(pub(crate) NodeRef<T>);
+
+impl<T> Clone for NodeHandle<T> {
+    fn clone(&self) -> Self {
+        NodeHandle(self.0.clone())
+    }
+}
+
+impl<T> NodeHandle<T> {
+    /// Borrows the value of the node.
+    ///
+    /// Panics if the value is currently borrowed mutably.
+    pub fn value(&self) -> Ref<'_, T> {
+        Ref::map(self.0 .0.borrow(), |n| &n.value)
+    }
+
+    /// Mutably borrows the value of the node.
+    ///
+    /// Panics if the value is currently borrowed.
+    pub fn value_mut(&self) -> RefMut<'_, T> {
+        RefMut::map(self.0 .0.borrow_mut(), |n| &mut n.value)
+    }
+
+    /// Returns a NodeHandle to the parent of the node, or None for the root.
+    pub fn parent(&self) -> Option<NodeHandle<T>> {
+        self.0
+             .0
+            .borrow()
+            .parent
+            .as_ref()
+            .and_then(|p| p.upgrade())
+            .map(|p| NodeHandle(NodeRef(p)))
+    }
+
+    /// Returns NodeHandles to the children of the node, in order.
+    pub fn children(&self) -> Vec<NodeHandle<T>> {
+        self.0.children().into_iter().map(NodeHandle).collect()
+    }
+
+    /// Returns a NodeHandle to the child at an index.
+    pub fn child(&self, index: usize) -> Option<NodeHandle<T>> {
+        self.0
+             .0
+            .borrow()
+            .children
+            .get(index)
+            .cloned()
+            .map(NodeHandle)
+    }
+
+    /// Returns the number of children of the node.
+    pub fn child_count(&self) -> usize {
+        self.0 .0.borrow().children.len()
+    }
+
+    /// Returns a boolean indicating both NodeHandles refer to the same node.
+    pub fn ptr_eq(&self, other: &NodeHandle<T>) -> bool {
+        Rc::ptr_eq(&self.0 .0, &other.0 .0)
+    }
+}
@@ -0,0 +1,8 @@
+//! A crate that implements an N-ary tree, a tree where each node can have any
+//! number of children.
+pub use crate::handle::NodeHandle;
+pub use crate::nary_tree::{BfsIterator, DfsIterator, NaryTree, NodeBuilder};
+
+mod handle;
+mod nary_tree;
+mod node;
@@ -0,0 +1,1047 @@
+use crate::handle::NodeHandle;
+use crate::node::{Node, NodeRef};
+use std::collections::VecDeque;
+use std::iter::Iterator;
+use std::rc::Rc;
+use traits::Collection;
+
+/// NaryTree is a hierarchy of nodes where each node holds a value T and any
+/// number of ordered children.
+pub struct NaryTree<T> {
+    root: Option<NodeRef<T>>,
+    size: usize,
+}
+
+impl<T> Default for NaryTree<T> {
+    fn default() -> Self {
+        NaryTree {
+            root: None,
+            size: 0,
+        }
+    }
+}
+
+/// NodeBuilder describes a node and its descendants, so a whole NaryTree can
+/// be built in one expression.
+///
+/// # Example
+///
+/// ```
+/// use nary_tree::NodeBuilder;
+///
+/// let tree = NodeBuilder::new("root")
+///     .child(NodeBuilder::new("a").child(NodeBuilder::new("a1")))
+///     .child(NodeBuilder::new("b"))
+///     .build();
+///
+/// assert_eq!(tree.len(), 4);
+/// assert_eq!(tree.dfs().collect::<Vec<_>>(), vec!["root", "a", "a1", "b"]);
+/// ```
+pub struct NodeBuilder<T> {
+    value: T,
+    children: Vec<NodeBuilder<T>>,
+}
+
+impl<T> NodeBuilder<T> {
+    /// Returns a NodeBuilder for a node holding `value` with no children.
+    pub fn new(value: T) -> NodeBuilder<T> {
+        NodeBuilder {
+            value,
+            children: Vec::new(),
+        }
+    }
+
+    /// Appends a child to the node.
+    pub fn child(mut self, child: NodeBuilder<T>) -> NodeBuilder<T> {
+        self.children.push(child);
+        self
+    }
+
+    /// Builds a NaryTree with this node as the root.
+    pub fn build(self) -> NaryTree<T> {
+        let mut size = 0;
+        let root = self.into_node(&mut size);
+
+        NaryTree {
+            root: Some(root),
+            size,
+        }
+    }
+
+    /// Creates the node and its descendants, counting them into `size`.
+    fn into_node(self, size: &mut usize) -> NodeRef<T> {
+        let node = NodeRef::new(Node::new(self.value));
+        *size += 1;
+
+        for child in self.children {
+            let child = child.into_node(size);
+            child.0.borrow_mut().parent = Some(Rc::downgrade(&node.0));
+            node.0.borrow_mut().children.push(child);
+        }
+
+        node
+    }
+}
+
+/// Iterates over the values of a NaryTree depth first, visiting each node
+/// before its children (pre-order). This Iterator will borrow the NaryTree.
+pub struct DfsIterator<'a, T> {
+    _tree: &'a NaryTree<T>,
+    stack: Vec<NodeRef<T>>,
+}
+
+impl<'a, T> Iterator for DfsIterator<'a, T>
+where
+    T: Clone,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let node = self.stack.pop()?;
+
+        // Children are pushed in reverse so the first child is visited first.
+        self.stack.extend(node.children().into_iter().rev());
+
+        let value = node.0.borrow().value.clone();
+        Some(value)
+    }
+}
+
+/// Iterates over the values of a NaryTree breadth first, visiting the nodes
+/// level by level. This Iterator will borrow the NaryTree.
+pub struct BfsIterator<'a, T> {
+    _tree: &'a NaryTree<T>,
+    queue: VecDeque<NodeRef<T>>,
+}
+
+impl<'a, T> Iterator for BfsIterator<'a, T>
+where
+    T: Clone,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let node = self.queue.pop_front()?;
+        self.queue.extend(node.children());
+
+        let value = node.0.borrow().value.clone();
+        Some(value)
+    }
+}
+
+impl<T> Collection for NaryTree<T>
+where
+    T: Clone,
+{
+    type Iter<'a>
+        = DfsIterator<'a, T>
+    where
+        T: 'a;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn clear(&mut self) {
+        self.clear()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.dfs()
+    }
+}
+
+/// Two NaryTrees are equal if they have the same shape and the same values at
+/// the same positions.
+impl<T: PartialEq> PartialEq for NaryTree<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.size == other.size && same_trees(&self.root, &other.root, &mut |a, b| a == b)
+    }
+}
+
+impl<T> NaryTree<T> {
+    /// Returns a NaryTree holding a single root node.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nary_tree::NaryTree;
+    ///
+    /// let tree = NaryTree::new("root");
+    /// assert_eq!(tree.len(), 1);
+    /// ```
+    pub fn new(root: T) -> NaryTree<T> {
+        NaryTree {
+            root: Some(NodeRef::new(Node::new(root))),
+            size: 1,
+        }
+    }
+
+    /// Returns the number of nodes in the NaryTree.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Returns a boolean indicating the NaryTree is empty.
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Removes every node from the NaryTree.
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.size = 0;
+    }
+
+    /// Returns a NodeHandle to the root of the NaryTree.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn root(&self) -> Option<NodeHandle<T>> {
+        self.root.clone().map(NodeHandle)
+    }
+
+    /// Appends a child holding `value` to a node, returning a NodeHandle to
+    /// the new child.
+    ///
+    /// The parent NodeHandle must have come from this NaryTree.
+    ///
+    /// Time Complexity: O(1) amortized
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nary_tree::NaryTree;
+    ///
+    /// let mut tree = NaryTree::new(1);
+    /// let root = tree.root().unwrap();
+    ///
+    /// let child = tree.push_child(&root, 2);
+    /// tree.push_child(&child, 3);
+    ///
+    /// assert_eq!(tree.len(), 3);
+    /// assert_eq!(*root.child(0).unwrap().value(), 2);
+    /// assert!(child.parent().unwrap().ptr_eq(&root));
+    /// ```
+    pub fn push_child(&mut self, parent: &NodeHandle<T>, value: T) -> NodeHandle<T> {
+        let mut node = Node::new(value);
+        node.parent = Some(Rc::downgrade(&parent.0 .0));
+
+        let child = NodeRef::new(node);
+        parent.0 .0.borrow_mut().children.push(child.clone());
+        self.size += 1;
+
+        NodeHandle(child)
+    }
+
+    /// Returns an iterator over the values, depth first (pre-order).
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(h), where h is the height of the NaryTree
+    pub fn dfs(&self) -> DfsIterator<'_, T> {
+        DfsIterator {
+            _tree: self,
+            stack: self.root.clone().into_iter().collect(),
+        }
+    }
+
+    /// Returns an iterator over the values, breadth first.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(w), where w is the width of the NaryTree
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nary_tree::NodeBuilder;
+    ///
+    /// let tree = NodeBuilder::new(1)
+    ///     .child(NodeBuilder::new(2).child(NodeBuilder::new(4)))
+    ///     .child(NodeBuilder::new(3))
+    ///     .build();
+    ///
+    /// assert_eq!(tree.bfs().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    /// ```
+    pub fn bfs(&self) -> BfsIterator<'_, T> {
+        BfsIterator {
+            _tree: self,
+            queue: self.root.clone().into_iter().collect(),
+        }
+    }
+
+    /// Returns a NodeHandle to the node at a path. A path is the index of the
+    /// child to follow at each level, starting from the root, so the empty
+    /// path is the root itself.
+    ///
+    /// Time Complexity: O(d), where d is the length of the path
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nary_tree::NodeBuilder;
+    ///
+    /// let tree = NodeBuilder::new("root")
+    ///     .child(NodeBuilder::new("a"))
+    ///     .child(NodeBuilder::new("b").child(NodeBuilder::new("b1")))
+    ///     .build();
+    ///
+    /// assert_eq!(*tree.path(&[1, 0]).unwrap().value(), "b1");
+    /// assert!(tree.path(&[2]).is_none());
+    /// ```
+    pub fn path(&self, indices: &[usize]) -> Option<NodeHandle<T>> {
+        let mut current = self.root()?;
+
+        for &i in indices {
+            current = current.child(i)?;
+        }
+
+        Some(current)
+    }
+
+    /// Returns the lowest common ancestor of two nodes, the deepest node
+    /// both are descendants of, a node being a descendant of itself. Returns
+    /// None if the nodes aren't both in this NaryTree.
+    ///
+    /// Without an order between the values, the nodes are walked up through
+    /// their parents: the deeper one to the depth of the other, then both
+    /// together until they meet.
+    ///
+    /// Time Complexity: O(h), where h is the height of the NaryTree
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nary_tree::NodeBuilder;
+    ///
+    /// let tree = NodeBuilder::new("/")
+    ///     .child(
+    ///         NodeBuilder::new("usr")
+    ///             .child(NodeBuilder::new("bin"))
+    ///             .child(NodeBuilder::new("lib").child(NodeBuilder::new("rustlib"))),
+    ///     )
+    ///     .child(NodeBuilder::new("etc"))
+    ///     .build();
+    ///
+    /// let bin = tree.path(&[0, 0]).unwrap();
+    /// let rustlib = tree.path(&[0, 1, 0]).unwrap();
+    /// let etc = tree.path(&[1]).unwrap();
+    ///
+    /// assert_eq!(*tree.lca(&bin, &rustlib).unwrap().value(), "usr");
+    /// assert_eq!(*tree.lca(&rustlib, &etc).unwrap().value(), "/");
+    /// ```
+    pub fn lca(&self, a: &NodeHandle<T>, b: &NodeHandle<T>) -> Option<NodeHandle<T>> {
+        let depth = |node: &NodeHandle<T>| {
+            let mut depth = 0;
+            let mut current = node.parent();
+            while let Some(parent) = current {
+                depth += 1;
+                current = parent.parent();
+            }
+
+            depth
+        };
+
+        let (mut a, mut b) = (a.clone(), b.clone());
+        let (mut depth_a, mut depth_b) = (depth(&a), depth(&b));
+        while depth_a > depth_b {
+            a = a.parent()?;
+            depth_a -= 1;
+        }
+        while depth_b > depth_a {
+            b = b.parent()?;
+            depth_b -= 1;
+        }
+        while !a.ptr_eq(&b) {
+            a = a.parent()?;
+            b = b.parent()?;
+        }
+
+        // Nodes of another tree, or of a detached subtree, don't lead up to
+        // this root.
+        let mut top = a.clone();
+        while let Some(parent) = top.parent() {
+            top = parent;
+        }
+        if !top.ptr_eq(&self.root()?) {
+            return None;
+        }
+
+        Some(a)
+    }
+
+    /// Returns the paths to every node whose value matches `pred`, in
+    /// depth first (pre-order) order.
+    ///
+    /// Time Complexity: O(n * h), where h is the height of the NaryTree
+    /// Space Complexity: O(n * h)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nary_tree::NodeBuilder;
+    ///
+    /// let tree = NodeBuilder::new(1)
+    ///     .child(NodeBuilder::new(2).child(NodeBuilder::new(4)))
+    ///     .child(NodeBuilder::new(3))
+    ///     .build();
+    ///
+    /// assert_eq!(tree.find_by(|v| v % 2 == 0), vec![vec![0], vec![0, 0]]);
+    /// ```
+    pub fn find_by<F>(&self, mut pred: F) -> Vec<Vec<usize>>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut result = Vec::new();
+        let mut stack: Vec<(NodeRef<T>, Vec<usize>)> = self
+            .root
+            .clone()
+            .into_iter()
+            .map(|root| (root, Vec::new()))
+            .collect();
+
+        while let Some((node, path)) = stack.pop() {
+            let children = node.children();
+            for (i, child) in children.into_iter().enumerate().rev() {
+                let mut child_path = path.clone();
+                child_path.push(i);
+                stack.push((child, child_path));
+            }
+
+            if pred(&node.0.borrow().value) {
+                result.push(path);
+            }
+        }
+
+        result
+    }
+
+    /// Removes the subtree at a path and returns it as its own NaryTree.
+    /// Detaching the empty path takes the whole tree, leaving this one empty.
+    ///
+    /// Time Complexity: O(d + k), where k is the size of the subtree
+    /// Space Complexity: O(h)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nary_tree::NodeBuilder;
+    ///
+    /// let mut tree = NodeBuilder::new(1)
+    ///     .child(NodeBuilder::new(2).child(NodeBuilder::new(3)))
+    ///     .build();
+    ///
+    /// let subtree = tree.detach(&[0]).unwrap();
+    /// assert_eq!(subtree.dfs().collect::<Vec<_>>(), vec![2, 3]);
+    /// assert_eq!(tree.len(), 1);
+    /// ```
+    pub fn detach(&mut self, indices: &[usize]) -> Option<NaryTree<T>> {
+        let (last, parent_indices) = match indices.split_last() {
+            Some(split) => split,
+            None => {
+                let size = self.size;
+                self.size = 0;
+
+                return self.root.take().map(|root| NaryTree {
+                    root: Some(root),
+                    size,
+                });
+            }
+        };
+
+        let parent = self.path(parent_indices)?;
+        if *last >= parent.child_count() {
+            return None;
+        }
+
+        let node = parent.0 .0.borrow_mut().children.remove(*last);
+        node.0.borrow_mut().parent = None;
+
+        let subtree = NaryTree {
+            size: count(&node),
+            root: Some(node),
+        };
+        self.size -= subtree.size;
+
+        Some(subtree)
+    }
+
+    /// Moves every node of `subtree` into this NaryTree, appending its root as
+    /// the last child of `parent`.
+    ///
+    /// The parent NodeHandle must have come from this NaryTree.
+    ///
+    /// Time Complexity: O(1) amortized
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nary_tree::{NaryTree, NodeBuilder};
+    ///
+    /// let mut tree = NaryTree::new(1);
+    /// let subtree = NodeBuilder::new(2).child(NodeBuilder::new(3)).build();
+    ///
+    /// let root = tree.root().unwrap();
+    /// tree.attach(&root, subtree);
+    ///
+    /// assert_eq!(tree.len(), 3);
+    /// assert_eq!(*tree.path(&[0, 0]).unwrap().value(), 3);
+    /// ```
+    pub fn attach(&mut self, parent: &NodeHandle<T>, mut subtree: NaryTree<T>) {
+        if let Some(node) = subtree.root.take() {
+            node.0.borrow_mut().parent = Some(Rc::downgrade(&parent.0 .0));
+            parent.0 .0.borrow_mut().children.push(node);
+            self.size += subtree.size;
+        }
+    }
+
+    /// Keeps only the nodes whose value matches `pred`, in one traversal.
+    ///
+    /// A node that doesn't match is removed together with its descendants,
+    /// which `pred` isn't called on, since there is nowhere to move the
+    /// children of a removed node to. If the root doesn't match, the
+    /// NaryTree is left empty.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(w), where w is the width of the NaryTree
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nary_tree::NodeBuilder;
+    ///
+    /// let mut tree = NodeBuilder::new(1)
+    ///     .child(NodeBuilder::new(2).child(NodeBuilder::new(4)))
+    ///     .child(NodeBuilder::new(3).child(NodeBuilder::new(6)))
+    ///     .build();
+    ///
+    /// tree.retain(|v| *v != 2);
+    /// assert_eq!(tree.dfs().collect::<Vec<_>>(), vec![1, 3, 6]);
+    /// assert_eq!(tree.len(), 3);
+    /// ```
+    pub fn retain<F>(&mut self, mut pred: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let root = match &self.root {
+            Some(root) => root.clone(),
+            None => return,
+        };
+
+        if !pred(&root.0.borrow().value) {
+            self.clear();
+            return;
+        }
+
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            let children = std::mem::take(&mut node.0.borrow_mut().children);
+
+            let mut kept = Vec::with_capacity(children.len());
+            for child in children {
+                if pred(&child.0.borrow().value) {
+                    stack.push(child.clone());
+                    kept.push(child);
+                } else {
+                    self.size -= count(&child);
+                }
+            }
+
+            node.0.borrow_mut().children = kept;
+        }
+    }
+}
+
+impl<T> NaryTree<T> {
+    /// Returns a boolean indicating both NaryTrees have the same shape: every
+    /// node has as many children as the node at the same position in the
+    /// other tree. The values are ignored, so the trees can hold different
+    /// types. Compare with `==` to check the values too.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(h)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nary_tree::NodeBuilder;
+    ///
+    /// // The parse trees of "a + b * c" and "1 - 2 / 3".
+    /// let sum = NodeBuilder::new('+')
+    ///     .child(NodeBuilder::new('a'))
+    ///     .child(NodeBuilder::new('*').child(NodeBuilder::new('b')).child(NodeBuilder::new('c')))
+    ///     .build();
+    /// let difference = NodeBuilder::new("-")
+    ///     .child(NodeBuilder::new("1"))
+    ///     .child(NodeBuilder::new("/").child(NodeBuilder::new("2")).child(NodeBuilder::new("3")))
+    ///     .build();
+    ///
+    /// assert!(sum.is_same_shape(&difference));
+    /// ```
+    pub fn is_same_shape<U>(&self, other: &NaryTree<U>) -> bool {
+        self.size == other.size && same_trees(&self.root, &other.root, &mut |_, _| true)
+    }
+
+    /// Returns a boolean indicating this NaryTree has the shape of the
+    /// subtree rooted at some node of `other`, ignoring the values. The
+    /// subtree takes every descendant of its root, and the empty tree is a
+    /// subtree of any tree.
+    ///
+    /// Time Complexity: O(n * m), where m is the size of `other`
+    /// Space Complexity: O(h + m)
+    pub fn is_subtree_shape_of<U>(&self, other: &NaryTree<U>) -> bool {
+        is_subtree(&self.root, self.size, &other.root, &mut |_, _| true)
+    }
+
+    /// Returns a boolean indicating this NaryTree is the subtree rooted at
+    /// some node of `other`, with the same shape and the same values. The
+    /// subtree takes every descendant of its root, and the empty tree is a
+    /// subtree of any tree.
+    ///
+    /// Time Complexity: O(n * m), where m is the size of `other`
+    /// Space Complexity: O(h + m)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nary_tree::NodeBuilder;
+    ///
+    /// let config = NodeBuilder::new("server")
+    ///     .child(NodeBuilder::new("tls").child(NodeBuilder::new("cert")))
+    ///     .child(NodeBuilder::new("port"))
+    ///     .build();
+    ///
+    /// let tls = NodeBuilder::new("tls").child(NodeBuilder::new("cert")).build();
+    /// assert!(tls.is_subtree_of(&config));
+    ///
+    /// // The tls node of the config has a child this tree doesn't.
+    /// let bare = NodeBuilder::new("tls").build();
+    /// assert!(!bare.is_subtree_of(&config));
+    /// assert!(bare.is_subtree_shape_of(&config));
+    /// ```
+    pub fn is_subtree_of(&self, other: &NaryTree<T>) -> bool
+    where
+        T: PartialEq,
+    {
+        is_subtree(&self.root, self.size, &other.root, &mut |a, b| a == b)
+    }
+}
+
+/// Returns a boolean indicating two trees have the same shape and `eq` holds
+/// for the values at every position.
+fn same_trees<T, U, F>(a: &Option<NodeRef<T>>, b: &Option<NodeRef<U>>, eq: &mut F) -> bool
+where
+    F: FnMut(&T, &U) -> bool,
+{
+    match (a, b) {
+        (Some(a), Some(b)) => same_nodes(a, b, eq),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Returns a boolean indicating the subtrees rooted at `a` and `b` have the
+/// same shape and `eq` holds for the values at every position.
+fn same_nodes<T, U, F>(a: &NodeRef<T>, b: &NodeRef<U>, eq: &mut F) -> bool
+where
+    F: FnMut(&T, &U) -> bool,
+{
+    let mut stack = vec![(a.clone(), b.clone())];
+
+    while let Some((a, b)) = stack.pop() {
+        let (a, b) = (a.0.borrow(), b.0.borrow());
+        if a.children.len() != b.children.len() || !eq(&a.value, &b.value) {
+            return false;
+        }
+
+        stack.extend(a.children.iter().cloned().zip(b.children.iter().cloned()));
+    }
+
+    true
+}
+
+/// Returns a boolean indicating the tree rooted at `root`, of `size` nodes,
+/// matches the subtree of some node of `other`.
+fn is_subtree<T, U, F>(
+    root: &Option<NodeRef<T>>,
+    size: usize,
+    other: &Option<NodeRef<U>>,
+    eq: &mut F,
+) -> bool
+where
+    F: FnMut(&T, &U) -> bool,
+{
+    let root = match root {
+        Some(root) => root,
+        None => return true,
+    };
+
+    // Only the subtrees of the same size can match, so the sizes of every
+    // subtree of other are counted first, children after their parent.
+    let mut nodes: Vec<NodeRef<U>> = other.clone().into_iter().collect();
+    let mut i = 0;
+    while i < nodes.len() {
+        let children = nodes[i].children();
+        nodes.extend(children);
+        i += 1;
+    }
+
+    let mut sizes: Vec<usize> = vec![1; nodes.len()];
+    let mut first_child = vec![0; nodes.len()];
+    let mut next = 1;
+    for (i, node) in nodes.iter().enumerate() {
+        first_child[i] = next;
+        next += node.0.borrow().children.len();
+    }
+    for i in (0..nodes.len()).rev() {
+        let children = nodes[i].0.borrow().children.len();
+        sizes[i] += sizes[first_child[i]..first_child[i] + children]
+            .iter()
+            .sum::<usize>();
+    }
+
+    nodes
+        .iter()
+        .zip(sizes)
+        .any(|(node, n)| n == size && same_nodes(root, node, eq))
+}
+
+/// Returns the number of nodes in the subtree rooted at `node`.
+fn count<T>(node: &NodeRef<T>) -> usize {
+    let mut size = 0;
+    let mut stack = vec![node.clone()];
+
+    while let Some(node) = stack.pop() {
+        size += 1;
+        stack.extend(node.children());
+    }
+
+    size
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sample() -> NaryTree<u32> {
+        //        1
+        //     /  |  \
+        //    2   3   4
+        //   / \      |
+        //  5   6     7
+        NodeBuilder::new(1)
+            .child(
+                NodeBuilder::new(2)
+                    .child(NodeBuilder::new(5))
+                    .child(NodeBuilder::new(6)),
+            )
+            .child(NodeBuilder::new(3))
+            .child(NodeBuilder::new(4).child(NodeBuilder::new(7)))
+            .build()
+    }
+
+    #[test]
+    fn init_tree() {
+        let tree = NaryTree::<u32>::default();
+        assert_eq!(tree.len(), 0);
+        assert!(tree.root().is_none());
+        assert_eq!(tree.dfs().count(), 0);
+        assert_eq!(tree.bfs().count(), 0);
+    }
+
+    #[test]
+    fn build_tree() {
+        let tree = sample();
+        assert_eq!(tree.len(), 7);
+
+        let root = tree.root().unwrap();
+        assert_eq!(*root.value(), 1);
+        assert_eq!(root.child_count(), 3);
+        assert!(root.parent().is_none());
+
+        let two = root.child(0).unwrap();
+        assert_eq!(*two.value(), 2);
+        assert!(two.parent().unwrap().ptr_eq(&root));
+        assert_eq!(
+            two.children()
+                .iter()
+                .map(|c| *c.value())
+                .collect::<Vec<_>>(),
+            vec![5, 6]
+        );
+        assert!(root.child(3).is_none());
+    }
+
+    #[test]
+    fn dfs() {
+        let tree = sample();
+        assert_eq!(tree.dfs().collect::<Vec<_>>(), vec![1, 2, 5, 6, 3, 4, 7]);
+    }
+
+    #[test]
+    fn bfs() {
+        let tree = sample();
+        assert_eq!(tree.bfs().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn push_child() {
+        let mut tree = NaryTree::new("root".to_string());
+        let root = tree.root().unwrap();
+
+        let a = tree.push_child(&root, "a".to_string());
+        tree.push_child(&root, "b".to_string());
+        tree.push_child(&a, "a1".to_string());
+
+        assert_eq!(tree.len(), 4);
+        assert_eq!(tree.dfs().collect::<Vec<_>>(), vec!["root", "a", "a1", "b"]);
+
+        *a.value_mut() = "A".to_string();
+        assert_eq!(tree.bfs().nth(1), Some("A".to_string()));
+    }
+
+    #[test]
+    fn generic_over_collection() {
+        fn sizes<C: Collection>(collection: &C) -> (usize, usize) {
+            (collection.len(), collection.iter().count())
+        }
+
+        let mut tree = sample();
+        assert_eq!(sizes(&tree), (7, 7));
+
+        Collection::clear(&mut tree);
+        assert_eq!(sizes(&tree), (0, 0));
+    }
+
+    #[test]
+    fn path() {
+        let tree = sample();
+
+        assert_eq!(*tree.path(&[]).unwrap().value(), 1);
+        assert_eq!(*tree.path(&[0, 1]).unwrap().value(), 6);
+        assert_eq!(*tree.path(&[2, 0]).unwrap().value(), 7);
+        assert!(tree.path(&[1, 0]).is_none());
+        assert!(tree.path(&[5]).is_none());
+        assert!(NaryTree::<u32>::default().path(&[]).is_none());
+    }
+
+    #[test]
+    fn lca() {
+        let tree = sample();
+        let node = |path: &[usize]| tree.path(path).unwrap();
+        let lca = |a: &[usize], b: &[usize]| *tree.lca(&node(a), &node(b)).unwrap().value();
+
+        assert_eq!(lca(&[0, 0], &[0, 1]), 2);
+        assert_eq!(lca(&[0, 1], &[2, 0]), 1);
+        assert_eq!(lca(&[0], &[0, 1]), 2);
+        assert_eq!(lca(&[2, 0], &[2, 0]), 7);
+        assert_eq!(lca(&[], &[1]), 1);
+
+        // Nodes outside the tree have no ancestor in it.
+        let other = sample();
+        let outside = other.path(&[0]).unwrap();
+        assert!(tree.lca(&node(&[0]), &outside).is_none());
+
+        let mut tree = sample();
+        let five = tree.path(&[0, 0]).unwrap();
+        let six = tree.path(&[0, 1]).unwrap();
+        let detached = tree.detach(&[0]).unwrap();
+        assert!(tree.lca(&five, &six).is_none());
+        assert_eq!(*detached.lca(&five, &six).unwrap().value(), 2);
+    }
+
+    #[test]
+    fn find_by() {
+        let tree = sample();
+
+        assert_eq!(
+            tree.find_by(|v| *v > 4),
+            vec![vec![0, 0], vec![0, 1], vec![2, 0]]
+        );
+        assert_eq!(tree.find_by(|v| *v == 1), vec![Vec::<usize>::new()]);
+        assert!(tree.find_by(|v| *v == 100).is_empty());
+    }
+
+    #[test]
+    fn detach_and_attach() {
+        let mut tree = sample();
+
+        let two = tree.detach(&[0]).unwrap();
+        assert_eq!(two.len(), 3);
+        assert_eq!(two.dfs().collect::<Vec<_>>(), vec![2, 5, 6]);
+        assert!(two.root().unwrap().parent().is_none());
+
+        assert_eq!(tree.len(), 4);
+        assert_eq!(tree.dfs().collect::<Vec<_>>(), vec![1, 3, 4, 7]);
+        assert!(tree.detach(&[5]).is_none());
+        assert!(tree.detach(&[1, 1]).is_none());
+
+        // Graft the detached subtree under 7.
+        let seven = tree.path(&[1, 0]).unwrap();
+        tree.attach(&seven, two);
+        assert_eq!(tree.len(), 7);
+        assert_eq!(tree.dfs().collect::<Vec<_>>(), vec![1, 3, 4, 7, 2, 5, 6]);
+        assert!(tree
+            .path(&[1, 0, 0])
+            .unwrap()
+            .parent()
+            .unwrap()
+            .ptr_eq(&seven));
+    }
+
+    #[test]
+    fn detach_root() {
+        let mut tree = sample();
+
+        let whole = tree.detach(&[]).unwrap();
+        assert_eq!(whole.len(), 7);
+        assert!(tree.is_empty());
+        assert!(tree.root().is_none());
+        assert!(tree.detach(&[]).is_none());
+    }
+
+    #[test]
+    fn retain() {
+        let mut tree = sample();
+        let mut calls = 0;
+
+        // Dropping 2 drops 5 and 6 without asking about them.
+        tree.retain(|v| {
+            calls += 1;
+            *v != 2 && *v != 3
+        });
+        assert_eq!(calls, 5);
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.dfs().collect::<Vec<_>>(), vec![1, 4, 7]);
+        assert!(tree
+            .path(&[0, 0])
+            .unwrap()
+            .parent()
+            .unwrap()
+            .ptr_eq(&tree.path(&[0]).unwrap()));
+
+        tree.retain(|_| true);
+        assert_eq!(tree.len(), 3);
+
+        tree.retain(|v| *v != 1);
+        assert!(tree.is_empty());
+        assert!(tree.root().is_none());
+
+        let mut empty = NaryTree::<u32>::default();
+        empty.retain(|_| false);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn same_shape_and_equality() {
+        let tree = sample();
+        let labels = NodeBuilder::new("r")
+            .child(
+                NodeBuilder::new("a")
+                    .child(NodeBuilder::new("c"))
+                    .child(NodeBuilder::new("d")),
+            )
+            .child(NodeBuilder::new("b"))
+            .child(NodeBuilder::new("e").child(NodeBuilder::new("f")))
+            .build();
+        assert!(tree.is_same_shape(&labels));
+        assert!(tree == sample());
+
+        // Same size, but 7 hangs off 3 instead of 4.
+        let moved = NodeBuilder::new(1)
+            .child(
+                NodeBuilder::new(2)
+                    .child(NodeBuilder::new(5))
+                    .child(NodeBuilder::new(6)),
+            )
+            .child(NodeBuilder::new(3).child(NodeBuilder::new(7)))
+            .child(NodeBuilder::new(4))
+            .build();
+        assert!(!tree.is_same_shape(&moved));
+        assert!(tree != moved);
+
+        let changed = sample();
+        *changed.path(&[2, 0]).unwrap().value_mut() = 8;
+        assert!(tree.is_same_shape(&changed));
+        assert!(tree != changed);
+
+        let empty = NaryTree::<u32>::default();
+        assert!(empty.is_same_shape(&NaryTree::<()>::default()));
+        assert!(!empty.is_same_shape(&tree));
+    }
+
+    #[test]
+    fn subtrees() {
+        let tree = sample();
+
+        let two = NodeBuilder::new(2)
+            .child(NodeBuilder::new(5))
+            .child(NodeBuilder::new(6))
+            .build();
+        assert!(two.is_subtree_of(&tree));
+        assert!(tree.is_subtree_of(&tree));
+        assert!(NaryTree::new(7).is_subtree_of(&tree));
+        assert!(NaryTree::default().is_subtree_of(&tree));
+
+        // A node of the tree with only some of its children isn't a subtree.
+        let partial = NodeBuilder::new(2).child(NodeBuilder::new(5)).build();
+        assert!(!partial.is_subtree_of(&tree));
+
+        // The shape of 4 -> 7 matches, but not the values.
+        let other = NodeBuilder::new(9).child(NodeBuilder::new(9)).build();
+        assert!(!other.is_subtree_of(&tree));
+        assert!(other.is_subtree_shape_of(&tree));
+        assert!(!two.is_subtree_shape_of(&other));
+        assert!(!tree.is_subtree_of(&NaryTree::default()));
+    }
+}
+
+// The ordering guarantees of the public API. These tests must keep passing
+// across any rewrite of the NaryTree internals.
+#[cfg(test)]
+mod contract {
+    use super::*;
+
+    fn tree() -> NaryTree<u32> {
+        NodeBuilder::new(1)
+            .child(NodeBuilder::new(2).child(NodeBuilder::new(4)))
+            .child(NodeBuilder::new(3).child(NodeBuilder::new(5)))
+            .build()
+    }
+
+    #[test]
+    fn dfs_is_pre_order_with_children_in_insertion_order() {
+        let mut tree = tree();
+        let root = tree.root().unwrap();
+        tree.push_child(&root, 6);
+
+        assert_eq!(tree.dfs().collect::<Vec<_>>(), vec![1, 2, 4, 3, 5, 6]);
+        assert_eq!(
+            Collection::iter(&tree).collect::<Vec<_>>(),
+            vec![1, 2, 4, 3, 5, 6]
+        );
+    }
+
+    #[test]
+    fn bfs_is_level_order_with_children_in_insertion_order() {
+        let tree = tree();
+
+        assert_eq!(tree.bfs().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn find_by_follows_dfs_order() {
+        let tree = tree();
+
+        assert_eq!(
+            tree.find_by(|_| true),
+            vec![vec![], vec![0], vec![0, 0], vec![1], vec![1, 0]]
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+/// Alias for a referenced Node.
+#[derive(Debug)]
+pub(crate) struct NodeRef<T>(pub Rc<RefCell<Node<T>>>);
+
+impl<T> Clone for NodeRef<T> {
+    fn clone(&self) -> Self {
+        NodeRef(Rc::clone(&self.0))
+    }
+}
+
+impl<T> NodeRef<T> {
+    /// new returns a NodeRef that wraps a Node<T> in a Rc<RefCell<Node<T>>.
+    pub fn new(v: Node<T>) -> NodeRef<T> {
+        NodeRef(Rc::new(RefCell::new(v)))
+    }
+
+    /// Returns the children of the Node.
+    pub fn children(&self) -> Vec<NodeRef<T>> {
+        self.0.borrow().children.clone()
+    }
+}
+
+/// Node is the structure in a NaryTree. It holds a value `T`, owns its
+/// children and keeps a weak pointer to its parent, so a parent and child
+/// don't keep each other alive.
+#[derive(Debug)]
+pub(crate) struct Node<T> {
+    pub value: T,
+    pub children: Vec<NodeRef<T>>,
+    pub parent: Option<Weak<RefCell<Node<T>>>>,
+}
+
+impl<T> Node<T> {
+    pub fn new(value: T) -> Node<T> {
+        Node {
+            value,
+            children: Vec::new(),
+            parent: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn init_node() {
+        let node = Node::new(5);
+        assert_eq!(node.value, 5);
+        assert!(node.children.is_empty());
+        assert!(node.parent.is_none());
+    }
+
+    #[test]
+    fn parent_does_not_keep_child_alive() {
+        let parent = NodeRef::new(Node::new(1));
+        let child = NodeRef::new(Node::new(2));
+
+        child.0.borrow_mut().parent = Some(Rc::downgrade(&parent.0));
+        parent.0.borrow_mut().children.push(child.clone());
+
+        assert_eq!(Rc::strong_count(&parent.0), 1);
+        assert_eq!(Rc::strong_count(&child.0), 2);
+
+        drop(parent);
+        assert!(child
+            .0
+            .borrow()
+            .parent
+            .as_ref()
+            .unwrap()
+            .upgrade()
+            .is_none());
+    }
+}
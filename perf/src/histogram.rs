@@ -0,0 +1,174 @@
+use std::convert::TryFrom;
+use std::time::Duration;
+
+/// The number of buckets, bucket i counts the latencies below 2^i
+/// nanoseconds that didn't fit in bucket i - 1.
+const BUCKETS: usize = 64;
+
+/// Histogram counts latencies in buckets of doubling width.
+///
+/// # Example
+///
+/// ```
+/// use perf::Histogram;
+/// use std::time::Duration;
+///
+/// let mut histogram = Histogram::default();
+/// histogram.record(Duration::from_nanos(100));
+/// histogram.record(Duration::from_nanos(300));
+///
+/// assert_eq!(histogram.count(), 2);
+/// assert_eq!(histogram.mean(), Some(Duration::from_nanos(200)));
+/// assert_eq!(histogram.max(), Some(Duration::from_nanos(300)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    buckets: [u64; BUCKETS],
+    count: u64,
+    total: u128,
+    min: u64,
+    max: u64,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Histogram {
+            buckets: [0; BUCKETS],
+            count: 0,
+            total: 0,
+            min: u64::MAX,
+            max: 0,
+        }
+    }
+}
+
+impl Histogram {
+    /// Records a latency.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn record(&mut self, latency: Duration) {
+        let nanos = u64::try_from(latency.as_nanos()).unwrap_or(u64::MAX);
+
+        self.buckets[bucket(nanos)] += 1;
+        self.count += 1;
+        self.total += nanos as u128;
+        self.min = self.min.min(nanos);
+        self.max = self.max.max(nanos);
+    }
+
+    /// Returns the number of latencies recorded.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the mean latency.
+    pub fn mean(&self) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+
+        Some(Duration::from_nanos(
+            (self.total / self.count as u128) as u64,
+        ))
+    }
+
+    /// Returns the smallest latency recorded.
+    pub fn min(&self) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+
+        Some(Duration::from_nanos(self.min))
+    }
+
+    /// Returns the largest latency recorded.
+    pub fn max(&self) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+
+        Some(Duration::from_nanos(self.max))
+    }
+
+    /// Returns an upper bound of the latency below which `p` percent of the
+    /// latencies fall, never more than the largest latency recorded.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn percentile(&self, p: f64) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let rank = ((p.clamp(0.0, 100.0) / 100.0) * self.count as f64).ceil() as u64;
+        let rank = rank.max(1);
+
+        let mut seen = 0;
+        for (i, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return Some(Duration::from_nanos(upper_bound(i).min(self.max)));
+            }
+        }
+
+        Some(Duration::from_nanos(self.max))
+    }
+}
+
+/// Returns the bucket counting a latency of `nanos`.
+fn bucket(nanos: u64) -> usize {
+    (u64::BITS - nanos.leading_zeros()).min(BUCKETS as u32 - 1) as usize
+}
+
+/// Returns the largest latency counted by bucket `i`.
+fn upper_bound(i: usize) -> u64 {
+    if i >= BUCKETS - 1 {
+        return u64::MAX;
+    }
+
+    (1u64 << i) - 1
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn buckets() {
+        assert_eq!(bucket(0), 0);
+        assert_eq!(bucket(1), 1);
+        assert_eq!(bucket(2), 2);
+        assert_eq!(bucket(3), 2);
+        assert_eq!(bucket(4), 3);
+        assert_eq!(bucket(u64::MAX), BUCKETS - 1);
+
+        for nanos in 0..1000 {
+            assert!(nanos <= upper_bound(bucket(nanos)));
+        }
+    }
+
+    #[test]
+    fn percentiles() {
+        let mut histogram = Histogram::default();
+        for nanos in 1..=100 {
+            histogram.record(Duration::from_nanos(nanos));
+        }
+
+        // 50 falls in the bucket of 32..=63.
+        assert_eq!(histogram.percentile(50.0), Some(Duration::from_nanos(63)));
+        assert_eq!(histogram.percentile(100.0), Some(Duration::from_nanos(100)));
+        assert_eq!(histogram.percentile(0.0), Some(Duration::from_nanos(1)));
+        assert_eq!(histogram.min(), Some(Duration::from_nanos(1)));
+    }
+
+    #[test]
+    fn empty() {
+        let histogram = Histogram::default();
+
+        assert_eq!(histogram.count(), 0);
+        assert_eq!(histogram.mean(), None);
+        assert_eq!(histogram.percentile(50.0), None);
+        assert_eq!(histogram.max(), None);
+    }
+}
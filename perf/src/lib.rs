@@ -0,0 +1,7 @@
+//! A crate that measures the latency of the operations on the data
+//! structures, by wrapping them in a Timed structure.
+pub use crate::histogram::Histogram;
+pub use crate::timed::{Report, Timed};
+
+mod histogram;
+mod timed;
@@ -0,0 +1,312 @@
+use crate::histogram::Histogram;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Instant;
+use traits::{Collection, Deque, Map, Queue, Set, Stack};
+
+/// Timed wraps a data structure and records the latency of every operation
+/// called through it, in a Histogram per operation name. The Histograms are
+/// kept in a RefCell, so the operations that only borrow the structure, like
+/// `len()` and `contains()`, are recorded too.
+///
+/// # Example
+///
+/// ```
+/// use perf::Timed;
+/// use std::collections::VecDeque;
+/// use traits::Queue;
+///
+/// let mut queue = Timed::new(VecDeque::new());
+/// queue.enqueue(1);
+/// queue.enqueue(2);
+/// queue.dequeue();
+///
+/// let report = queue.report();
+/// assert_eq!(report.get("enqueue").unwrap().count(), 2);
+/// assert_eq!(report.get("dequeue").unwrap().count(), 1);
+/// println!("{}", report);
+/// ```
+pub struct Timed<C> {
+    inner: C,
+    histograms: RefCell<BTreeMap<&'static str, Histogram>>,
+}
+
+impl<C> Timed<C> {
+    /// Returns a Timed wrapping `inner`, with no latencies recorded.
+    pub fn new(inner: C) -> Timed<C> {
+        Timed {
+            inner,
+            histograms: RefCell::new(BTreeMap::new()),
+        }
+    }
+
+    /// Returns a reference to the wrapped structure. Calls made through it
+    /// aren't timed.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Returns the wrapped structure, dropping the latencies.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    /// Returns the latencies recorded so far.
+    pub fn report(&self) -> Report {
+        Report {
+            histograms: self.histograms.borrow().clone(),
+        }
+    }
+
+    /// Forgets the latencies recorded so far.
+    pub fn reset(&mut self) {
+        self.histograms.get_mut().clear();
+    }
+
+    /// Runs `f` on the wrapped structure and records how long it took.
+    fn time<R, F>(&mut self, operation: &'static str, f: F) -> R
+    where
+        F: FnOnce(&mut C) -> R,
+    {
+        let start = Instant::now();
+        let result = f(&mut self.inner);
+        self.record(operation, start);
+
+        result
+    }
+
+    /// Runs `f` on a shared borrow of the wrapped structure and records how
+    /// long it took.
+    fn time_ref<R, F>(&self, operation: &'static str, f: F) -> R
+    where
+        F: FnOnce(&C) -> R,
+    {
+        let start = Instant::now();
+        let result = f(&self.inner);
+        self.record(operation, start);
+
+        result
+    }
+
+    /// Records the latency of an operation that began at `start`.
+    fn record(&self, operation: &'static str, start: Instant) {
+        let latency = start.elapsed();
+
+        self.histograms
+            .borrow_mut()
+            .entry(operation)
+            .or_default()
+            .record(latency);
+    }
+}
+
+impl<C> Collection for Timed<C>
+where
+    C: Collection,
+{
+    type Iter<'a>
+        = C::Iter<'a>
+    where
+        C: 'a;
+
+    fn len(&self) -> usize {
+        self.time_ref("len", |c| c.len())
+    }
+
+    fn clear(&mut self) {
+        self.time("clear", |c| c.clear())
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.inner.iter()
+    }
+}
+
+impl<C, T> Stack<T> for Timed<C>
+where
+    C: Stack<T>,
+{
+    fn push(&mut self, v: T) {
+        self.time("push", |c| c.push(v))
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.time("pop", |c| c.pop())
+    }
+}
+
+impl<C, T> Queue<T> for Timed<C>
+where
+    C: Queue<T>,
+{
+    fn enqueue(&mut self, v: T) {
+        self.time("enqueue", |c| c.enqueue(v))
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.time("dequeue", |c| c.dequeue())
+    }
+}
+
+impl<C, T> Deque<T> for Timed<C>
+where
+    C: Deque<T>,
+{
+    fn push_front(&mut self, v: T) {
+        self.time("push_front", |c| c.push_front(v))
+    }
+
+    fn push_back(&mut self, v: T) {
+        self.time("push_back", |c| c.push_back(v))
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        self.time("pop_front", |c| c.pop_front())
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        self.time("pop_back", |c| c.pop_back())
+    }
+}
+
+impl<C, K, V> Map<K, V> for Timed<C>
+where
+    C: Map<K, V>,
+{
+    fn insert(&mut self, key: K, value: V) {
+        self.time("insert", |c| c.insert(key, value))
+    }
+
+    fn get(&mut self, key: &K) -> Option<V> {
+        self.time("get", |c| c.get(key))
+    }
+}
+
+impl<C, T> Set<T> for Timed<C>
+where
+    C: Set<T>,
+{
+    fn insert(&mut self, value: T) -> bool {
+        self.time("insert", |c| c.insert(value))
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.time_ref("contains", |c| c.contains(value))
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        self.time("remove", |c| c.remove(value))
+    }
+}
+
+/// Report is a snapshot of the latencies recorded by a Timed structure. Its
+/// Display implementation prints a line per operation.
+#[derive(Debug, Clone)]
+pub struct Report {
+    histograms: BTreeMap<&'static str, Histogram>,
+}
+
+impl Report {
+    /// Returns the Histogram of an operation, e.g. "push".
+    pub fn get(&self, operation: &str) -> Option<&Histogram> {
+        self.histograms.get(operation)
+    }
+
+    /// Returns the names of the operations that were recorded, sorted.
+    pub fn operations(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.histograms.keys().copied()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (operation, histogram) in &self.histograms {
+            writeln!(
+                f,
+                "{}: count={} mean={:?} p50={:?} p99={:?} max={:?}",
+                operation,
+                histogram.count(),
+                histogram.mean().unwrap_or_default(),
+                histogram.percentile(50.0).unwrap_or_default(),
+                histogram.percentile(99.0).unwrap_or_default(),
+                histogram.max().unwrap_or_default(),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::{HashMap, HashSet, VecDeque};
+
+    #[test]
+    fn records_each_operation() {
+        let mut stack = Timed::new(Vec::new());
+        for i in 0..10 {
+            stack.push(i);
+        }
+        stack.pop();
+        Collection::clear(&mut stack);
+
+        let report = stack.report();
+        assert_eq!(report.get("push").unwrap().count(), 10);
+        assert_eq!(report.get("pop").unwrap().count(), 1);
+        assert_eq!(report.get("clear").unwrap().count(), 1);
+        assert!(report.get("enqueue").is_none());
+        assert_eq!(
+            report.operations().collect::<Vec<_>>(),
+            vec!["clear", "pop", "push"]
+        );
+    }
+
+    #[test]
+    fn wraps_every_trait() {
+        let mut deque = Timed::new(VecDeque::new());
+        deque.push_back(1);
+        deque.push_front(0);
+        assert_eq!(deque.pop_back(), Some(1));
+        assert_eq!(Collection::len(&deque), 1);
+        assert_eq!(deque.iter().collect::<Vec<_>>(), vec![&0]);
+
+        let mut map = Timed::new(HashMap::new());
+        Map::insert(&mut map, "a", 1);
+        assert_eq!(Map::get(&mut map, &"a"), Some(1));
+
+        let mut set = Timed::new(HashSet::new());
+        assert!(Set::insert(&mut set, 1));
+        assert!(Set::contains(&set, &1));
+        assert!(Set::remove(&mut set, &1));
+        assert_eq!(set.report().get("remove").unwrap().count(), 1);
+    }
+
+    #[test]
+    fn records_shared_borrows() {
+        let mut set = Timed::new(HashSet::new());
+        Set::insert(&mut set, 1);
+        assert!(Set::contains(&set, &1));
+        assert!(!Set::contains(&set, &2));
+        assert_eq!(Collection::len(&set), 1);
+
+        let report = set.report();
+        assert_eq!(report.get("contains").unwrap().count(), 2);
+        assert_eq!(report.get("len").unwrap().count(), 1);
+        assert!(set.report().to_string().contains("contains: count=2"));
+    }
+
+    #[test]
+    fn display_and_reset() {
+        let mut queue = Timed::new(VecDeque::new());
+        queue.enqueue(1);
+
+        let report = queue.report().to_string();
+        assert!(report.starts_with("enqueue: count=1 mean="));
+
+        queue.reset();
+        assert!(queue.report().to_string().is_empty());
+        assert_eq!(queue.into_inner(), VecDeque::from(vec![1]));
+    }
+}
@@ -0,0 +1,5 @@
+//! A crate that implements a PriorityQueue, a binary min-heap whose entries
+//! can be reprioritized and removed through stable handles.
+pub use crate::priority_queue::{Handle, IntoIterSorted, PriorityQueue};
+
+mod priority_queue;
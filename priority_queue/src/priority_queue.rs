@@ -0,0 +1,581 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Handle refers to an entry of a PriorityQueue, returned by `push()`.
+///
+/// A Handle is a generational index, once its entry is popped or removed the
+/// Handle is stale and every method given it returns None or false, even
+/// after the entry's slot is reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Handle {
+    slot: usize,
+    generation: u64,
+}
+
+/// A slot holding an entry, or free for the next push. The generation is
+/// bumped every time the slot is freed.
+struct Slot<T, P> {
+    generation: u64,
+    entry: Option<Entry<T, P>>,
+}
+
+struct Entry<T, P> {
+    value: T,
+    priority: P,
+    // The index of the entry in the heap.
+    position: usize,
+}
+
+/// PriorityQueue is a binary min-heap of values ordered by priority, where
+/// `push()` returns a Handle used to lower the priority of the entry or to
+/// remove it.
+///
+/// Datastructure:
+/// - Vec: the heap, holding the slot of each entry
+/// - Vec: the slots, holding the entries, which don't move when the heap is
+///   reordered
+///
+/// # Example
+///
+/// ```
+/// use priority_queue::PriorityQueue;
+///
+/// let mut queue = PriorityQueue::new();
+/// queue.push("write", 3);
+/// let read = queue.push("read", 5);
+/// let close = queue.push("close", 9);
+///
+/// queue.decrease_key(read, 1);
+/// queue.remove(close);
+///
+/// assert_eq!(queue.pop(), Some(("read", 1)));
+/// assert_eq!(queue.pop(), Some(("write", 3)));
+/// assert_eq!(queue.pop(), None);
+/// ```
+pub struct PriorityQueue<T, P> {
+    heap: Vec<usize>,
+    slots: Vec<Slot<T, P>>,
+    free: Vec<usize>,
+}
+
+impl<T, P: Ord> Default for PriorityQueue<T, P> {
+    fn default() -> Self {
+        PriorityQueue {
+            heap: Vec::new(),
+            slots: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+}
+
+impl<T, P: Ord> PriorityQueue<T, P> {
+    /// Returns an empty PriorityQueue.
+    pub fn new() -> PriorityQueue<T, P> {
+        PriorityQueue::default()
+    }
+
+    /// Returns the number of entries in the PriorityQueue.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns a boolean indicating the PriorityQueue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Adds a value with a priority, returning the Handle of its entry.
+    ///
+    /// Time Complexity: O(log n)
+    /// Space Complexity: O(1) amortized
+    pub fn push(&mut self, value: T, priority: P) -> Handle {
+        let entry = Entry {
+            value,
+            priority,
+            position: self.heap.len(),
+        };
+
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.slots[slot].entry = Some(entry);
+                slot
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    entry: Some(entry),
+                });
+                self.slots.len() - 1
+            }
+        };
+
+        self.heap.push(slot);
+        self.sift_up(self.heap.len() - 1);
+
+        Handle {
+            slot,
+            generation: self.slots[slot].generation,
+        }
+    }
+
+    /// Returns the value and priority with the lowest priority, without
+    /// removing them.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn peek(&self) -> Option<(&T, &P)> {
+        let entry = self.slots[*self.heap.first()?].entry.as_ref()?;
+        Some((&entry.value, &entry.priority))
+    }
+
+    /// Removes and returns the value and priority with the lowest priority.
+    /// Entries with equal priorities come out in no particular order.
+    ///
+    /// Time Complexity: O(log n)
+    /// Space Complexity: O(1)
+    pub fn pop(&mut self) -> Option<(T, P)> {
+        let slot = *self.heap.first()?;
+        Some(self.take(slot))
+    }
+
+    /// Returns the value and priority of an entry, or None if the Handle is
+    /// stale.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn get(&self, handle: Handle) -> Option<(&T, &P)> {
+        let entry = self.entry(handle)?;
+        Some((&entry.value, &entry.priority))
+    }
+
+    /// Returns a boolean indicating the entry of a Handle is still in the
+    /// PriorityQueue.
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.entry(handle).is_some()
+    }
+
+    /// Lowers the priority of an entry, moving it towards the top. Returns
+    /// false, changing nothing, if the Handle is stale or the priority isn't
+    /// lower than the current one.
+    ///
+    /// Time Complexity: O(log n)
+    /// Space Complexity: O(1)
+    pub fn decrease_key(&mut self, handle: Handle, priority: P) -> bool {
+        let position = match self.slots.get_mut(handle.slot) {
+            Some(Slot {
+                generation,
+                entry: Some(entry),
+            }) if *generation == handle.generation && priority < entry.priority => {
+                entry.priority = priority;
+                entry.position
+            }
+            _ => return false,
+        };
+
+        self.sift_up(position);
+        true
+    }
+
+    /// Removes an entry from anywhere in the PriorityQueue, returning its
+    /// value and priority, or None if the Handle is stale.
+    ///
+    /// Time Complexity: O(log n)
+    /// Space Complexity: O(1)
+    pub fn remove(&mut self, handle: Handle) -> Option<(T, P)> {
+        self.entry(handle)?;
+        Some(self.take(handle.slot))
+    }
+
+    /// Removes every entry. Every Handle given out so far becomes stale.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    pub fn clear(&mut self) {
+        for slot in self.heap.drain(..) {
+            self.slots[slot].entry = None;
+            self.slots[slot].generation += 1;
+            self.free.push(slot);
+        }
+    }
+
+    /// Returns an Iterator that pops the entries in ascending order of
+    /// priority. The entries are popped one at a time as the Iterator is
+    /// advanced, so taking the first k of them only costs O(k log n), not a
+    /// full sort.
+    ///
+    /// Time Complexity: O(log n) per entry
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use priority_queue::PriorityQueue;
+    ///
+    /// let mut queue = PriorityQueue::new();
+    /// for (job, eta) in vec![("build", 30), ("lint", 5), ("test", 60), ("fmt", 1)] {
+    ///     queue.push(job, eta);
+    /// }
+    ///
+    /// let first: Vec<_> = queue.into_iter_sorted().take(2).collect();
+    /// assert_eq!(first, vec![("fmt", 1), ("lint", 5)]);
+    /// ```
+    pub fn into_iter_sorted(self) -> IntoIterSorted<T, P> {
+        IntoIterSorted { queue: self }
+    }
+
+    /// Returns the k values and priorities with the lowest priorities, in
+    /// ascending order, without changing the PriorityQueue. Only the part of
+    /// the heap above the k-th entry is visited, smallest first.
+    ///
+    /// Time Complexity: O(k log k)
+    /// Space Complexity: O(k)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use priority_queue::PriorityQueue;
+    ///
+    /// let mut queue = PriorityQueue::new();
+    /// for (i, p) in [5, 3, 8, 1].iter().enumerate() {
+    ///     queue.push(i, *p);
+    /// }
+    ///
+    /// assert_eq!(queue.k_smallest(2), vec![(&3, &1), (&1, &3)]);
+    /// assert_eq!(queue.len(), 4);
+    /// ```
+    pub fn k_smallest(&self, k: usize) -> Vec<(&T, &P)> {
+        let mut smallest = Vec::with_capacity(k.min(self.len()));
+        // The positions whose parent has been taken, by priority. The next
+        // smallest entry is always one of them.
+        let mut frontier = BinaryHeap::new();
+        if !self.heap.is_empty() {
+            frontier.push(Reverse((&self.entry_at(0).priority, 0)));
+        }
+
+        while smallest.len() < k {
+            let position = match frontier.pop() {
+                Some(Reverse((_, position))) => position,
+                None => break,
+            };
+            let entry = self.entry_at(position);
+            smallest.push((&entry.value, &entry.priority));
+
+            for child in [2 * position + 1, 2 * position + 2] {
+                if child < self.heap.len() {
+                    frontier.push(Reverse((&self.entry_at(child).priority, child)));
+                }
+            }
+        }
+
+        smallest
+    }
+
+    /// Returns the k values and priorities with the highest priorities, in
+    /// descending order, without changing the PriorityQueue. The heap only
+    /// orders the lowest priorities, so every entry is looked at, keeping
+    /// the k highest so far in a heap of their own rather than sorting them
+    /// all.
+    ///
+    /// Time Complexity: O(n log k)
+    /// Space Complexity: O(k)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use priority_queue::PriorityQueue;
+    ///
+    /// let mut queue = PriorityQueue::new();
+    /// for (i, p) in [5, 3, 8, 1].iter().enumerate() {
+    ///     queue.push(i, *p);
+    /// }
+    ///
+    /// assert_eq!(queue.k_largest(2), vec![(&2, &8), (&0, &5)]);
+    /// ```
+    pub fn k_largest(&self, k: usize) -> Vec<(&T, &P)> {
+        if k == 0 {
+            return Vec::new();
+        }
+
+        // A min-heap of the k highest priorities seen, the lowest of them on
+        // top to be replaced.
+        let mut largest = BinaryHeap::with_capacity(k.min(self.len()) + 1);
+        for position in 0..self.heap.len() {
+            largest.push(Reverse((&self.entry_at(position).priority, position)));
+            if largest.len() > k {
+                largest.pop();
+            }
+        }
+
+        largest
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((_, position))| {
+                let entry = self.entry_at(position);
+                (&entry.value, &entry.priority)
+            })
+            .collect()
+    }
+
+    /// Returns the entry of a Handle that isn't stale.
+    fn entry(&self, handle: Handle) -> Option<&Entry<T, P>> {
+        let slot = self.slots.get(handle.slot)?;
+        if slot.generation != handle.generation {
+            return None;
+        }
+
+        slot.entry.as_ref()
+    }
+
+    /// Removes the entry in a slot from the heap and frees the slot.
+    fn take(&mut self, slot: usize) -> (T, P) {
+        let entry = self.slots[slot]
+            .entry
+            .take()
+            .expect("the slot is in the heap");
+        self.slots[slot].generation += 1;
+        self.free.push(slot);
+
+        // Fill the hole with the last entry, which may belong above or below
+        // it.
+        let last = self.heap.pop().expect("the heap holds the slot");
+        if last != slot {
+            self.heap[entry.position] = last;
+            self.entry_at_mut(entry.position).position = entry.position;
+            let position = self.sift_up(entry.position);
+            self.sift_down(position);
+        }
+
+        (entry.value, entry.priority)
+    }
+
+    /// Moves the entry at a position up until its parent isn't greater,
+    /// returning its new position.
+    fn sift_up(&mut self, mut position: usize) -> usize {
+        while position > 0 {
+            let parent = (position - 1) / 2;
+            if self.entry_at(parent).priority <= self.entry_at(position).priority {
+                break;
+            }
+
+            self.swap(parent, position);
+            position = parent;
+        }
+
+        position
+    }
+
+    /// Moves the entry at a position down until no child is smaller.
+    fn sift_down(&mut self, mut position: usize) {
+        loop {
+            let mut smallest = position;
+            for child in [2 * position + 1, 2 * position + 2] {
+                if child < self.heap.len()
+                    && self.entry_at(child).priority < self.entry_at(smallest).priority
+                {
+                    smallest = child;
+                }
+            }
+
+            if smallest == position {
+                return;
+            }
+
+            self.swap(smallest, position);
+            position = smallest;
+        }
+    }
+
+    /// Swaps two entries of the heap, keeping their positions up to date.
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.entry_at_mut(a).position = a;
+        self.entry_at_mut(b).position = b;
+    }
+
+    fn entry_at(&self, position: usize) -> &Entry<T, P> {
+        self.slots[self.heap[position]]
+            .entry
+            .as_ref()
+            .expect("the heap only holds full slots")
+    }
+
+    fn entry_at_mut(&mut self, position: usize) -> &mut Entry<T, P> {
+        self.slots[self.heap[position]]
+            .entry
+            .as_mut()
+            .expect("the heap only holds full slots")
+    }
+}
+
+/// An Iterator popping the entries of a PriorityQueue in ascending order of
+/// priority, returned by `PriorityQueue::into_iter_sorted()`.
+pub struct IntoIterSorted<T, P> {
+    queue: PriorityQueue<T, P>,
+}
+
+impl<T, P: Ord> Iterator for IntoIterSorted<T, P> {
+    type Item = (T, P);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.queue.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.queue.len(), Some(self.queue.len()))
+    }
+}
+
+impl<T, P: Ord> ExactSizeIterator for IntoIterSorted<T, P> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Checks every parent is at most its children, and every entry knows
+    /// its position.
+    fn assert_heap<T, P: Ord>(queue: &PriorityQueue<T, P>) {
+        for position in 0..queue.heap.len() {
+            assert_eq!(queue.entry_at(position).position, position);
+            if position > 0 {
+                let parent = (position - 1) / 2;
+                assert!(queue.entry_at(parent).priority <= queue.entry_at(position).priority);
+            }
+        }
+    }
+
+    #[test]
+    fn pops_in_priority_order() {
+        let mut queue = PriorityQueue::new();
+        for (i, p) in [5, 3, 8, 1, 9, 2, 7].iter().enumerate() {
+            queue.push(i, *p);
+            assert_heap(&queue);
+        }
+
+        assert_eq!(queue.len(), 7);
+        assert_eq!(queue.peek(), Some((&3, &1)));
+
+        let mut priorities = Vec::new();
+        while let Some((_, p)) = queue.pop() {
+            assert_heap(&queue);
+            priorities.push(p);
+        }
+        assert_eq!(priorities, vec![1, 2, 3, 5, 7, 8, 9]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn into_iter_sorted() {
+        let mut queue = PriorityQueue::new();
+        let handle = queue.push(9, 4);
+        for (i, p) in [5, 3, 8, 1, 9].iter().enumerate() {
+            queue.push(i, *p);
+        }
+        queue.remove(handle);
+
+        let sorted = queue.into_iter_sorted();
+        assert_eq!(sorted.len(), 5);
+        assert_eq!(
+            sorted.map(|(_, p)| p).collect::<Vec<_>>(),
+            vec![1, 3, 5, 8, 9]
+        );
+
+        let empty: PriorityQueue<(), u32> = PriorityQueue::new();
+        assert_eq!(empty.into_iter_sorted().next(), None);
+    }
+
+    #[test]
+    fn k_smallest_and_largest() {
+        let mut queue = PriorityQueue::new();
+        let priorities = [14, 3, 8, 3, 11, 0, 7, 20, 5, 9, 1, 16];
+        for (i, p) in priorities.iter().enumerate() {
+            queue.push(i, *p);
+        }
+
+        let mut sorted = priorities.to_vec();
+        sorted.sort_unstable();
+        for k in 0..=priorities.len() + 2 {
+            let smallest: Vec<u32> = queue.k_smallest(k).into_iter().map(|(_, p)| *p).collect();
+            let largest: Vec<u32> = queue.k_largest(k).into_iter().map(|(_, p)| *p).collect();
+
+            let k = k.min(priorities.len());
+            assert_eq!(smallest, sorted[..k].to_vec());
+            assert_eq!(
+                largest,
+                sorted.iter().rev().take(k).copied().collect::<Vec<_>>()
+            );
+        }
+
+        // Each value comes with its own priority.
+        for (v, p) in queue.k_smallest(5).into_iter().chain(queue.k_largest(5)) {
+            assert_eq!(priorities[*v], *p);
+        }
+        assert_eq!(queue.len(), priorities.len());
+        assert_heap(&queue);
+    }
+
+    #[test]
+    fn decrease_key() {
+        let mut queue = PriorityQueue::new();
+        let handles: Vec<Handle> = (0..10).map(|i| queue.push(i, i * 10)).collect();
+
+        assert!(queue.decrease_key(handles[7], 5));
+        assert_heap(&queue);
+        assert_eq!(queue.peek(), Some((&0, &0)));
+        assert_eq!(queue.get(handles[7]), Some((&7, &5)));
+
+        // A higher or equal priority isn't a decrease.
+        assert!(!queue.decrease_key(handles[7], 5));
+        assert!(!queue.decrease_key(handles[3], 100));
+        assert_eq!(queue.get(handles[3]), Some((&3, &30)));
+
+        assert_eq!(queue.pop(), Some((0, 0)));
+        assert_eq!(queue.pop(), Some((7, 5)));
+        assert_eq!(queue.pop(), Some((1, 10)));
+    }
+
+    #[test]
+    fn remove() {
+        let mut queue = PriorityQueue::new();
+        let handles: Vec<Handle> = (0..10).map(|i| queue.push(i, (i * 7) % 10)).collect();
+
+        for i in [4, 0, 9, 5] {
+            assert_eq!(queue.remove(handles[i]), Some((i, (i * 7) % 10)));
+            assert_heap(&queue);
+        }
+        assert_eq!(queue.len(), 6);
+
+        let mut values = Vec::new();
+        while let Some((v, _)) = queue.pop() {
+            values.push(v);
+        }
+        assert_eq!(values, vec![3, 6, 2, 8, 1, 7]);
+    }
+
+    #[test]
+    fn stale_handles() {
+        let mut queue = PriorityQueue::new();
+        let a = queue.push("a", 1);
+        assert_eq!(queue.pop(), Some(("a", 1)));
+
+        // The new entry reuses the slot of "a", with the next generation.
+        let b = queue.push("b", 2);
+        assert_eq!(a.slot, b.slot);
+        assert!(!queue.contains(a));
+        assert_eq!(queue.get(a), None);
+        assert!(!queue.decrease_key(a, 0));
+        assert_eq!(queue.remove(a), None);
+        assert_eq!(queue.get(b), Some((&"b", &2)));
+
+        queue.clear();
+        assert!(queue.is_empty());
+        assert!(!queue.contains(b));
+
+        let c = queue.push("c", 3);
+        assert!(queue.contains(c));
+        assert_eq!(queue.remove(c), Some(("c", 3)));
+        assert_eq!(queue.remove(c), None);
+    }
+}
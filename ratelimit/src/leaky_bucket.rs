@@ -0,0 +1,181 @@
+use amortized_queue::AmortizedQueue;
+use std::time::Instant;
+use traits::{Clock, SystemClock};
+
+/// LeakyBucket is a bounded queue whose items leak out at `rate` items per
+/// second. Offering an item to a full bucket refuses it, and `poll()` only
+/// returns an item once it is due, so the output rate is smooth whatever the
+/// input rate is.
+///
+/// The items are queued in an AmortizedQueue. Time only accrues while items
+/// are waiting, so an idle bucket doesn't release a burst later on.
+///
+/// # Example
+///
+/// ```
+/// use ratelimit::{LeakyBucket, ManualClock};
+/// use std::time::Duration;
+///
+/// let clock = ManualClock::default();
+/// let mut bucket = LeakyBucket::with_clock(2, 1.0, clock.clone());
+///
+/// assert!(bucket.offer("a").is_ok());
+/// assert!(bucket.offer("b").is_ok());
+/// assert_eq!(bucket.offer("c"), Err("c"));
+///
+/// assert_eq!(bucket.poll(), None);
+/// clock.advance(Duration::from_secs(1));
+/// assert_eq!(bucket.poll(), Some("a"));
+/// assert_eq!(bucket.poll(), None);
+/// ```
+pub struct LeakyBucket<T, C = SystemClock> {
+    capacity: usize,
+    rate: f64,
+    queue: AmortizedQueue<T>,
+    // The fraction of items that have leaked and not been polled yet.
+    credit: f64,
+    last: Instant,
+    clock: C,
+}
+
+impl<T> LeakyBucket<T, SystemClock> {
+    /// Returns an empty LeakyBucket that reads the system clock.
+    pub fn new(capacity: usize, rate: f64) -> LeakyBucket<T, SystemClock> {
+        LeakyBucket::with_clock(capacity, rate, SystemClock)
+    }
+}
+
+impl<T, C> LeakyBucket<T, C>
+where
+    C: Clock,
+{
+    /// Returns an empty LeakyBucket that reads `clock`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is negative or not finite.
+    pub fn with_clock(capacity: usize, rate: f64, clock: C) -> LeakyBucket<T, C> {
+        assert!(
+            rate.is_finite() && rate >= 0.0,
+            "the leak rate must be a finite, non-negative number"
+        );
+
+        let last = clock.now();
+        LeakyBucket {
+            capacity,
+            rate,
+            queue: AmortizedQueue::default(),
+            credit: 0.0,
+            last,
+            clock,
+        }
+    }
+
+    /// Returns the number of items waiting in the LeakyBucket.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns a boolean indicating the LeakyBucket is empty.
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Adds an item to the LeakyBucket, or returns it if the bucket is full.
+    ///
+    /// Time Complexity: O(1) amortized
+    /// Space Complexity: O(1) amortized
+    pub fn offer(&mut self, item: T) -> Result<(), T> {
+        self.accrue();
+
+        if self.queue.len() >= self.capacity {
+            return Err(item);
+        }
+
+        self.queue.push(item);
+        Ok(())
+    }
+
+    /// Returns the oldest item if it has leaked out of the LeakyBucket.
+    ///
+    /// Time Complexity: O(1) amortized
+    /// Space Complexity: O(1)
+    pub fn poll(&mut self) -> Option<T> {
+        self.accrue();
+
+        if self.credit < 1.0 {
+            return None;
+        }
+
+        let item = self.queue.pop()?;
+        self.credit -= 1.0;
+        Some(item)
+    }
+
+    /// Adds the items leaked since the last call, never more than the number
+    /// of items waiting.
+    fn accrue(&mut self) {
+        let now = self.clock.now();
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+
+        self.credit = (self.credit + elapsed * self.rate).min(self.queue.len() as f64);
+        self.last = now;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+    use traits::ManualClock;
+
+    #[test]
+    fn leaks_at_a_constant_rate() {
+        let clock = ManualClock::default();
+        let mut bucket = LeakyBucket::with_clock(10, 2.0, clock.clone());
+        for i in 0..4 {
+            bucket.offer(i).unwrap();
+        }
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(bucket.poll(), Some(0));
+        assert_eq!(bucket.poll(), Some(1));
+        assert_eq!(bucket.poll(), None);
+
+        clock.advance(Duration::from_millis(500));
+        assert_eq!(bucket.poll(), Some(2));
+        assert_eq!(bucket.len(), 1);
+    }
+
+    #[test]
+    fn idle_bucket_does_not_burst() {
+        let clock = ManualClock::default();
+        let mut bucket = LeakyBucket::with_clock(10, 1.0, clock.clone());
+
+        clock.advance(Duration::from_secs(60));
+        bucket.offer(1).unwrap();
+        bucket.offer(2).unwrap();
+
+        assert_eq!(bucket.poll(), None);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(bucket.poll(), Some(1));
+        assert_eq!(bucket.poll(), None);
+    }
+
+    #[test]
+    fn full_bucket_refuses() {
+        let clock = ManualClock::default();
+        let mut bucket = LeakyBucket::with_clock(1, 1.0, clock.clone());
+
+        assert_eq!(bucket.offer(1), Ok(()));
+        assert_eq!(bucket.offer(2), Err(2));
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(bucket.poll(), Some(1));
+        assert_eq!(bucket.offer(2), Ok(()));
+        assert!(!bucket.is_empty());
+    }
+}
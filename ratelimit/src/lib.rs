@@ -0,0 +1,7 @@
+//! A crate that implements the TokenBucket and LeakyBucket rate limiters.
+pub use crate::leaky_bucket::LeakyBucket;
+pub use crate::token_bucket::TokenBucket;
+pub use traits::{Clock, ManualClock, SystemClock};
+
+mod leaky_bucket;
+mod token_bucket;
@@ -0,0 +1,152 @@
+use std::time::Instant;
+use traits::{Clock, SystemClock};
+
+/// TokenBucket holds up to `capacity` tokens and refills them at `rate` tokens
+/// per second. Each request takes tokens, and is refused if there aren't
+/// enough, so bursts of up to `capacity` are allowed while the average rate is
+/// bounded by `rate`.
+///
+/// The tokens are refilled lazily from the time elapsed on the Clock, nothing
+/// runs in the background.
+///
+/// # Example
+///
+/// ```
+/// use ratelimit::{ManualClock, TokenBucket};
+/// use std::time::Duration;
+///
+/// let clock = ManualClock::default();
+/// let mut bucket = TokenBucket::with_clock(2, 1.0, clock.clone());
+///
+/// assert!(bucket.try_acquire(2));
+/// assert!(!bucket.try_acquire(1));
+///
+/// clock.advance(Duration::from_secs(1));
+/// assert!(bucket.try_acquire(1));
+/// ```
+pub struct TokenBucket<C = SystemClock> {
+    capacity: f64,
+    rate: f64,
+    tokens: f64,
+    last: Instant,
+    clock: C,
+}
+
+impl TokenBucket<SystemClock> {
+    /// Returns a full TokenBucket that reads the system clock.
+    pub fn new(capacity: u64, rate: f64) -> TokenBucket<SystemClock> {
+        TokenBucket::with_clock(capacity, rate, SystemClock)
+    }
+}
+
+impl<C> TokenBucket<C>
+where
+    C: Clock,
+{
+    /// Returns a full TokenBucket that reads `clock`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is negative or not finite.
+    pub fn with_clock(capacity: u64, rate: f64, clock: C) -> TokenBucket<C> {
+        assert!(
+            rate.is_finite() && rate >= 0.0,
+            "the refill rate must be a finite, non-negative number"
+        );
+
+        let last = clock.now();
+        TokenBucket {
+            capacity: capacity as f64,
+            rate,
+            tokens: capacity as f64,
+            last,
+            clock,
+        }
+    }
+
+    /// Takes `n` tokens if they are available. Returns false and takes nothing
+    /// otherwise.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn try_acquire(&mut self, n: u64) -> bool {
+        self.refill();
+
+        if self.tokens < n as f64 {
+            return false;
+        }
+
+        self.tokens -= n as f64;
+        true
+    }
+
+    /// Returns the number of whole tokens that can be acquired now.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn available(&mut self) -> u64 {
+        self.refill();
+        self.tokens as u64
+    }
+
+    /// Adds the tokens earned since the last refill, up to the capacity.
+    fn refill(&mut self) {
+        let now = self.clock.now();
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.last = now;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+    use traits::ManualClock;
+
+    #[test]
+    fn refills_up_to_capacity() {
+        let clock = ManualClock::default();
+        let mut bucket = TokenBucket::with_clock(3, 2.0, clock.clone());
+
+        assert!(bucket.try_acquire(3));
+        assert_eq!(bucket.available(), 0);
+
+        clock.advance(Duration::from_millis(500));
+        assert_eq!(bucket.available(), 1);
+
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(bucket.available(), 3);
+    }
+
+    #[test]
+    fn refused_requests_take_nothing() {
+        let clock = ManualClock::default();
+        let mut bucket = TokenBucket::with_clock(2, 1.0, clock);
+
+        assert!(!bucket.try_acquire(3));
+        assert_eq!(bucket.available(), 2);
+    }
+
+    #[test]
+    fn partial_tokens_accumulate() {
+        let clock = ManualClock::default();
+        let mut bucket = TokenBucket::with_clock(1, 1.0, clock.clone());
+        assert!(bucket.try_acquire(1));
+
+        for _ in 0..3 {
+            clock.advance(Duration::from_millis(250));
+            assert!(!bucket.try_acquire(1));
+        }
+
+        clock.advance(Duration::from_millis(250));
+        assert!(bucket.try_acquire(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "refill rate")]
+    fn negative_rate() {
+        TokenBucket::with_clock(1, -1.0, ManualClock::default());
+    }
+}
@@ -0,0 +1,4 @@
+//! A crate that implements a SortedVecSet.
+pub use crate::sorted_vec_set::SortedVecSet;
+
+mod sorted_vec_set;
@@ -0,0 +1,302 @@
+use std::iter::FromIterator;
+use traits::{Collection, Set};
+
+/// SortedVecSet is a set of unique values kept sorted in a Vec.
+///
+/// # Example
+///
+/// ```
+/// use sorted_vec_set::SortedVecSet;
+///
+/// let set: SortedVecSet<u32> = vec![5, 1, 3, 1].into_iter().collect();
+///
+/// assert_eq!(set.as_slice(), &[1, 3, 5]);
+/// assert!(set.contains(&3));
+/// assert!(set.contains_all(&[1, 5]));
+/// assert!(!set.contains_all(&[1, 2]));
+/// ```
+pub struct SortedVecSet<T> {
+    items: Vec<T>,
+}
+
+impl<T> Default for SortedVecSet<T> {
+    fn default() -> Self {
+        SortedVecSet { items: Vec::new() }
+    }
+}
+
+impl<T> FromIterator<T> for SortedVecSet<T>
+where
+    T: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut items: Vec<T> = iter.into_iter().collect();
+        items.sort_unstable();
+        items.dedup();
+
+        SortedVecSet { items }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SortedVecSet<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+impl<T> Collection for SortedVecSet<T>
+where
+    T: Ord,
+{
+    type Iter<'a>
+        = std::slice::Iter<'a, T>
+    where
+        T: 'a;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn clear(&mut self) {
+        self.clear()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.iter()
+    }
+}
+
+impl<T> Set<T> for SortedVecSet<T>
+where
+    T: Ord,
+{
+    fn insert(&mut self, value: T) -> bool {
+        SortedVecSet::insert(self, value)
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        SortedVecSet::contains(self, value)
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        SortedVecSet::remove(self, value)
+    }
+}
+
+impl<T> SortedVecSet<T>
+where
+    T: Ord,
+{
+    /// Returns the number of values in the SortedVecSet.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns a boolean indicating the SortedVecSet is empty.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the values as a sorted slice.
+    pub fn as_slice(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Returns an Iterator over the values in ascending order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.items.iter()
+    }
+
+    /// Removes every value from the SortedVecSet.
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    /// Adds a value to the SortedVecSet, returning false if it was already
+    /// present.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1) amortized
+    pub fn insert(&mut self, value: T) -> bool {
+        let index = lower_bound(&self.items, &value);
+        if self.items.get(index) == Some(&value) {
+            return false;
+        }
+
+        self.items.insert(index, value);
+        true
+    }
+
+    /// Removes a value from the SortedVecSet, returning false if it wasn't
+    /// present.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(1)
+    pub fn remove(&mut self, value: &T) -> bool {
+        let index = lower_bound(&self.items, value);
+        if self.items.get(index) != Some(value) {
+            return false;
+        }
+
+        self.items.remove(index);
+        true
+    }
+
+    /// Returns a boolean indicating the value is in the SortedVecSet.
+    ///
+    /// Time Complexity: O(log n)
+    /// Space Complexity: O(1)
+    pub fn contains(&self, value: &T) -> bool {
+        self.items.get(lower_bound(&self.items, value)) == Some(value)
+    }
+
+    /// Returns a boolean indicating every value is in the SortedVecSet.
+    ///
+    /// While the values are ascending, each one is found by galloping forward
+    /// from the previous match, so a sorted batch costs O(m log(n / m))
+    /// instead of O(m log n). A value smaller than the previous one falls back
+    /// to a full binary search.
+    ///
+    /// Time Complexity: O(m log(n / m)) for sorted values, O(m log n) otherwise
+    /// Space Complexity: O(1)
+    pub fn contains_all<'a, I>(&self, values: I) -> bool
+    where
+        I: IntoIterator<Item = &'a T>,
+        T: 'a,
+    {
+        let mut from = 0;
+        let mut previous: Option<&T> = None;
+
+        for value in values {
+            let index = match previous {
+                Some(p) if p <= value => gallop(&self.items, from, value),
+                _ => lower_bound(&self.items, value),
+            };
+
+            if self.items.get(index) != Some(value) {
+                return false;
+            }
+
+            from = index;
+            previous = Some(value);
+        }
+
+        true
+    }
+}
+
+/// Returns the index of the first item that isn't less than `value`, with a
+/// binary search whose loop has no data dependent branch.
+fn lower_bound<T: Ord>(items: &[T], value: &T) -> usize {
+    if items.is_empty() {
+        return 0;
+    }
+
+    let mut base = 0;
+    let mut size = items.len();
+
+    while size > 1 {
+        let half = size / 2;
+        let mid = base + half;
+        // Compiles to a conditional move rather than a branch.
+        base = if items[mid] < *value { mid } else { base };
+        size -= half;
+    }
+
+    base + (items[base] < *value) as usize
+}
+
+/// Returns the lower bound of `value` in `items[from..]`, doubling the step
+/// from `from` until the value is passed and then binary searching the last
+/// step.
+fn gallop<T: Ord>(items: &[T], from: usize, value: &T) -> usize {
+    let mut low = from;
+    let mut step = 1;
+
+    while low + step < items.len() && items[low + step] < *value {
+        low += step;
+        step *= 2;
+    }
+
+    let high = (low + step + 1).min(items.len());
+    low + lower_bound(&items[low..high], value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lower_bound_matches_std() {
+        let items: Vec<u32> = (0..50).map(|i| i * 2).collect();
+
+        for len in 0..items.len() {
+            for value in 0..102 {
+                let expected = items[..len].partition_point(|v| *v < value);
+                assert_eq!(lower_bound(&items[..len], &value), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn gallop_matches_lower_bound() {
+        let items: Vec<u32> = (0..100).map(|i| i * 3).collect();
+
+        for from in 0..items.len() {
+            for value in items[from]..310 {
+                assert_eq!(gallop(&items, from, &value), lower_bound(&items, &value));
+            }
+        }
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut set = SortedVecSet::default();
+
+        assert!(set.insert(3));
+        assert!(set.insert(1));
+        assert!(set.insert(2));
+        assert!(!set.insert(2));
+        assert_eq!(set.as_slice(), &[1, 2, 3]);
+
+        assert!(set.remove(&2));
+        assert!(!set.remove(&2));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![&1, &3]);
+    }
+
+    #[test]
+    fn contains_all() {
+        let set: SortedVecSet<u32> = (0..1000).filter(|v| v % 3 == 0).collect();
+
+        let sorted: Vec<u32> = (0..1000).step_by(99).map(|v| v - v % 3).collect();
+        assert!(set.contains_all(&sorted));
+
+        let unsorted = vec![300, 3, 999, 0, 600];
+        assert!(set.contains_all(&unsorted));
+
+        assert!(!set.contains_all(&[3, 6, 7]));
+        assert!(!set.contains_all(&[1000]));
+        assert!(set.contains_all(&[]));
+    }
+
+    #[test]
+    fn generic_over_set() {
+        fn fill<S: Set<u32>>(set: &mut S) {
+            set.insert(2);
+            set.insert(1);
+            set.insert(2);
+        }
+
+        let mut set = SortedVecSet::default();
+        fill(&mut set);
+
+        assert_eq!(Collection::len(&set), 2);
+        assert!(Set::contains(&set, &1));
+    }
+}
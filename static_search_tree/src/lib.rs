@@ -0,0 +1,4 @@
+//! A crate that implements a StaticSearchTree.
+pub use crate::static_search_tree::{StaticSearchTree, StaticSearchTreeIterator};
+
+mod static_search_tree;
@@ -0,0 +1,387 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+
+/// StaticSearchTree is an immutable sorted map stored in Eytzinger order.
+///
+/// Positions are 1-based, like the textbook layout, so position k is stored
+/// at `entries[k - 1]`, its children are at 2k and 2k + 1 and its parent is
+/// at k / 2.
+///
+/// # Example
+///
+/// ```
+/// use static_search_tree::StaticSearchTree;
+///
+/// let tree: StaticSearchTree<u32, &str> =
+///     vec![(3, "c"), (1, "a"), (2, "b")].into_iter().collect();
+///
+/// assert_eq!(tree.get(&2), Some(&"b"));
+/// assert_eq!(tree.get(&4), None);
+/// assert_eq!(tree.lower_bound(&0), Some((&1, &"a")));
+/// ```
+pub struct StaticSearchTree<K, V> {
+    entries: Vec<(K, V)>,
+}
+
+impl<K, V> From<BTreeMap<K, V>> for StaticSearchTree<K, V> {
+    /// Builds the StaticSearchTree from the sorted entries of a BTreeMap.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(n)
+    fn from(map: BTreeMap<K, V>) -> Self {
+        build(map.len(), map.into_iter())
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for StaticSearchTree<K, V>
+where
+    K: Ord,
+{
+    /// Builds the StaticSearchTree from entries in any order. When a key is
+    /// repeated, the last value is kept.
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        iter.into_iter().collect::<BTreeMap<K, V>>().into()
+    }
+}
+
+/// Builds a StaticSearchTree from `len` entries sorted by key, with no
+/// repeated keys.
+fn build<K, V, I>(len: usize, mut sorted: I) -> StaticSearchTree<K, V>
+where
+    I: Iterator<Item = (K, V)>,
+{
+    let mut slots: Vec<Option<(K, V)>> = (0..len).map(|_| None).collect();
+
+    fill(&mut slots, &mut sorted, 1);
+
+    StaticSearchTree {
+        entries: slots
+            .into_iter()
+            .map(|s| s.expect("every slot is filled"))
+            .collect(),
+    }
+}
+
+/// Places the sorted entries into the slots with an in-order walk of the
+/// positions, so the smallest entry lands in the leftmost position.
+fn fill<K, V, I>(slots: &mut [Option<(K, V)>], sorted: &mut I, k: usize)
+where
+    I: Iterator<Item = (K, V)>,
+{
+    if k > slots.len() {
+        return;
+    }
+
+    fill(slots, sorted, 2 * k);
+    slots[k - 1] = sorted.next();
+    fill(slots, sorted, 2 * k + 1);
+}
+
+/// Takes the entries out of the slots with an in-order walk of the
+/// positions, the reverse of `fill()`.
+fn drain<K, V>(slots: &mut [Option<(K, V)>], sorted: &mut Vec<(K, V)>, k: usize) {
+    if k > slots.len() {
+        return;
+    }
+
+    drain(slots, sorted, 2 * k);
+    sorted.push(slots[k - 1].take().expect("each slot is taken once"));
+    drain(slots, sorted, 2 * k + 1);
+}
+
+impl<K, V> StaticSearchTree<K, V>
+where
+    K: Ord,
+{
+    /// Returns the number of entries in the StaticSearchTree.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns a boolean indicating the StaticSearchTree is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the value associated with a key.
+    ///
+    /// Time Complexity: O(log n)
+    /// Space Complexity: O(1)
+    pub fn get(&self, key: &K) -> Option<&V> {
+        match self.lower_bound(key) {
+            Some((k, v)) if k == key => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns a boolean indicating the key is in the StaticSearchTree.
+    ///
+    /// Time Complexity: O(log n)
+    /// Space Complexity: O(1)
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns the entry with the smallest key that isn't less than `key`.
+    ///
+    /// Time Complexity: O(log n)
+    /// Space Complexity: O(1)
+    pub fn lower_bound(&self, key: &K) -> Option<(&K, &V)> {
+        let position = self.lower_bound_position(key);
+        if position == 0 {
+            return None;
+        }
+
+        let (k, v) = &self.entries[position - 1];
+        Some((k, v))
+    }
+
+    /// Returns an Iterator over the entries in ascending order of their keys.
+    pub fn iter(&self) -> StaticSearchTreeIterator<'_, K, V> {
+        StaticSearchTreeIterator {
+            entries: &self.entries,
+            position: leftmost(1, self.entries.len()),
+        }
+    }
+
+    /// Combines two StaticSearchTrees into one, e.g. to reunite the shards of
+    /// an index. When both hold a key, the value from `other` is kept.
+    ///
+    /// The entries of both are merged in order and the tree is rebuilt, which
+    /// is linear whether their key ranges overlap or not. Subtrees can't be
+    /// grafted without a rebuild, since the positions of every entry depend
+    /// on the total length.
+    ///
+    /// Time Complexity: O(n + m)
+    /// Space Complexity: O(n + m)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use static_search_tree::StaticSearchTree;
+    ///
+    /// let low: StaticSearchTree<u32, &str> = vec![(1, "a"), (3, "c")].into_iter().collect();
+    /// let high: StaticSearchTree<u32, &str> = vec![(2, "b"), (3, "C")].into_iter().collect();
+    ///
+    /// let tree = low.merge(high);
+    /// assert_eq!(tree.len(), 3);
+    /// assert_eq!(tree.get(&3), Some(&"C"));
+    /// assert_eq!(tree.lower_bound(&2), Some((&2, &"b")));
+    /// ```
+    pub fn merge(self, other: StaticSearchTree<K, V>) -> StaticSearchTree<K, V> {
+        let mut left = self.into_sorted().into_iter().peekable();
+        let mut right = other.into_sorted().into_iter().peekable();
+        let mut merged = Vec::with_capacity(left.len() + right.len());
+
+        loop {
+            let next = match (left.peek(), right.peek()) {
+                (Some((l, _)), Some((r, _))) => match l.cmp(r) {
+                    Ordering::Less => left.next(),
+                    Ordering::Greater => right.next(),
+                    Ordering::Equal => {
+                        left.next();
+                        right.next()
+                    }
+                },
+                (Some(_), None) => left.next(),
+                (None, Some(_)) => right.next(),
+                (None, None) => break,
+            };
+            merged.extend(next);
+        }
+
+        build(merged.len(), merged.into_iter())
+    }
+
+    /// Returns the entries in ascending order of their keys.
+    fn into_sorted(self) -> Vec<(K, V)> {
+        let mut slots: Vec<Option<(K, V)>> = self.entries.into_iter().map(Some).collect();
+        let mut sorted = Vec::with_capacity(slots.len());
+
+        drain(&mut slots, &mut sorted, 1);
+
+        sorted
+    }
+
+    /// Walks down from the root, going right while the key at the position is
+    /// less than `key`. The walk always ends past a leaf, the lower bound is
+    /// the last position where it went left, which is found by dropping the
+    /// trailing right turns and the final left turn from the position.
+    ///
+    /// Returns 0 if every key is less than `key`.
+    fn lower_bound_position(&self, key: &K) -> usize {
+        let len = self.entries.len();
+        let mut k = 1;
+
+        while k <= len {
+            k = 2 * k + (self.entries[k - 1].0 < *key) as usize;
+        }
+
+        k >> (k.trailing_ones() + 1)
+    }
+}
+
+/// Returns the leftmost position in the subtree rooted at `k`, or 0 if the
+/// subtree is empty.
+fn leftmost(mut k: usize, len: usize) -> usize {
+    if k > len {
+        return 0;
+    }
+
+    while 2 * k <= len {
+        k *= 2;
+    }
+
+    k
+}
+
+/// The Iterator implementation for the StaticSearchTree, visiting the
+/// positions in order. This Iterator will borrow the StaticSearchTree.
+pub struct StaticSearchTreeIterator<'a, K, V> {
+    entries: &'a [(K, V)],
+    // The next position to visit, 0 once the walk is done.
+    position: usize,
+}
+
+impl<'a, K, V> Iterator for StaticSearchTreeIterator<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position == 0 {
+            return None;
+        }
+
+        let k = self.position;
+        let len = self.entries.len();
+
+        // The successor is the leftmost position of the right subtree, or the
+        // first ancestor reached from a left child.
+        self.position = if 2 * k < len {
+            leftmost(2 * k + 1, len)
+        } else {
+            let mut up = k;
+            while up % 2 == 1 {
+                up /= 2;
+            }
+            up / 2
+        };
+
+        let (key, value) = &self.entries[k - 1];
+        Some((key, value))
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a StaticSearchTree<K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = StaticSearchTreeIterator<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn tree(len: u32) -> StaticSearchTree<u32, u32> {
+        (0..len).map(|i| (i * 2, i)).collect()
+    }
+
+    #[test]
+    fn get_every_size() {
+        for len in 0..64 {
+            let tree = tree(len);
+            assert_eq!(tree.len(), len as usize);
+
+            for i in 0..len {
+                assert_eq!(tree.get(&(i * 2)), Some(&i));
+                assert_eq!(tree.get(&(i * 2 + 1)), None);
+            }
+        }
+    }
+
+    #[test]
+    fn lower_bound() {
+        let tree = tree(10);
+
+        assert_eq!(tree.lower_bound(&0), Some((&0, &0)));
+        assert_eq!(tree.lower_bound(&5), Some((&6, &3)));
+        assert_eq!(tree.lower_bound(&18), Some((&18, &9)));
+        assert_eq!(tree.lower_bound(&19), None);
+    }
+
+    #[test]
+    fn iterates_in_order() {
+        for len in 0..40 {
+            let tree = tree(len);
+            let keys: Vec<u32> = tree.iter().map(|(k, _)| *k).collect();
+
+            assert_eq!(keys, (0..len).map(|i| i * 2).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn from_btree_map() {
+        let mut map = BTreeMap::new();
+        map.insert("b", 2);
+        map.insert("a", 1);
+        map.insert("c", 3);
+
+        let tree = StaticSearchTree::from(map);
+        assert!(tree.contains_key(&"a"));
+        assert_eq!(tree.get(&"c"), Some(&3));
+        assert!(!tree.contains_key(&"d"));
+    }
+
+    #[test]
+    fn repeated_keys_keep_the_last_value() {
+        let tree: StaticSearchTree<u32, u32> = vec![(1, 1), (1, 2)].into_iter().collect();
+
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree.get(&1), Some(&2));
+    }
+
+    #[test]
+    fn empty() {
+        let tree = tree(0);
+
+        assert!(tree.is_empty());
+        assert_eq!(tree.get(&0), None);
+        assert_eq!(tree.iter().next(), None);
+    }
+
+    #[test]
+    fn merge() {
+        let evens: StaticSearchTree<u32, u32> = (0..20).step_by(2).map(|k| (k, 0)).collect();
+        let odds: StaticSearchTree<u32, u32> = (1..20).step_by(2).map(|k| (k, 1)).collect();
+
+        let tree = evens.merge(odds);
+        assert_eq!(tree.len(), 20);
+        for k in 0..20 {
+            assert_eq!(tree.get(&k), Some(&(k % 2)));
+        }
+        assert_eq!(
+            tree.iter().map(|(k, _)| *k).collect::<Vec<_>>(),
+            (0..20).collect::<Vec<_>>()
+        );
+
+        // Disjoint ranges, and an overlapping key taking the other value.
+        let tree = tree.merge((19..30).map(|k| (k, 2)).collect());
+        assert_eq!(tree.len(), 30);
+        assert_eq!(tree.get(&18), Some(&0));
+        assert_eq!(tree.get(&19), Some(&2));
+        assert_eq!(tree.lower_bound(&29), Some((&29, &2)));
+
+        let tree = tree.merge(StaticSearchTree::from(BTreeMap::new()));
+        assert_eq!(tree.len(), 30);
+        assert!(tree.merge(self::tree(0)).contains_key(&0));
+    }
+}
@@ -0,0 +1,4 @@
+//! A crate that implements a TopK tracker.
+pub use crate::top_k::TopK;
+
+mod top_k;
@@ -0,0 +1,190 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// TopK keeps the k largest items offered to it. The items are held in a
+/// min-heap, so the smallest tracked item is the one compared against and
+/// evicted.
+pub struct TopK<T> {
+    k: usize,
+    heap: BinaryHeap<Reverse<T>>,
+}
+
+impl<T> TopK<T>
+where
+    T: Ord,
+{
+    /// Returns an empty TopK that keeps the `k` largest items.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use top_k::TopK;
+    ///
+    /// let top = TopK::<u32>::new(3);
+    /// assert_eq!(top.k(), 3);
+    /// assert!(top.is_empty());
+    /// ```
+    pub fn new(k: usize) -> TopK<T> {
+        TopK {
+            k,
+            heap: BinaryHeap::with_capacity(k),
+        }
+    }
+
+    /// Returns the number of items the TopK keeps.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Returns the number of items currently tracked, at most k.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns a boolean indicating the TopK is empty.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Offers an item to the TopK. Returns true if the item is kept, false if
+    /// it is smaller than every tracked item and the TopK is full.
+    ///
+    /// Time Complexity: O(log k)
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use top_k::TopK;
+    ///
+    /// let mut top = TopK::new(2);
+    /// assert!(top.offer(1));
+    /// assert!(top.offer(5));
+    /// assert!(top.offer(3));
+    /// assert!(!top.offer(2));
+    ///
+    /// assert_eq!(top.items(), vec![&5, &3]);
+    /// ```
+    pub fn offer(&mut self, item: T) -> bool {
+        if self.heap.len() < self.k {
+            self.heap.push(Reverse(item));
+            return true;
+        }
+
+        match self.heap.peek_mut() {
+            Some(mut smallest) if item > smallest.0 => {
+                *smallest = Reverse(item);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the smallest tracked item, the one a new item has to beat once
+    /// the TopK is full.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn threshold(&self) -> Option<&T> {
+        self.heap.peek().map(|r| &r.0)
+    }
+
+    /// Returns the tracked items, largest first.
+    ///
+    /// Time Complexity: O(k log k)
+    /// Space Complexity: O(k)
+    pub fn items(&self) -> Vec<&T> {
+        let mut items: Vec<&T> = self.heap.iter().map(|r| &r.0).collect();
+        items.sort_by(|a, b| b.cmp(a));
+        items
+    }
+
+    /// Consumes the TopK and returns the tracked items, largest first.
+    ///
+    /// Time Complexity: O(k log k)
+    /// Space Complexity: O(k)
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        // Sorting the Reverse items ascending yields the items descending.
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|r| r.0)
+            .collect()
+    }
+
+    /// Removes every tracked item.
+    ///
+    /// Time Complexity: O(k)
+    /// Space Complexity: O(1)
+    pub fn clear(&mut self) {
+        self.heap.clear();
+    }
+}
+
+impl<T> Extend<T> for TopK<T>
+where
+    T: Ord,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.offer(item);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn keeps_largest() {
+        let mut top = TopK::new(3);
+        top.extend(vec![5, 1, 9, 3, 7, 2, 8]);
+
+        assert_eq!(top.len(), 3);
+        assert_eq!(top.threshold(), Some(&7));
+        assert_eq!(top.items(), vec![&9, &8, &7]);
+        assert_eq!(top.into_sorted_vec(), vec![9, 8, 7]);
+    }
+
+    #[test]
+    fn fewer_items_than_k() {
+        let mut top = TopK::new(5);
+        top.extend(vec![2, 1]);
+
+        assert_eq!(top.len(), 2);
+        assert_eq!(top.items(), vec![&2, &1]);
+    }
+
+    #[test]
+    fn ties_are_not_kept() {
+        let mut top = TopK::new(1);
+
+        assert!(top.offer(4));
+        assert!(!top.offer(4));
+        assert!(top.offer(5));
+        assert_eq!(top.threshold(), Some(&5));
+    }
+
+    #[test]
+    fn zero_k() {
+        let mut top = TopK::new(0);
+
+        assert!(!top.offer(1));
+        assert!(top.is_empty());
+        assert_eq!(top.threshold(), None);
+    }
+
+    #[test]
+    fn clear() {
+        let mut top = TopK::new(2);
+        top.extend(vec![1, 2, 3]);
+        top.clear();
+
+        assert!(top.is_empty());
+        assert!(top.offer(1));
+    }
+}
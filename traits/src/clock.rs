@@ -0,0 +1,87 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// Clock is the source of time for the structures that expire or refill over
+/// time, e.g. the PriorityCache of `lru` and the rate limiters of
+/// `ratelimit`. It must never go backwards.
+pub trait Clock {
+    fn now(&self) -> Instant;
+}
+
+/// SystemClock reads the monotonic system clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// ManualClock only moves when it is advanced, so time can be tested or
+/// simulated without waiting. Clones share the same time, so a test can keep
+/// a clone and advance the clock of a structure.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use traits::{Clock, ManualClock};
+///
+/// let clock = ManualClock::default();
+/// let shared = clock.clone();
+/// let start = clock.now();
+///
+/// clock.advance(Duration::from_secs(2));
+/// assert_eq!(shared.now() - start, Duration::from_secs(2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    now: Rc<Cell<Instant>>,
+}
+
+/// The clock starts at the time it is created.
+impl Default for ManualClock {
+    fn default() -> Self {
+        ManualClock {
+            now: Rc::new(Cell::new(Instant::now())),
+        }
+    }
+}
+
+impl ManualClock {
+    /// Moves the time forward by `by`.
+    pub fn advance(&self, by: Duration) {
+        self.now.set(self.now.get() + by);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.now.get()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn system_clock_moves_forward() {
+        let clock = SystemClock;
+        let before = clock.now();
+
+        assert!(clock.now() >= before);
+    }
+
+    #[test]
+    fn manual_clock_only_moves_when_advanced() {
+        let clock = ManualClock::default();
+        let start = clock.now();
+        assert_eq!(clock.now(), start);
+
+        clock.clone().advance(Duration::from_millis(1500));
+        assert_eq!(clock.now() - start, Duration::from_millis(1500));
+    }
+}
@@ -0,0 +1,39 @@
+//! A crate that defines the traits shared by the data structures, so
+//! algorithms can be written generically over any backend.
+//!
+//! # Example
+//!
+//! ```
+//! use std::collections::VecDeque;
+//! use traits::Queue;
+//!
+//! /// Moves every value from one queue to another, keeping their order.
+//! fn transfer<T, A: Queue<T>, B: Queue<T>>(from: &mut A, to: &mut B) {
+//!     while let Some(v) = from.dequeue() {
+//!         to.enqueue(v);
+//!     }
+//! }
+//!
+//! let mut from: VecDeque<u32> = vec![1, 2, 3].into();
+//! let mut to = VecDeque::new();
+//! transfer(&mut from, &mut to);
+//!
+//! assert_eq!(to, vec![1, 2, 3]);
+//! ```
+//!
+//! # Features
+//!
+//! - `std` (default) - implements the traits for `HashMap` and `HashSet`, and
+//!   adds the Clock shared by the structures that read the time. Without it
+//!   the crate is `no_std` and only needs `alloc`.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub use crate::clock::{Clock, ManualClock, SystemClock};
+pub use crate::traits::{Collection, Deque, Map, Queue, Set, Stack};
+
+#[cfg(feature = "std")]
+mod clock;
+mod traits;
@@ -0,0 +1,349 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+
+/// Collection is implemented by every data structure, exposing the operations
+/// that don't depend on how the items are organised.
+pub trait Collection {
+    /// The iterator returned by `iter()`, borrowing the Collection.
+    type Iter<'a>: Iterator
+    where
+        Self: 'a;
+
+    /// Returns the number of items in the Collection.
+    fn len(&self) -> usize;
+
+    /// Returns a boolean indicating the Collection is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes every item from the Collection.
+    fn clear(&mut self);
+
+    /// Returns an iterator over the items in the Collection, in the same order
+    /// as the structure's own iterator.
+    fn iter(&self) -> Self::Iter<'_>;
+}
+
+/// Map is a Collection associating keys with values.
+///
+/// `get()` takes `&mut self` and returns an owned value, so that structures
+/// that reorder themselves on reads (such as caches) or that can't hand out
+/// references into their nodes can implement it.
+pub trait Map<K, V>: Collection {
+    /// Associates a value with a key, replacing any previous value.
+    fn insert(&mut self, key: K, value: V);
+
+    /// Returns the value associated with a key.
+    fn get(&mut self, key: &K) -> Option<V>;
+}
+
+/// Set is a Collection of unique values.
+pub trait Set<T>: Collection {
+    /// Adds a value to the Set, returning false if it was already present.
+    fn insert(&mut self, value: T) -> bool;
+
+    /// Returns a boolean indicating the value is in the Set.
+    fn contains(&self, value: &T) -> bool;
+
+    /// Removes a value from the Set, returning false if it wasn't present.
+    fn remove(&mut self, value: &T) -> bool;
+}
+
+/// Stack is a LIFO structure, the last value pushed is the first value popped.
+pub trait Stack<T> {
+    /// Adds a value to the top of the Stack.
+    fn push(&mut self, v: T);
+
+    /// Returns the value at the top of the Stack and removes it from the
+    /// Stack.
+    fn pop(&mut self) -> Option<T>;
+}
+
+/// Queue is a FIFO structure, the first value enqueued is the first value
+/// dequeued.
+pub trait Queue<T> {
+    /// Adds a value to the back of the Queue.
+    fn enqueue(&mut self, v: T);
+
+    /// Returns the value at the front of the Queue and removes it from the
+    /// Queue.
+    fn dequeue(&mut self) -> Option<T>;
+}
+
+/// Deque is a double-ended queue, values can be pushed and popped at both
+/// ends.
+pub trait Deque<T> {
+    /// Adds a value to the front of the Deque.
+    fn push_front(&mut self, v: T);
+
+    /// Adds a value to the back of the Deque.
+    fn push_back(&mut self, v: T);
+
+    /// Returns the value at the front of the Deque and removes it from the
+    /// Deque.
+    fn pop_front(&mut self) -> Option<T>;
+
+    /// Returns the value at the back of the Deque and removes it from the
+    /// Deque.
+    fn pop_back(&mut self) -> Option<T>;
+}
+
+impl<T> Collection for Vec<T> {
+    type Iter<'a>
+        = core::slice::Iter<'a, T>
+    where
+        T: 'a;
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn clear(&mut self) {
+        Vec::clear(self)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.as_slice().iter()
+    }
+}
+
+impl<T> Collection for VecDeque<T> {
+    type Iter<'a>
+        = alloc::collections::vec_deque::Iter<'a, T>
+    where
+        T: 'a;
+
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+
+    fn clear(&mut self) {
+        VecDeque::clear(self)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        VecDeque::iter(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> Collection for HashMap<K, V, S> {
+    type Iter<'a>
+        = std::collections::hash_map::Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+        S: 'a;
+
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+
+    fn clear(&mut self) {
+        HashMap::clear(self)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        HashMap::iter(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> Map<K, V> for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: Clone,
+    S: BuildHasher,
+{
+    fn insert(&mut self, key: K, value: V) {
+        HashMap::insert(self, key, value);
+    }
+
+    fn get(&mut self, key: &K) -> Option<V> {
+        HashMap::get(self, key).cloned()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S> Collection for HashSet<T, S> {
+    type Iter<'a>
+        = std::collections::hash_set::Iter<'a, T>
+    where
+        T: 'a,
+        S: 'a;
+
+    fn len(&self) -> usize {
+        HashSet::len(self)
+    }
+
+    fn clear(&mut self) {
+        HashSet::clear(self)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        HashSet::iter(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S> Set<T> for HashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    fn insert(&mut self, value: T) -> bool {
+        HashSet::insert(self, value)
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        HashSet::contains(self, value)
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        HashSet::remove(self, value)
+    }
+}
+
+impl<T> Stack<T> for Vec<T> {
+    fn push(&mut self, v: T) {
+        Vec::push(self, v)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        Vec::pop(self)
+    }
+}
+
+impl<T> Queue<T> for VecDeque<T> {
+    fn enqueue(&mut self, v: T) {
+        self.push_back(v)
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+}
+
+impl<T> Deque<T> for VecDeque<T> {
+    fn push_front(&mut self, v: T) {
+        VecDeque::push_front(self, v)
+    }
+
+    fn push_back(&mut self, v: T) {
+        VecDeque::push_back(self, v)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        VecDeque::pop_front(self)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        VecDeque::pop_back(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn drain_stack<S: Stack<u32>>(stack: &mut S) -> Vec<u32> {
+        let mut result = Vec::new();
+        while let Some(v) = stack.pop() {
+            result.push(v);
+        }
+        result
+    }
+
+    fn drain_queue<Q: Queue<u32>>(queue: &mut Q) -> Vec<u32> {
+        let mut result = Vec::new();
+        while let Some(v) = queue.dequeue() {
+            result.push(v);
+        }
+        result
+    }
+
+    #[test]
+    fn vec_stack() {
+        let mut stack = Vec::new();
+        for i in 1..4 {
+            Stack::push(&mut stack, i);
+        }
+
+        assert_eq!(drain_stack(&mut stack), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn vec_deque_queue() {
+        let mut queue = VecDeque::new();
+        for i in 1..4 {
+            queue.enqueue(i);
+        }
+
+        assert_eq!(drain_queue(&mut queue), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn vec_deque_deque() {
+        let mut deque = VecDeque::new();
+        Deque::push_back(&mut deque, 2);
+        Deque::push_front(&mut deque, 1);
+        Deque::push_back(&mut deque, 3);
+
+        assert_eq!(Deque::pop_back(&mut deque), Some(3));
+        assert_eq!(Deque::pop_front(&mut deque), Some(1));
+        assert_eq!(Deque::pop_front(&mut deque), Some(2));
+        assert_eq!(Deque::pop_front(&mut deque), None);
+    }
+
+    fn sizes<C: Collection>(collection: &C) -> (usize, usize) {
+        (collection.len(), collection.iter().count())
+    }
+
+    #[test]
+    fn collections() {
+        let mut v = vec![1, 2, 3];
+        assert_eq!(sizes(&v), (3, 3));
+        Collection::clear(&mut v);
+        assert!(Collection::is_empty(&v));
+
+        let mut deque: VecDeque<u32> = vec![1, 2].into();
+        assert_eq!(sizes(&deque), (2, 2));
+        Collection::clear(&mut deque);
+        assert_eq!(sizes(&deque), (0, 0));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hash_map() {
+        fn fill<M: Map<String, u32>>(map: &mut M) {
+            map.insert("a".to_string(), 1);
+            map.insert("b".to_string(), 2);
+            map.insert("a".to_string(), 3);
+        }
+
+        let mut map = HashMap::new();
+        fill(&mut map);
+
+        assert_eq!(sizes(&map), (2, 2));
+        assert_eq!(Map::get(&mut map, &"a".to_string()), Some(3));
+        assert_eq!(Map::get(&mut map, &"c".to_string()), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hash_set() {
+        let mut set = HashSet::new();
+
+        assert!(Set::insert(&mut set, 1));
+        assert!(!Set::insert(&mut set, 1));
+        assert!(Set::contains(&set, &1));
+        assert!(Set::remove(&mut set, &1));
+        assert!(!Set::contains(&set, &1));
+        assert!(Collection::is_empty(&set));
+    }
+}
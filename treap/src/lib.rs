@@ -0,0 +1,5 @@
+//! A crate that implements a Treap, a balanced sorted map that can be split
+//! and joined by key.
+pub use crate::treap::{Treap, TreapIterator};
+
+mod treap;
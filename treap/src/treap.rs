@@ -0,0 +1,1123 @@
+use std::cmp::Ordering;
+use std::iter::FromIterator;
+use std::ops::{Bound, RangeBounds};
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+
+type Link<K, V> = Option<Box<Node<K, V>>>;
+
+/// Node is a single entry of the Treap and the root of its subtree.
+struct Node<K, V> {
+    key: K,
+    value: V,
+    priority: u64,
+    size: usize,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+impl<K, V> Node<K, V> {
+    fn new(key: K, value: V) -> Self {
+        Node {
+            key,
+            value,
+            priority: priority(),
+            size: 1,
+            left: None,
+            right: None,
+        }
+    }
+
+    /// Recomputes the size of the subtree after a child has changed.
+    fn update(&mut self) {
+        self.size = 1 + size(&self.left) + size(&self.right);
+    }
+}
+
+/// Treap is a sorted map balanced by random priorities, that can be split and
+/// joined by key in logarithmic time.
+///
+/// # Example
+///
+/// ```
+/// use treap::Treap;
+///
+/// let mut treap = Treap::new();
+/// treap.insert(2, "b");
+/// treap.insert(1, "a");
+/// treap.insert(3, "c");
+///
+/// assert_eq!(treap.get(&2), Some(&"b"));
+/// assert_eq!(treap.remove(&1), Some("a"));
+/// assert_eq!(treap.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec![2, 3]);
+/// ```
+pub struct Treap<K, V> {
+    root: Link<K, V>,
+}
+
+impl<K, V> Default for Treap<K, V> {
+    fn default() -> Self {
+        Treap { root: None }
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for Treap<K, V>
+where
+    K: Ord,
+{
+    /// Builds the Treap from entries in any order. When a key is repeated,
+    /// the last value is kept.
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut treap = Treap::new();
+        for (key, value) in iter {
+            treap.insert(key, value);
+        }
+
+        treap
+    }
+}
+
+impl<K, V> Treap<K, V>
+where
+    K: Ord,
+{
+    /// Returns an empty Treap.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of entries in the Treap.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    /// Returns a boolean indicating the Treap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Returns the value associated with a key.
+    ///
+    /// Time Complexity: O(log n) expected
+    /// Space Complexity: O(1)
+    pub fn get(&self, key: &K) -> Option<&V> {
+        find(self.root.as_deref(), key).map(|node| &node.value)
+    }
+
+    /// Returns a mutable reference to the value associated with a key.
+    ///
+    /// Time Complexity: O(log n) expected
+    /// Space Complexity: O(1)
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let mut link = &mut self.root;
+
+        while let Some(node) = link {
+            link = match key.cmp(&node.key) {
+                Ordering::Less => &mut node.left,
+                Ordering::Greater => &mut node.right,
+                Ordering::Equal => return Some(&mut node.value),
+            };
+        }
+
+        None
+    }
+
+    /// Returns a boolean indicating the key is in the Treap.
+    ///
+    /// Time Complexity: O(log n) expected
+    /// Space Complexity: O(1)
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns the key of the lowest common ancestor of the nodes holding
+    /// `a` and `b`, None if either key isn't in the Treap.
+    ///
+    /// The ancestor is the first node on the path from the root whose key is
+    /// between `a` and `b`, so it is found by comparing keys, without parent
+    /// pointers.
+    ///
+    /// Time Complexity: O(log n) expected
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use treap::Treap;
+    ///
+    /// let treap: Treap<u32, ()> = (0..10).map(|k| (k, ())).collect();
+    ///
+    /// let ancestor = *treap.lca(&2, &7).unwrap();
+    /// assert!(2 <= ancestor && ancestor <= 7);
+    /// assert_eq!(treap.lca(&4, &4), Some(&4));
+    /// assert_eq!(treap.lca(&4, &12), None);
+    /// ```
+    pub fn lca(&self, a: &K, b: &K) -> Option<&K> {
+        let (low, high) = if a <= b { (a, b) } else { (b, a) };
+        let mut node = self.root.as_deref()?;
+
+        loop {
+            node = if *high < node.key {
+                node.left.as_deref()?
+            } else if *low > node.key {
+                node.right.as_deref()?
+            } else {
+                break;
+            };
+        }
+
+        // The paths to both keys split at this node, so they can only be
+        // found below it.
+        if find(Some(node), a).is_some() && find(Some(node), b).is_some() {
+            Some(&node.key)
+        } else {
+            None
+        }
+    }
+
+    /// Inserts a key and value into the Treap, returning the previous value
+    /// if the key was already present.
+    ///
+    /// A new entry is inserted by splitting the Treap at its key and joining
+    /// the two halves back on either side of it.
+    ///
+    /// Time Complexity: O(log n) expected
+    /// Space Complexity: O(1)
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(old) = self.get_mut(&key) {
+            return Some(std::mem::replace(old, value));
+        }
+
+        let (left, right) = split(self.root.take(), &|k| *k < key);
+        let node = Some(Box::new(Node::new(key, value)));
+        self.root = join(join(left, node), right);
+
+        None
+    }
+
+    /// Removes a key from the Treap, returning its value if it was present.
+    /// The children of the removed node are joined in its place.
+    ///
+    /// Time Complexity: O(log n) expected
+    /// Space Complexity: O(1)
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        remove(&mut self.root, key)
+    }
+
+    /// Splits the Treap into the entries with keys less than `key` and the
+    /// entries with keys greater than or equal to it.
+    ///
+    /// Together with `join()`, this is enough to build bulk operations on a
+    /// range of keys without visiting the range, e.g. cutting out `lo..hi` is
+    /// two splits and a join.
+    ///
+    /// Time Complexity: O(log n) expected
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use treap::Treap;
+    ///
+    /// let treap: Treap<u32, ()> = (0..10).map(|k| (k, ())).collect();
+    ///
+    /// // Extract the keys 3..7 and keep the rest.
+    /// let (low, rest) = treap.split(&3);
+    /// let (range, high) = rest.split(&7);
+    /// let treap = Treap::join(low, high);
+    ///
+    /// assert_eq!(range.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec![3, 4, 5, 6]);
+    /// assert_eq!(treap.len(), 6);
+    /// assert!(!treap.contains_key(&5));
+    /// ```
+    pub fn split(self, key: &K) -> (Treap<K, V>, Treap<K, V>) {
+        let (left, right) = split(self.root, &|k| k < key);
+
+        (Treap { root: left }, Treap { root: right })
+    }
+
+    /// Joins two Treaps into one.
+    ///
+    /// Panics if a key of `left` isn't less than every key of `right`,
+    /// interleaved keys need a merge instead.
+    ///
+    /// Time Complexity: O(log n + log m) expected
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use treap::Treap;
+    ///
+    /// let low: Treap<u32, &str> = vec![(1, "a"), (2, "b")].into_iter().collect();
+    /// let high: Treap<u32, &str> = vec![(5, "e")].into_iter().collect();
+    ///
+    /// let treap = Treap::join(low, high);
+    /// assert_eq!(treap.len(), 3);
+    /// assert_eq!(treap.get(&5), Some(&"e"));
+    /// ```
+    pub fn join(left: Treap<K, V>, right: Treap<K, V>) -> Treap<K, V> {
+        if let (Some(max), Some(min)) = (left.last(), right.first()) {
+            assert!(
+                max < min,
+                "every key of left must be less than every key of right"
+            );
+        }
+
+        Treap {
+            root: join(left.root, right.root),
+        }
+    }
+
+    /// Moves every entry of `other` into this Treap. When a key is in both,
+    /// the value of `other` is kept.
+    ///
+    /// If the keys of one Treap are all less than the keys of the other,
+    /// they are joined, as by `join()`. Otherwise the Treaps are merged by
+    /// their priorities: the root with the higher priority stays on top, the
+    /// other Treap is split at its key and each half merged on its side.
+    /// Runs of keys from one side are moved as whole subtrees, so merging a
+    /// small Treap into a large one doesn't rebuild the large one.
+    ///
+    /// Time Complexity: O(log n + log m) expected for disjoint keys,
+    /// O(m log(n / m + 1)) expected otherwise, m <= n the smaller size
+    /// Space Complexity: O(log n + log m) expected
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use treap::Treap;
+    ///
+    /// // Two shards of an index, with an entry updated in the second.
+    /// let mut index: Treap<u32, &str> = vec![(1, "a"), (4, "d"), (6, "f")].into_iter().collect();
+    /// let shard: Treap<u32, &str> = vec![(2, "b"), (4, "D"), (9, "i")].into_iter().collect();
+    ///
+    /// index.merge(shard);
+    /// assert_eq!(
+    ///     index.iter().collect::<Vec<_>>(),
+    ///     vec![(&1, &"a"), (&2, &"b"), (&4, &"D"), (&6, &"f"), (&9, &"i")]
+    /// );
+    /// ```
+    pub fn merge(&mut self, other: Treap<K, V>) {
+        let below = |low: &Treap<K, V>, high: &Treap<K, V>| match (low.last(), high.first()) {
+            (Some(max), Some(min)) => max < min,
+            _ => true,
+        };
+        let (before, after) = (below(self, &other), below(&other, self));
+
+        let root = self.root.take();
+        self.root = if before {
+            join(root, other.root)
+        } else if after {
+            join(other.root, root)
+        } else {
+            merge(root, other.root)
+        };
+    }
+
+    /// Removes the entries with keys in `range` and returns them as a Treap.
+    ///
+    /// The range is cut out with two splits and the rest is joined back, so
+    /// the entries in the range are detached as a whole rather than removed
+    /// one by one.
+    ///
+    /// Time Complexity: O(log n) expected
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use treap::Treap;
+    ///
+    /// let mut treap: Treap<u32, ()> = (0..10).map(|k| (k, ())).collect();
+    ///
+    /// let removed = treap.remove_range(3..=6);
+    /// assert_eq!(removed.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec![3, 4, 5, 6]);
+    /// assert_eq!(treap.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec![0, 1, 2, 7, 8, 9]);
+    /// ```
+    pub fn remove_range<R: RangeBounds<K>>(&mut self, range: R) -> Treap<K, V> {
+        let (low, rest) = match range.start_bound() {
+            Bound::Included(start) => split(self.root.take(), &|k| k < start),
+            Bound::Excluded(start) => split(self.root.take(), &|k| k <= start),
+            Bound::Unbounded => (None, self.root.take()),
+        };
+        let (removed, high) = match range.end_bound() {
+            Bound::Included(end) => split(rest, &|k| k <= end),
+            Bound::Excluded(end) => split(rest, &|k| k < end),
+            Bound::Unbounded => (rest, None),
+        };
+
+        self.root = join(low, high);
+
+        Treap { root: removed }
+    }
+
+    /// Keeps only the entries for which `pred` returns true, removing the
+    /// others in a single traversal. `pred` is called on the entries in
+    /// ascending order of their keys and may update the values it keeps.
+    ///
+    /// The children of a removed node are joined in its place on the way
+    /// back up, so the Treap stays balanced without removing and looking up
+    /// each key in turn.
+    ///
+    /// Time Complexity: O(n) expected
+    /// Space Complexity: O(log n) expected
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use treap::Treap;
+    ///
+    /// let mut stock: Treap<&str, u32> =
+    ///     vec![("apple", 3), ("kiwi", 0), ("pear", 1)].into_iter().collect();
+    ///
+    /// // Drop what's sold out and sell one of the rest.
+    /// stock.retain(|_, count| {
+    ///     *count = count.saturating_sub(1);
+    ///     *count > 0
+    /// });
+    ///
+    /// assert_eq!(stock.iter().collect::<Vec<_>>(), vec![(&"apple", &2)]);
+    /// ```
+    pub fn retain<F>(&mut self, mut pred: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.root = retain(self.root.take(), &mut pred);
+    }
+
+    /// Returns a Treap with the same keys and shape, and the values mapped by
+    /// `f`. The nodes are copied in a single traversal, keeping their
+    /// priorities, so nothing is inserted or rebalanced. `f` is called on
+    /// the values in ascending order of their keys.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(n)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use treap::Treap;
+    ///
+    /// let prices: Treap<&str, u32> = vec![("apple", 120), ("pear", 80)].into_iter().collect();
+    /// let labels = prices.map_values(|cents| format!("${:.2}", *cents as f64 / 100.0));
+    ///
+    /// assert_eq!(labels.get(&"apple"), Some(&"$1.20".to_string()));
+    /// assert_eq!(labels.get(&"pear"), Some(&"$0.80".to_string()));
+    /// ```
+    pub fn map_values<U, F>(&self, mut f: F) -> Treap<K, U>
+    where
+        K: Clone,
+        F: FnMut(&V) -> U,
+    {
+        Treap {
+            root: map_values(&self.root, &mut f),
+        }
+    }
+
+    /// Returns an Iterator over the entries in ascending order of their keys.
+    pub fn iter(&self) -> TreapIterator<'_, K, V> {
+        let mut iter = TreapIterator { stack: Vec::new() };
+        iter.push_left(&self.root);
+
+        iter
+    }
+
+    /// Returns the smallest key.
+    fn first(&self) -> Option<&K> {
+        let mut node = self.root.as_ref()?;
+        while let Some(left) = &node.left {
+            node = left;
+        }
+
+        Some(&node.key)
+    }
+
+    /// Returns the largest key.
+    fn last(&self) -> Option<&K> {
+        let mut node = self.root.as_ref()?;
+        while let Some(right) = &node.right {
+            node = right;
+        }
+
+        Some(&node.key)
+    }
+}
+
+impl<K, V> Treap<K, V> {
+    /// Returns a boolean indicating both Treaps have the same shape: every
+    /// node has the same children as the node at the same position in the
+    /// other tree. The keys and values are ignored, so the Treaps can hold
+    /// different types. Use `is_same_tree()` to compare them too.
+    ///
+    /// The shape depends on the priorities drawn when the keys were
+    /// inserted, so Treaps holding the same keys usually have different
+    /// shapes, but a Treap and its `map_values()` always have the same one.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(log n) expected
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use treap::Treap;
+    ///
+    /// let prices: Treap<&str, u32> = vec![("apple", 120), ("pear", 80)].into_iter().collect();
+    /// let names = prices.map_values(|cents| cents.to_string());
+    ///
+    /// assert!(prices.is_same_shape(&names));
+    /// assert!(!prices.is_same_shape(&Treap::<u8, u8>::new()));
+    /// ```
+    pub fn is_same_shape<L, W>(&self, other: &Treap<L, W>) -> bool {
+        same_trees(&self.root, &other.root, &mut |_, _| true)
+    }
+
+    /// Returns a boolean indicating both Treaps have the same shape, with
+    /// the same keys and values at every position.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(log n) expected
+    pub fn is_same_tree(&self, other: &Treap<K, V>) -> bool
+    where
+        K: PartialEq,
+        V: PartialEq,
+    {
+        same_trees(&self.root, &other.root, &mut |a, b| {
+            a.key == b.key && a.value == b.value
+        })
+    }
+
+    /// Returns a boolean indicating this Treap has the shape of the subtree
+    /// rooted at some node of `other`, ignoring the keys and values. The
+    /// subtree takes every descendant of its root, and the empty Treap is a
+    /// subtree of any Treap.
+    ///
+    /// Only the nodes of `other` whose subtree has as many nodes as this
+    /// Treap are compared, the sizes being kept in the nodes.
+    ///
+    /// Time Complexity: O(n * m), where m is the size of `other`
+    /// Space Complexity: O(log m) expected
+    pub fn is_subtree_shape_of<L, W>(&self, other: &Treap<L, W>) -> bool {
+        is_subtree(&self.root, &other.root, &mut |_, _| true)
+    }
+
+    /// Returns a boolean indicating this Treap is the subtree rooted at some
+    /// node of `other`, with the same shape, keys and values. The subtree
+    /// takes every descendant of its root, and the empty Treap is a subtree
+    /// of any Treap.
+    ///
+    /// Time Complexity: O(n * m), where m is the size of `other`
+    /// Space Complexity: O(log m) expected
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use treap::Treap;
+    ///
+    /// let squares: Treap<u32, u32> = (0..10).map(|k| (k, k * k)).collect();
+    ///
+    /// let copy = squares.map_values(|v| *v);
+    /// assert!(copy.is_subtree_of(&squares));
+    ///
+    /// let doubled = squares.map_values(|v| v * 2);
+    /// assert!(!doubled.is_subtree_of(&squares));
+    /// assert!(doubled.is_subtree_shape_of(&squares));
+    /// assert!(Treap::new().is_subtree_of(&squares));
+    /// ```
+    pub fn is_subtree_of(&self, other: &Treap<K, V>) -> bool
+    where
+        K: PartialEq,
+        V: PartialEq,
+    {
+        is_subtree(&self.root, &other.root, &mut |a, b| {
+            a.key == b.key && a.value == b.value
+        })
+    }
+}
+
+/// Returns a boolean indicating two subtrees have the same shape and `eq`
+/// holds for the nodes at every position.
+fn same_trees<K, V, L, W, F>(a: &Link<K, V>, b: &Link<L, W>, eq: &mut F) -> bool
+where
+    F: FnMut(&Node<K, V>, &Node<L, W>) -> bool,
+{
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            a.size == b.size
+                && eq(a, b)
+                && same_trees(&a.left, &b.left, eq)
+                && same_trees(&a.right, &b.right, eq)
+        }
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Returns a boolean indicating the subtree `root` matches the subtree of
+/// some node of `other`.
+fn is_subtree<K, V, L, W, F>(root: &Link<K, V>, other: &Link<L, W>, eq: &mut F) -> bool
+where
+    F: FnMut(&Node<K, V>, &Node<L, W>) -> bool,
+{
+    if root.is_none() {
+        return true;
+    }
+
+    match other {
+        // A smaller subtree can't hold a match.
+        Some(node) if node.size >= size(root) => {
+            (node.size == size(root) && same_trees(root, other, eq))
+                || is_subtree(root, &node.left, eq)
+                || is_subtree(root, &node.right, eq)
+        }
+        _ => false,
+    }
+}
+
+/// Returns the size of a subtree, 0 if it is empty.
+fn size<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map_or(0, |node| node.size)
+}
+
+/// Returns the node holding a key in a subtree.
+fn find<'a, K: Ord, V>(mut node: Option<&'a Node<K, V>>, key: &K) -> Option<&'a Node<K, V>> {
+    while let Some(n) = node {
+        node = match key.cmp(&n.key) {
+            Ordering::Less => n.left.as_deref(),
+            Ordering::Greater => n.right.as_deref(),
+            Ordering::Equal => return Some(n),
+        };
+    }
+
+    None
+}
+
+/// Returns the next priority, mixing a shared counter with splitmix64.
+fn priority() -> u64 {
+    static SEED: AtomicU64 = AtomicU64::new(0);
+
+    let mut z = SEED
+        .fetch_add(0x9E37_79B9_7F4A_7C15, AtomicOrdering::Relaxed)
+        .wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Splits a subtree into the nodes whose keys go `left` and the rest. `left`
+/// must hold for every key below one that it holds for, so this walks down a
+/// single path, like a search.
+fn split<K, V, F>(link: Link<K, V>, left: &F) -> (Link<K, V>, Link<K, V>)
+where
+    F: Fn(&K) -> bool,
+{
+    match link {
+        None => (None, None),
+        Some(mut node) => {
+            if left(&node.key) {
+                let (left, right) = split(node.right.take(), left);
+                node.right = left;
+                node.update();
+                (Some(node), right)
+            } else {
+                let (left, right) = split(node.left.take(), left);
+                node.left = right;
+                node.update();
+                (left, Some(node))
+            }
+        }
+    }
+}
+
+/// Joins two subtrees, every key of `left` being less than every key of
+/// `right`. The root with the higher priority stays on top, walking down the
+/// right spine of `left` and the left spine of `right`.
+fn join<K, V>(left: Link<K, V>, right: Link<K, V>) -> Link<K, V> {
+    match (left, right) {
+        (None, right) => right,
+        (left, None) => left,
+        (Some(mut left), Some(mut right)) => {
+            if left.priority > right.priority {
+                left.right = join(left.right.take(), Some(right));
+                left.update();
+                Some(left)
+            } else {
+                right.left = join(Some(left), right.left.take());
+                right.update();
+                Some(right)
+            }
+        }
+    }
+}
+
+/// Merges two subtrees with interleaved keys, the values of `theirs` being
+/// kept for the keys in both. The root with the higher priority stays on
+/// top and the other subtree is split around its key.
+fn merge<K: Ord, V>(mine: Link<K, V>, theirs: Link<K, V>) -> Link<K, V> {
+    let (mut mine, mut theirs) = match (mine, theirs) {
+        (None, link) | (link, None) => return link,
+        (Some(mine), Some(theirs)) => (mine, theirs),
+    };
+
+    if mine.priority >= theirs.priority {
+        let (less, equal, greater) = split_at(Some(theirs), &mine.key);
+        if let Some(equal) = equal {
+            mine.value = equal.value;
+        }
+        mine.left = merge(mine.left.take(), less);
+        mine.right = merge(mine.right.take(), greater);
+        mine.update();
+        Some(mine)
+    } else {
+        // The entry of mine with the same key, if any, is dropped.
+        let (less, _, greater) = split_at(Some(mine), &theirs.key);
+        theirs.left = merge(less, theirs.left.take());
+        theirs.right = merge(greater, theirs.right.take());
+        theirs.update();
+        Some(theirs)
+    }
+}
+
+/// Splits a subtree into the entries with keys less than `key`, the entry
+/// with the key, and the entries with greater keys.
+fn split_at<K: Ord, V>(link: Link<K, V>, key: &K) -> (Link<K, V>, Link<K, V>, Link<K, V>) {
+    let (less, rest) = split(link, &|k| k < key);
+    let (equal, greater) = split(rest, &|k| k <= key);
+
+    (less, equal, greater)
+}
+
+/// Copies a subtree with its values mapped by `f`, visiting the nodes in
+/// order.
+fn map_values<K, V, U, F>(link: &Link<K, V>, f: &mut F) -> Link<K, U>
+where
+    K: Clone,
+    F: FnMut(&V) -> U,
+{
+    link.as_ref().map(|node| {
+        let left = map_values(&node.left, f);
+        let value = f(&node.value);
+        let right = map_values(&node.right, f);
+
+        Box::new(Node {
+            key: node.key.clone(),
+            value,
+            priority: node.priority,
+            size: node.size,
+            left,
+            right,
+        })
+    })
+}
+
+/// Keeps the entries of a subtree matching `pred`, visiting them in order.
+/// A removed node is replaced by the join of its filtered children, which
+/// keeps the priorities ordered.
+fn retain<K, V, F>(link: Link<K, V>, pred: &mut F) -> Link<K, V>
+where
+    F: FnMut(&K, &mut V) -> bool,
+{
+    let mut node = link?;
+    let left = retain(node.left.take(), pred);
+    let keep = pred(&node.key, &mut node.value);
+    let right = retain(node.right.take(), pred);
+
+    if !keep {
+        return join(left, right);
+    }
+
+    node.left = left;
+    node.right = right;
+    node.update();
+    Some(node)
+}
+
+/// Removes a key from a subtree, updating the sizes along the path.
+fn remove<K: Ord, V>(link: &mut Link<K, V>, key: &K) -> Option<V> {
+    let node = link.as_mut()?;
+
+    let value = match key.cmp(&node.key) {
+        Ordering::Less => remove(&mut node.left, key),
+        Ordering::Greater => remove(&mut node.right, key),
+        Ordering::Equal => {
+            let mut node = link.take()?;
+            *link = join(node.left.take(), node.right.take());
+            return Some(node.value);
+        }
+    };
+
+    if value.is_some() {
+        node.size -= 1;
+    }
+
+    value
+}
+
+/// The Iterator implementation for the Treap, visiting the entries in order.
+/// This Iterator will borrow the Treap.
+pub struct TreapIterator<'a, K, V> {
+    stack: Vec<&'a Node<K, V>>,
+}
+
+impl<'a, K, V> TreapIterator<'a, K, V> {
+    /// Pushes the left spine of a subtree, its smallest key ends on top.
+    fn push_left(&mut self, mut link: &'a Link<K, V>) {
+        while let Some(node) = link {
+            self.stack.push(node);
+            link = &node.left;
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for TreapIterator<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left(&node.right);
+
+        Some((&node.key, &node.value))
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a Treap<K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = TreapIterator<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn treap(len: u32) -> Treap<u32, u32> {
+        (0..len).map(|i| (i * 2, i)).collect()
+    }
+
+    fn keys(treap: &Treap<u32, u32>) -> Vec<u32> {
+        treap.iter().map(|(k, _)| *k).collect()
+    }
+
+    fn height<K, V>(link: &Link<K, V>) -> usize {
+        link.as_ref()
+            .map_or(0, |node| 1 + height(&node.left).max(height(&node.right)))
+    }
+
+    /// Checks the keys are in order, every priority is at most its parent's
+    /// and every size is up to date.
+    fn assert_valid<K: Ord, V>(link: &Link<K, V>) {
+        if let Some(node) = link {
+            for (child, is_left) in [(&node.left, true), (&node.right, false)] {
+                if let Some(child) = child {
+                    assert!(child.priority <= node.priority);
+                    assert_eq!(child.key < node.key, is_left);
+                }
+                assert_valid(child);
+            }
+            assert_eq!(node.size, 1 + size(&node.left) + size(&node.right));
+        }
+    }
+
+    #[test]
+    fn insert_get_remove() {
+        let mut treap = treap(50);
+        assert_eq!(treap.len(), 50);
+
+        for i in 0..50 {
+            assert_eq!(treap.get(&(i * 2)), Some(&i));
+            assert_eq!(treap.get(&(i * 2 + 1)), None);
+        }
+
+        assert_eq!(treap.insert(10, 100), Some(5));
+        assert_eq!(treap.len(), 50);
+        *treap.get_mut(&10).unwrap() += 1;
+        assert_eq!(treap.get(&10), Some(&101));
+
+        for i in (0..50).step_by(2) {
+            assert_eq!(treap.remove(&(i * 2)), Some(if i == 5 { 101 } else { i }));
+            assert_eq!(treap.remove(&(i * 2)), None);
+        }
+        assert_eq!(treap.len(), 25);
+        assert_eq!(
+            keys(&treap),
+            (1..50).step_by(2).map(|i| i * 2).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn lowest_common_ancestor() {
+        // The keys on the path from the root to a key.
+        fn path(treap: &Treap<u32, u32>, key: u32) -> Vec<u32> {
+            let mut path = Vec::new();
+            let mut link = &treap.root;
+            while let Some(node) = link {
+                path.push(node.key);
+                link = match key.cmp(&node.key) {
+                    Ordering::Less => &node.left,
+                    Ordering::Greater => &node.right,
+                    Ordering::Equal => break,
+                };
+            }
+
+            path
+        }
+
+        let treap = treap(40);
+        for a in (0..80).step_by(2) {
+            for b in (0..80).step_by(2) {
+                let common = path(&treap, a)
+                    .into_iter()
+                    .zip(path(&treap, b))
+                    .take_while(|(x, y)| x == y)
+                    .last()
+                    .map(|(x, _)| x);
+                assert_eq!(treap.lca(&a, &b).copied(), common);
+            }
+        }
+
+        assert_eq!(treap.lca(&2, &3), None);
+        assert_eq!(treap.lca(&81, &2), None);
+        assert_eq!(Treap::<u32, u32>::new().lca(&1, &1), None);
+    }
+
+    #[test]
+    fn stays_balanced_on_sorted_inserts() {
+        let treap = treap(4096);
+
+        // A plain binary search tree would be 4096 deep.
+        assert!(height(&treap.root) < 64);
+    }
+
+    #[test]
+    fn split_every_key() {
+        for key in 0..22 {
+            let (left, right) = treap(10).split(&key);
+
+            assert_eq!(
+                keys(&left),
+                (0..10)
+                    .map(|i| i * 2)
+                    .filter(|k| *k < key)
+                    .collect::<Vec<_>>()
+            );
+            assert_eq!(
+                keys(&right),
+                (0..10)
+                    .map(|i| i * 2)
+                    .filter(|k| *k >= key)
+                    .collect::<Vec<_>>()
+            );
+            assert_eq!(left.len() + right.len(), 10);
+
+            let joined = Treap::join(left, right);
+            assert_eq!(keys(&joined), keys(&treap(10)));
+            assert_eq!(joined.len(), 10);
+        }
+    }
+
+    #[test]
+    fn range_delete() {
+        let treap = treap(1000);
+
+        let (low, rest) = treap.split(&100);
+        let (_, high) = rest.split(&1900);
+        let mut treap = Treap::join(low, high);
+
+        assert_eq!(treap.len(), 100);
+        assert!(treap.contains_key(&98));
+        assert!(!treap.contains_key(&100));
+        assert!(treap.contains_key(&1900));
+        assert!(height(&treap.root) < 48);
+
+        treap.insert(1000, 0);
+        assert_eq!(treap.len(), 101);
+        assert_eq!(keys(&treap)[50], 1000);
+    }
+
+    #[test]
+    fn join_empty() {
+        let treap = Treap::join(Treap::new(), treap(3));
+        assert_eq!(keys(&treap), vec![0, 2, 4]);
+
+        let treap = Treap::join(treap, Treap::new());
+        assert_eq!(treap.len(), 3);
+
+        let empty: Treap<u32, u32> = Treap::join(Treap::new(), Treap::new());
+        assert!(empty.is_empty());
+        assert_eq!(empty.iter().next(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "every key of left must be less than every key of right")]
+    fn join_overlapping() {
+        Treap::join(treap(3), treap(2));
+    }
+
+    #[test]
+    fn remove_range() {
+        let mut treap = treap(10);
+
+        assert_eq!(keys(&treap.remove_range(4..8)), vec![4, 6]);
+        assert_eq!(keys(&treap.remove_range(9..=12)), vec![10, 12]);
+        assert_eq!(keys(&treap.remove_range(..=0)), vec![0]);
+        assert_eq!(keys(&treap), vec![2, 8, 14, 16, 18]);
+
+        // Excluded start bounds, e.g. from a BTreeMap style range.
+        let removed = treap.remove_range((Bound::Excluded(2), Bound::Unbounded));
+        assert_eq!(keys(&removed), vec![8, 14, 16, 18]);
+        assert_eq!(removed.len(), 4);
+        assert_eq!(keys(&treap), vec![2]);
+
+        assert!(treap.remove_range(3..).is_empty());
+        assert_eq!(keys(&treap.remove_range(..)), vec![2]);
+        assert!(treap.is_empty());
+    }
+
+    #[test]
+    fn retain() {
+        let mut treap = treap(200);
+        let mut seen = Vec::new();
+        treap.retain(|k, v| {
+            seen.push(*k);
+            *v += 1000;
+            k % 3 == 0
+        });
+
+        // Called once per entry, in order of the keys.
+        assert_eq!(seen, (0..200).map(|i| i * 2).collect::<Vec<_>>());
+        let expected: Vec<u32> = (0..400).step_by(6).collect();
+        assert_eq!(keys(&treap), expected);
+        assert_eq!(treap.len(), expected.len());
+        assert_eq!(treap.get(&12), Some(&1006));
+        assert_valid(&treap.root);
+
+        treap.retain(|_, _| false);
+        assert!(treap.is_empty());
+        treap.retain(|_, _| true);
+        assert!(treap.is_empty());
+    }
+
+    #[test]
+    fn merge_interleaved() {
+        // Keys 0, 2, .., 198 holding their half, and every third key from 0
+        // to 297 holding 1000 more.
+        let mut merged = treap(100);
+        let other: Treap<u32, u32> = (0..100).map(|i| (i * 3, i * 3 + 1000)).collect();
+        merged.merge(other);
+        assert_valid(&merged.root);
+
+        let mut expected: Vec<(u32, u32)> = (0..100)
+            .map(|i| i * 2)
+            .filter(|k| k % 3 != 0)
+            .map(|k| (k, k / 2))
+            .chain((0..100).map(|i| (i * 3, i * 3 + 1000)))
+            .collect();
+        expected.sort_unstable();
+        assert_eq!(merged.len(), expected.len());
+        assert_eq!(
+            merged.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(),
+            expected
+        );
+
+        // A single entry in the middle of a large Treap.
+        let mut large = treap(1000);
+        large.merge(vec![(501, 0)].into_iter().collect());
+        assert_valid(&large.root);
+        assert_eq!(large.len(), 1001);
+        assert_eq!(large.get(&501), Some(&0));
+    }
+
+    #[test]
+    fn merge_disjoint() {
+        let low: Treap<u32, u32> = (0..50).map(|k| (k, k)).collect();
+        let high: Treap<u32, u32> = (50..100).map(|k| (k, k)).collect();
+
+        let mut merged = Treap::new();
+        merged.merge(high);
+        merged.merge(Treap::new());
+        merged.merge(low);
+        assert_valid(&merged.root);
+        assert_eq!(keys(&merged), (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn shapes_and_subtrees() {
+        let whole = treap(100);
+        let root = whole.root.as_ref().unwrap();
+        let subtree = |link: &Link<u32, u32>| Treap {
+            root: super::map_values(link, &mut |v: &u32| *v),
+        };
+
+        let left = subtree(&root.left);
+        let right_left = subtree(&root.right.as_ref().unwrap().left);
+        for part in [&left, &right_left] {
+            assert!(part.is_subtree_of(&whole));
+            assert!(part.is_subtree_shape_of(&whole));
+            assert!(!whole.is_subtree_of(part));
+        }
+        assert!(whole.is_subtree_of(&whole));
+        assert!(whole.is_same_tree(&subtree(&whole.root)));
+        assert!(!whole.is_same_tree(&left));
+
+        // The same shape with other values.
+        let labels = left.map_values(|v| format!("#{}", v));
+        assert!(labels.is_same_shape(&left));
+        assert!(labels.is_subtree_shape_of(&whole));
+        let shifted = left.map_values(|v| v + 1);
+        assert!(shifted.is_same_shape(&left));
+        assert!(!shifted.is_same_tree(&left));
+        assert!(!shifted.is_subtree_of(&whole));
+
+        // The same keys in a different shape.
+        let mut rebuilt = treap(100);
+        while rebuilt.is_same_shape(&whole) {
+            rebuilt = treap(100);
+        }
+        assert!(!rebuilt.is_same_tree(&whole));
+        assert!(!rebuilt.is_subtree_of(&whole));
+
+        let empty = Treap::<u32, u32>::new();
+        assert!(empty.is_same_shape(&Treap::<(), ()>::new()));
+        assert!(empty.is_subtree_of(&whole));
+        assert!(empty.is_subtree_of(&empty));
+        assert!(!whole.is_same_shape(&empty));
+    }
+
+    #[test]
+    fn map_values() {
+        fn shape<K: Copy, V>(link: &Link<K, V>, out: &mut Vec<(K, u64, usize)>) {
+            if let Some(node) = link {
+                out.push((node.key, node.priority, node.size));
+                shape(&node.left, out);
+                shape(&node.right, out);
+            }
+        }
+
+        let treap = treap(100);
+        let mut seen = Vec::new();
+        let mapped = treap.map_values(|v| {
+            seen.push(*v);
+            format!("{}", v)
+        });
+
+        // Called once per value, in order of the keys.
+        assert_eq!(seen, (0..100).collect::<Vec<_>>());
+        assert_eq!(mapped.get(&10), Some(&"5".to_string()));
+        assert_eq!(mapped.len(), 100);
+
+        let (mut before, mut after) = (Vec::new(), Vec::new());
+        shape(&treap.root, &mut before);
+        shape(&mapped.root, &mut after);
+        assert_eq!(before, after);
+
+        assert!(Treap::<u32, u32>::new().map_values(|v| *v).is_empty());
+    }
+}
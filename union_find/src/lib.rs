@@ -0,0 +1,5 @@
+//! A crate that implements a UnionFind (disjoint-set) whose unions can be
+//! rolled back.
+pub use crate::union_find::UnionFind;
+
+mod union_find;
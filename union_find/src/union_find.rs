@@ -0,0 +1,238 @@
+/// UnionFind partitions the elements `0..n` into disjoint sets, journaling
+/// every union so the last ones can be rolled back.
+///
+/// Datastructure:
+/// - Vec: the parent of each element
+/// - Vec: the size of each set, at its representative
+/// - Vec: the journal of unions
+///
+/// # Example
+///
+/// ```
+/// use union_find::UnionFind;
+///
+/// let mut sets = UnionFind::new(4);
+/// sets.union(0, 1);
+/// sets.union(2, 3);
+/// sets.union(1, 2);
+/// assert!(sets.connected(0, 3));
+///
+/// sets.rollback(1);
+/// assert!(!sets.connected(0, 3));
+/// assert!(sets.connected(2, 3));
+/// ```
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    sets: usize,
+    // The representative attached below another by each union, None if the
+    // union merged nothing.
+    journal: Vec<Option<usize>>,
+}
+
+impl UnionFind {
+    /// Returns a UnionFind of `n` elements, each in a set of its own.
+    ///
+    /// Time Complexity: O(n)
+    /// Space Complexity: O(n)
+    pub fn new(n: usize) -> UnionFind {
+        UnionFind {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            sets: n,
+            journal: Vec::new(),
+        }
+    }
+
+    /// Returns the number of elements.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Returns a boolean indicating the UnionFind has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Returns the number of disjoint sets.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn set_count(&self) -> usize {
+        self.sets
+    }
+
+    /// Returns the representative of the set of an element. Paths aren't
+    /// compressed, so this doesn't change the UnionFind.
+    ///
+    /// Time Complexity: O(log n)
+    /// Space Complexity: O(1)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element isn't in the UnionFind.
+    pub fn find(&self, mut element: usize) -> usize {
+        assert!(element < self.len(), "the element must be in the UnionFind");
+
+        while self.parent[element] != element {
+            element = self.parent[element];
+        }
+
+        element
+    }
+
+    /// Returns a boolean indicating two elements are in the same set.
+    ///
+    /// Time Complexity: O(log n)
+    /// Space Complexity: O(1)
+    pub fn connected(&self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Returns the size of the set of an element.
+    ///
+    /// Time Complexity: O(log n)
+    /// Space Complexity: O(1)
+    pub fn set_size(&self, element: usize) -> usize {
+        self.size[self.find(element)]
+    }
+
+    /// Merges the sets of two elements, returning false if they were already
+    /// in the same set. Either way the union is journaled, so it counts
+    /// towards `rollback()`.
+    ///
+    /// Time Complexity: O(log n)
+    /// Space Complexity: O(1) amortized
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            self.journal.push(None);
+            return false;
+        }
+
+        // Attach the smaller set below the larger to keep the trees shallow.
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.sets -= 1;
+        self.journal.push(Some(b));
+
+        true
+    }
+
+    /// Returns the number of unions in the journal, the most that can be
+    /// rolled back.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn unions(&self) -> usize {
+        self.journal.len()
+    }
+
+    /// Undoes the last `k` unions, most recent first, or every union if
+    /// there are fewer than `k`.
+    ///
+    /// Time Complexity: O(k)
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use union_find::UnionFind;
+    ///
+    /// let mut sets = UnionFind::new(3);
+    /// sets.union(0, 1);
+    /// let checkpoint = sets.unions();
+    ///
+    /// sets.union(1, 2);
+    /// sets.union(0, 2);
+    /// assert_eq!(sets.set_count(), 1);
+    ///
+    /// sets.rollback(sets.unions() - checkpoint);
+    /// assert_eq!(sets.set_count(), 2);
+    /// assert!(sets.connected(0, 1));
+    /// ```
+    pub fn rollback(&mut self, k: usize) {
+        for _ in 0..k {
+            let child = match self.journal.pop() {
+                Some(Some(child)) => child,
+                Some(None) => continue,
+                None => return,
+            };
+
+            // The parent of child is still the representative it was attached
+            // below, every later union has been undone.
+            let root = self.parent[child];
+            self.size[root] -= self.size[child];
+            self.parent[child] = child;
+            self.sets += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn union_and_find() {
+        let mut sets = UnionFind::new(6);
+        assert_eq!(sets.len(), 6);
+        assert_eq!(sets.set_count(), 6);
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(3, 4));
+        assert!(!sets.union(2, 4));
+
+        assert_eq!(sets.set_count(), 3);
+        assert!(sets.connected(1, 0));
+        assert!(sets.connected(2, 4));
+        assert!(!sets.connected(1, 2));
+        assert_eq!(sets.set_size(4), 3);
+        assert_eq!(sets.set_size(5), 1);
+        assert_eq!(sets.unions(), 4);
+    }
+
+    #[test]
+    fn rollback() {
+        let mut sets = UnionFind::new(5);
+        sets.union(0, 1);
+        sets.union(2, 3);
+        let before = sets.clone();
+
+        sets.union(1, 2);
+        // Already connected, still one union to roll back.
+        sets.union(0, 3);
+        sets.union(3, 4);
+        assert_eq!(sets.set_count(), 1);
+        assert_eq!(sets.set_size(0), 5);
+
+        sets.rollback(1);
+        assert!(!sets.connected(4, 0));
+        assert!(sets.connected(3, 0));
+
+        sets.rollback(2);
+        assert_eq!(sets.parent, before.parent);
+        assert_eq!(sets.size, before.size);
+        assert_eq!(sets.set_count(), 3);
+
+        // Rolling back more than was journaled undoes everything.
+        sets.rollback(10);
+        assert_eq!(sets.unions(), 0);
+        assert_eq!(sets.set_count(), 5);
+        assert!((0..5).all(|e| sets.find(e) == e && sets.set_size(e) == 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "the element must be in the UnionFind")]
+    fn find_missing_element() {
+        UnionFind::new(2).find(2);
+    }
+}
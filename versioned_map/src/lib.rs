@@ -0,0 +1,5 @@
+//! A crate that implements a VersionedMap, a map that keeps every version of
+//! its entries and answers queries about past versions.
+pub use crate::versioned_map::{Change, VersionedMap};
+
+mod versioned_map;
@@ -0,0 +1,282 @@
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A difference between two versions of a key, returned by
+/// `VersionedMap::diff()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change<K, V> {
+    /// The key is only in the later version.
+    Added(K, V),
+    /// The key is only in the earlier version.
+    Removed(K, V),
+    /// The key is in both versions with different values, the earlier value
+    /// comes first.
+    Changed(K, V, V),
+}
+
+/// VersionedMap is a map that tags each write with a version, starting at 1,
+/// and keeps every version of its entries. Version 0 is the empty map.
+///
+/// # Example
+///
+/// ```
+/// use versioned_map::{Change, VersionedMap};
+///
+/// let mut map = VersionedMap::new();
+/// let v1 = map.insert("a", 1);
+/// let v2 = map.insert("a", 2);
+/// map.remove("a");
+///
+/// assert_eq!(map.get("a"), None);
+/// assert_eq!(map.get_at("a", v1), Some(&1));
+/// assert_eq!(map.diff(v1, v2), vec![Change::Changed("a", 1, 2)]);
+/// ```
+pub struct VersionedMap<K, V> {
+    // The writes to each key as (version, value), sorted by version. A None
+    // value is a removal.
+    history: BTreeMap<K, Vec<(u64, Option<V>)>>,
+    // The key written by each version, version v is at v - 1.
+    log: Vec<K>,
+    len: usize,
+}
+
+impl<K: Ord, V> Default for VersionedMap<K, V> {
+    fn default() -> Self {
+        VersionedMap {
+            history: BTreeMap::new(),
+            log: Vec::new(),
+            len: 0,
+        }
+    }
+}
+
+impl<K: Ord, V> VersionedMap<K, V> {
+    /// Returns an empty VersionedMap at version 0.
+    pub fn new() -> VersionedMap<K, V> {
+        VersionedMap::default()
+    }
+
+    /// Returns the latest version, the number of writes so far.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn version(&self) -> u64 {
+        self.log.len() as u64
+    }
+
+    /// Returns the number of keys in the latest version.
+    ///
+    /// Time Complexity: O(1)
+    /// Space Complexity: O(1)
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns a boolean indicating the latest version is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the value of a key in the latest version.
+    ///
+    /// Time Complexity: O(log n)
+    /// Space Complexity: O(1)
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.history.get(key)?.last()?.1.as_ref()
+    }
+
+    /// Returns the value of a key as of `version`, a version later than the
+    /// latest reads the latest.
+    ///
+    /// Time Complexity: O(log n + log k), k the number of writes to the key
+    /// Space Complexity: O(1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use versioned_map::VersionedMap;
+    ///
+    /// let mut map = VersionedMap::new();
+    /// map.insert("a", 1);
+    /// let v = map.insert("b", 2);
+    /// map.insert("a", 3);
+    ///
+    /// assert_eq!(map.get_at("a", 0), None);
+    /// assert_eq!(map.get_at("a", v), Some(&1));
+    /// assert_eq!(map.get_at("a", map.version()), Some(&3));
+    /// ```
+    pub fn get_at<Q>(&self, key: &Q, version: u64) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let writes = self.history.get(key)?;
+        let i = writes.partition_point(|(v, _)| *v <= version);
+
+        writes[..i].last()?.1.as_ref()
+    }
+}
+
+impl<K: Ord + Clone, V> VersionedMap<K, V> {
+    /// Inserts a value, returning the version it created.
+    ///
+    /// Time Complexity: O(log n)
+    /// Space Complexity: O(1) amortized
+    pub fn insert(&mut self, key: K, value: V) -> u64 {
+        if self.get(&key).is_none() {
+            self.len += 1;
+        }
+
+        self.write(key, Some(value))
+    }
+
+    /// Removes a key, returning the version it created, or None without
+    /// creating a version if the key is missing.
+    ///
+    /// Time Complexity: O(log n)
+    /// Space Complexity: O(1) amortized
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<u64>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key)?;
+        self.len -= 1;
+
+        // The key is in the history, so cloning it out of there avoids
+        // asking the caller for an owned key.
+        let key = self.history.get_key_value(key)?.0.clone();
+        Some(self.write(key, None))
+    }
+
+    /// Returns the keys added, removed or changed from version `from` to
+    /// version `to`, sorted by key. Swapping the versions reverses the
+    /// changes.
+    ///
+    /// Time Complexity: O(w log w), w the number of writes between the versions
+    /// Space Complexity: O(w)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use versioned_map::{Change, VersionedMap};
+    ///
+    /// let mut map = VersionedMap::new();
+    /// map.insert("a", 1);
+    /// let v = map.insert("b", 2);
+    /// map.remove("a");
+    /// map.insert("c", 3);
+    /// map.insert("b", 2);
+    ///
+    /// assert_eq!(
+    ///     map.diff(v, map.version()),
+    ///     vec![Change::Removed("a", 1), Change::Added("c", 3)]
+    /// );
+    /// ```
+    pub fn diff(&self, from: u64, to: u64) -> Vec<Change<K, V>>
+    where
+        V: Clone + PartialEq,
+    {
+        let start = from.min(to).min(self.version()) as usize;
+        let end = from.max(to).min(self.version()) as usize;
+
+        // Only the keys written between the versions can differ.
+        let keys: BTreeSet<&K> = self.log[start..end].iter().collect();
+
+        keys.into_iter()
+            .filter_map(|key| match (self.get_at(key, from), self.get_at(key, to)) {
+                (None, Some(new)) => Some(Change::Added(key.clone(), new.clone())),
+                (Some(old), None) => Some(Change::Removed(key.clone(), old.clone())),
+                (Some(old), Some(new)) if old != new => {
+                    Some(Change::Changed(key.clone(), old.clone(), new.clone()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Records a write to a key as the next version.
+    fn write(&mut self, key: K, value: Option<V>) -> u64 {
+        let version = self.version() + 1;
+
+        self.log.push(key.clone());
+        self.history.entry(key).or_default().push((version, value));
+
+        version
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn versions() {
+        let mut map = VersionedMap::new();
+        assert_eq!(map.version(), 0);
+        assert!(map.is_empty());
+
+        assert_eq!(map.insert("a".to_string(), 1), 1);
+        assert_eq!(map.insert("b".to_string(), 2), 2);
+        assert_eq!(map.insert("a".to_string(), 3), 3);
+        assert_eq!(map.len(), 2);
+
+        // Removing a missing key doesn't create a version.
+        assert_eq!(map.remove("c"), None);
+        assert_eq!(map.remove("a"), Some(4));
+        assert_eq!(map.version(), 4);
+        assert_eq!(map.len(), 1);
+
+        assert_eq!(map.get("a"), None);
+        assert_eq!(map.get_at("a", 0), None);
+        assert_eq!(map.get_at("a", 2), Some(&1));
+        assert_eq!(map.get_at("a", 3), Some(&3));
+        assert_eq!(map.get_at("a", 100), None);
+        assert_eq!(map.get_at("b", 1), None);
+        assert_eq!(map.get_at("b", 4), Some(&2));
+
+        // A removed key can come back.
+        assert_eq!(map.insert("a".to_string(), 5), 5);
+        assert_eq!(map.get("a"), Some(&5));
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn diff() {
+        let mut map = VersionedMap::new();
+        map.insert("a", 1);
+        map.insert("b", 2);
+        let v = map.version();
+
+        map.insert("a", 10);
+        map.remove("b");
+        map.insert("c", 3);
+        // Added and removed again, so it is unchanged.
+        map.insert("d", 4);
+        map.remove("d");
+
+        let forward = vec![
+            Change::Changed("a", 1, 10),
+            Change::Removed("b", 2),
+            Change::Added("c", 3),
+        ];
+        assert_eq!(map.diff(v, map.version()), forward);
+
+        let backward = vec![
+            Change::Changed("a", 10, 1),
+            Change::Added("b", 2),
+            Change::Removed("c", 3),
+        ];
+        assert_eq!(map.diff(map.version(), v), backward);
+
+        assert!(map.diff(v, v).is_empty());
+        assert_eq!(
+            map.diff(0, 100),
+            vec![Change::Added("a", 10), Change::Added("c", 3)]
+        );
+    }
+}